@@ -503,7 +503,22 @@ fn to_pascal_case(s: &str) -> String {
 ///
 /// let pair = PairSignals::new(Pair { first: 1i32, second: "hello".to_string() });
 /// ```
-#[proc_macro_derive(SignalFields)]
+///
+/// # Persisted state
+///
+/// With guido's `serde` feature, `#[signal_fields(persist = "prefix")]` registers
+/// every field with `register_state` as `"prefix.field"` when `new()` is called,
+/// so it is included in `snapshot_state()` / `restore_state()`.
+///
+/// ```ignore
+/// #[derive(Clone, PartialEq, SignalFields)]
+/// #[signal_fields(persist = "settings")]
+/// pub struct Settings {
+///     pub volume: u8,
+///     pub muted: bool,
+/// }
+/// ```
+#[proc_macro_derive(SignalFields, attributes(signal_fields))]
 pub fn derive_signal_fields(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
 
@@ -530,6 +545,27 @@ pub fn derive_signal_fields(input: TokenStream) -> TokenStream {
         }
     };
 
+    // Optional `#[signal_fields(persist = "prefix")]`
+    let mut persist_prefix: Option<String> = None;
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("signal_fields"))
+    {
+        let result = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("persist") {
+                let prefix: syn::LitStr = meta.value()?.parse()?;
+                persist_prefix = Some(prefix.value());
+                Ok(())
+            } else {
+                Err(meta.error("unknown signal_fields attribute, expected `persist`"))
+            }
+        });
+        if let Err(e) = result {
+            return e.to_compile_error().into();
+        }
+    }
+
     let signals_name = format_ident!("{}Signals", struct_name);
     let writers_name = format_ident!("{}Writers", struct_name);
 
@@ -557,6 +593,14 @@ pub fn derive_signal_fields(input: TokenStream) -> TokenStream {
         quote! { #name: ::guido::reactive::signal::create_signal(initial.#name) }
     });
 
+    // Generate register_state() calls for persisted structs
+    let register_calls = persist_prefix.iter().flat_map(|prefix| {
+        field_names.iter().map(move |name| {
+            let key = format!("{prefix}.{name}");
+            quote! { ::guido::reactive::register_state(#key, signals.#name); }
+        })
+    });
+
     // Generate writers() field initializers: self.field.writer()
     let writers_inits = field_names.iter().map(|name| {
         quote! { #name: self.#name.writer() }
@@ -581,9 +625,11 @@ pub fn derive_signal_fields(input: TokenStream) -> TokenStream {
 
         impl #impl_generics #signals_name #ty_generics #where_clause {
             pub fn new(initial: #struct_name #ty_generics) -> Self {
-                Self {
+                let signals = Self {
                     #(#new_inits,)*
-                }
+                };
+                #(#register_calls)*
+                signals
             }
 
             pub fn writers(&self) -> #writers_name #ty_generics {
@@ -0,0 +1,244 @@
+//! Accessibility tree export.
+//!
+//! Widgets describe themselves to assistive technology through
+//! [`Widget::accessibility`](crate::widgets::Widget::accessibility), which
+//! returns a role plus an optional name and value. [`access_tree`] walks the
+//! widget tree and combines those descriptions with layout bounds and keyboard
+//! focus into an [`AccessNode`] tree. With the `accesskit` feature (on by
+//! default) every surface publishes this tree over AT-SPI, so screen readers
+//! such as Orca can read it and follow keyboard focus.
+//!
+//! # Example
+//!
+//! ```ignore
+//! let root = access_tree(&tree, root_id);
+//! for child in &root.children {
+//!     println!("{:?} {:?}", child.role, child.name);
+//! }
+//! ```
+
+use crate::reactive::focused_widget;
+use crate::tree::{Tree, WidgetId};
+use crate::widgets::Rect;
+
+/// What kind of element a widget is, from the point of view of a screen reader.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    /// A layout container with no semantics of its own
+    Group,
+    /// Static text
+    Label,
+    /// Editable text field
+    TextInput,
+    /// Clickable element
+    Button,
+    /// Image or icon
+    Image,
+    /// Set of mutually exclusive options
+    RadioGroup,
+    /// One option of a radio group
+    RadioButton,
+}
+
+/// A widget's self-description for assistive technology.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AccessInfo {
+    pub role: Role,
+    /// Accessible name (e.g. a label's text)
+    pub name: Option<String>,
+    /// Current value (e.g. a text input's contents)
+    pub value: Option<String>,
+    /// Checked state of toggles such as radio buttons
+    pub checked: Option<bool>,
+}
+
+impl AccessInfo {
+    pub fn new(role: Role) -> Self {
+        Self {
+            role,
+            name: None,
+            value: None,
+            checked: None,
+        }
+    }
+
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    pub fn value(mut self, value: impl Into<String>) -> Self {
+        self.value = Some(value.into());
+        self
+    }
+
+    pub fn checked(mut self, checked: bool) -> Self {
+        self.checked = Some(checked);
+        self
+    }
+}
+
+/// A node in the exported accessibility tree.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AccessNode {
+    pub id: WidgetId,
+    pub role: Role,
+    pub name: Option<String>,
+    pub value: Option<String>,
+    pub checked: Option<bool>,
+    /// Surface-relative bounds
+    pub bounds: Rect,
+    /// Whether the widget has keyboard focus
+    pub focused: bool,
+    pub children: Vec<AccessNode>,
+}
+
+impl AccessNode {
+    /// Find the node for `id` in this subtree.
+    pub fn find(&self, id: WidgetId) -> Option<&AccessNode> {
+        if self.id == id {
+            return Some(self);
+        }
+        self.children.iter().find_map(|child| child.find(id))
+    }
+
+    /// Concatenated names of all labels in this subtree.
+    fn label_text(&self) -> String {
+        let mut text = String::new();
+        self.collect_labels(&mut text);
+        text
+    }
+
+    fn collect_labels(&self, text: &mut String) {
+        if self.role == Role::Label
+            && let Some(ref name) = self.name
+        {
+            if !text.is_empty() {
+                text.push(' ');
+            }
+            text.push_str(name);
+        }
+        for child in &self.children {
+            child.collect_labels(text);
+        }
+    }
+}
+
+/// Build the accessibility tree rooted at `root`.
+///
+/// Widgets that don't describe themselves are exported as [`Role::Group`].
+/// Buttons and radio buttons without an explicit name are named after the
+/// labels they contain.
+pub fn access_tree(tree: &Tree, root: WidgetId) -> AccessNode {
+    build_node(tree, root, focused_widget())
+}
+
+fn build_node(tree: &Tree, id: WidgetId, focused: Option<WidgetId>) -> AccessNode {
+    let info = tree
+        .with_widget(id, |widget| widget.accessibility())
+        .flatten()
+        .unwrap_or_else(|| AccessInfo::new(Role::Group));
+    let children = tree
+        .get_children(id)
+        .iter()
+        .map(|&child| build_node(tree, child, focused))
+        .collect();
+    let mut node = AccessNode {
+        id,
+        role: info.role,
+        name: info.name,
+        value: info.value,
+        checked: info.checked,
+        bounds: tree.get_surface_relative_bounds(id).unwrap_or_default(),
+        focused: focused == Some(id),
+        children,
+    };
+    if matches!(node.role, Role::Button | Role::RadioButton) && node.name.is_none() {
+        let label = node.label_text();
+        if !label.is_empty() {
+            node.name = Some(label);
+        }
+    }
+    node
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout::{Constraints, Size};
+    use crate::renderer::PaintContext;
+    use crate::widgets::Widget;
+
+    struct MockWidget(Option<AccessInfo>);
+
+    impl Widget for MockWidget {
+        fn layout(&mut self, _tree: &mut Tree, _id: WidgetId, constraints: Constraints) -> Size {
+            Size::new(constraints.max_width, constraints.max_height)
+        }
+
+        fn paint(&self, _tree: &Tree, _id: WidgetId, _ctx: &mut PaintContext) {}
+
+        fn accessibility(&self) -> Option<AccessInfo> {
+            self.0.clone()
+        }
+    }
+
+    #[test]
+    fn test_button_is_named_after_its_labels() {
+        let mut tree = Tree::new();
+        let button = tree.register(Box::new(MockWidget(Some(AccessInfo::new(Role::Button)))));
+        let label = tree.register(Box::new(MockWidget(Some(
+            AccessInfo::new(Role::Label).name("Save"),
+        ))));
+        tree.set_parent(label, button);
+
+        let node = access_tree(&tree, button);
+        assert_eq!(node.role, Role::Button);
+        assert_eq!(node.name.as_deref(), Some("Save"));
+        assert_eq!(node.find(label).map(|n| n.role), Some(Role::Label));
+    }
+
+    #[test]
+    fn test_undescribed_widgets_are_groups() {
+        let mut tree = Tree::new();
+        let root = tree.register(Box::new(MockWidget(None)));
+        let input = tree.register(Box::new(MockWidget(Some(
+            AccessInfo::new(Role::TextInput).value("hello"),
+        ))));
+        tree.set_parent(input, root);
+
+        let node = access_tree(&tree, root);
+        assert_eq!(node.role, Role::Group);
+        assert_eq!(node.name, None);
+        assert_eq!(node.children[0].value.as_deref(), Some("hello"));
+        assert!(!node.children[0].focused);
+    }
+
+    #[test]
+    fn test_dynamic_children_describe_themselves() {
+        use crate::widgets::{container, image, text};
+
+        let mut tree = Tree::new();
+        let root = tree.register(Box::new(container().children(move || {
+            [
+                (0u64, (|| text("Volume").into_any()) as fn() -> _),
+                (1, || image("./speaker.svg").alt("Muted").into_any()),
+            ]
+        })));
+        tree.with_widget_mut(root, |widget, id, tree| widget.register_children(tree, id));
+        tree.with_widget_mut(root, |widget, id, tree| {
+            widget.layout(tree, id, Constraints::loose(Size::new(200.0, 50.0)))
+        });
+
+        let node = access_tree(&tree, root);
+        let children: Vec<_> = node
+            .children
+            .iter()
+            .map(|child| (child.role, child.name.as_deref()))
+            .collect();
+        assert_eq!(
+            children,
+            [(Role::Label, Some("Volume")), (Role::Image, Some("Muted"))]
+        );
+    }
+}
@@ -0,0 +1,128 @@
+//! Injectable clock for animations.
+//!
+//! Animations, ripples and marquees read the time through [`now`] instead of
+//! `Instant::now()`. By default it returns the real time; tests can
+//! [`pause_clock`] and [`advance_clock`] to step animations deterministically
+//! and assert intermediate values.
+//!
+//! Running animations measure their progress with a [`Stopwatch`], which
+//! advances by at most [`MAX_ANIMATION_STEP`] per frame. After the loop
+//! stalls (a slow frame, or the process being suspended) animations resume
+//! where they were instead of jumping ahead.
+//!
+//! # Example
+//!
+//! ```ignore
+//! pause_clock();
+//! let mut width = AnimationState::new(0.0f32, Transition::new(200.0, TimingFunction::Linear));
+//! width.animate_to(100.0);
+//! advance_clock(Duration::from_millis(100));
+//! width.advance();
+//! assert_eq!(*width.current(), 50.0);
+//! resume_clock();
+//! ```
+
+use std::cell::Cell;
+use std::time::{Duration, Instant};
+
+/// Longest stretch of time a single animation step covers.
+pub const MAX_ANIMATION_STEP: Duration = Duration::from_millis(100);
+
+thread_local! {
+    /// Frozen time while the clock is paused
+    static VIRTUAL_NOW: Cell<Option<Instant>> = const { Cell::new(None) };
+}
+
+/// The current animation time.
+pub fn now() -> Instant {
+    VIRTUAL_NOW
+        .with(|now| now.get())
+        .unwrap_or_else(Instant::now)
+}
+
+/// Time elapsed since `earlier` according to the animation clock.
+pub fn elapsed_since(earlier: Instant) -> Duration {
+    now().saturating_duration_since(earlier)
+}
+
+/// Freeze the animation clock at the current time.
+///
+/// Time only moves forward through [`advance_clock`] until [`resume_clock`].
+pub fn pause_clock() {
+    VIRTUAL_NOW.with(|now| {
+        if now.get().is_none() {
+            now.set(Some(Instant::now()));
+        }
+    });
+}
+
+/// Move the paused animation clock forward, pausing it first if needed.
+pub fn advance_clock(by: Duration) {
+    pause_clock();
+    VIRTUAL_NOW.with(|now| now.set(now.get().map(|t| t + by)));
+}
+
+/// Return the animation clock to real time.
+pub fn resume_clock() {
+    VIRTUAL_NOW.with(|now| now.set(None));
+}
+
+/// Whether the animation clock is paused.
+pub fn is_clock_paused() -> bool {
+    VIRTUAL_NOW.with(|now| now.get().is_some())
+}
+
+/// Animation time elapsed since a start, advanced once per frame.
+///
+/// Each [`tick`](Self::tick) adds the time since the previous one, capped at
+/// [`MAX_ANIMATION_STEP`], so a stall of the event loop only costs one capped
+/// step instead of skipping the animation ahead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stopwatch {
+    elapsed: Duration,
+    last_tick: Instant,
+}
+
+impl Stopwatch {
+    /// Start measuring from the current animation time.
+    pub fn start() -> Self {
+        Self {
+            elapsed: Duration::ZERO,
+            last_tick: now(),
+        }
+    }
+
+    /// Advance to the current animation time and return the total elapsed.
+    pub fn tick(&mut self) -> Duration {
+        let now = now();
+        self.elapsed += now
+            .saturating_duration_since(self.last_tick)
+            .min(MAX_ANIMATION_STEP);
+        self.last_tick = now;
+        self.elapsed
+    }
+
+    /// Elapsed time as of the last tick.
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stopwatch_caps_each_step() {
+        advance_clock(Duration::ZERO);
+        let mut stopwatch = Stopwatch::start();
+        advance_clock(Duration::from_millis(40));
+        assert_eq!(stopwatch.tick(), Duration::from_millis(40));
+
+        // A one-second stall only moves the animation by one capped step
+        advance_clock(Duration::from_secs(1));
+        assert_eq!(stopwatch.tick(), Duration::from_millis(140));
+        assert_eq!(stopwatch.elapsed(), Duration::from_millis(140));
+        resume_clock();
+    }
+}
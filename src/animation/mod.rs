@@ -1,8 +1,12 @@
 mod animatable;
+pub mod clock;
+mod motion;
 mod spring;
 mod timing;
 
 pub use animatable::Animatable;
+pub(crate) use motion::{end_finish_animations, finishing_animations, skip_animations};
+pub use motion::{finish_all_animations, reduce_motion, set_reduce_motion};
 pub use spring::{SpringConfig, SpringState};
 pub use timing::TimingFunction;
 
@@ -0,0 +1,55 @@
+//! Global "reduce motion" preference.
+//!
+//! When enabled, every [`Transition`](super::Transition) resolves instantly
+//! and decorative loops (ripples, marquees) are disabled, so widgets don't
+//! need to opt out individually.
+//!
+//! [`finish_all_animations`] applies the same shortcut once: animations in
+//! flight jump to their targets on the next frame.
+
+use std::cell::Cell;
+
+thread_local! {
+    static REDUCE_MOTION: Cell<bool> = const { Cell::new(false) };
+    /// Set until the end of the frame after `finish_all_animations`
+    static FINISH_ANIMATIONS: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Enable or disable reduced motion for the whole application.
+///
+/// Usually set once through [`App::reduce_motion`](crate::App::reduce_motion),
+/// but can be toggled at runtime (e.g. from a settings panel).
+pub fn set_reduce_motion(enabled: bool) {
+    REDUCE_MOTION.with(|flag| flag.set(enabled));
+}
+
+/// Whether reduced motion is enabled.
+pub fn reduce_motion() -> bool {
+    REDUCE_MOTION.with(|flag| flag.get())
+}
+
+/// Jump every in-flight animation to its target on the next frame.
+///
+/// Transitions and springs settle on their final values and ripples end,
+/// so their animation jobs stop and a capture taken afterwards shows the
+/// resting state. Animations started later run normally.
+pub fn finish_all_animations() {
+    FINISH_ANIMATIONS.with(|flag| flag.set(true));
+    crate::jobs::request_frame();
+}
+
+/// Whether [`finish_all_animations`] was called this frame.
+pub(crate) fn finishing_animations() -> bool {
+    FINISH_ANIMATIONS.with(|flag| flag.get())
+}
+
+/// Whether animations should resolve instantly: reduced motion is enabled,
+/// or [`finish_all_animations`] was called this frame.
+pub(crate) fn skip_animations() -> bool {
+    reduce_motion() || finishing_animations()
+}
+
+/// Let animations run again after the frame that finished them.
+pub(crate) fn end_finish_animations() {
+    FINISH_ANIMATIONS.with(|flag| flag.set(false));
+}
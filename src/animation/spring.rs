@@ -15,6 +15,12 @@
 //! - [`SpringConfig::BOUNCY`] - Noticeable overshoot and oscillation
 //! - [`SpringConfig::SNAPPY`] - Quick response with subtle overshoot
 //! - [`SpringConfig::GENTLE`] - Slow, smooth motion with minimal overshoot
+//! - [`SpringConfig::WOBBLY`] - Lively motion that swings back and forth a few times
+//! - [`SpringConfig::STIFF`] - Fast and firm, with a slight overshoot
+//! - [`SpringConfig::SLOW`] - Heavily damped, eases in without any overshoot
+//!
+//! For other feels, [`SpringConfig::from_response`] builds a spring from its
+//! response time and damping ratio, like SwiftUI's and iOS's spring APIs.
 //!
 //! ## Usage
 //!
@@ -67,6 +73,55 @@ impl SpringConfig {
         stiffness: 100.0,
         damping: 15.0,
     };
+
+    /// Wobbly spring - swings past the target a few times before settling
+    pub const WOBBLY: Self = Self {
+        mass: 1.0,
+        stiffness: 180.0,
+        damping: 12.0,
+    };
+
+    /// Stiff spring - fast and firm, with a slight overshoot
+    pub const STIFF: Self = Self {
+        mass: 1.0,
+        stiffness: 210.0,
+        damping: 20.0,
+    };
+
+    /// Slow spring - overdamped, eases into the target without overshoot
+    pub const SLOW: Self = Self {
+        mass: 1.0,
+        stiffness: 280.0,
+        damping: 60.0,
+    };
+
+    /// Create a spring from its response and damping ratio.
+    ///
+    /// `response` is the period of the undamped oscillation in seconds:
+    /// smaller values make a faster spring. `damping_ratio` controls the
+    /// overshoot: `1.0` settles as fast as possible without overshooting,
+    /// lower values bounce (0.5 is lively, 0.8 barely overshoots) and higher
+    /// values approach the target more slowly.
+    ///
+    /// ```ignore
+    /// Transition::spring(SpringConfig::from_response(0.4, 0.8))
+    /// ```
+    pub fn from_response(response: f32, damping_ratio: f32) -> Self {
+        let response = response.max(0.01);
+        let angular_frequency = std::f32::consts::TAU / response;
+        Self {
+            mass: 1.0,
+            stiffness: angular_frequency * angular_frequency,
+            damping: 2.0 * damping_ratio.max(0.0) * angular_frequency,
+        }
+    }
+
+    /// Ratio of the damping to the critical damping (`1.0`).
+    ///
+    /// Springs below `1.0` overshoot the target, springs above it don't.
+    pub fn damping_ratio(&self) -> f32 {
+        self.damping / (2.0 * (self.stiffness * self.mass).sqrt())
+    }
 }
 
 /// State for spring physics simulation
@@ -170,6 +225,41 @@ mod tests {
         );
     }
 
+    /// Peak position of a spring over 2 seconds at 60 fps, and its final position.
+    fn simulate(config: &SpringConfig) -> (f32, f32) {
+        let mut state = SpringState::new();
+        let mut peak: f32 = 0.0;
+        let mut position = 0.0;
+        for i in 0..120 {
+            position = state.step(i as f32 / 60.0, config);
+            peak = peak.max(position);
+        }
+        (peak, position)
+    }
+
+    #[test]
+    fn test_from_response() {
+        let config = SpringConfig::from_response(0.4, 0.8);
+        assert!((config.stiffness - 246.74).abs() < 0.01);
+        assert!((config.damping - 25.13).abs() < 0.01);
+        assert!((config.damping_ratio() - 0.8).abs() < 1e-4);
+
+        // Barely overshoots and has settled well within two seconds
+        let (peak, position) = simulate(&config);
+        assert!(peak > 1.0 && peak < 1.03, "peak was {peak}");
+        assert!((position - 1.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_presets_feel_distinct() {
+        let (wobbly, _) = simulate(&SpringConfig::WOBBLY);
+        let (stiff, _) = simulate(&SpringConfig::STIFF);
+        let (slow, _) = simulate(&SpringConfig::SLOW);
+        assert!(wobbly > stiff && stiff > 1.0);
+        assert!(slow <= 1.0);
+        assert!(SpringConfig::SLOW.damping_ratio() > 1.0);
+    }
+
     #[test]
     fn test_spring_overshoots() {
         let mut state = SpringState::new();
@@ -21,18 +21,36 @@
 //!
 //! When a job is pushed, the system automatically wakes the event loop via a ping
 //! mechanism, ensuring the frame is processed promptly.
+//!
+//! ## Feedback Loops
+//!
+//! A frame normally ends with only animation jobs queued. If layout, paint or
+//! other jobs are requested again during every frame (e.g. layout writes a
+//! signal that layout reads), the loop never goes idle. After
+//! [`FEEDBACK_LOOP_FRAMES`] such frames in a row, a warning with the pending
+//! [`JobCounts`] is logged. [`pending_job_counts`] exposes the same counts.
+//!
+//! ## Frame Pacing
+//!
+//! Signals written faster than the frame rate (a timer or background thread
+//! updating a width every few milliseconds) would otherwise wake the loop and
+//! re-layout once per write. Without input or animations pending, the loop
+//! waits out the rest of [`FRAME_INTERVAL`] after the last frame (see
+//! [`frame_delay`]), so all writes of that interval land in one layout.
 
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::collections::HashSet;
 use std::sync::{
     Mutex,
     atomic::{AtomicBool, AtomicU8, Ordering},
 };
+use std::time::Duration;
 
 use smallvec::SmallVec;
 use smithay_client_toolkit::reexports::calloop::ping::Ping;
 
 use crate::reactive::invalidation::clear_widget_subscribers;
+use crate::reactive::leave_hover_cursor;
 use crate::tree::{Tree, WidgetId};
 
 /// Job queue with O(1) dedup via HashSet + Vec for ordered iteration.
@@ -77,6 +95,26 @@ impl JobQueue {
     fn is_empty(&self) -> bool {
         self.vec.is_empty()
     }
+
+    fn has_animations(&self) -> bool {
+        self.vec
+            .iter()
+            .any(|job| job.job_type == JobType::Animation)
+    }
+
+    fn counts(&self) -> JobCounts {
+        let mut counts = JobCounts::default();
+        for job in &self.vec {
+            match job.job_type {
+                JobType::Layout => counts.layout += 1,
+                JobType::Paint => counts.paint += 1,
+                JobType::Reconcile => counts.reconcile += 1,
+                JobType::Unregister => counts.unregister += 1,
+                JobType::Animation => counts.animation += 1,
+            }
+        }
+        counts
+    }
 }
 
 // Thread-local job queue for pending reactive updates.
@@ -84,6 +122,38 @@ impl JobQueue {
 // so no Mutex is needed.
 thread_local! {
     static PENDING_JOBS: RefCell<JobQueue> = RefCell::new(JobQueue::new());
+    /// Root widgets of surfaces whose rendering is suspended
+    static SUSPENDED_ROOTS: RefCell<Vec<WidgetId>> = const { RefCell::new(Vec::new()) };
+    /// Animation jobs held back until their surface resumes: (root, widget)
+    static PARKED_ANIMATIONS: RefCell<Vec<(WidgetId, WidgetId)>> = const { RefCell::new(Vec::new()) };
+    /// Consecutive frames that ended with non-animation jobs queued
+    static BUSY_FRAMES: Cell<u32> = const { Cell::new(0) };
+}
+
+/// Consecutive frames ending with non-animation jobs queued before a
+/// feedback loop is reported (about five seconds at 60fps).
+pub const FEEDBACK_LOOP_FRAMES: u32 = 300;
+
+/// Number of pending jobs of each [`JobType`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct JobCounts {
+    pub layout: usize,
+    pub paint: usize,
+    pub reconcile: usize,
+    pub unregister: usize,
+    pub animation: usize,
+}
+
+impl JobCounts {
+    /// Pending jobs other than animation continuations.
+    pub fn non_animation(&self) -> usize {
+        self.layout + self.paint + self.reconcile + self.unregister
+    }
+
+    /// All pending jobs.
+    pub fn total(&self) -> usize {
+        self.non_animation() + self.animation
+    }
 }
 
 /// Job types for reactive invalidation (stored in the queue)
@@ -212,9 +282,15 @@ pub fn process_jobs(jobs: &[Job], tree: &mut Tree, layout_roots: &mut Vec<Widget
     // Process in required order
     for id in unregister {
         clear_widget_subscribers(id);
+        leave_hover_cursor(id);
         tree.unregister(id);
     }
     for id in animation {
+        // Animations of suspended surfaces wait until the surface resumes
+        if let Some(root) = suspended_root(tree, id) {
+            PARKED_ANIMATIONS.with(|parked| parked.borrow_mut().push((root, id)));
+            continue;
+        }
         tree.with_widget_mut(id, |widget, wid, tree| {
             widget.advance_animations(tree, wid);
         });
@@ -241,12 +317,138 @@ pub fn process_jobs(jobs: &[Job], tree: &mut Tree, layout_roots: &mut Vec<Widget
     }
 }
 
+/// The suspended surface root `id` belongs to, if any.
+fn suspended_root(tree: &Tree, id: WidgetId) -> Option<WidgetId> {
+    SUSPENDED_ROOTS.with(|roots| {
+        let roots = roots.borrow();
+        if roots.is_empty() {
+            return None;
+        }
+        let mut current = id;
+        while let Some(parent) = tree.get_parent(current) {
+            current = parent;
+        }
+        roots.contains(&current).then_some(current)
+    })
+}
+
+/// Suspend or resume animations of the surface whose root widget is `root`.
+///
+/// While suspended, animation jobs of its widgets are parked instead of
+/// advanced, so the event loop can sleep. Resuming re-queues them.
+pub(crate) fn set_root_suspended(root: WidgetId, suspended: bool) {
+    SUSPENDED_ROOTS.with(|roots| {
+        let mut roots = roots.borrow_mut();
+        if suspended {
+            if !roots.contains(&root) {
+                roots.push(root);
+            }
+        } else {
+            roots.retain(|&r| r != root);
+        }
+    });
+    if !suspended {
+        let resumed: Vec<WidgetId> = PARKED_ANIMATIONS.with(|parked| {
+            parked
+                .borrow_mut()
+                .extract_if(.., |(r, _)| *r == root)
+                .map(|(_, id)| id)
+                .collect()
+        });
+        for id in resumed {
+            request_job(id, JobRequest::Animation(RequiredJob::None));
+        }
+    }
+}
+
 /// Check if there are pending jobs.
 /// This includes both regular jobs and animation jobs.
 pub fn has_pending_jobs() -> bool {
     PENDING_JOBS.with(|jobs| !jobs.borrow().is_empty())
 }
 
+/// Count the jobs waiting for the next frame, by type.
+///
+/// Useful to inspect reactive churn, e.g. from a debug overlay or a test.
+pub fn pending_job_counts() -> JobCounts {
+    PENDING_JOBS.with(|jobs| jobs.borrow().counts())
+}
+
+/// Update the busy-frame streak with the jobs left at the end of a frame.
+///
+/// Returns `true` once, when the streak reaches [`FEEDBACK_LOOP_FRAMES`].
+fn track_busy_frame(streak: &Cell<u32>, left: JobCounts) -> bool {
+    if left.non_animation() == 0 {
+        streak.set(0);
+        return false;
+    }
+    let frames = streak.get().saturating_add(1);
+    streak.set(frames);
+    frames == FEEDBACK_LOOP_FRAMES
+}
+
+/// Check the jobs left at the end of a frame for a feedback loop.
+///
+/// Called by the main loop after all surfaces have rendered.
+pub(crate) fn check_feedback_loop() {
+    let left = pending_job_counts();
+    if BUSY_FRAMES.with(|streak| track_busy_frame(streak, left)) {
+        log::warn!(
+            "Jobs have been requested again during each of the last {} frames \
+             ({} layout, {} paint, {} reconcile, {} unregister pending). \
+             A signal is probably written by the layout, paint or effect that reads it.",
+            FEEDBACK_LOOP_FRAMES,
+            left.layout,
+            left.paint,
+            left.reconcile,
+            left.unregister,
+        );
+    }
+}
+
+/// Check if any widget has animation jobs pending.
+pub fn has_animation_jobs() -> bool {
+    PENDING_JOBS.with(|jobs| jobs.borrow().has_animations())
+}
+
+/// Shortest time between two frames rendered for reactive updates alone.
+pub const FRAME_INTERVAL: Duration = Duration::from_millis(16);
+
+/// How long the event loop should keep collecting updates before the next
+/// frame, given the time since the last one.
+///
+/// Returns `None` to render right away: when the last frame is at least
+/// [`FRAME_INTERVAL`] old, or when input or animations are waiting, which must
+/// not be delayed (animations are already paced by the frame loop).
+pub fn frame_delay(since_last_frame: Duration, urgent: bool) -> Option<Duration> {
+    if urgent {
+        return None;
+    }
+    FRAME_INTERVAL
+        .checked_sub(since_last_frame)
+        .filter(|wait| !wait.is_zero())
+}
+
+/// Keep collecting updates until the next frame is due.
+///
+/// While jobs are pending and [`frame_delay`] asks for a delay, `wait`
+/// dispatches the event loop for at most that long; signal writes it delivers
+/// join the same frame. `since_last_frame` and `urgent` are re-read from
+/// `state` after every wait. Returns early when an exit is requested.
+pub(crate) fn collect_until_frame<S>(
+    state: &mut S,
+    since_last_frame: impl Fn(&S) -> Duration,
+    urgent: impl Fn(&S) -> bool,
+    mut wait: impl FnMut(&mut S, Duration),
+) {
+    while has_pending_jobs()
+        && let Some(delay) = frame_delay(since_last_frame(state), urgent(state))
+        && get_exit_request() == ExitRequest::Running
+    {
+        wait(state, delay);
+    }
+}
+
 /// Clear all pending jobs (for testing)
 #[cfg(test)]
 fn clear_pending_jobs() {
@@ -326,6 +528,9 @@ pub(crate) fn reset_jobs() {
     PENDING_JOBS.with(|jobs| {
         jobs.borrow_mut().drain_all();
     });
+    SUSPENDED_ROOTS.with(|roots| roots.borrow_mut().clear());
+    PARKED_ANIMATIONS.with(|parked| parked.borrow_mut().clear());
+    BUSY_FRAMES.with(|streak| streak.set(0));
     FRAME_REQUESTED.store(false, Ordering::Relaxed);
     EXIT_REQUEST.store(ExitRequest::Running as u8, Ordering::Relaxed);
     if let Ok(mut guard) = WAKEUP_PING.lock() {
@@ -435,4 +640,155 @@ mod tests {
         let remaining = drain_pending_jobs();
         assert_eq!(remaining.len(), 2);
     }
+
+    #[test]
+    fn pending_job_counts_by_type() {
+        clear_pending_jobs();
+        request_job(widget_id(1), JobRequest::Animation(RequiredJob::Layout));
+        request_job(widget_id(2), JobRequest::Paint);
+        request_job(widget_id(2), JobRequest::Paint);
+
+        let counts = pending_job_counts();
+        assert_eq!(
+            counts,
+            JobCounts {
+                layout: 1,
+                paint: 1,
+                animation: 1,
+                ..JobCounts::default()
+            }
+        );
+        assert_eq!(counts.total(), 3);
+        clear_pending_jobs();
+    }
+
+    #[test]
+    fn busy_frames_report_once_per_streak() {
+        let streak = Cell::new(0);
+        let busy = JobCounts {
+            layout: 1,
+            ..JobCounts::default()
+        };
+        let animating = JobCounts {
+            animation: 4,
+            ..JobCounts::default()
+        };
+        let reports = (0..FEEDBACK_LOOP_FRAMES * 2)
+            .filter(|_| track_busy_frame(&streak, busy))
+            .count();
+        assert_eq!(reports, 1);
+
+        // Frames ending with only animations queued are idle
+        assert!(!track_busy_frame(&streak, animating));
+        assert_eq!(streak.get(), 0);
+    }
+
+    #[test]
+    fn frame_delay_waits_out_the_interval() {
+        let ms = Duration::from_millis;
+        assert_eq!(frame_delay(ms(4), false), Some(ms(12)));
+        assert_eq!(frame_delay(FRAME_INTERVAL, false), None);
+        assert_eq!(frame_delay(ms(40), false), None);
+        // Input and animations render right away
+        assert_eq!(frame_delay(ms(4), true), None);
+    }
+
+    #[test]
+    fn signal_writes_in_one_frame_lay_out_once() {
+        use crate::layout::{Constraints, Size};
+        use crate::reactive::create_signal;
+        use crate::widgets::container;
+
+        clear_pending_jobs();
+        let width = create_signal(10.0f32);
+        let mut tree = Tree::new();
+        let root = tree.register(Box::new(
+            container().child(container().width(move || width.get())),
+        ));
+        tree.with_widget_mut(root, |widget, id, tree| widget.register_children(tree, id));
+        let layout = |tree: &mut Tree, id: WidgetId| {
+            tree.with_widget_mut(id, |widget, id, tree| {
+                widget.layout(tree, id, Constraints::loose(Size::new(400.0, 400.0)))
+            });
+        };
+        layout(&mut tree, root);
+        let child = tree.get_children(root)[0];
+        clear_pending_jobs();
+
+        // A frame was just rendered; a write wakes the loop, and nine more
+        // follow a millisecond apart, well within the frame interval
+        struct Clock {
+            since_last_frame: Duration,
+            writes: u32,
+        }
+        let mut clock = Clock {
+            since_last_frame: Duration::ZERO,
+            writes: 1,
+        };
+        width.set(11.0);
+        let mut waits = 0;
+        collect_until_frame(
+            &mut clock,
+            |clock| clock.since_last_frame,
+            |_| false,
+            |clock, delay| {
+                waits += 1;
+                if clock.writes < 10 {
+                    // The write's wakeup ends the dispatch early
+                    clock.since_last_frame += Duration::from_millis(1);
+                    clock.writes += 1;
+                    width.set(10.0 + clock.writes as f32);
+                } else {
+                    clock.since_last_frame += delay;
+                }
+            },
+        );
+        // Every write was collected before the frame was due
+        assert_eq!(clock.writes, 10);
+        assert_eq!(clock.since_last_frame, FRAME_INTERVAL);
+        assert_eq!(waits, 10);
+
+        // ...and the one frame lays the tree out once
+        let jobs = drain_pending_jobs();
+        assert_eq!(jobs.len(), 1);
+        let mut layout_roots = Vec::new();
+        process_jobs(&jobs, &mut tree, &mut layout_roots);
+        assert_eq!(layout_roots, [root]);
+
+        layout(&mut tree, root);
+        assert_eq!(tree.cached_size(child), Some(Size::new(20.0, 0.0)));
+        assert!(!tree.needs_layout(child));
+    }
+
+    #[test]
+    fn urgent_updates_skip_collecting() {
+        clear_pending_jobs();
+        request_job(widget_id(1), JobRequest::Paint);
+        let mut waited = false;
+        collect_until_frame(&mut (), |_| Duration::ZERO, |_| true, |_, _| waited = true);
+        assert!(!waited);
+        clear_pending_jobs();
+    }
+
+    #[test]
+    fn suspended_surface_parks_animations_until_resumed() {
+        use crate::widgets::container;
+
+        clear_pending_jobs();
+        let mut tree = Tree::new();
+        let root = tree.register(Box::new(container().child(container())));
+        tree.with_widget_mut(root, |widget, id, tree| widget.register_children(tree, id));
+        let child = tree.get_children(root)[0];
+
+        set_root_suspended(root, true);
+        let anim = Job {
+            widget_id: child,
+            job_type: JobType::Animation,
+        };
+        process_jobs(&[anim], &mut tree, &mut Vec::new());
+        assert!(drain_pending_jobs().is_empty());
+
+        set_root_suspended(root, false);
+        assert_eq!(drain_pending_jobs(), vec![anim]);
+    }
 }
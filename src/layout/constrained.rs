@@ -0,0 +1,94 @@
+//! Layout that imposes extra size constraints on its child.
+
+use crate::tree::{Tree, WidgetId};
+
+use super::{Constraints, Layout, Size, layout_child};
+
+/// Layout that narrows the constraints passed to its children to a min/max
+/// range, like Flutter's `ConstrainedBox`.
+///
+/// The range is enforced within the incoming constraints: a maximum above
+/// what the parent allows is capped, and a minimum below what the parent
+/// requires is raised. Children are placed at the same position and the
+/// layout sizes to the largest of them.
+///
+/// Use `f32::INFINITY` as a maximum to leave it to the parent.
+pub struct Constrained {
+    min_width: f32,
+    max_width: f32,
+    min_height: f32,
+    max_height: f32,
+}
+
+impl Constrained {
+    /// Create a constrained layout with the given width and height ranges
+    pub fn new(min_width: f32, max_width: f32, min_height: f32, max_height: f32) -> Self {
+        Self {
+            min_width,
+            max_width,
+            min_height,
+            max_height,
+        }
+    }
+
+    /// The constraints children get when the parent passes `constraints`.
+    fn enforce(&self, constraints: Constraints) -> Constraints {
+        let clamp_width = |v: f32| v.clamp(constraints.min_width, constraints.max_width);
+        let clamp_height = |v: f32| v.clamp(constraints.min_height, constraints.max_height);
+        let min_width = clamp_width(self.min_width);
+        let min_height = clamp_height(self.min_height);
+        Constraints::new(
+            min_width,
+            min_height,
+            clamp_width(self.max_width).max(min_width),
+            clamp_height(self.max_height).max(min_height),
+        )
+    }
+}
+
+impl Layout for Constrained {
+    fn layout(
+        &mut self,
+        tree: &mut Tree,
+        children: &[WidgetId],
+        constraints: Constraints,
+        origin: (f32, f32),
+    ) -> Size {
+        let inner = self.enforce(constraints);
+        let mut size = Size::zero();
+        for &child_id in children {
+            let margin = tree
+                .with_widget(child_id, |w| w.layout_hints().margin)
+                .unwrap_or_default();
+            if let Some(child_size) = layout_child(tree, child_id, inner, margin) {
+                tree.set_origin(child_id, origin.0 + margin.left, origin.1 + margin.top);
+                size.width = size.width.max(child_size.width);
+                size.height = size.height.max(child_size.height);
+            }
+        }
+        inner.constrain(size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_enforce_within_parent() {
+        let parent = Constraints::new(0.0, 0.0, 800.0, 600.0);
+
+        // A max width below the parent's narrows it
+        let inner = Constrained::new(0.0, 300.0, 0.0, f32::INFINITY).enforce(parent);
+        assert_eq!(inner, Constraints::new(0.0, 0.0, 300.0, 600.0));
+
+        // Minimums tighten, but never past the parent's maximum
+        let inner = Constrained::new(100.0, 1000.0, 900.0, f32::INFINITY).enforce(parent);
+        assert_eq!(inner, Constraints::new(100.0, 600.0, 800.0, 600.0));
+
+        // The parent's minimum wins over a smaller maximum
+        let tight = Constraints::tight(Size::new(400.0, 50.0));
+        let inner = Constrained::new(0.0, 300.0, 0.0, 20.0).enforce(tight);
+        assert_eq!(inner, tight);
+    }
+}
@@ -19,19 +19,21 @@
 //! **Cross axis** ([`CrossAlignment`]):
 //! - `Start`, `Center`, `End` - Align children along cross axis
 //! - `Stretch` - Stretch children to fill cross axis (default)
+//! - `Baseline` - Align the first text baseline of children in a row
 //!
 //! ## Usage
 //!
 //! ```ignore
 //! container()
-//!     .layout(Flex::row().spacing(8.0).main_alignment(MainAlignment::Center))
+//!     .layout(Flex::row().gap(8.0).main_alignment(MainAlignment::Center))
 //!     .children([button_a, button_b, button_c])
 //! ```
 
-use super::{Axis, Constraints, CrossAlignment, Layout, MainAlignment, Size};
+use super::{Axis, Constraints, CrossAlignment, Layout, MainAlignment, Size, layout_child};
 use crate::{
     reactive::{IntoSignal, OptionSignalExt, Signal, create_stored},
     tree::{Tree, WidgetId},
+    widgets::Padding,
 };
 
 /// Children with main-axis size below this threshold are treated as invisible
@@ -42,10 +44,14 @@ const MIN_VISIBLE_SIZE: f32 = 0.5;
 pub struct Flex {
     direction: Signal<Axis>,
     spacing: Option<Signal<f32>>,
+    row_gap: Option<Signal<f32>>,
+    column_gap: Option<Signal<f32>>,
     main_alignment: Option<Signal<MainAlignment>>,
     cross_alignment: Option<Signal<CrossAlignment>>,
 
+    /// Child sizes including their margins
     child_sizes: Vec<Size>,
+    child_margins: Vec<Padding>,
     fill_indices: Vec<usize>,
 }
 
@@ -59,9 +65,12 @@ impl Flex {
         Self {
             direction: create_stored(direction),
             spacing: None,
+            row_gap: None,
+            column_gap: None,
             main_alignment: None,
             cross_alignment: None,
             child_sizes: Vec::with_capacity(8),
+            child_margins: Vec::with_capacity(8),
             fill_indices: Vec::new(),
         }
     }
@@ -82,6 +91,25 @@ impl Flex {
         self
     }
 
+    /// Set the gap between children (same as [`spacing`](Self::spacing))
+    pub fn gap<M>(self, gap: impl IntoSignal<f32, M>) -> Self {
+        self.spacing(gap)
+    }
+
+    /// Set the gap between children stacked vertically, overriding
+    /// [`gap`](Self::gap) while the direction is a column
+    pub fn row_gap<M>(mut self, gap: impl IntoSignal<f32, M>) -> Self {
+        self.row_gap = Some(gap.into_signal());
+        self
+    }
+
+    /// Set the gap between children placed side by side, overriding
+    /// [`gap`](Self::gap) while the direction is a row
+    pub fn column_gap<M>(mut self, gap: impl IntoSignal<f32, M>) -> Self {
+        self.column_gap = Some(gap.into_signal());
+        self
+    }
+
     /// Set the main axis alignment
     pub fn main_alignment<M>(mut self, alignment: impl IntoSignal<MainAlignment, M>) -> Self {
         self.main_alignment = Some(alignment.into_signal());
@@ -133,7 +161,11 @@ impl Flex {
         origin: (f32, f32),
         axis: Axis,
     ) -> Size {
-        let spacing = self.spacing.get_or(0.0);
+        let axis_gap = match axis {
+            Axis::Horizontal => self.column_gap,
+            Axis::Vertical => self.row_gap,
+        };
+        let spacing = axis_gap.or(self.spacing).get_or(0.0);
         let main_align = self.main_alignment.get_or(MainAlignment::Start);
         let cross_align = self.cross_alignment.get_or(CrossAlignment::Stretch);
 
@@ -176,6 +208,9 @@ impl Flex {
         // Pre-allocate child_sizes
         self.child_sizes.clear();
         self.child_sizes.resize(children.len(), Size::zero());
+        self.child_margins.clear();
+        self.child_margins
+            .resize(children.len(), Padding::default());
 
         // Pass 1: layout non-fill children and collect fill child indices
         let mut non_fill_main = 0.0f32;
@@ -183,18 +218,21 @@ impl Flex {
         self.fill_indices.clear();
 
         for (i, &child_id) in children.iter().enumerate() {
-            let is_fill = tree
-                .with_widget(child_id, |w| match axis {
-                    Axis::Horizontal => w.layout_hints().fill_width,
-                    Axis::Vertical => w.layout_hints().fill_height,
-                })
-                .unwrap_or(false);
-
-            if is_fill {
+            let hints = tree
+                .with_widget(child_id, |w| w.layout_hints())
+                .unwrap_or_default();
+            let is_fill = match axis {
+                Axis::Horizontal => hints.fill_width,
+                Axis::Vertical => hints.fill_height,
+            };
+            self.child_margins[i] = hints.margin;
+
+            // Without a main-axis limit there is no space to fill, so fill
+            // children are sized to their content like the others
+            if is_fill && main_max.is_finite() {
                 self.fill_indices.push(i);
-            } else if let Some(size) = tree.with_widget_mut(child_id, |widget, id, tree| {
-                widget.layout(tree, id, child_constraints)
-            }) {
+            } else if let Some(size) = layout_child(tree, child_id, child_constraints, hints.margin)
+            {
                 non_fill_main += size.main_axis(axis);
                 max_cross = max_cross.max(size.cross_axis(axis));
                 self.child_sizes[i] = size;
@@ -231,9 +269,9 @@ impl Flex {
 
             for &i in &self.fill_indices {
                 let child_id = children[i];
-                if let Some(size) = tree.with_widget_mut(child_id, |widget, id, tree| {
-                    widget.layout(tree, id, fill_constraints)
-                }) {
+                if let Some(size) =
+                    layout_child(tree, child_id, fill_constraints, self.child_margins[i])
+                {
                     max_cross = max_cross.max(size.cross_axis(axis));
                     self.child_sizes[i] = size;
                 }
@@ -249,6 +287,20 @@ impl Flex {
             Axis::Vertical => (constraints.min_height, constraints.min_width),
         };
 
+        // Baseline rows need room above the deepest baseline and below the
+        // lowest descent, which can exceed the tallest child
+        let mut max_baseline = 0.0f32;
+        if cross_align == CrossAlignment::Baseline && axis == Axis::Horizontal {
+            let mut max_descent = 0.0f32;
+            for (i, &child_id) in children.iter().enumerate() {
+                let height = self.child_sizes[i].height;
+                let baseline = child_baseline(tree, child_id, self.child_margins[i], height);
+                max_baseline = max_baseline.max(baseline);
+                max_descent = max_descent.max(height - baseline);
+            }
+            max_cross = max_cross.max(max_baseline + max_descent);
+        }
+
         let cross_size = max_cross.max(cross_constraint_min).min(cross_max);
 
         // For Stretch: if we didn't have a known cross size before, re-layout children
@@ -290,9 +342,9 @@ impl Flex {
                         max_height: main_constraint,
                     },
                 };
-                if let Some(size) = tree.with_widget_mut(child_id, |widget, id, tree| {
-                    widget.layout(tree, id, stretch_constraints)
-                }) {
+                if let Some(size) =
+                    layout_child(tree, child_id, stretch_constraints, self.child_margins[i])
+                {
                     children_main += size.main_axis(axis);
                     self.child_sizes[i] = size;
                 }
@@ -366,6 +418,14 @@ impl Flex {
                     Axis::Horizontal => origin.1,
                     Axis::Vertical => origin.0,
                 },
+                CrossAlignment::Baseline => match axis {
+                    Axis::Horizontal => {
+                        let margin = self.child_margins[i];
+                        let baseline = child_baseline(tree, child_id, margin, child_cross);
+                        origin.1 + max_baseline - baseline
+                    }
+                    Axis::Vertical => origin.0,
+                },
             };
 
             let (x, y) = match axis {
@@ -373,7 +433,9 @@ impl Flex {
                 Axis::Vertical => (cross_pos, main_pos),
             };
 
-            tree.set_origin(child_id, x, y);
+            // Sizes include the margin; the widget itself starts inside it
+            let margin = self.child_margins[i];
+            tree.set_origin(child_id, x + margin.left, y + margin.top);
             main_pos += child_main;
 
             if child_main > MIN_VISIBLE_SIZE {
@@ -385,6 +447,14 @@ impl Flex {
     }
 }
 
+/// A child's baseline measured from the top of its margin box. Children
+/// without text use their bottom edge (`outer_height`).
+fn child_baseline(tree: &Tree, child_id: WidgetId, margin: Padding, outer_height: f32) -> f32 {
+    tree.baseline(child_id)
+        .map(|baseline| baseline + margin.top)
+        .unwrap_or(outer_height)
+}
+
 impl Layout for Flex {
     fn layout(
         &mut self,
@@ -396,4 +466,171 @@ impl Layout for Flex {
         let direction = self.direction.get();
         self.layout_axis(tree, children, constraints, origin, direction)
     }
+
+    fn track_signals(&self) {
+        let _ = self.direction.get();
+        for gap in [self.spacing, self.row_gap, self.column_gap]
+            .into_iter()
+            .flatten()
+        {
+            let _ = gap.get();
+        }
+        let _ = self.main_alignment.map(|a| a.get());
+        let _ = self.cross_alignment.map(|a| a.get());
+    }
+
+    fn set_spacing(&mut self, spacing: Signal<f32>) {
+        self.spacing = Some(spacing);
+    }
+
+    fn set_main_alignment(&mut self, alignment: Signal<MainAlignment>) {
+        self.main_alignment = Some(alignment);
+    }
+
+    fn set_cross_alignment(&mut self, alignment: Signal<CrossAlignment>) {
+        self.cross_alignment = Some(alignment);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jobs::{Job, JobType, drain_pending_jobs};
+    use crate::reactive::create_signal;
+    use crate::widgets::container;
+
+    fn block(tree: &mut Tree, height: f32) -> WidgetId {
+        tree.register(Box::new(container().width(20.0).height(height)))
+    }
+
+    #[test]
+    fn test_gap_only_between_children() {
+        let mut tree = Tree::new();
+        let children = [
+            block(&mut tree, 10.0),
+            block(&mut tree, 30.0),
+            block(&mut tree, 10.0),
+        ];
+
+        let mut flex = Flex::column().gap(12.0);
+        let size = flex.layout(
+            &mut tree,
+            &children,
+            Constraints::loose(Size::new(100.0, 500.0)),
+            (0.0, 0.0),
+        );
+
+        assert_eq!(size.height, 10.0 + 30.0 + 10.0 + 2.0 * 12.0);
+        let tops: Vec<f32> = children
+            .iter()
+            .map(|&c| tree.get_bounds(c).unwrap().y)
+            .collect();
+        assert_eq!(tops, [0.0, 22.0, 64.0]);
+    }
+
+    #[test]
+    fn test_baseline_aligns_text_of_different_sizes() {
+        use crate::widgets::text;
+
+        let mut tree = Tree::new();
+        let id = tree.register(Box::new(
+            container()
+                .layout(Flex::row().cross_alignment(CrossAlignment::Baseline))
+                .child(text("Small").font_size(10.0))
+                .child(text("Big").font_size(32.0))
+                // Nested text passes its baseline up through the container
+                .child(container().padding(6.0).child(text("Mid").font_size(18.0))),
+        ));
+        tree.with_widget_mut(id, |widget, id, tree| widget.register_children(tree, id));
+        tree.with_widget_mut(id, |widget, id, tree| {
+            widget.layout(tree, id, Constraints::loose(Size::new(400.0, 200.0)))
+        });
+
+        let baselines: Vec<f32> = tree
+            .get_children(id)
+            .iter()
+            .map(|&child| tree.get_bounds(child).unwrap().y + tree.baseline(child).unwrap())
+            .collect();
+        assert!(
+            baselines.iter().all(|b| (b - baselines[0]).abs() < 0.01),
+            "{baselines:?}"
+        );
+        // The small text is pushed down to meet the big one's baseline
+        let small = tree.get_children(id)[0];
+        assert!(tree.get_bounds(small).unwrap().y > 0.0);
+    }
+
+    #[test]
+    fn test_gap_signal_relayouts_container() {
+        let gap = create_signal(4.0f32);
+        let mut tree = Tree::new();
+        let id = tree.register(Box::new(
+            container()
+                .layout(Flex::column().gap(gap))
+                .children([container().height(10.0), container().height(10.0)]),
+        ));
+        tree.with_widget_mut(id, |widget, id, tree| widget.register_children(tree, id));
+        tree.with_widget_mut(id, |widget, id, tree| {
+            widget.layout(tree, id, Constraints::loose(Size::new(100.0, 100.0)))
+        });
+        drain_pending_jobs();
+
+        gap.set(8.0);
+        let jobs = drain_pending_jobs();
+        assert!(jobs.contains(&Job {
+            widget_id: id,
+            job_type: JobType::Layout,
+        }));
+    }
+
+    #[test]
+    fn test_axis_gap_overrides_gap() {
+        let mut tree = Tree::new();
+        let children = [block(&mut tree, 10.0), block(&mut tree, 10.0)];
+
+        let mut flex = Flex::row().gap(4.0).row_gap(50.0).column_gap(6.0);
+        let size = flex.layout(
+            &mut tree,
+            &children,
+            Constraints::loose(Size::new(500.0, 100.0)),
+            (0.0, 0.0),
+        );
+
+        assert_eq!(size.width, 20.0 + 6.0 + 20.0);
+    }
+
+    fn row_lefts(id: WidgetId, tree: &mut Tree) -> Vec<f32> {
+        tree.with_widget_mut(id, |widget, id, tree| widget.register_children(tree, id));
+        tree.with_widget_mut(id, |widget, id, tree| {
+            widget.layout(tree, id, Constraints::loose(Size::new(500.0, 100.0)))
+        });
+        tree.get_children(id)
+            .iter()
+            .map(|&c| tree.get_bounds(c).unwrap().x)
+            .collect()
+    }
+
+    #[test]
+    fn test_adjacent_margins_add_up() {
+        let mut tree = Tree::new();
+        let id = tree.register(Box::new(container().layout(Flex::row()).children([
+            container().width(20.0).height(10.0).margin(8),
+            container().width(20.0).height(10.0).margin(8),
+        ])));
+
+        // 8px before the first child, then 20px + 8px + 8px to the second
+        assert_eq!(row_lefts(id, &mut tree), [8.0, 44.0]);
+    }
+
+    #[test]
+    fn test_keyed_children_keep_margins() {
+        let mut tree = Tree::new();
+        let id = tree.register(Box::new(container().layout(Flex::row()).children(
+            move || {
+                (0..2u64).map(|key| (key, move || container().width(20.0).height(10.0).margin(8)))
+            },
+        )));
+
+        assert_eq!(row_lefts(id, &mut tree), [8.0, 44.0]);
+    }
 }
@@ -1,12 +1,16 @@
+pub mod constrained;
 pub mod flex;
 pub mod flex_layout;
 pub mod overlay;
 
+pub use constrained::Constrained;
 pub use flex::{Constraints, Size};
 pub use flex_layout::Flex;
 pub use overlay::Overlay;
 
+use crate::reactive::Signal;
 use crate::tree::{Tree, WidgetId};
+use crate::widgets::Padding;
 
 /// Trait for types that can be converted to f32 for use in layout dimensions.
 ///
@@ -133,6 +137,22 @@ pub fn at_most(max: impl IntoF32) -> Length {
     }
 }
 
+/// Create a length that sizes to the content.
+///
+/// This is the default for containers without a width or height; use it where a
+/// [`Length`] is needed, e.g. to switch between a fixed and a natural size.
+///
+/// # Examples
+/// ```
+/// use guido::prelude::*;
+///
+/// let open = create_signal(false);
+/// container().height(move || if open.get() { auto() } else { Length::exact(0) });
+/// ```
+pub fn auto() -> Length {
+    Length::default()
+}
+
 /// Create a length that fills all available space.
 ///
 /// # Examples
@@ -223,6 +243,39 @@ pub trait Layout {
         constraints: Constraints,
         origin: (f32, f32),
     ) -> Size;
+
+    /// Read the layout's reactive properties.
+    ///
+    /// The owning container calls this inside its layout signal tracking, so
+    /// a change to any of them re-lays out the container.
+    fn track_signals(&self) {}
+
+    /// Set the spacing between children. Layouts without spacing ignore it.
+    fn set_spacing(&mut self, _spacing: Signal<f32>) {}
+
+    /// Set the main axis alignment. Layouts without alignment ignore it.
+    fn set_main_alignment(&mut self, _alignment: Signal<MainAlignment>) {}
+
+    /// Set the cross axis alignment. Layouts without alignment ignore it.
+    fn set_cross_alignment(&mut self, _alignment: Signal<CrossAlignment>) {}
+}
+
+/// Lay out a child inside its margin, returning its size including the margin.
+fn layout_child(
+    tree: &mut Tree,
+    child_id: WidgetId,
+    constraints: Constraints,
+    margin: Padding,
+) -> Option<Size> {
+    let (h, v) = (margin.horizontal(), margin.vertical());
+    let inner = Constraints {
+        min_width: (constraints.min_width - h).max(0.0),
+        min_height: (constraints.min_height - v).max(0.0),
+        max_width: (constraints.max_width - h).max(0.0),
+        max_height: (constraints.max_height - v).max(0.0),
+    };
+    tree.with_widget_mut(child_id, |widget, id, tree| widget.layout(tree, id, inner))
+        .map(|size| Size::new(size.width + h, size.height + v))
 }
 
 /// Direction for flex layout
@@ -250,4 +303,7 @@ pub enum CrossAlignment {
     Center,
     End,
     Stretch,
+    /// Align children on their first text baseline (rows only; columns
+    /// treat it as `Start`). Children without text align their bottom edge.
+    Baseline,
 }
@@ -2,12 +2,14 @@
 
 use crate::tree::{Tree, WidgetId};
 
-use super::{Constraints, Layout, Size};
+use super::{Constraints, Layout, Size, layout_child};
 
 /// Overlay layout that places all children at the same position,
-/// stacking them on top of each other. Later children appear on top.
+/// stacking them on top of each other. Later children appear on top,
+/// unless a child sets an explicit z-index (see `Container::z_index`).
 ///
-/// The size of the overlay is determined by the largest child.
+/// The size of the overlay is determined by the largest child, including
+/// its margin.
 pub struct Overlay;
 
 impl Overlay {
@@ -36,10 +38,11 @@ impl Layout for Overlay {
 
         // Layout all children at the same origin, giving them the full constraints
         for &child_id in children.iter() {
-            if let Some(child_size) = tree.with_widget_mut(child_id, |widget, id, tree| {
-                widget.layout(tree, id, constraints)
-            }) {
-                tree.set_origin(child_id, origin.0, origin.1);
+            let margin = tree
+                .with_widget(child_id, |w| w.layout_hints().margin)
+                .unwrap_or_default();
+            if let Some(child_size) = layout_child(tree, child_id, constraints, margin) {
+                tree.set_origin(child_id, origin.0 + margin.left, origin.1 + margin.top);
                 max_width = max_width.max(child_size.width);
                 max_height = max_height.max(child_size.height);
             }
@@ -1,3 +1,19 @@
+/// Enter a `tracing` span until the end of the enclosing block.
+///
+/// Only emits spans with the `tracing` feature; expands to nothing otherwise.
+#[cfg(feature = "tracing")]
+macro_rules! frame_span {
+    ($($args:tt)*) => {
+        let _span = tracing::info_span!($($args)*).entered();
+    };
+}
+
+#[cfg(not(feature = "tracing"))]
+macro_rules! frame_span {
+    ($($args:tt)*) => {};
+}
+
+pub mod accessibility;
 pub mod animation;
 pub mod image_metadata;
 mod jobs;
@@ -18,11 +34,14 @@ pub mod renderer;
 
 // Re-export macros
 pub use guido_macros::{SignalFields, component};
+pub use jobs::{FEEDBACK_LOOP_FRAMES, JobCounts, pending_job_counts};
+
+// Lets `#[component]` expansions inside this crate resolve `::guido` paths
+extern crate self as guido;
 
 use std::cell::{Cell, RefCell};
 use std::sync::Arc;
 
-use layout::Constraints;
 use platform::create_wayland_app;
 use reactive::owner::with_owner;
 use reactive::{OwnerId, set_system_clipboard, take_clipboard_change, take_cursor_change};
@@ -41,13 +60,57 @@ use smithay_client_toolkit::reexports::calloop_wayland_source::WaylandSource;
 thread_local! {
     static DEFAULT_FONT_FAMILY: RefCell<FontFamily> = const { RefCell::new(FontFamily::SansSerif) };
     static CUSTOM_FONTS: RefCell<Vec<Arc<Vec<u8>>>> = const { RefCell::new(Vec::new()) };
+    static FONT_FALLBACKS: RefCell<Vec<FontFamily>> = const { RefCell::new(Vec::new()) };
     static FONTS_CONSUMED: Cell<bool> = const { Cell::new(false) };
+    static UI_SCALE: Cell<f32> = const { Cell::new(1.0) };
+    static LAYOUT_LOGGING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Log every surface's layout tree after it is laid out.
+///
+/// Each widget is printed with the constraints it received and the size it
+/// returned (see [`Tree::format_layout`](tree::Tree::format_layout)), at the
+/// `debug` log level. Meant for finding out why a widget ended up a certain
+/// size; it slows down every layout.
+pub fn set_layout_logging(enabled: bool) {
+    LAYOUT_LOGGING.with(|l| l.set(enabled));
+}
+
+/// Set the application-wide UI zoom factor.
+///
+/// All widgets and text are laid out in units that are `scale` times larger
+/// on screen, on top of the display's HiDPI scale. Rendering happens at the
+/// combined scale, so zoomed content stays crisp and layouts reflow to the
+/// smaller available space. Surface sizes, margins and exclusive zones keep
+/// using compositor units; content-sized surfaces grow with their content.
+///
+/// Non-positive or non-finite values are ignored.
+///
+/// # Example
+///
+/// ```ignore
+/// set_ui_scale(1.25); // everything 25% larger
+/// ```
+pub fn set_ui_scale(scale: f32) {
+    if !scale.is_finite() || scale <= 0.0 {
+        log::warn!("Ignoring invalid UI scale {}", scale);
+        return;
+    }
+    if UI_SCALE.with(|s| s.replace(scale)) != scale {
+        surface::request_full_relayout();
+    }
+}
+
+/// Get the current application-wide UI zoom factor (1.0 by default).
+pub fn ui_scale() -> f32 {
+    UI_SCALE.with(|s| s.get())
 }
 
 /// Set the application-wide default font family.
 ///
-/// This should be called before creating any widgets. Widgets created after this
-/// call will use the specified font family as their default.
+/// Widgets without an explicit font family use this default. When called
+/// while the app is running, all surfaces are re-laid out and repainted so
+/// existing text switches to the new family.
 ///
 /// # Example
 ///
@@ -58,6 +121,9 @@ pub fn set_default_font_family(family: FontFamily) {
     DEFAULT_FONT_FAMILY.with(|f| {
         *f.borrow_mut() = family;
     });
+    if FONTS_CONSUMED.with(|f| f.get()) {
+        surface::request_full_relayout();
+    }
 }
 
 /// Get the current application-wide default font family.
@@ -82,7 +148,7 @@ pub fn load_font(data: Vec<u8>) {
     if FONTS_CONSUMED.with(|f| f.get()) {
         log::warn!(
             "load_font() called after FontSystem initialization — \
-             this font will not be available. Call load_font() before App::run()."
+             this font will not be available. Use add_font_runtime() instead."
         );
     }
     CUSTOM_FONTS.with(|fonts| {
@@ -90,6 +156,62 @@ pub fn load_font(data: Vec<u8>) {
     });
 }
 
+/// Load custom font data while the app is running.
+///
+/// The font is added to every live FontSystem (text measurement and
+/// rendering), text caches are invalidated and all surfaces are re-laid out,
+/// so the font can be used via `FontFamily::Name(...)` immediately. Before
+/// `App::run()` this behaves like [`load_font`].
+///
+/// # Example
+///
+/// ```ignore
+/// let data = std::fs::read(path)?;
+/// guido::add_font_runtime(data);
+/// guido::set_default_font_family(FontFamily::Name("Inter".into()));
+/// ```
+pub fn add_font_runtime(data: Vec<u8>) {
+    CUSTOM_FONTS.with(|fonts| {
+        fonts.borrow_mut().push(Arc::new(data));
+    });
+    if FONTS_CONSUMED.with(|f| f.get()) {
+        renderer::sync_measurer_fonts();
+        surface::request_full_relayout();
+    }
+}
+
+/// Set the font families tried, in order, for glyphs the requested family
+/// cannot display (emoji, CJK, symbols, ...).
+///
+/// The families are consulted before the platform's built-in fallback list.
+/// Generic families resolve to the system's configured font for that family.
+/// Like [`load_font`], this must be called before `App::run()`.
+///
+/// # Example
+///
+/// ```ignore
+/// guido::set_font_fallbacks(vec![
+///     FontFamily::Name("Noto Color Emoji".into()),
+///     FontFamily::Name("Noto Sans CJK JP".into()),
+/// ]);
+/// ```
+pub fn set_font_fallbacks(families: Vec<FontFamily>) {
+    if FONTS_CONSUMED.with(|f| f.get()) {
+        log::warn!(
+            "set_font_fallbacks() called after FontSystem initialization — \
+             the fallbacks will not be used. Call set_font_fallbacks() before App::run()."
+        );
+    }
+    FONT_FALLBACKS.with(|f| {
+        *f.borrow_mut() = families;
+    });
+}
+
+/// The fallback families set with [`set_font_fallbacks`].
+pub(crate) fn font_fallbacks() -> Vec<FontFamily> {
+    FONT_FALLBACKS.with(|f| f.borrow().clone())
+}
+
 /// Get all registered custom font data (for loading into FontSystems).
 ///
 /// Returns cloned `Arc` pointers so every FontSystem (measurer, renderer)
@@ -99,6 +221,19 @@ pub(crate) fn get_registered_fonts() -> Vec<Arc<Vec<u8>>> {
     CUSTOM_FONTS.with(|fonts| fonts.borrow().clone())
 }
 
+/// Number of registered custom fonts.
+pub(crate) fn registered_font_count() -> usize {
+    CUSTOM_FONTS.with(|fonts| fonts.borrow().len())
+}
+
+/// Get the custom fonts registered after the first `loaded` ones.
+///
+/// FontSystems remember how many fonts they loaded and call this to pick up
+/// fonts added with [`add_font_runtime`].
+pub(crate) fn registered_fonts_since(loaded: usize) -> Vec<Arc<Vec<u8>>> {
+    CUSTOM_FONTS.with(|fonts| fonts.borrow().get(loaded..).unwrap_or_default().to_vec())
+}
+
 /// The reason the application's main loop exited.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum ExitReason {
@@ -124,35 +259,49 @@ pub fn quit_app() {
 }
 
 pub mod prelude {
-    pub use crate::animation::{SpringConfig, TimingFunction, Transition, TransitionConfig};
+    pub use crate::animation::{
+        SpringConfig, TimingFunction, Transition, TransitionConfig, finish_all_animations,
+        reduce_motion, set_reduce_motion,
+    };
     pub use crate::layout::{
-        Axis, Constraints, CrossAlignment, Flex, IntoF32, Length, MainAlignment, Overlay, Size,
-        at_least, at_most, fill,
+        Axis, Constrained, Constraints, CrossAlignment, Flex, IntoF32, Length, MainAlignment,
+        Overlay, Size, at_least, at_most, auto, fill,
     };
     pub use crate::platform::{Anchor, KeyboardInteractivity, Layer};
     pub use crate::reactive::{
-        CursorIcon, Memo, OptionSignalExt, RwSignal, Service, Signal, WriteSignal, create_derived,
-        create_effect, create_memo, create_service, create_signal, create_stored, expect_context,
-        has_context, on_cleanup, provide_context, provide_signal_context, set_cursor, use_context,
-        with_context,
+        CursorIcon, Memo, OptionSignalExt, RwSignal, Service, Signal, WriteSignal,
+        create_animation_frame, create_derived, create_effect, create_interval, create_memo,
+        create_service, create_signal, create_stored, create_timeout, expect_context, has_context,
+        on_cleanup, provide_context, provide_signal_context, set_cursor, set_custom_cursor,
+        use_context, with_context,
     };
-    pub use crate::renderer::{PaintContext, Shadow, measure_text};
+    #[cfg(feature = "serde")]
+    pub use crate::reactive::{register_state, restore_state, snapshot_state, unregister_state};
+    pub use crate::renderer::{PaintContext, Shadow, available_fonts, font_exists, measure_text};
     pub use crate::surface::{
-        SurfaceConfig, SurfaceHandle, SurfaceId, spawn_surface, surface_handle,
+        Breakpoint, SurfaceConfig, SurfaceHandle, SurfaceId, active_surface, breakpoint, hairline,
+        on_escape, pointer_position, request_full_repaint, safe_area, spawn_surface,
+        surface_handle,
     };
     pub use crate::transform::Transform;
     pub use crate::transform_origin::{HorizontalAnchor, TransformOrigin, VerticalAnchor};
-    pub use crate::widget_ref::{WidgetRef, create_widget_ref};
+    pub use crate::widget_ref::{WidgetRef, create_widget_ref, on_click_outside};
     pub use crate::widgets::{
-        AnyWidget, Border, Color, Container, ContentFit, Event, EventResponse, FontFamily,
-        FontWeight, GradientDirection, Image, ImageSource, IntoChildren, Key, LinearGradient,
-        Modifiers, MouseButton, Overflow, Padding, Rect, ScrollAxis, ScrollSource,
-        ScrollbarBuilder, ScrollbarVisibility, Selection, StateStyle, Text, TextInput, Widget,
-        container, image, text, text_input,
+        AnyWidget, Border, Button, ButtonVariant, Color, Container, ContentFit, Event,
+        EventResponse, FontFamily, FontStyle, FontWeight, GesturePhase, GradientDirection,
+        IconButton, Image, ImageSource, IntoChildren, Key, KeyEvent, LinearGradient,
+        ListNavigation, Modifiers, MouseButton, Overflow, Padding, Pane, Rect, ResizeHandle,
+        RippleConfig, ScrollAxis, ScrollSource, ScrollbarBuilder, ScrollbarVisibility, Selection,
+        Skeleton, SnapAlign, SplitPane, StateStyle, Text, TextDirection, TextInput, ToastCorner,
+        ToastId, ToastStack, VirtualList, Widget, WidgetStyle, WrapMode, button, column,
+        constrained, container, dismiss_toast, icon, icon_button, image, list_navigation,
+        radio_group, repaint_boundary, resize_handle, row, show_toast, skeleton, split_pane, stack,
+        text, text_input, toast_stack, virtual_list,
     };
     pub use crate::{
-        App, ExitReason, SignalFields, component, default_font_family, load_font, quit_app,
-        restart_app, set_default_font_family,
+        App, ExitReason, SignalFields, add_font_runtime, component, default_font_family, load_font,
+        quit_app, restart_app, set_default_font_family, set_font_fallbacks, set_layout_logging,
+        set_ui_scale, ui_scale,
     };
 }
 
@@ -181,6 +330,7 @@ fn process_surface_commands(
     wayland_state: &mut platform::WaylandState,
     qh: &QueueHandle<platform::WaylandState>,
     tree: &mut Tree,
+    layout_roots: &mut Vec<WidgetId>,
 ) -> bool {
     for cmd in drain_surface_commands() {
         match cmd {
@@ -196,7 +346,9 @@ fn process_surface_commands(
 
                 // Create the widget inside an owner scope so that signals/effects
                 // created in the factory are properly owned.
-                let (widget, owner_id) = with_owner(widget_fn);
+                surface::set_surface_size(id, config.width, config.height);
+                let (widget, owner_id) =
+                    surface::with_current_surface(id, || with_owner(widget_fn));
                 let managed = ManagedSurface::new(id, config, widget, owner_id, tree);
                 surface_manager.add(managed);
             }
@@ -204,6 +356,8 @@ fn process_surface_commands(
                 log::info!("Closing dynamic surface {:?}", id);
                 wayland_state.destroy_surface(id);
                 surface_manager.remove(id);
+                surface::remove_surface_size(id);
+                surface::set_surface_active(id, false);
 
                 // If no surfaces left, exit
                 if surface_manager.is_empty() {
@@ -213,18 +367,43 @@ fn process_surface_commands(
             }
             SurfaceCommand::SetLayer { id, layer } => {
                 wayland_state.set_surface_layer(id, layer);
+                // Kept in the config so a recreated layer surface keeps it
+                if let Some(surface) = surface_manager.get_mut(id) {
+                    surface.config.layer = layer;
+                }
             }
             SurfaceCommand::SetKeyboardInteractivity { id, mode } => {
                 wayland_state.set_surface_keyboard_interactivity(id, mode);
+                if let Some(surface) = surface_manager.get_mut(id) {
+                    surface.config.keyboard_interactivity = mode;
+                }
             }
             SurfaceCommand::SetAnchor { id, anchor } => {
                 wayland_state.set_surface_anchor(id, anchor);
+                if let Some(surface) = surface_manager.get_mut(id) {
+                    surface.config.anchor = anchor;
+                    // The zone may now be measured along the other axis
+                    surface.requested_exclusive_zone = None;
+                }
+                jobs::request_frame();
             }
             SurfaceCommand::SetSize { id, width, height } => {
                 wayland_state.set_surface_size(id, width, height);
             }
             SurfaceCommand::SetExclusiveZone { id, zone } => {
                 wayland_state.set_surface_exclusive_zone(id, zone);
+                if let Some(surface) = surface_manager.get_mut(id) {
+                    surface.config.auto_exclusive_zone = false;
+                    surface.config.exclusive_zone = Some(zone);
+                }
+            }
+            SurfaceCommand::SetAutoExclusiveZone { id, enabled } => {
+                if let Some(surface) = surface_manager.get_mut(id) {
+                    surface.config.auto_exclusive_zone = enabled;
+                    surface.requested_exclusive_zone = None;
+                }
+                // The zone is updated after the next layout
+                jobs::request_frame();
             }
             SurfaceCommand::SetMargin {
                 id,
@@ -235,6 +414,58 @@ fn process_surface_commands(
             } => {
                 wayland_state.set_surface_margin(id, top, right, bottom, left);
             }
+            SurfaceCommand::SetNamespace { id, namespace } => {
+                if let Some(surface) = surface_manager.get_mut(id)
+                    && surface.config.namespace != namespace
+                {
+                    log::info!("Recreating surface {:?} with namespace {:?}", id, namespace);
+                    surface.config.namespace = namespace;
+                    // The GPU surface belongs to the old wl_surface; it is
+                    // created again once the new layer surface is configured
+                    surface.wgpu_surface = None;
+                    wayland_state.recreate_surface(qh, id, &surface.config);
+                    tree.mark_subtree_needs_paint(surface.widget_id);
+                }
+            }
+            SurfaceCommand::SetRenderingEnabled { id, enabled } => {
+                if let Some(surface) = surface_manager.get_mut(id) {
+                    surface.rendering_enabled = enabled;
+                }
+                // Resuming is picked up by the next frame
+                jobs::request_frame();
+            }
+            SurfaceCommand::FullRelayout => {
+                let ids: Vec<SurfaceId> = surface_manager.ids().collect();
+                for id in ids {
+                    if let Some(surface) = surface_manager.get_mut(id) {
+                        // mark_needs_layout stops at already-dirty nodes, so
+                        // register the root directly.
+                        tree.mark_subtree_needs_layout(surface.widget_id);
+                        tree.mark_subtree_needs_paint(surface.widget_id);
+                        if !layout_roots.contains(&surface.widget_id) {
+                            layout_roots.push(surface.widget_id);
+                        }
+                    }
+                }
+            }
+            SurfaceCommand::CapturePng { id, path } => {
+                if let Some(surface) = surface_manager.get_mut(id) {
+                    surface.pending_capture = Some(path);
+                    // Repaint so a frame is rendered even if nothing changed
+                    tree.mark_subtree_needs_paint(surface.widget_id);
+                }
+            }
+            SurfaceCommand::FullRepaint(target) => {
+                let ids: Vec<SurfaceId> = match target {
+                    Some(id) => vec![id],
+                    None => surface_manager.ids().collect(),
+                };
+                for id in ids {
+                    if let Some(surface) = surface_manager.get_mut(id) {
+                        tree.mark_subtree_needs_paint(surface.widget_id);
+                    }
+                }
+            }
         }
     }
     true
@@ -264,6 +495,20 @@ fn cache_paint_results(tree: &mut Tree, node: &renderer::RenderNode) {
     }
 }
 
+/// Report a logical damage rect in buffer pixels, rounded outwards so
+/// fractional edges are still covered.
+fn damage_buffer_rect(
+    wl_surface: &smithay_client_toolkit::reexports::client::protocol::wl_surface::WlSurface,
+    rect: widgets::Rect,
+    scale: f32,
+) {
+    let x = (rect.x * scale).floor();
+    let y = (rect.y * scale).floor();
+    let right = ((rect.x + rect.width) * scale).ceil();
+    let bottom = ((rect.y + rect.height) * scale).ceil();
+    wl_surface.damage_buffer(x as i32, y as i32, (right - x) as i32, (bottom - y) as i32);
+}
+
 /// Render a single surface using the hierarchical renderer.
 #[allow(clippy::too_many_arguments)]
 fn render_surface(
@@ -296,11 +541,24 @@ fn render_surface(
     let scale_factor_received = wayland_surface.scale_factor_received;
     let wl_surface = wayland_surface.wl_surface.clone();
 
+    // Publish the size before events and jobs so breakpoints react this frame
+    surface::set_surface_size(id, width, height);
+    surface::set_surface_scale(id, scale_factor * ui_scale());
+    for event in &events {
+        match event {
+            widgets::Event::FocusIn => surface::set_surface_active(id, true),
+            widgets::Event::FocusOut => surface::set_surface_active(id, false),
+            _ => {}
+        }
+    }
+
     // Skip if GPU not ready (will be initialized next frame)
     if !surface.is_gpu_ready() {
         return;
     }
 
+    frame_span!("frame", surface = id.raw());
+
     // Check for paste events
     let has_paste_event = events.iter().any(|e| {
         matches!(
@@ -316,11 +574,44 @@ fn render_surface(
         set_system_clipboard(text);
     }
 
+    // Pointer positions arrive in surface units; widgets use UI-scaled units
+    let ui_scale = ui_scale();
+
     // Dispatch events to widget
-    for event in &events {
-        tree.with_widget_mut(surface.widget_id, |widget, id, tree| {
-            widget.event(tree, id, event);
-        });
+    {
+        frame_span!("events", count = events.len());
+        // Only the frame's last pointer position reaches `pointer_position()`
+        let mut pointer = None;
+        for event in &events {
+            let event = match event.coords() {
+                Some((x, y)) if ui_scale != 1.0 => event.with_coords(x / ui_scale, y / ui_scale),
+                _ => event.clone(),
+            };
+            if let widgets::Event::MouseMove { x, y } = event {
+                pointer = Some((x, y));
+            }
+            let response = tree.with_widget_mut(surface.widget_id, |widget, id, tree| {
+                widget.event(tree, id, &event)
+            });
+            // Unhandled Escape falls back to the surface's `on_escape` handler
+            if response != Some(widgets::EventResponse::Handled)
+                && matches!(
+                    event,
+                    widgets::Event::KeyDown {
+                        key: widgets::Key::Escape,
+                        ..
+                    }
+                )
+            {
+                surface::dispatch_escape(id);
+            }
+            if let widgets::Event::MouseDown { x, y, .. } = event {
+                widget_ref::dispatch_click_outside(tree, surface.widget_id, x, y);
+            }
+        }
+        if let Some((x, y)) = pointer {
+            surface::set_pointer_position(x, y);
+        }
     }
 
     // Sync clipboard to Wayland if it changed (copy operations)
@@ -330,20 +621,27 @@ fn render_surface(
 
     // Sync cursor to Wayland if it changed
     if let Some(cursor) = take_cursor_change() {
-        wayland_state.set_cursor(cursor, qh);
+        match cursor {
+            reactive::Cursor::Icon(icon) => wayland_state.set_cursor(icon, qh),
+            reactive::Cursor::Custom(custom) => {
+                wayland_state.set_custom_cursor(&custom, qh, scale_factor)
+            }
+        }
     }
 
     // Calculate physical pixel dimensions (for HiDPI)
     let scale = scale_factor as u32;
     let physical_width = width * scale;
     let physical_height = height * scale;
+    // Widgets are drawn at the display scale times the UI zoom
+    let render_scale = scale_factor * ui_scale;
 
     let wgpu_surface = surface.wgpu_surface.as_mut().unwrap();
 
     // Check for resize or scale change
     let needs_resize =
         wgpu_surface.width() != physical_width || wgpu_surface.height() != physical_height;
-    let scale_changed = scale_factor != surface.previous_scale_factor;
+    let scale_changed = render_scale != surface.previous_scale_factor;
 
     if needs_resize {
         log::info!(
@@ -361,9 +659,9 @@ fn render_surface(
             "Surface {:?} scale factor changed: {} -> {}",
             id,
             surface.previous_scale_factor,
-            scale_factor
+            render_scale
         );
-        surface.previous_scale_factor = scale_factor;
+        surface.previous_scale_factor = render_scale;
     }
 
     // Process ALL pending jobs BEFORE paint.
@@ -385,13 +683,24 @@ fn render_surface(
     // picks up continuation jobs and re-advances. This is practically harmless —
     // advance() is time-based (computes nearly the same value), and follow-up
     // jobs are deduped by the JobQueue HashSet.
-    let jobs = drain_pending_jobs();
-    process_jobs(&jobs, tree, layout_roots);
+    {
+        frame_span!("jobs");
+        let jobs = drain_pending_jobs();
+        process_jobs(&jobs, tree, layout_roots);
+
+        // Process follow-up jobs from animation advances and reconciliation
+        let followup = drain_non_animation_jobs();
+        if !followup.is_empty() {
+            process_jobs(&followup, tree, layout_roots);
+        }
+    }
 
-    // Process follow-up jobs from animation advances and reconciliation
-    let followup = drain_non_animation_jobs();
-    if !followup.is_empty() {
-        process_jobs(&followup, tree, layout_roots);
+    // Paused surfaces keep their last frame (see `set_rendering_enabled`)
+    let occluded = wayland_state
+        .get_surface(id)
+        .is_some_and(|s| s.first_frame_presented && s.is_occluded());
+    if surface.update_suspended(occluded, tree) {
+        return;
     }
 
     // Check render conditions
@@ -409,15 +718,19 @@ fn render_surface(
     {
         // Update renderer for this surface
         renderer.set_screen_size(physical_width as f32, physical_height as f32);
-        renderer.set_scale_factor(scale_factor);
+        renderer.set_scale_factor(render_scale);
 
         // Re-layout using partial layout from boundaries when available
-        let constraints = Constraints::new(0.0, 0.0, width as f32, height as f32);
+        let constraints = surface.root_constraints(width as f32, height as f32);
+        let laid_out = !layout_roots.is_empty() || needs_resize;
         if !layout_roots.is_empty() {
             // Partial layout: only update dirty subtrees starting from boundaries
+            frame_span!("layout", roots = layout_roots.len());
             let mut roots = Vec::new();
             std::mem::swap(&mut roots, layout_roots);
             for root_id in &roots {
+                // Damage where the subtree was before it moves
+                tree.add_subtree_damage(*root_id);
                 // Use cached constraints for boundaries, or fall back to parent constraints
                 let cached = tree.cached_constraints(*root_id).unwrap_or(constraints);
 
@@ -426,11 +739,13 @@ fn render_surface(
                 });
             }
             // Layout may reposition children — conservatively mark subtrees as needing paint
+            // (repaint boundaries inside them keep their cache unless they were re-laid out)
             for root_id in &roots {
-                tree.mark_subtree_needs_paint(*root_id);
+                tree.mark_relayout_needs_paint(*root_id);
             }
         } else if needs_resize {
             // Full layout from root only when explicitly needed (first frame, resize, etc.)
+            frame_span!("layout", roots = 1);
             tree.with_widget_mut(surface.widget_id, |widget, id, tree| {
                 widget.layout(tree, id, constraints);
             });
@@ -438,9 +753,34 @@ fn render_surface(
         }
         // If neither condition is true, skip layout entirely - nothing is dirty
 
+        if laid_out && LAYOUT_LOGGING.with(|l| l.get()) {
+            log::debug!(
+                "Layout of surface {:?}:\n{}",
+                id,
+                tree.format_layout(surface.widget_id)
+            );
+        }
+
         // Update widget ref signals with current bounds after layout
         widget_ref::update_widget_refs(tree);
 
+        // Bars with an automatic exclusive zone reserve their laid-out size
+        if let Some(zone) = surface.take_exclusive_zone_change(tree) {
+            wayland_state.set_surface_exclusive_zone(id, zone);
+        }
+
+        // Content-sized surfaces follow their measured root size. Before the
+        // first frame, wait for the compositor to apply the new size so the
+        // surface is never presented at a stale size.
+        if let Some((content_width, content_height)) = surface.take_content_size_change(tree)
+            && (content_width, content_height) != (width, height)
+        {
+            wayland_state.set_surface_size(id, content_width, content_height);
+            if !first_frame_presented {
+                return;
+            }
+        }
+
         // Force full repaint on resize, scale change, or during initialization
         if force_render_surface || needs_resize || scale_changed {
             tree.mark_subtree_needs_paint(surface.widget_id);
@@ -456,9 +796,11 @@ fn render_surface(
         // Clear and reuse render tree (preserves capacity)
         surface.render_tree.clear();
         surface.root_node.clear();
-        surface.root_node.bounds = widgets::Rect::new(0.0, 0.0, width as f32, height as f32);
+        surface.root_node.bounds =
+            widgets::Rect::new(0.0, 0.0, width as f32 / ui_scale, height as f32 / ui_scale);
 
         time_phase!(render_stats::Phase::Paint, {
+            frame_span!("paint");
             tree.with_widget_mut(surface.widget_id, |widget, id, tree| {
                 let mut ctx = PaintContext::new(&mut surface.root_node);
                 widget.paint(tree, id, &mut ctx);
@@ -475,10 +817,16 @@ fn render_surface(
         // Flatten tree into reused buffer
         let layer_boundaries;
         time_phase!(render_stats::Phase::Flatten, {
+            frame_span!("flatten");
             layer_boundaries =
                 flatten_tree_into(&mut surface.render_tree, &mut surface.flattened_commands);
         });
+        let wgpu_surface = surface.wgpu_surface.as_mut().unwrap();
+        if let Some(path) = surface.pending_capture.take() {
+            renderer.capture_next_frame(path);
+        }
         time_phase!(render_stats::Phase::GpuRender, {
+            frame_span!("gpu_submit");
             renderer.render(
                 wgpu_surface,
                 &surface.flattened_commands,
@@ -495,9 +843,24 @@ fn render_surface(
         // Cache paint results AFTER flatten so cached_flatten data is preserved.
         // This enables incremental flatten for paint-cached nodes on subsequent frames.
         time_phase!(render_stats::Phase::CachePaintResults, {
+            frame_span!("cache_paint");
             cache_paint_results(tree, &surface.root_node);
         });
 
+        // Tell input methods where the focused text field's caret is
+        let caret = reactive::focus::focused_caret()
+            .filter(|(caret_id, _)| tree.root_of(*caret_id) == surface.widget_id)
+            .and_then(|(caret_id, caret)| {
+                let bounds = tree.get_surface_relative_bounds(caret_id)?;
+                Some(widgets::Rect::new(
+                    (bounds.x + caret.x) * ui_scale,
+                    (bounds.y + caret.y) * ui_scale,
+                    caret.width * ui_scale,
+                    caret.height * ui_scale,
+                ))
+            });
+        wayland_state.set_text_input_caret(id, caret);
+
         // Report damage region to Wayland compositor
         let damage = tree.take_damage();
 
@@ -510,19 +873,28 @@ fn render_surface(
                 wl_surface.damage_buffer(0, 0, physical_width as i32, physical_height as i32);
             }
             DamageRegion::Partial(rect) => {
-                let scale = scale_factor;
-                wl_surface.damage_buffer(
-                    (rect.x * scale) as i32,
-                    (rect.y * scale) as i32,
-                    (rect.width * scale).ceil() as i32,
-                    (rect.height * scale).ceil() as i32,
-                );
+                damage_buffer_rect(&wl_surface, rect, render_scale);
+            }
+            DamageRegion::Multiple(rects) => {
+                for rect in rects {
+                    damage_buffer_rect(&wl_surface, rect, render_scale);
+                }
             }
             DamageRegion::Full => {
                 wl_surface.damage_buffer(0, 0, physical_width as i32, physical_height as i32);
             }
         }
 
+        // Ask to hear when this frame is shown; a callback that never arrives
+        // means the compositor stopped showing the surface
+        if first_frame_presented
+            && let Some(state) = wayland_state.get_surface_mut(id)
+            && state.frame_callback_since.is_none()
+        {
+            wl_surface.frame(qh, wl_surface.clone());
+            state.frame_callback_since = Some(std::time::Instant::now());
+        }
+
         // Commit surface
         wl_surface.commit();
 
@@ -543,6 +915,8 @@ pub struct App {
     /// Root owner for the reactive graph. When disposed, cascades cleanup
     /// through all signals, effects, and cleanup callbacks.
     root_owner_id: Option<OwnerId>,
+    /// MSAA samples per pixel requested with `msaa()` (1 = off)
+    msaa_samples: u32,
 }
 
 impl App {
@@ -552,6 +926,7 @@ impl App {
             tree: Tree::new(),
             layout_roots: Vec::new(),
             root_owner_id: None,
+            msaa_samples: 1,
         }
     }
 
@@ -573,6 +948,77 @@ impl App {
         self
     }
 
+    /// Zoom the whole UI by `scale`, independent of the display scale.
+    ///
+    /// Meant for accessibility: `ui_scale(1.25)` makes all widgets and text
+    /// 25% larger while keeping them crisp. Can also be changed at runtime
+    /// with [`set_ui_scale`].
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// App::new()
+    ///     .ui_scale(1.25)
+    ///     .run(|app| { /* ... */ });
+    /// ```
+    pub fn ui_scale(self, scale: f32) -> Self {
+        set_ui_scale(scale);
+        self
+    }
+
+    /// Reduce motion for users sensitive to it.
+    ///
+    /// All transitions resolve instantly and ripples and marquees are
+    /// disabled. Can also be toggled at runtime with
+    /// [`set_reduce_motion`](animation::set_reduce_motion).
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// App::new()
+    ///     .reduce_motion(true)
+    ///     .run(|app| { /* ... */ });
+    /// ```
+    pub fn reduce_motion(self, enabled: bool) -> Self {
+        animation::set_reduce_motion(enabled);
+        self
+    }
+
+    /// Multisample anti-aliasing with `samples` per pixel (1, 2 or 4).
+    ///
+    /// Shapes already have smooth edges, but thin rotated shapes and
+    /// transformed text can still show jagged edges; MSAA smooths them at the
+    /// cost of GPU memory and fill rate. The default of 1 disables it. If the
+    /// GPU doesn't support the requested count, the highest supported count
+    /// below it is used.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// App::new()
+    ///     .msaa(4)
+    ///     .run(|app| { /* ... */ });
+    /// ```
+    pub fn msaa(mut self, samples: u32) -> Self {
+        self.msaa_samples = samples.max(1);
+        self
+    }
+
+    /// Log the layout tree with constraints after every layout (see
+    /// [`set_layout_logging`]).
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// App::new()
+    ///     .log_layout(cfg!(debug_assertions))
+    ///     .run(|app| { /* ... */ });
+    /// ```
+    pub fn log_layout(self, enabled: bool) -> Self {
+        set_layout_logging(enabled);
+        self
+    }
+
     /// Add a surface to the application.
     ///
     /// This method allows creating multiple layer shell surfaces within a single app.
@@ -675,7 +1121,10 @@ impl App {
 
             // Create the widget inside an owner scope so that signals/effects
             // created in the factory (e.g. create_memo) are properly owned.
-            let (widget, owner_id) = with_owner(|| (def.widget_fn)());
+            surface::set_surface_size(def.id, wayland_surface.width, wayland_surface.height);
+            surface::set_surface_scale(def.id, wayland_surface.scale_factor * ui_scale());
+            let (widget, owner_id) =
+                surface::with_current_surface(def.id, || with_owner(|| (def.widget_fn)()));
             let mut managed =
                 ManagedSurface::new(def.id, def.config, widget, owner_id, &mut self.tree);
 
@@ -694,10 +1143,21 @@ impl App {
             if renderer.is_none()
                 && let Some(ref wgpu_surface) = managed.wgpu_surface
             {
+                let format = wgpu_surface.config.format;
+                let samples = gpu_context.supported_sample_count(format, self.msaa_samples);
+                if samples != self.msaa_samples {
+                    log::warn!(
+                        "{}x MSAA is not supported for {:?}, using {}x",
+                        self.msaa_samples,
+                        format,
+                        samples
+                    );
+                }
                 let r = Renderer::new(
                     wgpu_surface.device.clone(),
                     wgpu_surface.queue.clone(),
-                    wgpu_surface.config.format,
+                    format,
+                    samples,
                 );
                 renderer = Some(r);
             }
@@ -712,6 +1172,10 @@ impl App {
             EventLoop::try_new().expect("Failed to create event loop");
         let loop_handle = event_loop.handle();
 
+        // Key repeat and reactive timers run on the event loop
+        wayland_state.set_loop_handle(loop_handle.clone());
+        reactive::init_timers(loop_handle.clone());
+
         // Create ping mechanism for wakeup on signal changes
         let (ping, ping_source) = make_ping().expect("Failed to create ping");
         init_wakeup(ping);
@@ -728,6 +1192,11 @@ impl App {
             .insert(loop_handle.clone())
             .expect("Failed to insert Wayland source");
 
+        // Whether frame time signals are in use (see `create_animation_frame`)
+        let mut frame_signals_active = false;
+        // When a frame was last rendered for pending updates (see `jobs::frame_delay`)
+        let mut last_frame: Option<std::time::Instant> = None;
+
         // Main loop - event-driven, blocks until Wayland event or signal update
         loop {
             // Check if all surfaces are fully initialized
@@ -736,7 +1205,7 @@ impl App {
 
             // Check if we need to actively poll (jobs pushed during previous frame)
             let has_pending = has_pending_jobs();
-            let needs_polling = has_pending || force_render;
+            let needs_polling = has_pending || force_render || frame_signals_active;
 
             // Dispatch events from calloop:
             // - If polling needed (animations/callbacks/init), use timeout
@@ -751,6 +1220,25 @@ impl App {
                 .dispatch(timeout, &mut wayland_state)
                 .expect("Failed to dispatch event loop");
 
+            // Signal writes arriving faster than the frame rate join the next
+            // frame instead of each causing a layout of their own
+            if !force_render {
+                jobs::collect_until_frame(
+                    &mut wayland_state,
+                    |_| last_frame.map_or(jobs::FRAME_INTERVAL, |t| t.elapsed()),
+                    |state| {
+                        frame_signals_active
+                            || state.has_pending_events()
+                            || jobs::has_animation_jobs()
+                    },
+                    |state, wait| {
+                        event_loop
+                            .dispatch(Some(wait), state)
+                            .expect("Failed to dispatch event loop");
+                    },
+                );
+            }
+
             // Check for programmatic exit/restart requests
             match get_exit_request() {
                 jobs::ExitRequest::Quit => break,
@@ -762,16 +1250,24 @@ impl App {
                 break;
             }
 
+            // Publish this frame's time before jobs run
+            frame_signals_active = reactive::advance_animation_frames();
+
             // Process dynamic surface commands
             if !process_surface_commands(
                 &mut surface_manager,
                 &mut wayland_state,
                 &qh,
                 &mut self.tree,
+                &mut self.layout_roots,
             ) {
                 break;
             }
 
+            // Publish the areas other surfaces reserve (zones of auto-sized
+            // bars are from their previous layout)
+            surface_manager.update_safe_areas();
+
             // Initialize GPU for any pending surfaces (newly created dynamic surfaces)
             surface_manager.init_pending_gpu(
                 &gpu_context,
@@ -785,8 +1281,17 @@ impl App {
             // are processed into jobs before we check the frame request flag.
             reactive::flush_bg_writes();
 
+            // Deliver input injected through WidgetRef::click/hover/dispatch
+            widget_ref::dispatch_synthetic_events(&mut self.tree);
+
+            // Clicks and focus changes requested by screen readers
+            surface_manager.perform_access_actions(&mut self.tree);
+
             // Check frame request once for all surfaces (not per-surface)
             let frame_requested = take_frame_request();
+            if frame_requested {
+                last_frame = Some(std::time::Instant::now());
+            }
 
             // Render each surface
             let surface_ids: Vec<SurfaceId> = surface_manager.ids().collect();
@@ -794,19 +1299,30 @@ impl App {
                 let Some(surface) = surface_manager.get_mut(id) else {
                     continue;
                 };
-                render_surface(
-                    id,
-                    surface,
-                    &mut wayland_state,
-                    &mut renderer,
-                    &connection,
-                    &qh,
-                    &mut self.tree,
-                    &mut self.layout_roots,
-                    frame_requested,
-                );
+                surface::with_current_surface(id, || {
+                    render_surface(
+                        id,
+                        surface,
+                        &mut wayland_state,
+                        &mut renderer,
+                        &connection,
+                        &qh,
+                        &mut self.tree,
+                        &mut self.layout_roots,
+                        frame_requested,
+                    )
+                });
             }
 
+            // Tell screen readers about this frame's widgets and focus
+            surface_manager.publish_access_trees(&self.tree);
+
+            // Animations finished by finish_all_animations() settled this frame
+            animation::end_finish_animations();
+
+            // Warn about signals that keep re-invalidating every frame
+            jobs::check_feedback_loop();
+
             // Flush the connection once for all surfaces
             connection.flush().expect("Failed to flush connection");
         }
@@ -834,6 +1350,9 @@ impl Drop for App {
         jobs::reset_jobs();
         surface::reset_surface_commands();
         widget_ref::reset_widget_refs();
+        widgets::toast::reset_toasts();
+        animation::set_reduce_motion(false);
+        animation::end_finish_animations();
         FONTS_CONSUMED.with(|f| f.set(false));
     }
 }
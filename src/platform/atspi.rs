@@ -0,0 +1,266 @@
+//! AccessKit bridge publishing each surface's accessibility tree over AT-SPI.
+//!
+//! Every surface owns an [`AccessAdapter`]. The adapter stays idle until an
+//! assistive technology (e.g. Orca) connects; from then on the surface's
+//! [`access_tree`] is rebuilt after each frame and sent whenever it changed,
+//! including which widget holds keyboard focus. Actions requested by the
+//! assistive technology (click, focus) are queued and performed on the main
+//! thread at the start of the next frame.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{Receiver, Sender, channel};
+
+use accesskit::{
+    Action, ActionHandler, ActionRequest, ActivationHandler, DeactivationHandler, Node, NodeId,
+    TreeId, TreeUpdate,
+};
+use accesskit_unix::Adapter;
+
+use crate::accessibility::{AccessNode, Role, access_tree};
+use crate::jobs::request_frame;
+use crate::reactive::request_focus;
+use crate::tree::{Tree, WidgetId};
+use crate::widgets::{Event, MouseButton};
+
+/// Connection of one surface to the platform accessibility bus.
+pub(crate) struct AccessAdapter {
+    adapter: Adapter,
+    /// Whether an assistive technology is listening
+    active: Arc<AtomicBool>,
+    /// Set on activation: the next update must carry the full tree
+    needs_full_tree: Arc<AtomicBool>,
+    actions: Receiver<ActionRequest>,
+    /// Tree sent with the last update, to skip unchanged frames
+    published: Option<AccessNode>,
+    window_focused: bool,
+}
+
+impl AccessAdapter {
+    pub(crate) fn new() -> Self {
+        let active = Arc::new(AtomicBool::new(false));
+        let needs_full_tree = Arc::new(AtomicBool::new(false));
+        let (sender, actions) = channel();
+        let adapter = Adapter::new(
+            Activation {
+                active: active.clone(),
+                needs_full_tree: needs_full_tree.clone(),
+            },
+            Actions { sender },
+            Deactivation {
+                active: active.clone(),
+            },
+        );
+        Self {
+            adapter,
+            active,
+            needs_full_tree,
+            actions,
+            published: None,
+            window_focused: false,
+        }
+    }
+
+    /// Perform the actions requested since the last frame on the surface
+    /// whose root widget is `root`.
+    pub(crate) fn perform_actions(&mut self, tree: &mut Tree, root: WidgetId) {
+        while let Ok(request) = self.actions.try_recv() {
+            let id = WidgetId::from_u64(request.target_node.0);
+            let Some(bounds) = tree.get_surface_relative_bounds(id) else {
+                continue;
+            };
+            match request.action {
+                Action::Focus => request_focus(id),
+                Action::Click => {
+                    // Through the root, like a real click at the widget's center
+                    let (x, y) = bounds.center();
+                    let button = MouseButton::Left;
+                    for event in [
+                        Event::MouseDown { x, y, button },
+                        Event::MouseUp { x, y, button },
+                    ] {
+                        tree.with_widget_mut(root, |widget, id, tree| {
+                            widget.event(tree, id, &event)
+                        });
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Publish the tree under `root` if it changed since the last update.
+    ///
+    /// `window_focused` is whether the surface has keyboard focus.
+    pub(crate) fn update(&mut self, tree: &Tree, root: WidgetId, window_focused: bool) {
+        if window_focused != self.window_focused {
+            self.window_focused = window_focused;
+            self.adapter.update_window_focus_state(window_focused);
+        }
+        if self.needs_full_tree.swap(false, Ordering::AcqRel) {
+            self.published = None;
+        }
+        if !self.active.load(Ordering::Acquire) {
+            self.published = None;
+            return;
+        }
+        let node = access_tree(tree, root);
+        if self.published.as_ref() == Some(&node) {
+            return;
+        }
+        self.adapter
+            .update_if_active(|| tree_update(&node, crate::ui_scale()));
+        self.published = Some(node);
+    }
+}
+
+struct Activation {
+    active: Arc<AtomicBool>,
+    needs_full_tree: Arc<AtomicBool>,
+}
+
+impl ActivationHandler for Activation {
+    fn request_initial_tree(&mut self) -> Option<TreeUpdate> {
+        // The widget tree lives on the main thread; it sends the tree on the
+        // next frame
+        self.needs_full_tree.store(true, Ordering::Release);
+        self.active.store(true, Ordering::Release);
+        request_frame();
+        None
+    }
+}
+
+struct Deactivation {
+    active: Arc<AtomicBool>,
+}
+
+impl DeactivationHandler for Deactivation {
+    fn deactivate_accessibility(&mut self) {
+        self.active.store(false, Ordering::Release);
+    }
+}
+
+struct Actions {
+    sender: Sender<ActionRequest>,
+}
+
+impl ActionHandler for Actions {
+    fn do_action(&mut self, request: ActionRequest) {
+        if self.sender.send(request).is_ok() {
+            request_frame();
+        }
+    }
+}
+
+/// Convert an exported tree into a full AccessKit update.
+///
+/// Bounds are scaled from widget units to surface units by `ui_scale`.
+fn tree_update(root: &AccessNode, ui_scale: f32) -> TreeUpdate {
+    let root_id = NodeId(root.id.as_u64());
+    let mut update = TreeUpdate {
+        nodes: Vec::new(),
+        tree: Some(accesskit::Tree {
+            toolkit_name: Some("guido".into()),
+            toolkit_version: Some(env!("CARGO_PKG_VERSION").into()),
+            ..accesskit::Tree::new(root_id)
+        }),
+        tree_id: TreeId::ROOT,
+        focus: root_id,
+    };
+    push_node(root, ui_scale, &mut update);
+    update
+}
+
+fn push_node(node: &AccessNode, ui_scale: f32, update: &mut TreeUpdate) {
+    let id = NodeId(node.id.as_u64());
+    let mut out = Node::new(match node.role {
+        Role::Group => accesskit::Role::GenericContainer,
+        Role::Label => accesskit::Role::Label,
+        Role::TextInput => accesskit::Role::TextInput,
+        Role::Button => accesskit::Role::Button,
+        Role::Image => accesskit::Role::Image,
+        Role::RadioGroup => accesskit::Role::RadioGroup,
+        Role::RadioButton => accesskit::Role::RadioButton,
+    });
+    // Labels carry their text as the value
+    match (node.role, &node.name) {
+        (Role::Label, Some(name)) => out.set_value(name.as_str()),
+        (_, Some(name)) => out.set_label(name.as_str()),
+        _ => {}
+    }
+    if let Some(ref value) = node.value {
+        out.set_value(value.as_str());
+    }
+    if let Some(checked) = node.checked {
+        out.set_toggled(checked.into());
+    }
+    match node.role {
+        Role::Button | Role::RadioButton => out.add_action(Action::Click),
+        Role::TextInput | Role::RadioGroup => out.add_action(Action::Focus),
+        _ => {}
+    }
+    let b = node.bounds;
+    let scale = ui_scale as f64;
+    out.set_bounds(accesskit::Rect {
+        x0: b.x as f64 * scale,
+        y0: b.y as f64 * scale,
+        x1: (b.x + b.width) as f64 * scale,
+        y1: (b.y + b.height) as f64 * scale,
+    });
+    out.set_children(
+        node.children
+            .iter()
+            .map(|child| NodeId(child.id.as_u64()))
+            .collect::<Vec<_>>(),
+    );
+    if node.focused {
+        update.focus = id;
+    }
+    update.nodes.push((id, out));
+    for child in &node.children {
+        push_node(child, ui_scale, update);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::Rect;
+
+    fn node(id: u64, role: Role, children: Vec<AccessNode>) -> AccessNode {
+        AccessNode {
+            id: WidgetId::from_u64(id),
+            role,
+            name: None,
+            value: None,
+            checked: None,
+            bounds: Rect::new(10.0, 0.0, 20.0, 10.0),
+            focused: false,
+            children,
+        }
+    }
+
+    #[test]
+    fn test_tree_update_reports_nodes_and_focus() {
+        let mut input = node(2, Role::TextInput, vec![]);
+        input.focused = true;
+        let mut label = node(3, Role::Label, vec![]);
+        label.name = Some("Name".into());
+        let root = node(1, Role::Group, vec![input, label]);
+
+        let update = tree_update(&root, 2.0);
+        assert_eq!(update.focus, NodeId(2));
+        assert_eq!(update.tree.map(|t| t.root), Some(NodeId(1)));
+        assert_eq!(update.nodes.len(), 3);
+
+        let (_, root_node) = &update.nodes[0];
+        assert_eq!(root_node.children(), [NodeId(2), NodeId(3)]);
+        let bounds = root_node.bounds().unwrap();
+        assert_eq!((bounds.x0, bounds.x1), (20.0, 60.0));
+
+        let (_, label_node) = &update.nodes[2];
+        assert_eq!(label_node.role(), accesskit::Role::Label);
+        assert_eq!(label_node.value(), Some("Name"));
+        assert!(!update.nodes[1].1.supports_action(Action::Click));
+    }
+}
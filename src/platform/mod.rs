@@ -1,3 +1,5 @@
+#[cfg(feature = "accesskit")]
+pub(crate) mod atspi;
 pub mod wayland;
 
 pub use wayland::{WaylandState, WaylandSurfaceState, WaylandWindowWrapper, create_wayland_app};
@@ -11,12 +11,14 @@ use smithay_client_toolkit::{
         DataDeviceManagerState, ReadPipe,
     },
     delegate_compositor, delegate_data_device, delegate_keyboard, delegate_layer, delegate_output,
-    delegate_pointer, delegate_registry, delegate_seat,
+    delegate_pointer, delegate_registry, delegate_seat, delegate_shm,
     output::{OutputHandler, OutputState},
     registry::{ProvidesRegistryState, RegistryState},
     registry_handlers,
     seat::{
-        keyboard::{KeyEvent, KeyboardHandler, Keysym, Modifiers as WlModifiers, RawModifiers},
+        keyboard::{
+            KeyEvent, KeyboardHandler, Keysym, Modifiers as WlModifiers, RawModifiers, RepeatInfo,
+        },
         pointer::{
             cursor_shape::CursorShapeManager, PointerEvent, PointerEventKind, PointerHandler,
         },
@@ -26,16 +28,32 @@ use smithay_client_toolkit::{
         Anchor, KeyboardInteractivity, Layer, LayerShell, LayerShellHandler, LayerSurface,
         LayerSurfaceConfigure,
     },
+    shm::{
+        slot::{Buffer, SlotPool},
+        Shm, ShmHandler,
+    },
 };
+use smithay_client_toolkit::reexports::calloop::timer::{TimeoutAction, Timer};
+use smithay_client_toolkit::reexports::calloop::{LoopHandle, RegistrationToken};
 use smithay_client_toolkit::reexports::client::{
     globals::registry_queue_init,
     protocol::{
         wl_data_device::WlDataDevice, wl_data_device_manager::DndAction,
-        wl_data_source::WlDataSource, wl_keyboard, wl_output, wl_pointer, wl_seat, wl_surface,
+        wl_data_source::WlDataSource, wl_keyboard, wl_output, wl_pointer, wl_seat, wl_shm,
+        wl_surface,
     },
-    Connection, EventQueue, Proxy, QueueHandle,
+    delegate_noop, Connection, Dispatch, EventQueue, Proxy, QueueHandle,
 };
 use smithay_client_toolkit::reexports::protocols::wp::cursor_shape::v1::client::wp_cursor_shape_device_v1::Shape as WpCursorShape;
+use smithay_client_toolkit::reexports::protocols::wp::pointer_gestures::zv1::client::{
+    zwp_pointer_gesture_pinch_v1::{self, ZwpPointerGesturePinchV1},
+    zwp_pointer_gesture_swipe_v1::{self, ZwpPointerGestureSwipeV1},
+    zwp_pointer_gestures_v1::ZwpPointerGesturesV1,
+};
+use smithay_client_toolkit::reexports::protocols::wp::text_input::zv3::client::{
+    zwp_text_input_manager_v3::ZwpTextInputManagerV3,
+    zwp_text_input_v3::{self, ZwpTextInputV3},
+};
 use wayland_backend::sys::client::ObjectId;
 
 use std::collections::HashMap;
@@ -43,14 +61,24 @@ use std::fs::File;
 use std::io::{Read, Write};
 use std::os::fd::AsFd;
 use std::os::unix::io::OwnedFd;
+use std::time::{Duration, Instant};
 
-use crate::reactive::CursorIcon;
+use crate::reactive::{CursorIcon, CustomCursor};
 use crate::surface::SurfaceId;
-use crate::widgets::{Event, Key, Modifiers, MouseButton, ScrollSource};
+use crate::widgets::image::ImageSource;
+use crate::widgets::{Event, GesturePhase, Key, Modifiers, MouseButton, Rect, ScrollSource};
 
 /// Pixels per line for discrete scroll (mouse wheel)
 const SCROLL_PIXELS_PER_LINE: f32 = 40.0;
 
+/// Key repeat used until the compositor sends its repeat-info (xkb defaults)
+const DEFAULT_REPEAT_RATE: u32 = 25;
+const DEFAULT_REPEAT_DELAY_MS: u32 = 600;
+
+/// How long a frame callback may go unanswered before the surface is
+/// considered hidden and stops rendering
+pub const OCCLUSION_TIMEOUT: Duration = Duration::from_secs(1);
+
 /// Per-surface state for multi-surface support.
 pub struct WaylandSurfaceState {
     /// The layer surface protocol object
@@ -71,6 +99,10 @@ pub struct WaylandSurfaceState {
     pub first_frame_presented: bool,
     /// Pending events for this surface
     pub pending_events: Vec<Event>,
+    /// When the outstanding frame callback was requested, if any
+    pub frame_callback_since: Option<Instant>,
+    /// Margin set at runtime (top, right, bottom, left), if any
+    pub margin: Option<(i32, i32, i32, i32)>,
 }
 
 impl WaylandSurfaceState {
@@ -91,9 +123,18 @@ impl WaylandSurfaceState {
             scale_factor_received: false,
             first_frame_presented: false,
             pending_events: Vec::new(),
+            frame_callback_since: None,
+            margin: None,
         }
     }
 
+    /// Whether the compositor seems to have stopped showing the surface:
+    /// a frame callback has gone unanswered for [`OCCLUSION_TIMEOUT`].
+    pub fn is_occluded(&self) -> bool {
+        self.frame_callback_since
+            .is_some_and(|since| since.elapsed() > OCCLUSION_TIMEOUT)
+    }
+
     /// Take all pending events (drains the queue)
     pub fn take_events(&mut self) -> Vec<Event> {
         std::mem::take(&mut self.pending_events)
@@ -130,12 +171,46 @@ pub struct WaylandState {
     // Cursor shape
     cursor_shape_manager: Option<CursorShapeManager>,
 
+    // Custom image cursor (wl_shm buffer on a dedicated surface)
+    shm: Option<Shm>,
+    cursor_pool: Option<SlotPool>,
+    cursor_surface: Option<wl_surface::WlSurface>,
+    /// Buffer attached to the cursor surface, with the cursor and scale it was drawn for
+    cursor_buffer: Option<(Buffer, CustomCursor, i32)>,
+
+    // Touchpad gestures (zwp_pointer_gestures_v1)
+    pointer_gestures: Option<ZwpPointerGesturesV1>,
+    pinch_gesture: Option<ZwpPointerGesturePinchV1>,
+    swipe_gesture: Option<ZwpPointerGestureSwipeV1>,
+    /// Scale of the active pinch relative to its start (as reported by the compositor)
+    pinch_scale: f64,
+    /// Finger count of the active swipe
+    swipe_fingers: u32,
+
+    // Input methods and on-screen keyboards (zwp_text_input_v3)
+    text_input_manager: Option<ZwpTextInputManagerV3>,
+    text_input: Option<ZwpTextInputV3>,
+    /// Surface the text input is focused on (from its enter/leave events)
+    text_input_surface: Option<SurfaceId>,
+    /// Caret rectangle last sent, while the text input is enabled
+    text_input_caret: Option<(i32, i32, i32, i32)>,
+    /// Text committed by the input method, applied on `done`
+    pending_commit_string: Option<String>,
+
     // Keyboard state
     keyboard: Option<wl_keyboard::WlKeyboard>,
     modifiers: Modifiers,
     keyboard_serial: u32,
     /// Track raw_code → Key for press/release matching (handles compose sequences)
     pressed_keys: HashMap<u32, Key>,
+    /// Compositor key repeat rate/delay (from wl_keyboard.repeat_info)
+    repeat_info: RepeatInfo,
+    /// Event loop handle used to schedule key repeat timers
+    loop_handle: Option<LoopHandle<'static, WaylandState>>,
+    /// Currently repeating key (raw code) and its timer
+    repeating_key: Option<(u32, RegistrationToken)>,
+    /// The compositor sends repeated key events itself (wl_keyboard v10)
+    compositor_repeat: bool,
 
     // Clipboard state
     data_device_manager: Option<DataDeviceManagerState>,
@@ -175,6 +250,28 @@ pub fn create_wayland_app() -> (
         log::warn!("Cursor shape manager not available - cursor changes will not work");
     }
 
+    // Initialize shared memory for custom image cursors
+    let shm = Shm::bind(&globals, &qh).ok();
+    if shm.is_none() {
+        log::warn!("wl_shm not available - custom cursors will not work");
+    }
+
+    // Initialize pointer gestures for touchpad pinch/swipe
+    let pointer_gestures = globals
+        .bind::<ZwpPointerGesturesV1, _, _>(&qh, 1..=1, ())
+        .ok();
+    if pointer_gestures.is_none() {
+        log::warn!("Pointer gestures not available - pinch and swipe events will not work");
+    }
+
+    // Initialize text input so input methods can place their popups at the caret
+    let text_input_manager = globals
+        .bind::<ZwpTextInputManagerV3, _, _>(&qh, 1..=1, ())
+        .ok();
+    if text_input_manager.is_none() {
+        log::warn!("Text input manager not available - input methods will not follow the caret");
+    }
+
     let state = WaylandState {
         registry_state: RegistryState::new(&globals),
         compositor_state,
@@ -192,10 +289,31 @@ pub fn create_wayland_app() -> (
         pointer_over_surface: false,
         pointer_enter_serial: 0,
         cursor_shape_manager,
+        shm,
+        cursor_pool: None,
+        cursor_surface: None,
+        cursor_buffer: None,
+        pointer_gestures,
+        pinch_gesture: None,
+        swipe_gesture: None,
+        pinch_scale: 1.0,
+        swipe_fingers: 0,
+        text_input_manager,
+        text_input: None,
+        text_input_surface: None,
+        text_input_caret: None,
+        pending_commit_string: None,
         keyboard: None,
         modifiers: Modifiers::default(),
         keyboard_serial: 0,
         pressed_keys: HashMap::new(),
+        repeat_info: RepeatInfo::Repeat {
+            rate: std::num::NonZeroU32::new(DEFAULT_REPEAT_RATE).unwrap(),
+            delay: DEFAULT_REPEAT_DELAY_MS,
+        },
+        loop_handle: None,
+        repeating_key: None,
+        compositor_repeat: false,
         data_device_manager,
         data_device: None,
         clipboard_content: None,
@@ -208,6 +326,69 @@ pub fn create_wayland_app() -> (
 }
 
 impl WaylandState {
+    /// Provide the event loop handle used for key repeat timers.
+    ///
+    /// Until this is called, held keys produce a single `KeyDown`.
+    pub fn set_loop_handle(&mut self, handle: LoopHandle<'static, WaylandState>) {
+        self.loop_handle = Some(handle);
+    }
+
+    /// Start repeating `key` (raw code `raw_code`) after the repeat delay.
+    ///
+    /// Only keys that [repeat](key_repeats) are scheduled; any other press
+    /// still cancels the previous key's repeat.
+    fn start_key_repeat(&mut self, raw_code: u32, key: Key) {
+        self.stop_key_repeat();
+        if self.compositor_repeat || !key_repeats(key) {
+            return;
+        }
+        let (RepeatInfo::Repeat { rate, delay }, Some(handle)) =
+            (self.repeat_info, self.loop_handle.as_ref())
+        else {
+            return;
+        };
+        let interval = Duration::from_micros(1_000_000 / rate.get() as u64);
+        let timer = Timer::from_duration(Duration::from_millis(delay as u64));
+        let token = handle.insert_source(timer, move |_, _, state| {
+            state.push_keyboard_event(Event::KeyDown {
+                key,
+                modifiers: state.modifiers,
+            });
+            TimeoutAction::ToDuration(interval)
+        });
+        match token {
+            Ok(token) => self.repeating_key = Some((raw_code, token)),
+            Err(e) => log::warn!("Failed to schedule key repeat: {}", e),
+        }
+    }
+
+    /// Cancel the key repeat timer, if any.
+    fn stop_key_repeat(&mut self) {
+        if let Some((_, token)) = self.repeating_key.take()
+            && let Some(ref handle) = self.loop_handle
+        {
+            handle.remove(token);
+        }
+    }
+
+    /// Queue an event for the surface with pointer focus.
+    fn push_pointer_event(&mut self, event: Event) {
+        if let Some(id) = self.current_pointer_surface
+            && let Some(surface_state) = self.surfaces.get_mut(&id)
+        {
+            surface_state.pending_events.push(event);
+        }
+    }
+
+    /// Queue an event for the surface with keyboard focus.
+    fn push_keyboard_event(&mut self, event: Event) {
+        if let Some(id) = self.current_keyboard_surface
+            && let Some(surface_state) = self.surfaces.get_mut(&id)
+        {
+            surface_state.pending_events.push(event);
+        }
+    }
+
     /// Create a layer surface with a specific SurfaceId.
     pub fn create_surface_with_id(
         &mut self,
@@ -270,6 +451,32 @@ impl WaylandState {
         );
     }
 
+    /// Replace a surface's layer surface with a new one built from `config`.
+    ///
+    /// Needed to change the namespace, which the layer shell protocol only
+    /// accepts on creation. The current size and margin carry over.
+    pub fn recreate_surface(
+        &mut self,
+        qh: &QueueHandle<Self>,
+        id: SurfaceId,
+        config: &crate::surface::SurfaceConfig,
+    ) {
+        let Some(old) = self.surfaces.get(&id) else {
+            return;
+        };
+        let config = crate::surface::SurfaceConfig {
+            width: old.width,
+            height: old.height,
+            ..config.clone()
+        };
+        let margin = old.margin;
+        self.destroy_surface(id);
+        self.create_surface_with_id(qh, id, &config);
+        if let Some((top, right, bottom, left)) = margin {
+            self.set_surface_margin(id, top, right, bottom, left);
+        }
+    }
+
     /// Destroy a surface by its SurfaceId.
     pub fn destroy_surface(&mut self, id: SurfaceId) {
         if let Some(surface_state) = self.surfaces.remove(&id) {
@@ -335,6 +542,48 @@ impl WaylandState {
         log::info!("Surface {:?} exclusive zone set to {}", id, zone);
     }
 
+    /// Report the caret of the focused text field on surface `id`, in
+    /// surface-local logical pixels, or `None` when no text field there has
+    /// focus.
+    ///
+    /// Enables the text input while there is a caret, so input methods and
+    /// on-screen keyboards place their popups next to it, and disables it
+    /// otherwise. Only sends requests when something changed.
+    pub fn set_text_input_caret(&mut self, id: SurfaceId, caret: Option<Rect>) {
+        let Some(ref text_input) = self.text_input else {
+            return;
+        };
+        if self.text_input_surface != Some(id) {
+            return;
+        }
+        let caret = caret.map(|rect| {
+            (
+                rect.x.round() as i32,
+                rect.y.round() as i32,
+                rect.width.ceil().max(1.0) as i32,
+                rect.height.ceil().max(1.0) as i32,
+            )
+        });
+        if caret == self.text_input_caret {
+            return;
+        }
+        match caret {
+            Some((x, y, width, height)) => {
+                if self.text_input_caret.is_none() {
+                    text_input.enable();
+                    text_input.set_content_type(
+                        zwp_text_input_v3::ContentHint::None,
+                        zwp_text_input_v3::ContentPurpose::Normal,
+                    );
+                }
+                text_input.set_cursor_rectangle(x, y, width, height);
+            }
+            None => text_input.disable(),
+        }
+        text_input.commit();
+        self.text_input_caret = caret;
+    }
+
     /// Set the margin for a surface.
     pub fn set_surface_margin(
         &mut self,
@@ -345,6 +594,9 @@ impl WaylandState {
         left: i32,
     ) {
         self.with_layer_surface(id, |ls| ls.set_margin(top, right, bottom, left));
+        if let Some(surface_state) = self.surfaces.get_mut(&id) {
+            surface_state.margin = Some((top, right, bottom, left));
+        }
         log::info!(
             "Surface {:?} margin set to top={}, right={}, bottom={}, left={}",
             id,
@@ -378,6 +630,11 @@ impl WaylandState {
         self.surfaces.values().all(|s| s.configured)
     }
 
+    /// Check if any surface has input waiting to be dispatched.
+    pub fn has_pending_events(&self) -> bool {
+        self.surfaces.values().any(|s| !s.pending_events.is_empty())
+    }
+
     /// Check if any surface needs rendering.
     pub fn any_surface_needs_render(&self) -> bool {
         self.surfaces
@@ -534,6 +791,117 @@ impl WaylandState {
         let device = manager.get_shape_device(pointer, qh);
         device.set_shape(self.pointer_enter_serial, shape);
     }
+
+    /// Show an image as the cursor
+    pub fn set_custom_cursor(
+        &mut self,
+        cursor: &CustomCursor,
+        qh: &QueueHandle<Self>,
+        scale_factor: f32,
+    ) {
+        let Some(ref pointer) = self.pointer else {
+            return;
+        };
+        let Some(ref shm) = self.shm else {
+            return;
+        };
+        let scale = (scale_factor.ceil() as i32).max(1);
+        let surface = self
+            .cursor_surface
+            .get_or_insert_with(|| self.compositor_state.create_surface(qh));
+
+        // Redraw only when the image or scale changed
+        let up_to_date = self
+            .cursor_buffer
+            .as_ref()
+            .is_some_and(|(_, drawn, drawn_scale)| drawn == cursor && *drawn_scale == scale);
+        if !up_to_date {
+            let Some((width, height, buffer_scale, pixels)) =
+                rasterize_cursor(&cursor.source, scale)
+            else {
+                log::warn!("Failed to load custom cursor image {:?}", cursor.source);
+                return;
+            };
+            let stride = width as i32 * 4;
+            if self.cursor_pool.is_none() {
+                match SlotPool::new(pixels.len(), shm) {
+                    Ok(pool) => self.cursor_pool = Some(pool),
+                    Err(e) => {
+                        log::warn!("Failed to create cursor buffer pool: {}", e);
+                        return;
+                    }
+                }
+            }
+            let pool = self.cursor_pool.as_mut().unwrap();
+            let (buffer, canvas) = match pool.create_buffer(
+                width as i32,
+                height as i32,
+                stride,
+                wl_shm::Format::Argb8888,
+            ) {
+                Ok(created) => created,
+                Err(e) => {
+                    log::warn!("Failed to create cursor buffer: {}", e);
+                    return;
+                }
+            };
+            // Premultiplied RGBA → little-endian ARGB8888 (BGRA in memory)
+            for (dst, src) in canvas.chunks_exact_mut(4).zip(pixels.chunks_exact(4)) {
+                dst.copy_from_slice(&[src[2], src[1], src[0], src[3]]);
+            }
+            surface.set_buffer_scale(buffer_scale);
+            if let Err(e) = buffer.attach_to(surface) {
+                log::warn!("Failed to attach cursor buffer: {:?}", e);
+                return;
+            }
+            surface.damage_buffer(0, 0, width as i32, height as i32);
+            surface.commit();
+            self.cursor_buffer = Some((buffer, cursor.clone(), scale));
+        }
+
+        pointer.set_cursor(
+            self.pointer_enter_serial,
+            Some(surface),
+            cursor.hotspot_x,
+            cursor.hotspot_y,
+        );
+    }
+}
+
+/// Decode a cursor image into premultiplied RGBA pixels.
+///
+/// Returns `(width, height, buffer_scale, pixels)`. Raster images map one pixel
+/// to one logical pixel; SVGs are rendered at `scale`.
+fn rasterize_cursor(source: &ImageSource, scale: i32) -> Option<(u32, u32, i32, Vec<u8>)> {
+    let raster = |img: image::DynamicImage| {
+        let rgba = img.to_rgba8();
+        let (width, height) = rgba.dimensions();
+        let mut pixels = rgba.into_raw();
+        for px in pixels.chunks_exact_mut(4) {
+            let a = px[3] as u16;
+            for c in &mut px[..3] {
+                *c = ((*c as u16 * a + 127) / 255) as u8;
+            }
+        }
+        (width, height, 1, pixels)
+    };
+    let svg = |data: &[u8]| {
+        let tree = resvg::usvg::Tree::from_data(data, &resvg::usvg::Options::default()).ok()?;
+        let size = tree.size();
+        let width = (size.width() * scale as f32).ceil() as u32;
+        let height = (size.height() * scale as f32).ceil() as u32;
+        let mut pixmap = resvg::tiny_skia::Pixmap::new(width, height)?;
+        let transform = resvg::tiny_skia::Transform::from_scale(scale as f32, scale as f32);
+        resvg::render(&tree, transform, &mut pixmap.as_mut());
+        Some((width, height, scale, pixmap.take()))
+    };
+    let decoded = match source {
+        ImageSource::Path(path) => raster(image::open(path).ok()?),
+        ImageSource::Bytes(bytes) => raster(image::load_from_memory(bytes).ok()?),
+        ImageSource::SvgPath(path) => svg(&std::fs::read(path).ok()?)?,
+        ImageSource::SvgBytes(bytes) => svg(bytes)?,
+    };
+    (decoded.0 > 0 && decoded.1 > 0).then_some(decoded)
 }
 
 pub struct WaylandWindowWrapper {
@@ -642,13 +1010,15 @@ impl CompositorHandler for WaylandState {
         // Find which surface this is for
         if let Some(id) = self.surface_lookup.get(&surface.id()).copied()
             && let Some(surface_state) = self.surfaces.get_mut(&id)
-            && !surface_state.first_frame_presented
         {
-            log::info!(
-                "Surface {:?} first frame presented by compositor - initialization complete",
-                id
-            );
-            surface_state.first_frame_presented = true;
+            surface_state.frame_callback_since = None;
+            if !surface_state.first_frame_presented {
+                log::info!(
+                    "Surface {:?} first frame presented by compositor - initialization complete",
+                    id
+                );
+                surface_state.first_frame_presented = true;
+            }
         }
     }
 }
@@ -766,6 +1136,10 @@ impl SeatHandler for WaylandState {
                 .seat_state
                 .get_pointer(qh, &seat)
                 .expect("Failed to get pointer");
+            if let Some(ref gestures) = self.pointer_gestures {
+                self.pinch_gesture = Some(gestures.get_pinch_gesture(&pointer, qh, ()));
+                self.swipe_gesture = Some(gestures.get_swipe_gesture(&pointer, qh, ()));
+            }
             self.pointer = Some(pointer);
         }
 
@@ -778,6 +1152,12 @@ impl SeatHandler for WaylandState {
                 .expect("Failed to get keyboard");
             self.keyboard = Some(keyboard);
 
+            if self.text_input.is_none()
+                && let Some(ref manager) = self.text_input_manager
+            {
+                self.text_input = Some(manager.get_text_input(&seat, qh, ()));
+            }
+
             // Create data device for clipboard when we have a seat
             if self.data_device.is_none()
                 && let Some(ref manager) = self.data_device_manager
@@ -798,6 +1178,12 @@ impl SeatHandler for WaylandState {
     ) {
         if capability == Capability::Pointer {
             log::info!("Pointer capability removed");
+            if let Some(pinch) = self.pinch_gesture.take() {
+                pinch.destroy();
+            }
+            if let Some(swipe) = self.swipe_gesture.take() {
+                swipe.destroy();
+            }
             if let Some(pointer) = self.pointer.take() {
                 pointer.release();
             }
@@ -807,6 +1193,11 @@ impl SeatHandler for WaylandState {
             if let Some(keyboard) = self.keyboard.take() {
                 keyboard.release();
             }
+            if let Some(text_input) = self.text_input.take() {
+                text_input.destroy();
+            }
+            self.text_input_surface = None;
+            self.text_input_caret = None;
         }
     }
 
@@ -943,10 +1334,138 @@ impl PointerHandler for WaylandState {
                             delta_x,
                             delta_y,
                             source: scroll_source,
+                            modifiers: self.modifiers,
+                        });
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl Dispatch<ZwpPointerGesturePinchV1, ()> for WaylandState {
+    fn event(
+        state: &mut Self,
+        _pinch: &ZwpPointerGesturePinchV1,
+        event: zwp_pointer_gesture_pinch_v1::Event,
+        _data: &(),
+        _conn: &Connection,
+        _qh: &QueueHandle<Self>,
+    ) {
+        // The protocol reports scale relative to the start of the gesture;
+        // widgets get the factor since the previous event so they can accumulate it.
+        let (scale, phase) = match event {
+            zwp_pointer_gesture_pinch_v1::Event::Begin { .. } => {
+                state.pinch_scale = 1.0;
+                (1.0, GesturePhase::Begin)
+            }
+            zwp_pointer_gesture_pinch_v1::Event::Update { scale, .. } => {
+                let factor = if state.pinch_scale > 0.0 {
+                    scale / state.pinch_scale
+                } else {
+                    1.0
+                };
+                state.pinch_scale = scale;
+                (factor as f32, GesturePhase::Update)
+            }
+            zwp_pointer_gesture_pinch_v1::Event::End { cancelled, .. } => {
+                let phase = if cancelled != 0 {
+                    GesturePhase::Cancel
+                } else {
+                    GesturePhase::End
+                };
+                (1.0, phase)
+            }
+            _ => return,
+        };
+        state.push_pointer_event(Event::Pinch {
+            x: state.pointer_x,
+            y: state.pointer_y,
+            scale,
+            phase,
+        });
+    }
+}
+
+impl Dispatch<ZwpPointerGestureSwipeV1, ()> for WaylandState {
+    fn event(
+        state: &mut Self,
+        _swipe: &ZwpPointerGestureSwipeV1,
+        event: zwp_pointer_gesture_swipe_v1::Event,
+        _data: &(),
+        _conn: &Connection,
+        _qh: &QueueHandle<Self>,
+    ) {
+        let (delta_x, delta_y, phase) = match event {
+            zwp_pointer_gesture_swipe_v1::Event::Begin { fingers, .. } => {
+                state.swipe_fingers = fingers;
+                (0.0, 0.0, GesturePhase::Begin)
+            }
+            zwp_pointer_gesture_swipe_v1::Event::Update { dx, dy, .. } => {
+                (dx as f32, dy as f32, GesturePhase::Update)
+            }
+            zwp_pointer_gesture_swipe_v1::Event::End { cancelled, .. } => {
+                let phase = if cancelled != 0 {
+                    GesturePhase::Cancel
+                } else {
+                    GesturePhase::End
+                };
+                (0.0, 0.0, phase)
+            }
+            _ => return,
+        };
+        state.push_pointer_event(Event::Swipe {
+            x: state.pointer_x,
+            y: state.pointer_y,
+            delta_x,
+            delta_y,
+            fingers: state.swipe_fingers,
+            phase,
+        });
+    }
+}
+
+impl Dispatch<ZwpTextInputV3, ()> for WaylandState {
+    fn event(
+        state: &mut Self,
+        _text_input: &ZwpTextInputV3,
+        event: zwp_text_input_v3::Event,
+        _data: &(),
+        _conn: &Connection,
+        _qh: &QueueHandle<Self>,
+    ) {
+        match event {
+            zwp_text_input_v3::Event::Enter { surface } => {
+                state.text_input_surface = state.surface_lookup.get(&surface.id()).copied();
+                // The caret is sent again once the surface reports it
+                state.text_input_caret = None;
+            }
+            zwp_text_input_v3::Event::Leave { .. } => {
+                state.text_input_surface = None;
+                state.text_input_caret = None;
+            }
+            zwp_text_input_v3::Event::CommitString { text } => {
+                state.pending_commit_string = text;
+            }
+            zwp_text_input_v3::Event::Done { .. } => {
+                // Committed text is typed into the focused field. Preedit and
+                // surrounding text deletion need surrounding text, which isn't
+                // reported, so they are ignored.
+                let Some(text) = state.pending_commit_string.take() else {
+                    return;
+                };
+                if let Some(id) = state.text_input_surface
+                    && let Some(surface_state) = state.surfaces.get_mut(&id)
+                {
+                    for c in text.chars() {
+                        surface_state.pending_events.push(Event::KeyDown {
+                            key: Key::Char(c),
+                            modifiers: Modifiers::default(),
                         });
                     }
                 }
             }
+            _ => {}
         }
     }
 }
@@ -1000,6 +1519,7 @@ impl KeyboardHandler for WaylandState {
         _serial: u32,
     ) {
         log::debug!("Keyboard focus left");
+        self.stop_key_repeat();
 
         // Route event to correct surface
         let surface_id = self.surface_lookup.get(&surface.id()).copied();
@@ -1028,17 +1548,13 @@ impl KeyboardHandler for WaylandState {
             // (e.g., composed 'é' instead of raw 'e' after a compose sequence)
             self.pressed_keys.insert(event.raw_code, key);
 
-            let key_event = Event::KeyDown {
+            self.push_keyboard_event(Event::KeyDown {
                 key,
                 modifiers: self.modifiers,
-            };
+            });
 
-            // Route to the surface with keyboard focus
-            if let Some(id) = self.current_keyboard_surface
-                && let Some(surface_state) = self.surfaces.get_mut(&id)
-            {
-                surface_state.pending_events.push(key_event);
-            }
+            // Holding the key repeats it at the compositor's rate
+            self.start_key_repeat(event.raw_code, key);
         }
     }
 
@@ -1052,6 +1568,13 @@ impl KeyboardHandler for WaylandState {
     ) {
         // Use the stored key from press_key if available (handles compose sequences
         // where the composed character differs from the raw keysym on release)
+        if self
+            .repeating_key
+            .is_some_and(|(raw_code, _)| raw_code == event.raw_code)
+        {
+            self.stop_key_repeat();
+        }
+
         let key = self
             .pressed_keys
             .remove(&event.raw_code)
@@ -1098,21 +1621,47 @@ impl KeyboardHandler for WaylandState {
         _serial: u32,
         event: KeyEvent,
     ) {
+        // The compositor repeats keys itself: stop synthesizing repeats
+        self.compositor_repeat = true;
+        self.stop_key_repeat();
+
         // Treat key repeat as a new key press
         if let Some(key) = keysym_to_key(event.keysym, event.utf8.as_deref(), true) {
-            let key_event = Event::KeyDown {
+            self.push_keyboard_event(Event::KeyDown {
                 key,
                 modifiers: self.modifiers,
-            };
-
-            // Route to the surface with keyboard focus
-            if let Some(id) = self.current_keyboard_surface
-                && let Some(surface_state) = self.surfaces.get_mut(&id)
-            {
-                surface_state.pending_events.push(key_event);
-            }
+            });
         }
     }
+
+    fn update_repeat_info(
+        &mut self,
+        _conn: &Connection,
+        _qh: &QueueHandle<Self>,
+        _keyboard: &wl_keyboard::WlKeyboard,
+        info: RepeatInfo,
+    ) {
+        log::debug!("Keyboard repeat info: {:?}", info);
+        self.repeat_info = info;
+        self.stop_key_repeat();
+    }
+}
+
+/// Whether holding `key` repeats it: typed characters and editing or
+/// navigation keys do, keys that trigger an action (Enter, Tab, Escape) don't.
+fn key_repeats(key: Key) -> bool {
+    match key {
+        Key::Char(c) => !c.is_control(),
+        Key::Backspace
+        | Key::Delete
+        | Key::Left
+        | Key::Right
+        | Key::Up
+        | Key::Down
+        | Key::Home
+        | Key::End => true,
+        Key::Enter | Key::Tab | Key::Escape => false,
+    }
 }
 
 /// Convert XKB keysym to our Key type
@@ -1164,6 +1713,12 @@ fn keysym_to_key(keysym: Keysym, utf8: Option<&str>, is_press: bool) -> Option<K
     None
 }
 
+impl ShmHandler for WaylandState {
+    fn shm_state(&mut self) -> &mut Shm {
+        self.shm.as_mut().expect("wl_shm bound")
+    }
+}
+
 impl ProvidesRegistryState for WaylandState {
     fn registry(&mut self) -> &mut RegistryState {
         &mut self.registry_state
@@ -1313,3 +1868,27 @@ delegate_pointer!(WaylandState);
 delegate_keyboard!(WaylandState);
 delegate_data_device!(WaylandState);
 delegate_registry!(WaylandState);
+delegate_shm!(WaylandState);
+delegate_noop!(WaylandState: ignore ZwpPointerGesturesV1);
+delegate_noop!(WaylandState: ignore ZwpTextInputManagerV3);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_text_and_navigation_keys_repeat() {
+        for key in [
+            Key::Char('a'),
+            Key::Backspace,
+            Key::Delete,
+            Key::Left,
+            Key::End,
+        ] {
+            assert!(key_repeats(key), "{key:?}");
+        }
+        for key in [Key::Enter, Key::Escape, Key::Tab, Key::Char('\n')] {
+            assert!(!key_repeats(key), "{key:?}");
+        }
+    }
+}
@@ -1,10 +1,18 @@
 //! Cursor management for changing the mouse cursor appearance.
 //!
-//! Widgets can request a cursor change by calling `set_cursor(CursorIcon::Text)`.
-//! The main event loop will pick up cursor changes and apply them via Wayland.
+//! Widgets can request a cursor change by calling `set_cursor(CursorIcon::Text)`,
+//! or show an image with `set_custom_cursor`. The main event loop will pick up
+//! cursor changes and apply them via Wayland.
+//!
+//! Containers declaring a cursor with `Container::cursor` register it while
+//! hovered; the deepest hovered one wins, so nested widgets each show their own
+//! cursor and leaving one falls back to its hovered ancestor.
 
 use std::cell::RefCell;
 
+use crate::tree::WidgetId;
+use crate::widgets::image::ImageSource;
+
 /// Standard cursor icons that can be displayed.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum CursorIcon {
@@ -44,20 +52,67 @@ pub enum CursorIcon {
     Progress,
 }
 
+/// A cursor drawn from an image.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CustomCursor {
+    pub source: ImageSource,
+    /// Horizontal position of the click point, in logical pixels from the left edge
+    pub hotspot_x: i32,
+    /// Vertical position of the click point, in logical pixels from the top edge
+    pub hotspot_y: i32,
+}
+
+/// A cursor to apply to the pointer.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Cursor {
+    Icon(CursorIcon),
+    Custom(CustomCursor),
+}
+
 thread_local! {
     /// Current requested cursor
-    static CURRENT_CURSOR: RefCell<CursorIcon> = const { RefCell::new(CursorIcon::Default) };
+    static CURRENT_CURSOR: RefCell<Cursor> = const { RefCell::new(Cursor::Icon(CursorIcon::Default)) };
 
     /// Flag indicating cursor was changed and needs to be synced to Wayland
     static CURSOR_CHANGED: RefCell<bool> = const { RefCell::new(false) };
+
+    /// Cursors of hovered widgets: (widget, tree depth, cursor)
+    static HOVER_CURSORS: RefCell<Vec<(WidgetId, usize, CursorIcon)>> =
+        const { RefCell::new(Vec::new()) };
 }
 
 /// Set the cursor to display.
 /// This should be called by widgets when they want to change the cursor appearance.
 pub fn set_cursor(cursor: CursorIcon) {
+    update_cursor(Cursor::Icon(cursor));
+}
+
+/// Show an image as the cursor, with the click point at `(hotspot_x, hotspot_y)`.
+///
+/// Raster images are shown at one image pixel per logical pixel; SVGs are
+/// rendered at the output scale. Call [`set_cursor`] to go back to a standard
+/// cursor, e.g. when the pointer leaves the widget.
+///
+/// ```ignore
+/// container().on_hover(|hovered| {
+///     if hovered {
+///         set_custom_cursor("icons/eyedropper.png", 1, 22);
+///     } else {
+///         set_cursor(CursorIcon::Default);
+///     }
+/// })
+/// ```
+pub fn set_custom_cursor(source: impl Into<ImageSource>, hotspot_x: i32, hotspot_y: i32) {
+    update_cursor(Cursor::Custom(CustomCursor {
+        source: source.into(),
+        hotspot_x,
+        hotspot_y,
+    }));
+}
+
+fn update_cursor(cursor: Cursor) {
     CURRENT_CURSOR.with(|c| {
-        let current = *c.borrow();
-        if current != cursor {
+        if *c.borrow() != cursor {
             *c.borrow_mut() = cursor;
             CURSOR_CHANGED.with(|changed| {
                 *changed.borrow_mut() = true;
@@ -66,9 +121,44 @@ pub fn set_cursor(cursor: CursorIcon) {
     });
 }
 
+/// Show `cursor` while widget `id`, at tree depth `depth`, is hovered.
+pub(crate) fn enter_hover_cursor(id: WidgetId, depth: usize, cursor: CursorIcon) {
+    HOVER_CURSORS.with(|cursors| {
+        let mut cursors = cursors.borrow_mut();
+        cursors.retain(|(widget, _, _)| *widget != id);
+        cursors.push((id, depth, cursor));
+    });
+    apply_hover_cursor();
+}
+
+/// Stop showing the cursor of widget `id`, falling back to the cursor of the
+/// deepest widget still hovered, or the default cursor.
+pub(crate) fn leave_hover_cursor(id: WidgetId) {
+    let removed = HOVER_CURSORS.with(|cursors| {
+        let mut cursors = cursors.borrow_mut();
+        let len = cursors.len();
+        cursors.retain(|(widget, _, _)| *widget != id);
+        cursors.len() != len
+    });
+    if removed {
+        apply_hover_cursor();
+    }
+}
+
+fn apply_hover_cursor() {
+    let cursor = HOVER_CURSORS.with(|cursors| {
+        cursors
+            .borrow()
+            .iter()
+            .max_by_key(|(_, depth, _)| *depth)
+            .map(|(_, _, cursor)| *cursor)
+    });
+    set_cursor(cursor.unwrap_or_default());
+}
+
 /// Take pending cursor change (returns cursor if it was changed since last call).
 /// Called by the main event loop to sync cursor to Wayland.
-pub fn take_cursor_change() -> Option<CursorIcon> {
+pub(crate) fn take_cursor_change() -> Option<Cursor> {
     let changed = CURSOR_CHANGED.with(|c| {
         let was_changed = *c.borrow();
         *c.borrow_mut() = false;
@@ -76,7 +166,7 @@ pub fn take_cursor_change() -> Option<CursorIcon> {
     });
 
     if changed {
-        Some(CURRENT_CURSOR.with(|c| *c.borrow()))
+        Some(CURRENT_CURSOR.with(|c| c.borrow().clone()))
     } else {
         None
     }
@@ -86,11 +176,17 @@ pub fn take_cursor_change() -> Option<CursorIcon> {
 ///
 /// Called during `App::drop()` to clear cursor state.
 pub(crate) fn reset_cursor() {
-    CURRENT_CURSOR.with(|c| *c.borrow_mut() = CursorIcon::Default);
+    CURRENT_CURSOR.with(|c| *c.borrow_mut() = Cursor::Icon(CursorIcon::Default));
     CURSOR_CHANGED.with(|c| *c.borrow_mut() = false);
+    HOVER_CURSORS.with(|c| c.borrow_mut().clear());
 }
 
 /// Get the current cursor without clearing the change flag.
+///
+/// Returns `CursorIcon::Default` while a custom cursor is shown.
 pub fn get_current_cursor() -> CursorIcon {
-    CURRENT_CURSOR.with(|c| *c.borrow())
+    CURRENT_CURSOR.with(|c| match *c.borrow() {
+        Cursor::Icon(icon) => icon,
+        Cursor::Custom(_) => CursorIcon::Default,
+    })
 }
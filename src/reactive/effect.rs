@@ -1,3 +1,5 @@
+use std::panic::Location;
+
 use super::owner::{effect_has_owner, register_effect};
 use super::runtime::{EffectId, with_runtime};
 
@@ -6,13 +8,17 @@ pub struct Effect {
 }
 
 impl Effect {
+    #[track_caller]
     pub fn new<F>(f: F) -> Self
     where
         F: FnMut() + 'static,
     {
+        let location = Location::caller();
         let id = with_runtime(|rt| {
-            let id = rt.allocate_effect(Box::new(f));
+            let id = rt.allocate_effect(Box::new(f), location);
             rt.run_effect(id);
+            // Propagate writes made by the first run
+            rt.flush_unless_batching();
             id
         });
 
@@ -57,6 +63,7 @@ impl Drop for Effect {
     }
 }
 
+#[track_caller]
 pub fn create_effect<F>(f: F) -> Effect
 where
     F: FnMut() + 'static,
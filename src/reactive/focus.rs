@@ -7,10 +7,13 @@ use std::cell::RefCell;
 
 use crate::jobs::{JobRequest, request_job};
 use crate::tree::WidgetId;
+use crate::widgets::Rect;
 
 thread_local! {
     /// The currently focused widget ID, if any
     static FOCUSED_WIDGET: RefCell<Option<WidgetId>> = const { RefCell::new(None) };
+    /// Caret last painted by a text field, in the field's local coordinates
+    static CARET: RefCell<Option<(WidgetId, Rect)>> = const { RefCell::new(None) };
 }
 
 /// Request keyboard focus for a widget.
@@ -57,6 +60,20 @@ pub fn focused_widget() -> Option<WidgetId> {
 /// Called during `App::drop()` to clear focus state.
 pub(crate) fn reset_focus() {
     FOCUSED_WIDGET.with(|f| *f.borrow_mut() = None);
+    CARET.with(|c| *c.borrow_mut() = None);
+}
+
+/// Record the caret of text field `id`, in its local coordinates.
+///
+/// Text fields call this while painting with focus, so input methods can be
+/// told where the caret is.
+pub(crate) fn report_caret(id: WidgetId, rect: Rect) {
+    CARET.with(|c| *c.borrow_mut() = Some((id, rect)));
+}
+
+/// The caret of the focused widget, if it is a text field that reported one.
+pub(crate) fn focused_caret() -> Option<(WidgetId, Rect)> {
+    CARET.with(|c| *c.borrow()).filter(|(id, _)| has_focus(*id))
 }
 
 /// Clear all focus (no widget will have focus).
@@ -0,0 +1,134 @@
+//! Per-frame time signal for custom animations.
+//!
+//! [`create_animation_frame`] returns a signal holding the current frame time.
+//! While a widget or effect reads it, the event loop keeps rendering frames
+//! and updates it once per frame; once nothing depends on it the loop goes
+//! back to sleep.
+//!
+//! # Example
+//!
+//! ```ignore
+//! let time = create_animation_frame();
+//!
+//! container()
+//!     .width(40.0)
+//!     .height(40.0)
+//!     .rotate(move || (time.get() * 90.0) as f32) // a quarter turn per second
+//! ```
+
+use std::cell::{Cell, RefCell};
+use std::time::Instant;
+
+use crate::animation::clock::{elapsed_since, now};
+use crate::jobs::request_frame;
+
+use super::invalidation::has_widget_subscribers;
+use super::runtime::{batch, with_runtime};
+use super::{RwSignal, Signal, create_signal, on_cleanup};
+
+/// A frame signal, and whether it has not been through a frame yet.
+struct FrameSignal {
+    time: RwSignal<f64>,
+    fresh: bool,
+}
+
+thread_local! {
+    static FRAME_SIGNALS: RefCell<Vec<FrameSignal>> = const { RefCell::new(Vec::new()) };
+    /// Start of the frame timeline
+    static EPOCH: Cell<Option<Instant>> = const { Cell::new(None) };
+}
+
+/// Seconds since the first frame signal was created, on the animation clock.
+fn frame_time() -> f64 {
+    let epoch = EPOCH.with(|epoch| {
+        let start = epoch.get().unwrap_or_else(now);
+        epoch.set(Some(start));
+        start
+    });
+    elapsed_since(epoch).as_secs_f64()
+}
+
+/// Create a signal holding the current frame time in seconds.
+///
+/// The value increases monotonically and is updated once per rendered frame
+/// while anything reads it, so reading it in a paint-time property animates
+/// that property at the display rate. The signal stops updating when its
+/// readers are removed and is dropped with the current owner.
+pub fn create_animation_frame() -> Signal<f64> {
+    let time = create_signal(frame_time());
+    FRAME_SIGNALS.with(|signals| signals.borrow_mut().push(FrameSignal { time, fresh: true }));
+    on_cleanup(move || {
+        FRAME_SIGNALS.with(|signals| signals.borrow_mut().retain(|s| s.time != time));
+    });
+    // Run a frame so the first readers can subscribe
+    request_frame();
+    time.read_only()
+}
+
+/// Publish the frame time to every frame signal in use.
+///
+/// Returns whether any signal is in use, i.e. whether the event loop should
+/// keep producing frames. A newly created signal counts as in use for one
+/// frame, giving its readers a chance to subscribe.
+pub(crate) fn advance_animation_frames() -> bool {
+    let active: Vec<RwSignal<f64>> = FRAME_SIGNALS.with(|signals| {
+        signals
+            .borrow_mut()
+            .iter_mut()
+            .filter_map(|signal| {
+                let id = signal.time.id();
+                let subscribed =
+                    has_widget_subscribers(id) || with_runtime(|rt| rt.has_subscribers(id));
+                let active = subscribed || signal.fresh;
+                signal.fresh = false;
+                active.then_some(signal.time)
+            })
+            .collect()
+    });
+    if active.is_empty() {
+        return false;
+    }
+    let time = frame_time();
+    batch(|| {
+        for signal in &active {
+            signal.set(time);
+        }
+    });
+    true
+}
+
+/// Forget all frame signals and restart the timeline.
+///
+/// Called during `App::drop()`.
+pub(crate) fn reset_animation_frames() {
+    FRAME_SIGNALS.with(|signals| signals.borrow_mut().clear());
+    EPOCH.with(|epoch| epoch.set(None));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::animation::clock::{advance_clock, resume_clock};
+    use crate::reactive::create_effect;
+    use std::time::Duration;
+
+    #[test]
+    fn test_frame_signal_updates_only_while_subscribed() {
+        reset_animation_frames();
+        advance_clock(Duration::ZERO);
+        let time = create_animation_frame();
+
+        // Fresh signals keep the loop running for one frame
+        assert!(advance_animation_frames());
+        assert!(!advance_animation_frames());
+
+        let seen = create_signal(0.0f64);
+        let _effect = create_effect(move || seen.set(time.get()));
+        advance_clock(Duration::from_millis(500));
+        assert!(advance_animation_frames());
+        assert_eq!(seen.get_untracked(), 0.5);
+
+        resume_clock();
+        reset_animation_frames();
+    }
+}
@@ -182,6 +182,16 @@ pub fn notify_signal_change(signal_id: usize) {
     }
 }
 
+/// Whether any widget depends on the signal
+pub(crate) fn has_widget_subscribers(signal_id: usize) -> bool {
+    REGISTRY.with(|reg| {
+        reg.borrow()
+            .signal_to_widgets
+            .get(signal_id)
+            .is_some_and(|subs| !subs.is_empty())
+    })
+}
+
 /// Clear signal subscribers for a specific signal (when signal is disposed)
 pub fn clear_signal_subscribers(signal_id: usize) {
     REGISTRY.with(|reg| {
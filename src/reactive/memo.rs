@@ -46,6 +46,7 @@ impl<T: Clone + PartialEq + Send + 'static> Copy for Memo<T> {}
 /// let label = create_memo(move || format!("Count: {}", count.get()));
 /// text(label)  // Only repaints when the formatted string actually changes
 /// ```
+#[track_caller]
 pub fn create_memo<T, F>(f: F) -> Memo<T>
 where
     T: Clone + PartialEq + Send + 'static,
@@ -124,4 +125,56 @@ mod tests {
         let sig: Signal<i32> = memo.into_signal();
         assert_eq!(sig.get(), 10);
     }
+
+    #[test]
+    fn test_memo_chain_propagates() {
+        use crate::reactive::owner::with_owner;
+
+        let ((source, doubled, plus_one), _owner) = with_owner(|| {
+            let source = create_signal(1);
+            let doubled = create_memo(move || source.get() * 2);
+            let plus_one = create_memo(move || doubled.get() + 1);
+            (source, doubled, plus_one)
+        });
+        source.set(5);
+        assert_eq!(doubled.get(), 10);
+        // Written from inside the first memo's effect
+        assert_eq!(plus_one.get(), 11);
+    }
+
+    #[test]
+    fn test_self_writing_memo_is_stopped() {
+        use crate::reactive::owner::with_owner;
+        use crate::reactive::runtime::MAX_EFFECT_ROUNDS;
+
+        let ((source, memo), _owner) = with_owner(|| {
+            let source = create_signal(0);
+            let memo = create_memo(move || {
+                let value = source.get();
+                source.set(value + 1);
+                value
+            });
+            (source, memo)
+        });
+        // The cycle is broken instead of looping forever
+        let runs = source.get_untracked() as usize;
+        assert!(runs > 1 && runs <= MAX_EFFECT_ROUNDS + 2, "{runs}");
+        assert!(memo.get() < source.get_untracked());
+
+        // A converging self-write settles normally
+        let (clamped, _owner) = with_owner(|| {
+            let clamped = create_signal(50);
+            let _memo = create_memo(move || {
+                let value = clamped.get();
+                if value > 10 {
+                    clamped.set(10);
+                }
+                value
+            });
+            clamped
+        });
+        assert_eq!(clamped.get_untracked(), 10);
+        clamped.set(30);
+        assert_eq!(clamped.get_untracked(), 10);
+    }
 }
@@ -3,14 +3,18 @@ pub mod context;
 pub mod cursor;
 pub mod effect;
 pub mod focus;
+pub mod frame;
 pub mod into_signal;
 pub mod invalidation;
 pub mod memo;
 pub mod owner;
+#[cfg(feature = "serde")]
+pub mod persist;
 pub mod runtime;
 pub mod service;
 pub mod signal;
 pub mod storage;
+pub mod timer;
 
 pub(crate) use clipboard::{
     clipboard_copy, clipboard_paste, set_system_clipboard, take_clipboard_change,
@@ -18,10 +22,12 @@ pub(crate) use clipboard::{
 pub use context::{
     expect_context, has_context, provide_context, provide_signal_context, use_context, with_context,
 };
-pub(crate) use cursor::take_cursor_change;
-pub use cursor::{CursorIcon, set_cursor};
+pub(crate) use cursor::{Cursor, enter_hover_cursor, leave_hover_cursor, take_cursor_change};
+pub use cursor::{CursorIcon, CustomCursor, set_cursor, set_custom_cursor};
 pub use effect::{Effect, create_effect};
-pub(crate) use focus::{focused_widget, has_focus, release_focus, request_focus};
+pub(crate) use focus::{clear_focus, focused_widget, has_focus, release_focus, request_focus};
+pub(crate) use frame::advance_animation_frames;
+pub use frame::create_animation_frame;
 #[doc(hidden)]
 pub use into_signal::{
     ClosureMarker, LossyMarker, MemoMarker, RwSignalMarker, SignalMarker, ValueMarker,
@@ -32,7 +38,9 @@ pub use memo::{Memo, create_memo};
 // Only on_cleanup is public API - with_owner, dispose_owner, and OwnerId are
 // internal and automatically used by the dynamic children system
 pub use owner::on_cleanup;
-pub(crate) use owner::{OwnerId, create_root_owner, dispose_owner, with_owner};
+pub(crate) use owner::{OwnerId, create_root_owner, dispose_owner, with_owner, without_owner};
+#[cfg(feature = "serde")]
+pub use persist::{register_state, restore_state, snapshot_state, unregister_state};
 
 /// Internal module for macro support. NOT PART OF PUBLIC API.
 /// Do not use directly - these are re-exported for proc macros only.
@@ -46,6 +54,8 @@ pub use service::{Service, ServiceContext, create_service};
 pub use signal::{
     OptionSignalExt, RwSignal, Signal, WriteSignal, create_derived, create_signal, create_stored,
 };
+pub(crate) use timer::init_timers;
+pub use timer::{create_interval, create_timeout};
 
 /// Reset all reactive system state.
 ///
@@ -60,4 +70,8 @@ pub(crate) fn reset_reactive() {
     cursor::reset_cursor();
     focus::reset_focus();
     context::reset_contexts();
+    timer::reset_timers();
+    frame::reset_animation_frames();
+    #[cfg(feature = "serde")]
+    persist::reset_state_registry();
 }
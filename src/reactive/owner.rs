@@ -169,6 +169,19 @@ pub fn with_owner<T>(f: impl FnOnce() -> T) -> (T, OwnerId) {
     (result, owner_id)
 }
 
+/// Execute a closure outside of any owner scope.
+///
+/// Signals, effects and timers created within the closure are not cleaned up
+/// with the caller's owner; they live until the app is torn down. Used for
+/// app-wide state that is created lazily from whichever component first
+/// needs it.
+pub(crate) fn without_owner<T>(f: impl FnOnce() -> T) -> T {
+    let prev_owner = CURRENT_OWNER.with(|current| current.borrow_mut().take());
+    let result = f();
+    CURRENT_OWNER.with(|current| *current.borrow_mut() = prev_owner);
+    result
+}
+
 /// Get the current owner ID, if any.
 ///
 /// Returns `None` if not currently inside an owner scope.
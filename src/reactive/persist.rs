@@ -0,0 +1,208 @@
+//! Named signal registry for snapshotting and restoring UI state.
+//!
+//! Signals registered with [`register_state`] can be dumped to a JSON value
+//! with [`snapshot_state`] and written back with [`restore_state`] — useful
+//! for persisting session state across restarts and for capturing the state
+//! of a UI while debugging or testing.
+//!
+//! `SignalFields` structs can register all their fields at creation with the
+//! `#[signal_fields(persist = "prefix")]` attribute; each field is registered
+//! as `"prefix.field"`.
+//!
+//! Requires the `serde` feature.
+//!
+//! # Example
+//!
+//! ```ignore
+//! let volume = create_signal(50u8);
+//! register_state("volume", volume);
+//!
+//! // On exit
+//! std::fs::write("state.json", snapshot_state().to_string())?;
+//!
+//! // On startup
+//! let saved: serde_json::Value = serde_json::from_str(&std::fs::read_to_string("state.json")?)?;
+//! restore_state(&saved)?;
+//! ```
+
+use std::cell::{Cell, RefCell};
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
+
+use super::owner::on_cleanup;
+use super::runtime::batch;
+use super::signal::RwSignal;
+
+/// Reads a signal as JSON.
+type SnapshotFn = Box<dyn Fn() -> serde_json::Result<Value>>;
+/// Decodes JSON into a deferred write to the signal.
+type RestoreFn = Box<dyn Fn(Value) -> serde_json::Result<Box<dyn FnOnce()>>>;
+
+struct StateEntry {
+    /// Identifies this registration, so cleanup doesn't remove a later
+    /// registration under the same name
+    key: u64,
+    name: String,
+    snapshot: SnapshotFn,
+    restore: RestoreFn,
+}
+
+thread_local! {
+    static STATE_REGISTRY: RefCell<Vec<StateEntry>> = const { RefCell::new(Vec::new()) };
+    static NEXT_STATE_KEY: Cell<u64> = const { Cell::new(0) };
+}
+
+/// Register a signal under `name` so it is included in [`snapshot_state`]
+/// and [`restore_state`].
+///
+/// Registering a name again replaces the previous signal. The registration
+/// is removed when the current owner is cleaned up, so signals of a disposed
+/// component (e.g. a removed dynamic child) never reach a snapshot.
+pub fn register_state<T>(name: impl Into<String>, signal: RwSignal<T>)
+where
+    T: Clone + PartialEq + Serialize + DeserializeOwned + 'static,
+{
+    let name = name.into();
+    let key = NEXT_STATE_KEY.with(|next| next.replace(next.get() + 1));
+    let entry = StateEntry {
+        key,
+        name: name.clone(),
+        snapshot: Box::new(move || signal.with_untracked(|value| serde_json::to_value(value))),
+        restore: Box::new(move |value| {
+            let value: T = serde_json::from_value(value)?;
+            Ok(Box::new(move || signal.set(value)))
+        }),
+    };
+    STATE_REGISTRY.with(|registry| {
+        let mut registry = registry.borrow_mut();
+        match registry.iter_mut().find(|e| e.name == name) {
+            Some(existing) => *existing = entry,
+            None => registry.push(entry),
+        }
+    });
+    on_cleanup(move || {
+        STATE_REGISTRY.with(|registry| registry.borrow_mut().retain(|e| e.key != key));
+    });
+}
+
+/// Remove a signal from the state registry.
+pub fn unregister_state(name: &str) {
+    STATE_REGISTRY.with(|registry| registry.borrow_mut().retain(|e| e.name != name));
+}
+
+/// Capture the current value of every registered signal as a JSON object
+/// keyed by registration name.
+///
+/// Values that fail to serialize are logged and left out.
+pub fn snapshot_state() -> Value {
+    STATE_REGISTRY.with(|registry| {
+        let mut map = Map::new();
+        for entry in registry.borrow().iter() {
+            match (entry.snapshot)() {
+                Ok(value) => {
+                    map.insert(entry.name.clone(), value);
+                }
+                Err(e) => log::warn!("Failed to snapshot state '{}': {}", entry.name, e),
+            }
+        }
+        Value::Object(map)
+    })
+}
+
+/// Write the values of a [`snapshot_state`] object back into the registered signals.
+///
+/// Keys without a registered signal are ignored, so snapshots from older
+/// versions of an app still restore. Every value is decoded before any signal
+/// is written: on error nothing changes. Writes are batched so dependents
+/// update once.
+pub fn restore_state(snapshot: &Value) -> serde_json::Result<()> {
+    let Some(map) = snapshot.as_object() else {
+        return Err(serde::de::Error::custom(
+            "state snapshot must be a JSON object",
+        ));
+    };
+    let writes = STATE_REGISTRY.with(|registry| {
+        registry
+            .borrow()
+            .iter()
+            .filter_map(|entry| {
+                map.get(&entry.name)
+                    .map(|value| (entry.restore)(value.clone()))
+            })
+            .collect::<serde_json::Result<Vec<_>>>()
+    })?;
+    batch(|| {
+        for write in writes {
+            write();
+        }
+    });
+    Ok(())
+}
+
+/// Clear the state registry (called during App teardown).
+pub(crate) fn reset_state_registry() {
+    STATE_REGISTRY.with(|registry| registry.borrow_mut().clear());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reactive::create_signal;
+
+    #[test]
+    fn test_snapshot_and_restore_round_trip() {
+        let count = create_signal(3i32);
+        let name = create_signal(String::from("bar"));
+        register_state("count", count);
+        register_state("name", name);
+
+        let snapshot = snapshot_state();
+        assert_eq!(snapshot, serde_json::json!({ "count": 3, "name": "bar" }));
+
+        count.set(10);
+        name.set("baz".into());
+        restore_state(&snapshot).unwrap();
+        assert_eq!(count.get_untracked(), 3);
+        assert_eq!(name.get_untracked(), "bar");
+        reset_state_registry();
+    }
+
+    #[test]
+    fn test_restore_is_all_or_nothing() {
+        let count = create_signal(1i32);
+        let flag = create_signal(false);
+        register_state("count", count);
+        register_state("flag", flag);
+
+        let bad = serde_json::json!({ "count": 7, "flag": "not a bool", "unknown": 1 });
+        assert!(restore_state(&bad).is_err());
+        assert_eq!(count.get_untracked(), 1);
+
+        let partial = serde_json::json!({ "flag": true, "unknown": 1 });
+        restore_state(&partial).unwrap();
+        assert!(flag.get_untracked());
+        assert_eq!(count.get_untracked(), 1);
+        reset_state_registry();
+    }
+
+    #[test]
+    fn test_disposed_owner_unregisters_its_state() {
+        use crate::reactive::{dispose_owner, with_owner};
+
+        let theme = create_signal(String::from("dark"));
+        register_state("theme", theme);
+        let (_, owner) = with_owner(|| register_state("row.expanded", create_signal(true)));
+        assert_eq!(
+            snapshot_state(),
+            serde_json::json!({ "theme": "dark", "row.expanded": true })
+        );
+
+        dispose_owner(owner);
+        // The row's signal slot may be reused by signals created later
+        let _reused = create_signal(0u8);
+        assert_eq!(snapshot_state(), serde_json::json!({ "theme": "dark" }));
+        reset_state_registry();
+    }
+}
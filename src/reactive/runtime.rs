@@ -15,12 +15,20 @@
 //! the effect's dependencies. When any dependency changes, the effect is scheduled
 //! to re-run.
 //!
+//! ## Cycles
+//!
+//! Writes made while an effect runs are propagated once it returns, so chains of
+//! memos settle within a single update. An effect that keeps re-triggering itself
+//! (directly or through other effects) is stopped after [`MAX_EFFECT_ROUNDS`]
+//! rounds with an error naming where it was created.
+//!
 //! ## Usage
 //!
 //! Most code should use the higher-level APIs in the `reactive` module rather than
 //! interacting with the runtime directly.
 
 use std::cell::{Cell, RefCell};
+use std::panic::Location;
 use std::sync::Mutex;
 use std::sync::atomic::{AtomicU64, Ordering};
 
@@ -43,8 +51,16 @@ thread_local! {
     /// Nesting depth for `batch()`. When > 0, `notify_write()` collects pending
     /// effects but defers `flush_effects()` until the batch completes.
     static BATCH_DEPTH: Cell<u32> = const { Cell::new(0) };
+
+    /// Signals written while the Runtime RefCell was borrowed (i.e. from inside
+    /// an effect). Their subscribers are scheduled once the effect returns.
+    static DEFERRED_WRITES: RefCell<Vec<SignalId>> = const { RefCell::new(Vec::new()) };
 }
 
+/// Maximum number of propagation rounds in one flush. Effects still pending
+/// after this many rounds keep re-triggering themselves and are dropped.
+pub const MAX_EFFECT_ROUNDS: usize = 100;
+
 /// Epoch counter for write filtering. Incremented on each runtime reset (App restart).
 /// Writes tagged with a stale epoch are silently discarded in `flush_bg_writes()`.
 static WRITE_EPOCH: AtomicU64 = AtomicU64::new(0);
@@ -150,6 +166,8 @@ pub struct Runtime {
     /// Pending effects to run. Uses Vec with dedup — most frames have 0–5 pending effects.
     pending_effects: Vec<EffectId>,
     effect_callbacks: Vec<Option<Box<dyn FnMut()>>>,
+    /// Where each effect was created, for cycle errors.
+    effect_locations: Vec<&'static Location<'static>>,
     /// Per-effect dependencies (which signals it reads). Vec with dedup — most effects
     /// depend on 1–3 signals, making linear scan faster than HashSet.
     effect_dependencies: Vec<Vec<SignalId>>,
@@ -174,10 +192,15 @@ impl Runtime {
         }
     }
 
-    pub fn allocate_effect(&mut self, callback: Box<dyn FnMut()>) -> EffectId {
+    pub fn allocate_effect(
+        &mut self,
+        callback: Box<dyn FnMut()>,
+        location: &'static Location<'static>,
+    ) -> EffectId {
         // Reuse a freed slot if available
         if let Some(id) = self.free_effect_ids.pop() {
             self.effect_callbacks[id] = Some(callback);
+            self.effect_locations[id] = location;
             self.effect_dependencies[id].clear();
             return id;
         }
@@ -185,23 +208,50 @@ impl Runtime {
         let id = self.next_effect_id;
         self.next_effect_id += 1;
         self.effect_callbacks.push(Some(callback));
+        self.effect_locations.push(location);
         self.effect_dependencies.push(Vec::new());
         id
     }
 
+    /// Whether any effect depends on the signal
+    pub(crate) fn has_subscribers(&self, signal_id: SignalId) -> bool {
+        self.signal_subscribers
+            .get(signal_id)
+            .is_some_and(|subs| !subs.is_empty())
+    }
+
     pub fn notify_write(&mut self, signal_id: SignalId) {
         // Check if this signal exists in our runtime (it might not if called from another thread)
         if signal_id >= self.signal_subscribers.len() {
             return;
         }
 
+        self.schedule_subscribers(signal_id);
+        self.flush_unless_batching();
+    }
+
+    /// Mark every effect that depends on the signal as pending.
+    fn schedule_subscribers(&mut self, signal_id: SignalId) {
+        let Some(subscribers) = self.signal_subscribers.get(signal_id) else {
+            return;
+        };
         // Iterate subscribers by index — avoids temporary Vec allocation
-        for i in 0..self.signal_subscribers[signal_id].len() {
+        for i in 0..subscribers.len() {
             let effect_id = self.signal_subscribers[signal_id][i];
             vec_insert(&mut self.pending_effects, effect_id);
         }
+    }
 
-        // When inside a batch(), defer effect execution until the batch completes
+    /// Schedule the subscribers of signals written during the last effect run.
+    fn schedule_deferred_writes(&mut self) {
+        let written = DEFERRED_WRITES.with(|w| std::mem::take(&mut *w.borrow_mut()));
+        for signal_id in written {
+            self.schedule_subscribers(signal_id);
+        }
+    }
+
+    /// Run pending effects, unless inside a `batch()` which flushes when it completes.
+    pub(crate) fn flush_unless_batching(&mut self) {
         let batching = BATCH_DEPTH.with(|d| d.get() > 0);
         if !batching {
             self.flush_effects();
@@ -247,14 +297,41 @@ impl Runtime {
         // Use swap + drain to preserve Vec capacity across frames.
         // mem::take would replace with a 0-capacity Vec, forcing re-allocation next frame.
         let mut to_run = Vec::new();
+        let mut rounds = 0;
+        self.schedule_deferred_writes();
         while !self.pending_effects.is_empty() {
+            if rounds == MAX_EFFECT_ROUNDS {
+                self.break_cycle();
+                return;
+            }
+            rounds += 1;
             std::mem::swap(&mut to_run, &mut self.pending_effects);
             for effect_id in to_run.drain(..) {
                 self.run_effect(effect_id);
+                self.schedule_deferred_writes();
             }
         }
     }
 
+    /// Drop the effects still pending after [`MAX_EFFECT_ROUNDS`] rounds.
+    ///
+    /// They re-run on the next write to one of their dependencies.
+    fn break_cycle(&mut self) {
+        let mut sites: Vec<String> = self
+            .pending_effects
+            .drain(..)
+            .map(|id| self.effect_locations[id].to_string())
+            .collect();
+        sites.sort();
+        sites.dedup();
+        log::error!(
+            "Reactive cycle: effect created at {} was still re-triggering after {} rounds \
+             in one update, stopping it. An effect or memo probably writes a signal it reads.",
+            sites.join(", "),
+            MAX_EFFECT_ROUNDS
+        );
+    }
+
     pub fn dispose_effect(&mut self, effect_id: EffectId) {
         // Clear dependencies
         let deps = std::mem::take(&mut self.effect_dependencies[effect_id]);
@@ -291,6 +368,17 @@ where
     });
 }
 
+/// Schedule the effects that depend on a written signal and run them.
+///
+/// Inside an effect the Runtime RefCell is already borrowed, so the write is
+/// recorded and its subscribers run once the current effect returns.
+pub(crate) fn notify_write(signal_id: SignalId) {
+    RUNTIME.with(|rt| match rt.try_borrow_mut() {
+        Ok(mut runtime) => runtime.notify_write(signal_id),
+        Err(_) => DEFERRED_WRITES.with(|w| vec_insert(&mut w.borrow_mut(), signal_id)),
+    });
+}
+
 /// Reset all runtime state (effects, tracking, batch depth, write queue).
 ///
 /// Called during `App::drop()` to ensure the next `App` run starts fresh.
@@ -299,6 +387,7 @@ where
 pub(crate) fn reset_runtime() {
     RUNTIME.with(|rt| *rt.borrow_mut() = Runtime::new());
     EFFECT_TRACKING.with(|et| et.borrow_mut().clear());
+    DEFERRED_WRITES.with(|w| w.borrow_mut().clear());
     BATCH_DEPTH.with(|bd| bd.set(0));
     // Increment epoch BEFORE clearing — writes queued between now and the next
     // flush_bg_writes() will carry the old epoch and be discarded.
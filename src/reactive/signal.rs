@@ -3,7 +3,8 @@ use std::marker::PhantomData;
 use super::invalidation::{notify_signal_change, record_signal_read};
 use super::owner::register_signal;
 use super::runtime::{
-    SignalId, current_write_epoch, queue_bg_write, record_effect_read, try_with_runtime,
+    SignalId, current_write_epoch, notify_write, queue_bg_write, record_effect_read,
+    try_with_runtime,
 };
 use super::storage::{
     allocate_signal_slot, compare_and_set_signal_value, compare_and_update_signal_value,
@@ -87,7 +88,7 @@ fn tracked_with<T: Clone + 'static, R>(
 fn write_and_notify<T: Clone + PartialEq + 'static>(id: SignalId, value: T) {
     if compare_and_set_signal_value(id, value) {
         notify_signal_change(id);
-        try_with_runtime(|rt| rt.notify_write(id));
+        notify_write(id);
     }
 }
 
@@ -95,7 +96,7 @@ fn write_and_notify<T: Clone + PartialEq + 'static>(id: SignalId, value: T) {
 fn update_and_notify<T: Clone + PartialEq + 'static>(id: SignalId, f: impl FnOnce(&mut T)) {
     if compare_and_update_signal_value(id, f) {
         notify_signal_change(id);
-        try_with_runtime(|rt| rt.notify_write(id));
+        notify_write(id);
     }
 }
 
@@ -204,6 +205,11 @@ impl<T: Clone + 'static> RwSignal<T> {
         with_signal_value(self.id, f)
     }
 
+    /// The id of the underlying signal.
+    pub(crate) fn id(&self) -> SignalId {
+        self.id
+    }
+
     /// Convert to a read-only [`Signal<T>`].
     pub fn read_only(self) -> Signal<T> {
         Signal {
@@ -0,0 +1,181 @@
+//! Main-thread timers for clocks, polling and delayed actions.
+//!
+//! Timers run on the app's event loop, so their callbacks can update signals
+//! directly without spawning threads. They belong to the current owner and
+//! stop when it is cleaned up (e.g. when a dynamic child is removed or the
+//! app exits).
+//!
+//! # Example
+//!
+//! ```ignore
+//! let tick = create_interval(Duration::from_secs(1));
+//! text(move || {
+//!     tick.get();
+//!     chrono::Local::now().format("%H:%M:%S").to_string()
+//! })
+//!
+//! create_timeout(Duration::from_secs(3), move || toast_visible.set(false));
+//! ```
+
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::time::Duration;
+
+use smithay_client_toolkit::reexports::calloop::timer::{TimeoutAction, Timer};
+use smithay_client_toolkit::reexports::calloop::{LoopHandle, RegistrationToken};
+
+use crate::platform::WaylandState;
+
+use super::{Signal, create_signal, on_cleanup};
+
+/// Shortest period of [`create_interval`]; shorter periods are raised to it
+/// so a zero period can't spin the event loop.
+pub const MIN_INTERVAL_PERIOD: Duration = Duration::from_millis(1);
+
+/// A timer waiting for the event loop, or already scheduled on it.
+enum TimerEntry {
+    Pending {
+        delay: Duration,
+        repeat: bool,
+        callback: Rc<dyn Fn()>,
+    },
+    Scheduled(RegistrationToken),
+}
+
+thread_local! {
+    static LOOP_HANDLE: RefCell<Option<LoopHandle<'static, WaylandState>>> =
+        const { RefCell::new(None) };
+    static TIMERS: RefCell<HashMap<u64, TimerEntry>> = RefCell::new(HashMap::new());
+    static NEXT_TIMER_ID: Cell<u64> = const { Cell::new(0) };
+}
+
+/// Create a counter that increases by one every `period`.
+///
+/// Read it inside a widget property or effect to re-run that code on every
+/// tick. The counter starts at 0 and the first tick happens after one period.
+///
+/// Periods below [`MIN_INTERVAL_PERIOD`] (including zero) are clamped to it.
+pub fn create_interval(period: Duration) -> Signal<u64> {
+    let count = create_signal(0u64);
+    let period = period.max(MIN_INTERVAL_PERIOD);
+    start_timer(period, true, Rc::new(move || count.update(|c| *c += 1)));
+    count.read_only()
+}
+
+/// Call `f` once after `delay`.
+///
+/// The call is skipped if the current owner is cleaned up first.
+pub fn create_timeout(delay: Duration, f: impl Fn() + 'static) {
+    start_timer(delay, false, Rc::new(f));
+}
+
+fn start_timer(delay: Duration, repeat: bool, callback: Rc<dyn Fn()>) {
+    let id = NEXT_TIMER_ID.with(|next| next.replace(next.get() + 1));
+    let entry = LOOP_HANDLE.with(|handle| match handle.borrow().as_ref() {
+        Some(handle) => schedule(handle, id, delay, repeat, callback),
+        None => Some(TimerEntry::Pending {
+            delay,
+            repeat,
+            callback,
+        }),
+    });
+    if let Some(entry) = entry {
+        TIMERS.with(|timers| timers.borrow_mut().insert(id, entry));
+    }
+    on_cleanup(move || cancel_timer(id));
+}
+
+/// Insert a timer into the event loop.
+fn schedule(
+    handle: &LoopHandle<'static, WaylandState>,
+    id: u64,
+    delay: Duration,
+    repeat: bool,
+    callback: Rc<dyn Fn()>,
+) -> Option<TimerEntry> {
+    let token = handle.insert_source(Timer::from_duration(delay), move |_, _, _| {
+        callback();
+        if repeat {
+            TimeoutAction::ToDuration(delay)
+        } else {
+            TIMERS.with(|timers| timers.borrow_mut().remove(&id));
+            TimeoutAction::Drop
+        }
+    });
+    match token {
+        Ok(token) => Some(TimerEntry::Scheduled(token)),
+        Err(e) => {
+            log::warn!("Failed to schedule timer: {}", e);
+            None
+        }
+    }
+}
+
+fn cancel_timer(id: u64) {
+    let entry = TIMERS.with(|timers| timers.borrow_mut().remove(&id));
+    if let Some(TimerEntry::Scheduled(token)) = entry {
+        LOOP_HANDLE.with(|handle| {
+            if let Some(ref handle) = *handle.borrow() {
+                handle.remove(token);
+            }
+        });
+    }
+}
+
+/// Start running timers on the app's event loop, including the ones created
+/// before the loop existed (e.g. in surface factories).
+pub(crate) fn init_timers(handle: LoopHandle<'static, WaylandState>) {
+    let pending: Vec<(u64, TimerEntry)> = TIMERS.with(|timers| {
+        timers
+            .borrow_mut()
+            .extract_if(|_, entry| matches!(entry, TimerEntry::Pending { .. }))
+            .collect()
+    });
+    for (id, entry) in pending {
+        if let TimerEntry::Pending {
+            delay,
+            repeat,
+            callback,
+        } = entry
+            && let Some(entry) = schedule(&handle, id, delay, repeat, callback)
+        {
+            TIMERS.with(|timers| timers.borrow_mut().insert(id, entry));
+        }
+    }
+    LOOP_HANDLE.with(|h| *h.borrow_mut() = Some(handle));
+}
+
+/// Remove all timers and forget the event loop.
+///
+/// Called during `App::drop()` so a restarted app starts without timers.
+pub(crate) fn reset_timers() {
+    let handle = LOOP_HANDLE.with(|h| h.borrow_mut().take());
+    let timers: Vec<TimerEntry> =
+        TIMERS.with(|timers| timers.borrow_mut().drain().map(|(_, e)| e).collect());
+    if let Some(handle) = handle {
+        for entry in timers {
+            if let TimerEntry::Scheduled(token) = entry {
+                handle.remove(token);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_zero_interval_is_clamped() {
+        create_interval(Duration::ZERO);
+        let id = NEXT_TIMER_ID.with(|next| next.get()) - 1;
+        TIMERS.with(|timers| match timers.borrow().get(&id) {
+            Some(TimerEntry::Pending { delay, repeat, .. }) => {
+                assert!(*repeat);
+                assert_eq!(*delay, MIN_INTERVAL_PERIOD);
+            }
+            _ => panic!("expected a pending interval"),
+        });
+    }
+}
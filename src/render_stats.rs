@@ -54,6 +54,8 @@ pub struct StatsSnapshot {
     pub paint_children_culled: u64,
     pub flatten_nodes_cached: u64,
     pub flatten_nodes_flattened: u64,
+    pub text_measure_cached: u64,
+    pub text_measure_shaped: u64,
     pub damage_none: u64,
     pub damage_partial: u64,
     pub damage_full: u64,
@@ -157,6 +159,9 @@ mod inner {
         // Flatten cache
         flatten_nodes_cached: u64,
         flatten_nodes_flattened: u64,
+        // Text measurement cache
+        text_measure_cached: u64,
+        text_measure_shaped: u64,
         // Damage regions
         damage_none: u64,
         damage_partial: u64,
@@ -188,6 +193,8 @@ mod inner {
                 paint_children_culled: 0,
                 flatten_nodes_cached: 0,
                 flatten_nodes_flattened: 0,
+                text_measure_cached: 0,
+                text_measure_shaped: 0,
                 damage_none: 0,
                 damage_partial: 0,
                 damage_full: 0,
@@ -214,6 +221,8 @@ mod inner {
             self.paint_children_culled = 0;
             self.flatten_nodes_cached = 0;
             self.flatten_nodes_flattened = 0;
+            self.text_measure_cached = 0;
+            self.text_measure_shaped = 0;
             self.damage_none = 0;
             self.damage_partial = 0;
             self.damage_full = 0;
@@ -309,6 +318,22 @@ mod inner {
         });
     }
 
+    /// Record a text measurement served from the measurement cache.
+    #[inline]
+    pub fn record_text_measure_cached() {
+        STATS.with(|s| {
+            s.borrow_mut().text_measure_cached += 1;
+        });
+    }
+
+    /// Record a text measurement that had to shape the text.
+    #[inline]
+    pub fn record_text_measure_shaped() {
+        STATS.with(|s| {
+            s.borrow_mut().text_measure_shaped += 1;
+        });
+    }
+
     /// Record a render pipeline phase duration.
     #[inline]
     pub fn record_phase_duration(phase: Phase, duration: Duration) {
@@ -350,6 +375,8 @@ mod inner {
                 paint_children_culled: stats.paint_children_culled,
                 flatten_nodes_cached: stats.flatten_nodes_cached,
                 flatten_nodes_flattened: stats.flatten_nodes_flattened,
+                text_measure_cached: stats.text_measure_cached,
+                text_measure_shaped: stats.text_measure_shaped,
                 damage_none: stats.damage_none,
                 damage_partial: stats.damage_partial,
                 damage_full: stats.damage_full,
@@ -378,7 +405,9 @@ mod inner {
 
             match damage {
                 DamageRegion::None => stats.damage_none += 1,
-                DamageRegion::Partial(_) => stats.damage_partial += 1,
+                DamageRegion::Partial(_) | DamageRegion::Multiple(_) => {
+                    stats.damage_partial += 1
+                }
                 DamageRegion::Full => stats.damage_full += 1,
             }
 
@@ -440,6 +469,16 @@ mod inner {
                     stats.flatten_nodes_flattened,
                     flatten_cache_rate
                 );
+                let text_total = stats.text_measure_cached + stats.text_measure_shaped;
+                if text_total > 0 {
+                    eprintln!(
+                        "  text: measured={} cached={} shaped={} cache_rate={:.1}%",
+                        text_total,
+                        stats.text_measure_cached,
+                        stats.text_measure_shaped,
+                        (stats.text_measure_cached as f64 / text_total as f64) * 100.0
+                    );
+                }
                 eprintln!(
                     "  damage: none={} partial={} full={}",
                     stats.damage_none, stats.damage_partial, stats.damage_full
@@ -523,6 +562,14 @@ pub fn record_flatten_cached() {}
 #[inline(always)]
 pub fn record_flatten_full() {}
 
+#[cfg(not(feature = "render-stats"))]
+#[inline(always)]
+pub fn record_text_measure_cached() {}
+
+#[cfg(not(feature = "render-stats"))]
+#[inline(always)]
+pub fn record_text_measure_shaped() {}
+
 #[cfg(not(feature = "render-stats"))]
 #[inline(always)]
 pub fn record_phase_duration(_phase: Phase, _duration: std::time::Duration) {}
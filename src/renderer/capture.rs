@@ -0,0 +1,172 @@
+//! Reading rendered frames back from the GPU.
+//!
+//! A capture copies the surface texture into a mappable buffer right after the
+//! frame is drawn, then writes it as a PNG once the copy completes. The frame
+//! is still presented as usual.
+
+use std::path::Path;
+
+use wgpu::{Device, Texture, TextureFormat};
+
+/// Rows of a texture-to-buffer copy must be aligned to this many bytes.
+const ROW_ALIGNMENT: u32 = wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+
+/// A pending copy of a frame into a readable buffer.
+pub(super) struct FrameCopy {
+    buffer: wgpu::Buffer,
+    width: u32,
+    height: u32,
+    padded_bytes_per_row: u32,
+    bgra: bool,
+}
+
+impl FrameCopy {
+    /// Record a copy of `texture` into a new buffer.
+    ///
+    /// Returns `None` (after logging why) if the texture can't be read back.
+    pub(super) fn record(
+        device: &Device,
+        encoder: &mut wgpu::CommandEncoder,
+        texture: &Texture,
+    ) -> Option<Self> {
+        if !texture.usage().contains(wgpu::TextureUsages::COPY_SRC) {
+            log::error!("Capture failed: the surface doesn't support reading back frames");
+            return None;
+        }
+        let bgra = match texture.format() {
+            TextureFormat::Bgra8Unorm | TextureFormat::Bgra8UnormSrgb => true,
+            TextureFormat::Rgba8Unorm | TextureFormat::Rgba8UnormSrgb => false,
+            format => {
+                log::error!("Capture failed: unsupported surface format {format:?}");
+                return None;
+            }
+        };
+
+        let (width, height) = (texture.width(), texture.height());
+        let padded_bytes_per_row = (width * 4).div_ceil(ROW_ALIGNMENT) * ROW_ALIGNMENT;
+        let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Capture Buffer"),
+            size: padded_bytes_per_row as u64 * height as u64,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+        encoder.copy_texture_to_buffer(
+            wgpu::TexelCopyTextureInfo {
+                texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+                aspect: wgpu::TextureAspect::All,
+            },
+            wgpu::TexelCopyBufferInfo {
+                buffer: &buffer,
+                layout: wgpu::TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(padded_bytes_per_row),
+                    rows_per_image: Some(height),
+                },
+            },
+            wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+        );
+
+        Some(Self {
+            buffer,
+            width,
+            height,
+            padded_bytes_per_row,
+            bgra,
+        })
+    }
+
+    /// Wait for the submitted copy and write it to `path` as a PNG.
+    pub(super) fn save_png(self, device: &Device, path: &Path) {
+        let slice = self.buffer.slice(..);
+        slice.map_async(wgpu::MapMode::Read, |_| {});
+        if let Err(e) = device.poll(wgpu::PollType::wait_indefinitely()) {
+            log::error!("Capture failed: {e}");
+            return;
+        }
+
+        let rgba = {
+            let data = slice.get_mapped_range();
+            to_straight_rgba(
+                &data,
+                self.width,
+                self.height,
+                self.padded_bytes_per_row,
+                self.bgra,
+            )
+        };
+        self.buffer.unmap();
+
+        match image::save_buffer(
+            path,
+            &rgba,
+            self.width,
+            self.height,
+            image::ExtendedColorType::Rgba8,
+        ) {
+            Ok(()) => log::info!("Captured frame to {}", path.display()),
+            Err(e) => log::error!("Capture failed: could not write {}: {e}", path.display()),
+        }
+    }
+}
+
+/// Convert padded, premultiplied rows into tightly packed straight-alpha RGBA.
+///
+/// Surfaces are composited with premultiplied alpha, while PNG stores
+/// straight alpha, so translucent pixels are divided back by their alpha.
+fn to_straight_rgba(
+    data: &[u8],
+    width: u32,
+    height: u32,
+    padded_bytes_per_row: u32,
+    bgra: bool,
+) -> Vec<u8> {
+    let row_bytes = (width * 4) as usize;
+    let mut out = Vec::with_capacity(row_bytes * height as usize);
+    for row in data
+        .chunks(padded_bytes_per_row as usize)
+        .take(height as usize)
+    {
+        for pixel in row[..row_bytes].chunks_exact(4) {
+            let (r, g, b, a) = if bgra {
+                (pixel[2], pixel[1], pixel[0], pixel[3])
+            } else {
+                (pixel[0], pixel[1], pixel[2], pixel[3])
+            };
+            let unpremultiply = |c: u8| match a {
+                0 => 0,
+                255 => c,
+                _ => ((c as u32 * 255 + a as u32 / 2) / a as u32).min(255) as u8,
+            };
+            out.extend_from_slice(&[unpremultiply(r), unpremultiply(g), unpremultiply(b), a]);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rows_are_unpadded_and_unpremultiplied() {
+        // Two BGRA pixels per row, padded to 12 bytes
+        let data = [
+            0, 0, 255, 255, /* opaque red */ 0, 64, 0, 128, /* half green */ 9, 9, 9, 9,
+            0, 0, 0, 0, /* transparent */ 255, 255, 255, 255, /* white */ 9, 9, 9, 9,
+        ];
+        let rgba = to_straight_rgba(&data, 2, 2, 12, true);
+        assert_eq!(
+            rgba,
+            [
+                255, 0, 0, 255, 0, 128, 0, 128, //
+                0, 0, 0, 0, 255, 255, 255, 255,
+            ]
+        );
+    }
+}
@@ -1,7 +1,7 @@
 //! Draw command definitions for the render tree.
 
 use super::types::{Gradient, Shadow};
-use crate::widgets::font::{FontFamily, FontWeight};
+use crate::widgets::font::{FontFamily, FontStyle, FontWeight, WrapMode};
 use crate::widgets::image::{ContentFit, ImageSource};
 use crate::widgets::{Color, Rect};
 
@@ -12,12 +12,27 @@ pub struct Border {
     pub width: f32,
     /// Border color
     pub color: Color,
+    /// Optional gradient along the border (overrides solid color)
+    pub gradient: Option<Gradient>,
 }
 
 impl Border {
     /// Create a new border.
     pub fn new(width: f32, color: Color) -> Self {
-        Self { width, color }
+        Self {
+            width,
+            color,
+            gradient: None,
+        }
+    }
+
+    /// Create a border filled with a gradient.
+    pub fn gradient(width: f32, gradient: Gradient) -> Self {
+        Self {
+            width,
+            color: gradient.start_color,
+            gradient: Some(gradient),
+        }
     }
 }
 
@@ -41,7 +56,7 @@ pub enum DrawCommand {
         border: Option<Border>,
         /// Optional shadow
         shadow: Option<Shadow>,
-        /// Optional gradient (overrides solid color)
+        /// Optional gradient (overrides solid color; ignored if the border has one)
         gradient: Option<Gradient>,
     },
 
@@ -69,6 +84,14 @@ pub enum DrawCommand {
         font_family: FontFamily,
         /// The font weight
         font_weight: FontWeight,
+        /// The font style (upright or slanted)
+        font_style: FontStyle,
+        /// Tab stop width in spaces
+        tab_width: u16,
+        /// How lines break at the rect width
+        wrap: WrapMode,
+        /// Gradient filling the glyphs across `rect` instead of `color`
+        gradient: Option<Gradient>,
     },
 
     /// Draw an image.
@@ -79,6 +102,8 @@ pub enum DrawCommand {
         rect: Rect,
         /// How the image content fits within the rect
         content_fit: ContentFit,
+        /// Color replacing the image colors, keeping their alpha
+        tint: Option<Color>,
     },
 }
 
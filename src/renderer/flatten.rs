@@ -25,7 +25,7 @@ pub enum RenderLayer {
 ///
 /// When a node has a clip region and its parent has rotation, the clip
 /// becomes an axis-aligned bounding box in world space.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct WorldClip {
     /// Axis-aligned clip rect in world coordinates (logical pixels).
     pub rect: Rect,
@@ -55,6 +55,8 @@ pub struct FlattenedCommand {
     /// Whether the clip is in local coordinates (use frag_pos in shader instead of world_pos).
     /// This is true for overlay clips on transformed containers.
     pub clip_is_local: bool,
+    /// Whether shapes are snapped to the physical pixel grid.
+    pub pixel_snap: bool,
 }
 
 /// Flatten a render tree into a list of commands ready for GPU submission.
@@ -183,7 +185,8 @@ pub fn flatten_tree_into(
 ///
 /// For nodes with `repainted == false` and a valid `cached_flatten`,
 /// reuse the cached commands with a translation offset instead of
-/// re-flattening the entire subtree.
+/// re-flattening the entire subtree. Only the ancestors of changed widgets
+/// are re-flattened; `render-stats` reports cached vs. flattened node counts.
 fn flatten_node(
     node: &mut RenderNode,
     parent_world_transform: Transform,
@@ -202,16 +205,13 @@ fn flatten_node(
     };
     let world_transform = parent_world_transform.then(&local_centered);
 
-    // Try cached flatten for clean subtrees (translation-only optimization)
+    // Try cached flatten for clean subtrees (translation-only optimization).
+    // A node's own clip moves with it, but a parent clip stays put: a subtree
+    // under one is reused only in place and under the same clip.
     if !node.repainted
-        && parent_clip.is_none()
-        && node.clip.is_none()
         && let Some(ref cached) = node.cached_flatten
-        && cached.world_transform.is_translation_only()
-        && world_transform.is_translation_only()
+        && let Some((dx, dy)) = cache_offset(cached, &world_transform, parent_clip)
     {
-        let dx = world_transform.tx() - cached.world_transform.tx();
-        let dy = world_transform.ty() - cached.world_transform.ty();
         for cmd in &cached.commands {
             let mut adjusted = cmd.clone();
             adjusted
@@ -236,8 +236,7 @@ fn flatten_node(
     // Track if we should cache this node's flatten output.
     // Snapshot captures lengths across all layer buckets so we can collect
     // everything added by this subtree (including children) for caching.
-    let should_cache =
-        node.clip.is_none() && parent_clip.is_none() && world_transform.is_translation_only();
+    let should_cache = world_transform.is_translation_only();
     let snap = if should_cache {
         Some(out.snapshot())
     } else {
@@ -280,6 +279,7 @@ fn flatten_node(
             layer,
             clip: effective_clip.clone(),
             clip_is_local: false,
+            pixel_snap: node.pixel_snap,
         });
     }
 
@@ -320,6 +320,7 @@ fn flatten_node(
             layer: RenderLayer::Overlay,
             clip: overlay_clip.clone(),
             clip_is_local: overlay_clip_is_local,
+            pixel_snap: false,
         });
     }
 
@@ -330,6 +331,7 @@ fn flatten_node(
         node.cached_flatten = Some(Box::new(CachedFlatten {
             commands: out.commands_since(&snap),
             world_transform,
+            parent_clip: parent_clip.cloned(),
         }));
     } else {
         node.cached_flatten = None;
@@ -337,6 +339,28 @@ fn flatten_node(
     crate::render_stats::record_flatten_full();
 }
 
+/// Translation to apply to a node's cached flatten output, or `None` if the
+/// cache can't be reused at the node's new position.
+fn cache_offset(
+    cached: &CachedFlatten,
+    world_transform: &Transform,
+    parent_clip: Option<&WorldClip>,
+) -> Option<(f32, f32)> {
+    if !cached.world_transform.is_translation_only()
+        || !world_transform.is_translation_only()
+        || cached.parent_clip.as_ref() != parent_clip
+    {
+        return None;
+    }
+    let dx = world_transform.tx() - cached.world_transform.tx();
+    let dy = world_transform.ty() - cached.world_transform.ty();
+    // Under a fixed parent clip, a moved subtree would intersect it differently
+    if parent_clip.is_some() && (dx != 0.0 || dy != 0.0) {
+        return None;
+    }
+    Some((dx, dy))
+}
+
 /// Compute axis-aligned bounding box from an array of points.
 fn aabb_from_points(points: &[(f32, f32)]) -> Rect {
     let (min_x, max_x, min_y, max_y) = points.iter().fold(
@@ -410,3 +434,67 @@ fn intersect_clips(a: &WorldClip, b: &WorldClip) -> WorldClip {
         curvature,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::Color;
+
+    fn circle(color: Color) -> Rc<DrawCommand> {
+        Rc::new(DrawCommand::Circle {
+            center: (5.0, 5.0),
+            radius: 5.0,
+            color,
+        })
+    }
+
+    /// A clipped root with one child drawing a circle.
+    fn clipped_tree() -> RenderTree {
+        let mut root = RenderNode::with_bounds(1, Rect::new(0.0, 0.0, 100.0, 100.0));
+        root.clip = Some(ClipRegion {
+            rect: Rect::new(0.0, 0.0, 100.0, 100.0),
+            corner_radius: 0.0,
+            curvature: 1.0,
+        });
+        let mut child = RenderNode::with_bounds(2, Rect::new(0.0, 0.0, 10.0, 10.0));
+        child.commands.push(circle(Color::WHITE));
+        root.children.push(child);
+        RenderTree { roots: vec![root] }
+    }
+
+    fn circle_color(commands: &[FlattenedCommand]) -> Color {
+        match *commands[0].command {
+            DrawCommand::Circle { color, .. } => color,
+            _ => panic!("expected a circle"),
+        }
+    }
+
+    #[test]
+    fn test_clean_subtree_under_clip_reuses_cache() {
+        let mut tree = clipped_tree();
+        flatten_tree(&mut tree);
+
+        // Only the root changed: the child's stale commands prove the cache was used
+        let child = &mut tree.roots[0].children[0];
+        child.repainted = false;
+        child.commands[0] = circle(Color::BLACK);
+        let (commands, _) = flatten_tree(&mut tree);
+        assert_eq!(circle_color(&commands), Color::WHITE);
+        assert!(commands[0].clip.is_some());
+    }
+
+    #[test]
+    fn test_changed_parent_clip_reflattens_subtree() {
+        let mut tree = clipped_tree();
+        flatten_tree(&mut tree);
+
+        let root = &mut tree.roots[0];
+        root.clip.as_mut().unwrap().rect.width = 50.0;
+        let child = &mut root.children[0];
+        child.repainted = false;
+        child.commands[0] = circle(Color::BLACK);
+        let (commands, _) = flatten_tree(&mut tree);
+        assert_eq!(circle_color(&commands), Color::BLACK);
+        assert_eq!(commands[0].clip.as_ref().unwrap().rect.width, 50.0);
+    }
+}
@@ -0,0 +1,98 @@
+//! User fallback families shared by the measuring and drawing font systems.
+//!
+//! Text measurement and the glyphon renderer link different cosmic-text
+//! versions, each with its own `Fallback` trait. [`FallbackFamilies`]
+//! implements both, so measured and drawn glyphs fall back to the same faces.
+
+use std::cell::RefCell;
+use std::collections::HashSet;
+
+use cosmic_text::fontdb::Database;
+use unicode_script::Script;
+
+thread_local! {
+    /// Family names handed out to font systems
+    static FAMILY_NAMES: RefCell<HashSet<&'static str>> = RefCell::new(HashSet::new());
+}
+
+/// A `'static` copy of a family name, allocated once per distinct name.
+///
+/// cosmic-text's `Fallback` trait returns `&'static str`, so the names have
+/// to outlive every font system. Interning them keeps a single copy of each
+/// name instead of a new one per font system.
+fn intern(name: &str) -> &'static str {
+    FAMILY_NAMES.with_borrow_mut(|names| match names.get(name) {
+        Some(&interned) => interned,
+        None => {
+            let interned: &'static str = Box::leak(name.into());
+            names.insert(interned);
+            interned
+        }
+    })
+}
+
+/// Platform font fallbacks with the user's fallback families tried first.
+pub(super) struct FallbackFamilies {
+    common: Vec<&'static str>,
+}
+
+impl FallbackFamilies {
+    /// The families set with [`set_font_fallbacks`](crate::set_font_fallbacks),
+    /// resolved against `db`, followed by the platform's `common` fallbacks.
+    ///
+    /// Returns `None` when no fallback families are set, so the font system
+    /// can keep the platform fallback.
+    pub(super) fn resolve(db: &Database, common: &[&'static str]) -> Option<Self> {
+        let mut names: Vec<&'static str> = crate::font_fallbacks()
+            .iter()
+            .map(|family| intern(db.family_name(&family.to_cosmic())))
+            .collect();
+        if names.is_empty() {
+            return None;
+        }
+        names.extend_from_slice(common);
+        Some(Self { common: names })
+    }
+}
+
+impl cosmic_text::Fallback for FallbackFamilies {
+    fn common_fallback(&self) -> &[&'static str] {
+        &self.common
+    }
+
+    fn forbidden_fallback(&self) -> &[&'static str] {
+        cosmic_text::PlatformFallback.forbidden_fallback()
+    }
+
+    fn script_fallback(&self, script: Script, locale: &str) -> &[&'static str] {
+        cosmic_text::PlatformFallback.script_fallback(script, locale)
+    }
+}
+
+impl glyphon::cosmic_text::Fallback for FallbackFamilies {
+    fn common_fallback(&self) -> &[&'static str] {
+        &self.common
+    }
+
+    fn forbidden_fallback(&self) -> &[&'static str] {
+        glyphon::cosmic_text::PlatformFallback.forbidden_fallback()
+    }
+
+    fn script_fallback(&self, script: Script, locale: &str) -> &[&'static str] {
+        glyphon::cosmic_text::PlatformFallback.script_fallback(script, locale)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_family_names_are_interned_once() {
+        let first = intern("Noto Color Emoji");
+        let again = intern(&String::from("Noto Color Emoji"));
+        assert_eq!(first, "Noto Color Emoji");
+        assert!(std::ptr::eq(first, again));
+        assert!(!std::ptr::eq(first, intern("Noto Sans CJK JP")));
+    }
+}
@@ -150,8 +150,10 @@ pub struct ShapeInstance {
     pub gradient_end: [f32; 4],
     /// Gradient type: 0=none, 1=horizontal, 2=vertical, 3=diagonal, 4=diagonal_reverse
     pub gradient_type: u32,
+    /// What the gradient paints: 0=fill, 1=border
+    pub gradient_target: u32,
     /// Padding for 16-byte alignment
-    pub _pad4: [u32; 3],
+    pub _pad4: [u32; 2],
 }
 
 impl Default for ShapeInstance {
@@ -179,7 +181,8 @@ impl Default for ShapeInstance {
             gradient_start: [0.0, 0.0, 0.0, 0.0],
             gradient_end: [0.0, 0.0, 0.0, 0.0],
             gradient_type: 0, // No gradient
-            _pad4: [0, 0, 0],
+            gradient_target: 0,
+            _pad4: [0, 0],
         }
     }
 }
@@ -244,12 +247,28 @@ impl ShapeInstance {
             border.color.b,
             border.color.a,
         ];
+        if let Some(gradient) = &border.gradient {
+            self = self.with_gradient(gradient);
+            self.gradient_target = 1;
+        }
         self
     }
 
     /// Set shadow properties.
+    ///
+    /// The shadow is drawn in the shape's local space, so it rotates and scales
+    /// with the shape. Call after [`with_transform`](Self::with_transform): the
+    /// offset is mapped back through the transform so it still points the same
+    /// way on screen (e.g. straight down) however the shape is rotated.
     pub fn with_shadow(mut self, shadow: &super::types::Shadow, scale: f32) -> Self {
-        self.shadow_offset = [shadow.offset.0 * scale, shadow.offset.1 * scale];
+        let [a, b, _, c, d, _] = self.transform;
+        let (x, y) = (shadow.offset.0 * scale, shadow.offset.1 * scale);
+        let det = a * d - b * c;
+        self.shadow_offset = if det.abs() > f32::EPSILON {
+            [(d * x - b * y) / det, (a * y - c * x) / det]
+        } else {
+            [x, y]
+        };
         self.shadow_blur = shadow.blur * scale;
         self.shadow_spread = shadow.spread * scale;
         self.shadow_color = [
@@ -261,6 +280,34 @@ impl ShapeInstance {
         self
     }
 
+    /// Round the edges and border width to whole physical pixels.
+    ///
+    /// Only applies when the transform is a pure translation; rotated or
+    /// scaled shapes can't line up with the pixel grid. Non-empty sides and
+    /// borders stay at least one pixel, so hairlines don't disappear.
+    pub fn snapped_to_pixels(mut self) -> Self {
+        let [a, b, tx, c, d, ty] = self.transform;
+        if a != 1.0 || b != 0.0 || c != 0.0 || d != 1.0 {
+            return self;
+        }
+        let snap = |start: f32, size: f32, offset: f32| {
+            let min = (start + offset).round();
+            let mut max = (start + size + offset).round();
+            if size > 0.0 && max <= min {
+                max = min + 1.0;
+            }
+            (min - offset, max - min)
+        };
+        let [x, y, width, height] = self.rect;
+        let (x, width) = snap(x, width, tx);
+        let (y, height) = snap(y, height, ty);
+        self.rect = [x, y, width, height];
+        if self.border_width > 0.0 {
+            self.border_width = self.border_width.round().max(1.0);
+        }
+        self
+    }
+
     /// Set gradient properties.
     pub fn with_gradient(mut self, gradient: &super::types::Gradient) -> Self {
         self.gradient_start = [
@@ -368,7 +415,7 @@ impl ShapeInstance {
                     shader_location: 13,
                     format: VertexFormat::Float32x4,
                 },
-                // gradient_type, _pad4[0], _pad4[1], _pad4[2]
+                // gradient_type, gradient_target, _pad4[0], _pad4[1]
                 VertexAttribute {
                     offset: 208,
                     shader_location: 14,
@@ -381,7 +428,9 @@ impl ShapeInstance {
 
 #[cfg(test)]
 mod tests {
+    use super::super::types::Shadow;
     use super::*;
+    use crate::widgets::Color;
 
     #[test]
     fn test_shape_instance_size() {
@@ -405,4 +454,42 @@ mod tests {
         assert_eq!(instance.transform, [1.0, 0.0, 0.0, 0.0, 1.0, 0.0]);
         assert_eq!(instance.shape_curvature, 1.0);
     }
+
+    #[test]
+    fn test_snapped_to_pixels() {
+        // A 0.5px tall divider at a fractional position, e.g. 1 logical px at 1.5x
+        let mut instance = ShapeInstance::from_rect([0.3, 10.6, 99.6, 0.5], [1.0; 4], 0.0, 1.0);
+        instance.transform[5] = 0.2;
+        instance.border_width = 1.5;
+        let snapped = instance.snapped_to_pixels();
+        let [x, y, width, height] = snapped.rect;
+        assert_eq!((x, width, height), (0.0, 100.0, 1.0));
+        // Top edge lands on a whole pixel once translated
+        assert!((y + 0.2 - 11.0).abs() < 1e-5);
+        assert_eq!(snapped.border_width, 2.0);
+
+        // Rotated shapes are left alone
+        let mut rotated = instance;
+        rotated.transform = [0.0, -1.0, 0.0, 1.0, 0.0, 0.0];
+        assert_eq!(rotated.snapped_to_pixels().rect, instance.rect);
+    }
+
+    #[test]
+    fn test_shadow_offset_follows_rotation() {
+        let shadow = Shadow::new((0.0, 4.0), 8.0, 0.0, Color::BLACK);
+
+        // Untransformed: offset is used as-is (scaled to physical pixels)
+        let instance = ShapeInstance::default().with_shadow(&shadow, 2.0);
+        assert_eq!(instance.shadow_offset, [0.0, 8.0]);
+
+        // Rotated 90°: the local offset maps back to straight down on screen
+        let rotation = crate::transform::Transform::rotate_degrees(90.0);
+        let instance = ShapeInstance::default()
+            .with_transform(&rotation, 1.0)
+            .with_shadow(&shadow, 1.0);
+        let [a, b, _, c, d, _] = instance.transform;
+        let [x, y] = instance.shadow_offset;
+        assert!((a * x + b * y).abs() < 1e-4);
+        assert!((c * x + d * y - 4.0).abs() < 1e-4);
+    }
 }
@@ -1,10 +1,11 @@
 use std::sync::Arc;
 
 use raw_window_handle::{HasDisplayHandle, HasWindowHandle};
-use wgpu::{Device, Instance, Queue, Surface, SurfaceConfiguration};
+use wgpu::{Adapter, Device, Instance, Queue, Surface, SurfaceConfiguration};
 
 pub struct GpuContext {
     pub instance: Instance,
+    pub adapter: Adapter,
     pub device: Arc<Device>,
     pub queue: Arc<Queue>,
 }
@@ -41,11 +42,18 @@ impl GpuContext {
 
         Self {
             instance,
+            adapter,
             device: Arc::new(device),
             queue: Arc::new(queue),
         }
     }
 
+    /// The highest MSAA sample count up to `requested` that `format` supports.
+    pub fn supported_sample_count(&self, format: wgpu::TextureFormat, requested: u32) -> u32 {
+        let flags = self.adapter.get_texture_format_features(format).flags;
+        highest_sample_count(flags, requested)
+    }
+
     pub fn create_surface<W>(&self, window: W, width: u32, height: u32) -> SurfaceState
     where
         W: HasWindowHandle + HasDisplayHandle,
@@ -88,8 +96,12 @@ impl GpuContext {
 
         log::info!("Using surface format: {:?}", format);
 
+        // Frames can be read back for captures when the surface allows it
+        let usage =
+            wgpu::TextureUsages::RENDER_ATTACHMENT | (caps.usages & wgpu::TextureUsages::COPY_SRC);
+
         let config = SurfaceConfiguration {
-            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            usage,
             format,
             width,
             height,
@@ -120,6 +132,14 @@ impl GpuContext {
     }
 }
 
+/// The highest MSAA sample count up to `requested` allowed by `flags`, or 1.
+fn highest_sample_count(flags: wgpu::TextureFormatFeatureFlags, requested: u32) -> u32 {
+    [8, 4, 2]
+        .into_iter()
+        .find(|&count| count <= requested && flags.sample_count_supported(count))
+        .unwrap_or(1)
+}
+
 pub struct SurfaceState {
     pub surface: Surface<'static>,
     pub config: SurfaceConfiguration,
@@ -144,3 +164,22 @@ impl SurfaceState {
         self.config.height
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wgpu::TextureFormatFeatureFlags as Flags;
+
+    #[test]
+    fn test_msaa_falls_back_to_supported_sample_counts() {
+        let flags = Flags::MULTISAMPLE_X2 | Flags::MULTISAMPLE_X4;
+        assert_eq!(highest_sample_count(flags, 4), 4);
+        // Unsupported counts fall back to the next lower one
+        assert_eq!(highest_sample_count(flags, 8), 4);
+        assert_eq!(highest_sample_count(flags, 3), 2);
+        assert_eq!(highest_sample_count(Flags::MULTISAMPLE_X2, 4), 2);
+        // 1 keeps multisampling off
+        assert_eq!(highest_sample_count(flags, 1), 1);
+        assert_eq!(highest_sample_count(Flags::empty(), 4), 1);
+    }
+}
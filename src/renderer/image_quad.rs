@@ -17,17 +17,19 @@ use super::commands::DrawCommand;
 use super::constants::{IMAGE_HASH_SAMPLE_SIZE, SVG_QUALITY_MULTIPLIER};
 use super::flatten::FlattenedCommand;
 use super::gpu::NO_CLIP_RECT;
-use super::textured_vertex::{TexturedVertex, to_ndc};
+use super::textured_vertex::{
+    NO_TINT, QuadVertexBuffer, TexturedVertex, draw_quad_run, quad_indices, quad_runs, to_ndc,
+};
 use crate::widgets::Rect;
 use crate::widgets::image::{ContentFit, ImageSource};
 
 /// A prepared image quad ready for rendering.
+///
+/// Its vertices live in the renderer's frame vertex buffer, at the quad's
+/// position in the prepared list.
 pub struct PreparedImageQuad {
-    #[allow(dead_code)] // Kept alive for GPU usage
     texture: Arc<CachedTexture>,
     bind_group: BindGroup,
-    /// Vertex buffer with pre-computed vertices in NDC
-    vertex_buffer: WgpuBuffer,
 }
 
 /// Cached texture data.
@@ -73,8 +75,10 @@ pub struct ImageQuadRenderer {
     bind_group_layout: BindGroupLayout,
     sampler: Sampler,
 
-    // Shared index buffer (vertices are per-quad)
+    // Shared index buffer for up to MAX_QUADS_PER_DRAW quads
     index_buffer: WgpuBuffer,
+    // Vertices of all quads prepared this frame
+    vertex_buffer: QuadVertexBuffer,
 
     // Texture cache
     texture_cache: HashMap<CacheKey, Arc<CachedTexture>>,
@@ -87,7 +91,7 @@ pub struct ImageQuadRenderer {
 }
 
 impl ImageQuadRenderer {
-    pub fn new(device: &Device, format: TextureFormat) -> Self {
+    pub fn new(device: &Device, format: TextureFormat, sample_count: u32) -> Self {
         // Load shader from dedicated file
         let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
             label: Some("ImageQuad Shader"),
@@ -165,7 +169,10 @@ impl ImageQuadRenderer {
                 conservative: false,
             },
             depth_stencil: None,
-            multisample: wgpu::MultisampleState::default(),
+            multisample: wgpu::MultisampleState {
+                count: sample_count,
+                ..Default::default()
+            },
             multiview_mask: None,
             cache: None,
         });
@@ -183,10 +190,9 @@ impl ImageQuadRenderer {
         });
 
         // Create index buffer
-        let indices: [u16; 6] = [0, 1, 2, 1, 3, 2];
         let index_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
             label: Some("ImageQuad Index Buffer"),
-            contents: bytemuck::cast_slice(&indices),
+            contents: bytemuck::cast_slice(&quad_indices()),
             usage: wgpu::BufferUsages::INDEX,
         });
 
@@ -195,6 +201,7 @@ impl ImageQuadRenderer {
             bind_group_layout,
             sampler,
             index_buffer,
+            vertex_buffer: QuadVertexBuffer::new("ImageQuad Vertex Buffer"),
             texture_cache: HashMap::new(),
             current_frame: 0,
             max_cache_size: 64,
@@ -499,10 +506,18 @@ impl ImageQuadRenderer {
         commands: &[FlattenedCommand],
         scale_factor: f32,
     ) -> Vec<PreparedImageQuad> {
-        commands
+        let mut vertices = Vec::with_capacity(commands.len() * 4);
+        let quads = commands
             .iter()
-            .filter_map(|cmd| self.prepare_single(device, queue, cmd, scale_factor))
-            .collect()
+            .filter_map(|cmd| {
+                let (quad, quad_vertices) =
+                    self.prepare_single(device, queue, cmd, scale_factor)?;
+                vertices.extend_from_slice(&quad_vertices);
+                Some(quad)
+            })
+            .collect();
+        self.vertex_buffer.write(device, queue, &vertices);
+        quads
     }
 
     /// Prepare a single image command.
@@ -512,13 +527,14 @@ impl ImageQuadRenderer {
         queue: &Queue,
         cmd: &FlattenedCommand,
         scale_factor: f32,
-    ) -> Option<PreparedImageQuad> {
-        let (source, rect, content_fit) = match &*cmd.command {
+    ) -> Option<(PreparedImageQuad, [TexturedVertex; 4])> {
+        let (source, rect, content_fit, tint) = match &*cmd.command {
             DrawCommand::Image {
                 source,
                 rect,
                 content_fit,
-            } => (source, rect, content_fit),
+                tint,
+            } => (source, rect, content_fit, tint),
             _ => return None,
         };
 
@@ -570,7 +586,7 @@ impl ImageQuadRenderer {
         };
 
         // Transform corners from local to screen coordinates
-        let vertices = self.compute_vertices(
+        let mut vertices = self.compute_vertices(
             &display_rect,
             &cmd.world_transform,
             uv,
@@ -578,19 +594,19 @@ impl ImageQuadRenderer {
             clip_rect,
             clip_params,
         );
+        if let Some(tint) = tint {
+            for vertex in &mut vertices {
+                vertex.tint = [tint.r, tint.g, tint.b, tint.a];
+            }
+        }
 
-        // Create vertex buffer
-        let vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-            label: Some("ImageQuad Vertex Buffer"),
-            contents: bytemuck::cast_slice(&vertices),
-            usage: wgpu::BufferUsages::VERTEX,
-        });
-
-        Some(PreparedImageQuad {
-            texture: cached,
-            bind_group,
-            vertex_buffer,
-        })
+        Some((
+            PreparedImageQuad {
+                texture: cached,
+                bind_group,
+            },
+            vertices,
+        ))
     }
 
     /// Calculate the display rect and UV coordinates based on content fit.
@@ -712,6 +728,7 @@ impl ImageQuadRenderer {
                 screen_pos: [screen_corners[0].0, screen_corners[0].1],
                 clip_rect,
                 clip_params,
+                tint: NO_TINT,
             },
             TexturedVertex {
                 position: to_ndc(
@@ -724,6 +741,7 @@ impl ImageQuadRenderer {
                 screen_pos: [screen_corners[1].0, screen_corners[1].1],
                 clip_rect,
                 clip_params,
+                tint: NO_TINT,
             },
             TexturedVertex {
                 position: to_ndc(
@@ -736,6 +754,7 @@ impl ImageQuadRenderer {
                 screen_pos: [screen_corners[2].0, screen_corners[2].1],
                 clip_rect,
                 clip_params,
+                tint: NO_TINT,
             },
             TexturedVertex {
                 position: to_ndc(
@@ -748,23 +767,29 @@ impl ImageQuadRenderer {
                 screen_pos: [screen_corners[3].0, screen_corners[3].1],
                 clip_rect,
                 clip_params,
+                tint: NO_TINT,
             },
         ]
     }
 
     /// Render the prepared image quads.
     pub fn render<'a>(&'a self, render_pass: &mut RenderPass<'a>, quads: &'a [PreparedImageQuad]) {
+        let Some(vertex_buffer) = self.vertex_buffer.buffer() else {
+            return;
+        };
         if quads.is_empty() {
             return;
         }
 
         render_pass.set_pipeline(&self.pipeline);
         render_pass.set_index_buffer(self.index_buffer.slice(..), wgpu::IndexFormat::Uint16);
+        render_pass.set_vertex_buffer(0, vertex_buffer.slice(..));
 
-        for quad in quads {
-            render_pass.set_bind_group(0, &quad.bind_group, &[]);
-            render_pass.set_vertex_buffer(0, quad.vertex_buffer.slice(..));
-            render_pass.draw_indexed(0..6, 0, 0..1);
+        // Consecutive quads showing the same image share one draw call
+        for run in quad_runs(quads.len(), |a, b| {
+            Arc::ptr_eq(&quads[a].texture, &quads[b].texture)
+        }) {
+            draw_quad_run(render_pass, &quads[run.start].bind_group, run);
         }
     }
 }
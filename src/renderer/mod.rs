@@ -10,14 +10,17 @@
 //! - World transforms are computed automatically by walking the tree during flatten
 //! - Overlays (like ripples) naturally render after children
 
+mod capture;
 mod commands;
 mod constants;
 mod flatten;
+mod font_fallback;
 mod gpu;
 mod gpu_context;
 mod image_quad;
 mod paint_context;
 mod render;
+mod svg;
 mod text;
 mod text_measurer;
 mod text_quad;
@@ -30,9 +33,14 @@ pub use flatten::{FlattenedCommand, LayerBoundaries, flatten_tree, flatten_tree_
 pub use gpu_context::{GpuContext, SurfaceState};
 pub use paint_context::PaintContext;
 pub use render::Renderer;
+pub use svg::render_widget_to_svg;
 pub use text_measurer::{
-    char_index_from_x, char_index_from_x_styled, measure_text, measure_text_styled,
-    measure_text_to_char, measure_text_to_char_styled,
+    DEFAULT_TAB_WIDTH, TextHit, TextMeasureParams, available_fonts, caret_offsets, caret_position,
+    char_index_from_x, char_index_from_x_styled, char_rect, clamp_text_lines, font_exists,
+    hit_test_text, measure_text, measure_text_styled, measure_text_to_char,
+    measure_text_to_char_styled, measure_text_with_params, measure_text_wrapped, text_baseline,
+    text_range_rects,
 };
+pub(crate) use text_measurer::{measurer_font_generation, sync_measurer_fonts};
 pub use tree::{NodeId, RenderNode, RenderTree};
 pub use types::{Gradient, GradientDir, ImageEntry, Shadow, TextEntry};
@@ -3,11 +3,11 @@
 use std::rc::Rc;
 
 use super::commands::{Border, DrawCommand};
+use super::text_measurer::TextMeasureParams;
 use super::tree::{ClipRegion, NodeId, RenderNode};
 use super::types::{Gradient, Shadow};
 use crate::transform::Transform;
 use crate::transform_origin::TransformOrigin;
-use crate::widgets::font::{FontFamily, FontWeight};
 use crate::widgets::image::{ContentFit, ImageSource};
 use crate::widgets::{Color, Rect};
 
@@ -138,6 +138,15 @@ impl<'a> PaintContext<'a> {
         self.node.transform_origin = origin;
     }
 
+    /// Snap this node's shapes to the physical pixel grid.
+    ///
+    /// Edges and border widths are rounded to whole device pixels (at least
+    /// one) when the node is only translated, so thin lines stay crisp at
+    /// fractional scales. Children are not affected.
+    pub fn set_pixel_snap(&mut self, snap: bool) {
+        self.node.pixel_snap = snap;
+    }
+
     // -------------------------------------------------------------------------
     // Clipping
     // -------------------------------------------------------------------------
@@ -277,6 +286,26 @@ impl<'a> PaintContext<'a> {
         }));
     }
 
+    /// Draw a border frame (no fill) colored by a gradient.
+    pub fn draw_gradient_border_frame(
+        &mut self,
+        rect: Rect,
+        gradient: Gradient,
+        radius: f32,
+        border_width: f32,
+        curvature: f32,
+    ) {
+        self.node.commands.push(Rc::new(DrawCommand::RoundedRect {
+            rect,
+            color: Color::TRANSPARENT,
+            radius,
+            curvature,
+            border: Some(Border::gradient(border_width, gradient)),
+            shadow: None,
+            gradient: None,
+        }));
+    }
+
     /// Draw a rounded rectangle with shadow.
     pub fn draw_rounded_rect_with_shadow(
         &mut self,
@@ -335,25 +364,41 @@ impl<'a> PaintContext<'a> {
 
     /// Draw text with default font settings.
     pub fn draw_text(&mut self, text: &str, rect: Rect, color: Color, font_size: f32) {
-        self.draw_text_styled(
-            text,
-            rect,
-            color,
-            font_size,
-            FontFamily::default(),
-            FontWeight::NORMAL,
-        );
+        self.draw_text_styled(text, rect, color, &TextMeasureParams::new(font_size));
     }
 
-    /// Draw text with custom font family and weight.
+    /// Draw text with the font, tab stop and wrap settings of `params`, the
+    /// same settings used to measure it. Wrapping lines break at the rect
+    /// width; `params.max_width` is not used.
     pub fn draw_text_styled(
         &mut self,
         text: &str,
         rect: Rect,
         color: Color,
-        font_size: f32,
-        font_family: FontFamily,
-        font_weight: FontWeight,
+        params: &TextMeasureParams,
+    ) {
+        self.push_text(text, rect, color, params, None);
+    }
+
+    /// Draw text filled with a gradient spanning `rect` instead of a solid
+    /// color. Takes the same settings as [`draw_text_styled`](Self::draw_text_styled).
+    pub fn draw_gradient_text(
+        &mut self,
+        text: &str,
+        rect: Rect,
+        gradient: Gradient,
+        params: &TextMeasureParams,
+    ) {
+        self.push_text(text, rect, Color::WHITE, params, Some(gradient));
+    }
+
+    fn push_text(
+        &mut self,
+        text: &str,
+        rect: Rect,
+        color: Color,
+        params: &TextMeasureParams,
+        gradient: Option<Gradient>,
     ) {
         // Skip empty text
         if text.is_empty() {
@@ -363,9 +408,13 @@ impl<'a> PaintContext<'a> {
             text: text.to_string(),
             rect,
             color,
-            font_size,
-            font_family,
-            font_weight,
+            font_size: params.font_size,
+            font_family: params.font_family.clone(),
+            font_weight: params.font_weight,
+            font_style: params.font_style,
+            tab_width: params.tab_width,
+            wrap: params.wrap,
+            gradient,
         }));
     }
 
@@ -379,6 +428,25 @@ impl<'a> PaintContext<'a> {
             source,
             rect,
             content_fit,
+            tint: None,
+        }));
+    }
+
+    /// Draw an image with every pixel in `tint`, keeping the image's alpha.
+    ///
+    /// Used for monochrome icons that follow a theme color.
+    pub fn draw_tinted_image(
+        &mut self,
+        source: ImageSource,
+        rect: Rect,
+        content_fit: ContentFit,
+        tint: Color,
+    ) {
+        self.node.commands.push(Rc::new(DrawCommand::Image {
+            source,
+            rect,
+            content_fit,
+            tint: Some(tint),
         }));
     }
 
@@ -421,6 +489,21 @@ impl<'a> PaintContext<'a> {
             }));
     }
 
+    /// Draw a gradient rectangle as overlay (rendered after children).
+    pub fn draw_overlay_gradient_rect(&mut self, rect: Rect, gradient: Gradient) {
+        self.node
+            .overlay_commands
+            .push(Rc::new(DrawCommand::RoundedRect {
+                rect,
+                color: gradient.start_color,
+                radius: 0.0,
+                curvature: 1.0,
+                border: None,
+                shadow: None,
+                gradient: Some(gradient),
+            }));
+    }
+
     /// Draw a rounded rectangle as overlay (rendered after children).
     pub fn draw_overlay_rounded_rect(&mut self, rect: Rect, color: Color, radius: f32) {
         self.node
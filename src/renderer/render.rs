@@ -3,6 +3,7 @@
 //! This module uses a single draw call per layer to render all shapes,
 //! significantly reducing CPU-GPU communication overhead.
 
+use std::path::PathBuf;
 use std::sync::Arc;
 
 use wgpu::util::DeviceExt;
@@ -10,6 +11,7 @@ use wgpu::{
     BindGroup, BindGroupLayout, Buffer, BufferUsages, Device, Queue, RenderPipeline, ShaderModule,
 };
 
+use super::capture::FrameCopy;
 use super::commands::DrawCommand;
 use super::flatten::FlattenedCommand;
 use super::gpu::{QUAD_INDICES, QUAD_VERTICES, QuadVertex, ShaderUniforms, ShapeInstance};
@@ -61,11 +63,27 @@ pub struct Renderer {
     screen_width: f32,
     screen_height: f32,
     scale_factor: f32,
+
+    // Where to save the next rendered frame, if a capture was requested
+    pending_capture: Option<PathBuf>,
+
+    // MSAA samples per pixel (1 = off) and the multisampled target resolved
+    // into the surface, recreated when the surface size changes
+    sample_count: u32,
+    msaa_target: Option<wgpu::Texture>,
 }
 
 impl Renderer {
     /// Create a new renderer with instanced rendering.
-    pub fn new(device: Arc<Device>, queue: Arc<Queue>, format: wgpu::TextureFormat) -> Self {
+    ///
+    /// With a `sample_count` above 1, frames are drawn into a multisampled
+    /// target and resolved into the surface (MSAA).
+    pub fn new(
+        device: Arc<Device>,
+        queue: Arc<Queue>,
+        format: wgpu::TextureFormat,
+        sample_count: u32,
+    ) -> Self {
         // Load shader
         let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
             label: Some("Renderer Shader"),
@@ -88,7 +106,8 @@ impl Renderer {
         });
 
         // Create pipeline
-        let pipeline = Self::create_pipeline(&device, &shader, &bind_group_layout, format);
+        let pipeline =
+            Self::create_pipeline(&device, &shader, &bind_group_layout, format, sample_count);
 
         // Create vertex buffer (unit quad)
         let vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
@@ -132,13 +151,13 @@ impl Renderer {
         });
 
         // Initialize text renderer
-        let text_state = TextRenderState::new(&device, &queue, format);
+        let text_state = TextRenderState::new(&device, &queue, format, sample_count);
 
         // Initialize transformed text renderer
-        let text_quad_renderer = TextQuadRenderer::new(&device, &queue, format);
+        let text_quad_renderer = TextQuadRenderer::new(&device, &queue, format, sample_count);
 
         // Initialize image renderer
-        let image_quad_renderer = ImageQuadRenderer::new(&device, format);
+        let image_quad_renderer = ImageQuadRenderer::new(&device, format, sample_count);
 
         Self {
             device,
@@ -160,6 +179,9 @@ impl Renderer {
             screen_width: 800.0,
             screen_height: 600.0,
             scale_factor: 1.0,
+            pending_capture: None,
+            sample_count,
+            msaa_target: None,
         }
     }
 
@@ -169,6 +191,7 @@ impl Renderer {
         shader: &ShaderModule,
         bind_group_layout: &BindGroupLayout,
         format: wgpu::TextureFormat,
+        sample_count: u32,
     ) -> RenderPipeline {
         let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
             label: Some("Renderer Pipeline Layout"),
@@ -216,7 +239,10 @@ impl Renderer {
                 conservative: false,
             },
             depth_stencil: None,
-            multisample: wgpu::MultisampleState::default(),
+            multisample: wgpu::MultisampleState {
+                count: sample_count,
+                ..Default::default()
+            },
             multiview_mask: None,
             cache: None,
         })
@@ -233,6 +259,40 @@ impl Renderer {
         self.scale_factor = scale;
     }
 
+    /// Save the next frame rendered to a surface as a PNG at `path`.
+    ///
+    /// The frame is copied out after drawing and still presented normally.
+    pub fn capture_next_frame(&mut self, path: PathBuf) {
+        self.pending_capture = Some(path);
+    }
+
+    /// View of the multisampled target matching `texture`, or `None` without MSAA.
+    fn msaa_view(&mut self, texture: &wgpu::Texture) -> Option<wgpu::TextureView> {
+        if self.sample_count <= 1 {
+            return None;
+        }
+        let size = texture.size();
+        let stale = self
+            .msaa_target
+            .as_ref()
+            .is_none_or(|target| target.size() != size || target.format() != texture.format());
+        if stale {
+            self.msaa_target = Some(self.device.create_texture(&wgpu::TextureDescriptor {
+                label: Some("Renderer MSAA Target"),
+                size,
+                mip_level_count: 1,
+                sample_count: self.sample_count,
+                dimension: wgpu::TextureDimension::D2,
+                format: texture.format(),
+                usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+                view_formats: &[],
+            }));
+        }
+        self.msaa_target
+            .as_ref()
+            .map(|target| target.create_view(&wgpu::TextureViewDescriptor::default()))
+    }
+
     /// Ensure instance buffer has enough capacity.
     fn ensure_instance_capacity(&mut self, count: usize) {
         if count > self.instance_buffer_capacity {
@@ -366,6 +426,13 @@ impl Renderer {
         let total_instances = self.shape_instance_buf.len() + self.overlay_instance_buf.len();
         self.ensure_instance_capacity(total_instances);
 
+        // With MSAA, draw into the multisampled target and resolve into the surface
+        let msaa_view = self.msaa_view(&output.texture);
+        let (target_view, resolve_target) = match &msaa_view {
+            Some(msaa_view) => (msaa_view, Some(&view)),
+            None => (&view, None),
+        };
+
         let mut encoder = self
             .device
             .create_command_encoder(&wgpu::CommandEncoderDescriptor {
@@ -376,8 +443,8 @@ impl Renderer {
             let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
                 label: Some("Renderer Render Pass"),
                 color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                    view: &view,
-                    resolve_target: None,
+                    view: target_view,
+                    resolve_target,
                     ops: wgpu::Operations {
                         load: wgpu::LoadOp::Clear(wgpu::Color {
                             r: clear_color.r as f64,
@@ -385,7 +452,12 @@ impl Renderer {
                             b: clear_color.b as f64,
                             a: clear_color.a as f64,
                         }),
-                        store: wgpu::StoreOp::Store,
+                        // The multisampled target is only needed until it is resolved
+                        store: if resolve_target.is_some() {
+                            wgpu::StoreOp::Discard
+                        } else {
+                            wgpu::StoreOp::Store
+                        },
                     },
                     depth_slice: None,
                 })],
@@ -463,8 +535,16 @@ impl Renderer {
             }
         }
 
+        let capture = self.pending_capture.take().and_then(|path| {
+            FrameCopy::record(&self.device, &mut encoder, &output.texture).map(|copy| (copy, path))
+        });
+
         self.queue.submit(std::iter::once(encoder.finish()));
         output.present();
+
+        if let Some((copy, path)) = capture {
+            copy.save_png(&self.device, &path);
+        }
     }
 }
 
@@ -499,12 +579,17 @@ fn command_to_instance(cmd: &FlattenedCommand, scale: f32) -> Option<ShapeInstan
             if let Some(s) = shadow {
                 instance = instance.with_shadow(s, scale);
             }
-            if let Some(g) = gradient {
+            if let Some(g) = gradient
+                && instance.gradient_target == 0
+            {
                 instance = instance.with_gradient(g);
             }
             if let Some(ref clip) = cmd.clip {
                 instance = instance.with_clip(clip, scale, cmd.clip_is_local);
             }
+            if cmd.pixel_snap {
+                instance = instance.snapped_to_pixels();
+            }
 
             Some(instance)
         }
@@ -549,6 +634,10 @@ fn command_to_text_entry(cmd: &FlattenedCommand) -> Option<TextEntry> {
             font_size,
             font_family,
             font_weight,
+            font_style,
+            tab_width,
+            wrap,
+            gradient,
         } => {
             // Convert WorldClip to Rect for text clipping
             let clip_rect = cmd.clip.as_ref().map(|clip| clip.rect);
@@ -560,6 +649,10 @@ fn command_to_text_entry(cmd: &FlattenedCommand) -> Option<TextEntry> {
                 font_size: *font_size,
                 font_family: font_family.clone(),
                 font_weight: *font_weight,
+                font_style: *font_style,
+                tab_width: *tab_width,
+                wrap: *wrap,
+                gradient: *gradient,
                 clip_rect,
                 transform: cmd.world_transform,
                 transform_origin: cmd.world_transform_origin,
@@ -49,7 +49,8 @@ struct InstanceInput {
     @location(12) gradient_start: vec4<f32>,
     // gradient_end RGBA
     @location(13) gradient_end: vec4<f32>,
-    // gradient_type (0=none, 1=horizontal, 2=vertical, 3=diagonal, 4=diagonal_reverse), _pad, _pad, _pad
+    // gradient_type (0=none, 1=horizontal, 2=vertical, 3=diagonal, 4=diagonal_reverse),
+    // gradient_target (0=fill, 1=border), _pad, _pad
     @location(14) gradient_params: vec4<u32>,
 }
 
@@ -83,6 +84,8 @@ struct VertexOutput {
     @location(12) gradient_end: vec4<f32>,
     // Gradient type (0=none, 1=horizontal, 2=vertical, 3=diagonal, 4=diagonal_reverse)
     @location(13) @interpolate(flat) gradient_type: u32,
+    // Gradient target (0=fill, 1=border)
+    @location(14) @interpolate(flat) gradient_target: u32,
 }
 
 // === Helper Functions ===
@@ -184,6 +187,7 @@ fn vs_main(vertex: VertexInput, instance: InstanceInput) -> VertexOutput {
     out.gradient_start = instance.gradient_start;
     out.gradient_end = instance.gradient_end;
     out.gradient_type = instance.gradient_params.x;
+    out.gradient_target = instance.gradient_params.y;
 
     return out;
 }
@@ -283,12 +287,16 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
     // Compute local UV coordinates (0..1 within the shape rect)
     let local_uv = (pos - in.shape_rect.xy) / in.shape_rect.zw;
 
-    // Determine fill color (gradient or solid)
-    var fill_color: vec4<f32>;
+    // Determine fill and border colors (gradient or solid)
+    var fill_color = in.fill_color;
+    var border_color = in.border_color;
     if (in.gradient_type > 0u) {
-        fill_color = compute_gradient_color(local_uv, in.gradient_start, in.gradient_end, in.gradient_type);
-    } else {
-        fill_color = in.fill_color;
+        let gradient_color = compute_gradient_color(local_uv, in.gradient_start, in.gradient_end, in.gradient_type);
+        if (in.gradient_target == 1u) {
+            border_color = gradient_color;
+        } else {
+            fill_color = gradient_color;
+        }
     }
 
     // === Shadow ===
@@ -327,11 +335,11 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
 
         if (fill_color.a <= 0.0) {
             // Border only (transparent fill)
-            shape_result = vec4<f32>(in.border_color.rgb, in.border_color.a * border_alpha);
+            shape_result = vec4<f32>(border_color.rgb, border_color.a * border_alpha);
         } else {
             // Fill + border composite
             let fill_contribution = vec4<f32>(fill_color.rgb, fill_color.a * fill_alpha);
-            let border_contribution = vec4<f32>(in.border_color.rgb, in.border_color.a * border_alpha);
+            let border_contribution = vec4<f32>(border_color.rgb, border_color.a * border_alpha);
 
             let result_rgb = border_contribution.rgb * border_contribution.a +
                              fill_contribution.rgb * fill_contribution.a * (1.0 - border_contribution.a);
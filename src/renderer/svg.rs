@@ -0,0 +1,526 @@
+//! SVG export of widget trees.
+//!
+//! [`render_widget_to_svg`] lays out and paints a widget like a surface does,
+//! flattens the render tree and writes each command as an SVG element instead
+//! of submitting it to the GPU. Useful for documentation and design specs.
+//!
+//! The output follows the GPU renderer closely but not exactly: superellipse
+//! corners are drawn as circular arcs, shadows use a gaussian blur filter and
+//! text is laid out by the SVG viewer with the same font names and line breaks.
+
+use std::fmt::Write;
+
+use crate::layout::Constraints;
+use crate::transform::Transform;
+use crate::tree::Tree;
+use crate::widgets::font::{FontFamily, FontStyle, WrapMode};
+use crate::widgets::image::{ContentFit, ImageSource};
+use crate::widgets::{Color, Rect, Widget};
+
+use super::commands::DrawCommand;
+use super::flatten::{FlattenedCommand, WorldClip, flatten_tree};
+use super::paint_context::PaintContext;
+use super::text_measurer::{TextMeasureParams, measure_text_wrapped, text_baseline};
+use super::tree::{RenderNode, RenderTree};
+use super::types::{Gradient, GradientDir};
+
+/// Render a widget to an SVG document of `width` x `height` logical pixels.
+///
+/// The widget is laid out with the same constraints as a surface of that
+/// size, so fill lengths take the whole area.
+///
+/// # Example
+///
+/// ```ignore
+/// let svg = render_widget_to_svg(card("Title", "Body"), 320.0, 200.0);
+/// std::fs::write("docs/card.svg", svg)?;
+/// ```
+pub fn render_widget_to_svg(widget: impl Widget + 'static, width: f32, height: f32) -> String {
+    let mut tree = Tree::new();
+    let id = tree.register(Box::new(widget));
+    tree.with_widget_mut(id, |widget, id, tree| widget.register_children(tree, id));
+    tree.with_widget_mut(id, |widget, id, tree| {
+        widget.layout(tree, id, Constraints::new(0.0, 0.0, width, height))
+    });
+
+    let mut root = RenderNode::with_bounds(id.as_u64(), Rect::new(0.0, 0.0, width, height));
+    tree.with_widget_mut(id, |widget, id, tree| {
+        let mut ctx = PaintContext::new(&mut root);
+        widget.paint(tree, id, &mut ctx);
+    });
+    let mut render_tree = RenderTree::new();
+    render_tree.add_root(root);
+    let (commands, _) = flatten_tree(&mut render_tree);
+    commands_to_svg(&commands, width, height)
+}
+
+/// Write flattened commands as an SVG document, in render order.
+fn commands_to_svg(commands: &[FlattenedCommand], width: f32, height: f32) -> String {
+    let mut svg = SvgWriter::default();
+    for cmd in commands {
+        svg.command(cmd);
+    }
+    let mut out = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\">\n",
+        num(width),
+        num(height),
+        num(width),
+        num(height)
+    );
+    if !svg.defs.is_empty() {
+        out.push_str("<defs>\n");
+        out.push_str(&svg.defs);
+        out.push_str("</defs>\n");
+    }
+    out.push_str(&svg.body);
+    out.push_str("</svg>\n");
+    out
+}
+
+/// Accumulates definitions (gradients, clips, filters) and drawn elements.
+#[derive(Default)]
+struct SvgWriter {
+    defs: String,
+    body: String,
+    next_id: usize,
+}
+
+impl SvgWriter {
+    fn id(&mut self, prefix: &str) -> String {
+        self.next_id += 1;
+        format!("{prefix}{}", self.next_id)
+    }
+
+    fn command(&mut self, cmd: &FlattenedCommand) {
+        // Clips are in world space (or local space for overlay clips), so
+        // the clip group wraps the transform group or sits inside it
+        let clip = cmd.clip.as_ref().map(|clip| self.clip_path(clip));
+        let transform = (!cmd.world_transform.is_identity())
+            .then(|| format!(" transform=\"{}\"", matrix(&cmd.world_transform)));
+        let (outer_clip, inner_clip) = match clip {
+            Some(clip) if cmd.clip_is_local => (None, Some(clip)),
+            clip => (clip, None),
+        };
+
+        if let Some(clip) = &outer_clip {
+            let _ = writeln!(self.body, "<g clip-path=\"url(#{clip})\">");
+        }
+        if let Some(transform) = &transform {
+            let _ = writeln!(self.body, "<g{transform}>");
+        }
+        if let Some(clip) = &inner_clip {
+            let _ = writeln!(self.body, "<g clip-path=\"url(#{clip})\">");
+        }
+
+        match &*cmd.command {
+            DrawCommand::RoundedRect {
+                rect,
+                color,
+                radius,
+                border,
+                shadow,
+                gradient,
+                ..
+            } => {
+                let radius = radius.min(rect.width / 2.0).min(rect.height / 2.0).max(0.0);
+                if let Some(shadow) = shadow
+                    && shadow.color.a > 0.0
+                {
+                    let filter = self.id("shadow");
+                    let _ = writeln!(
+                        self.defs,
+                        "<filter id=\"{filter}\" x=\"-50%\" y=\"-50%\" width=\"200%\" height=\"200%\">\
+                         <feGaussianBlur stdDeviation=\"{}\"/></filter>",
+                        num(shadow.blur / 2.0)
+                    );
+                    // The offset points the same way on screen however the shape is transformed
+                    let (dx, dy) = untransform_offset(&cmd.world_transform, shadow.offset);
+                    let spread = shadow.spread;
+                    let shadow_rect = Rect::new(
+                        rect.x + dx - spread,
+                        rect.y + dy - spread,
+                        rect.width + spread * 2.0,
+                        rect.height + spread * 2.0,
+                    );
+                    let _ = writeln!(
+                        self.body,
+                        "<rect {} rx=\"{}\" {} filter=\"url(#{filter})\"/>",
+                        rect_attrs(&shadow_rect),
+                        num(radius + spread),
+                        paint_attrs("fill", &shadow.color)
+                    );
+                }
+
+                // A border gradient replaces the border color, otherwise it fills the shape
+                let border_gradient = border.as_ref().and_then(|b| b.gradient);
+                let fill = match gradient {
+                    Some(gradient) if border_gradient.is_none() => {
+                        format!("fill=\"url(#{})\"", self.gradient(gradient))
+                    }
+                    _ => paint_attrs("fill", color),
+                };
+                let _ = writeln!(
+                    self.body,
+                    "<rect {} rx=\"{}\" {fill}/>",
+                    rect_attrs(rect),
+                    num(radius)
+                );
+
+                if let Some(border) = border
+                    && border.width > 0.0
+                {
+                    // The border is drawn inside the shape; SVG strokes are centered
+                    let half = border.width / 2.0;
+                    let inset = Rect::new(
+                        rect.x + half,
+                        rect.y + half,
+                        (rect.width - border.width).max(0.0),
+                        (rect.height - border.width).max(0.0),
+                    );
+                    let stroke = match &border.gradient {
+                        Some(gradient) => format!("stroke=\"url(#{})\"", self.gradient(gradient)),
+                        None => paint_attrs("stroke", &border.color),
+                    };
+                    let _ = writeln!(
+                        self.body,
+                        "<rect {} rx=\"{}\" fill=\"none\" {stroke} stroke-width=\"{}\"/>",
+                        rect_attrs(&inset),
+                        num((radius - half).max(0.0)),
+                        num(border.width)
+                    );
+                }
+            }
+            DrawCommand::Circle {
+                center,
+                radius,
+                color,
+            } => {
+                let _ = writeln!(
+                    self.body,
+                    "<circle cx=\"{}\" cy=\"{}\" r=\"{}\" {}/>",
+                    num(center.0),
+                    num(center.1),
+                    num(*radius),
+                    paint_attrs("fill", color)
+                );
+            }
+            DrawCommand::Text {
+                text,
+                rect,
+                color,
+                font_size,
+                font_family,
+                font_weight,
+                font_style,
+                tab_width,
+                wrap,
+                gradient,
+            } => {
+                let max_width =
+                    (*wrap != WrapMode::NoWrap && *wrap != WrapMode::Pre).then_some(rect.width);
+                let params = TextMeasureParams::new(*font_size)
+                    .max_width(max_width)
+                    .font_family(font_family.clone())
+                    .font_weight(*font_weight)
+                    .font_style(*font_style)
+                    .tab_width(*tab_width)
+                    .wrap(*wrap);
+                let baseline = text_baseline(text, &params);
+                let lines = measure_text_wrapped(text, &params);
+                let first_top = lines.first().map_or(0.0, |(_, line)| line.y);
+                let fill = match gradient {
+                    Some(gradient) => format!("fill=\"url(#{})\"", self.gradient(gradient)),
+                    None => paint_attrs("fill", color),
+                };
+                let style = match font_style {
+                    FontStyle::Normal => "",
+                    FontStyle::Italic => " font-style=\"italic\"",
+                    FontStyle::Oblique => " font-style=\"oblique\"",
+                };
+                let _ = writeln!(
+                    self.body,
+                    "<text font-family=\"{}\" font-size=\"{}\" font-weight=\"{}\"{style} {fill} xml:space=\"preserve\">",
+                    font_family_name(font_family),
+                    num(*font_size),
+                    font_weight.0
+                );
+                for (line, line_rect) in &lines {
+                    let _ = writeln!(
+                        self.body,
+                        "<tspan x=\"{}\" y=\"{}\">{}</tspan>",
+                        num(rect.x),
+                        num(rect.y + baseline + line_rect.y - first_top),
+                        escape(line)
+                    );
+                }
+                self.body.push_str("</text>\n");
+            }
+            DrawCommand::Image {
+                source,
+                rect,
+                content_fit,
+                tint,
+            } => match data_uri(source) {
+                Some(uri) => {
+                    let filter = tint.map(|tint| {
+                        let filter = self.id("tint");
+                        let _ = writeln!(
+                            self.defs,
+                            "<filter id=\"{filter}\"><feFlood flood-color=\"{}\" flood-opacity=\"{}\"/>\
+                             <feComposite in2=\"SourceAlpha\" operator=\"in\"/></filter>",
+                            hex(&tint),
+                            num(tint.a)
+                        );
+                        format!(" filter=\"url(#{filter})\"")
+                    });
+                    let aspect = match content_fit {
+                        ContentFit::Contain | ContentFit::None => "xMidYMid meet",
+                        ContentFit::Cover => "xMidYMid slice",
+                        ContentFit::Fill => "none",
+                    };
+                    let _ = writeln!(
+                        self.body,
+                        "<image {} preserveAspectRatio=\"{aspect}\" href=\"{uri}\"{}/>",
+                        rect_attrs(rect),
+                        filter.unwrap_or_default()
+                    );
+                }
+                None => log::warn!("SVG export: could not read image {source:?}"),
+            },
+        }
+
+        if inner_clip.is_some() {
+            self.body.push_str("</g>\n");
+        }
+        if transform.is_some() {
+            self.body.push_str("</g>\n");
+        }
+        if outer_clip.is_some() {
+            self.body.push_str("</g>\n");
+        }
+    }
+
+    /// Define a clip path and return its id.
+    fn clip_path(&mut self, clip: &WorldClip) -> String {
+        let id = self.id("clip");
+        let radius = clip
+            .corner_radius
+            .min(clip.rect.width / 2.0)
+            .min(clip.rect.height / 2.0)
+            .max(0.0);
+        let _ = writeln!(
+            self.defs,
+            "<clipPath id=\"{id}\"><rect {} rx=\"{}\"/></clipPath>",
+            rect_attrs(&clip.rect),
+            num(radius)
+        );
+        id
+    }
+
+    /// Define a linear gradient across the element's bounds and return its id.
+    fn gradient(&mut self, gradient: &Gradient) -> String {
+        let id = self.id("gradient");
+        let (x1, y1, x2, y2) = match gradient.direction {
+            GradientDir::Horizontal => (0, 0, 1, 0),
+            GradientDir::Vertical => (0, 0, 0, 1),
+            GradientDir::Diagonal => (0, 0, 1, 1),
+            GradientDir::DiagonalReverse => (0, 1, 1, 0),
+        };
+        let _ = writeln!(
+            self.defs,
+            "<linearGradient id=\"{id}\" x1=\"{x1}\" y1=\"{y1}\" x2=\"{x2}\" y2=\"{y2}\">\
+             <stop offset=\"0\" stop-color=\"{}\" stop-opacity=\"{}\"/>\
+             <stop offset=\"1\" stop-color=\"{}\" stop-opacity=\"{}\"/></linearGradient>",
+            hex(&gradient.start_color),
+            num(gradient.start_color.a),
+            hex(&gradient.end_color),
+            num(gradient.end_color.a)
+        );
+        id
+    }
+}
+
+/// Format a number without trailing zeros.
+fn num(value: f32) -> String {
+    let rounded = (value * 100.0).round() / 100.0;
+    if rounded == 0.0 {
+        // Avoid "-0"
+        return "0".to_string();
+    }
+    format!("{rounded}")
+}
+
+fn rect_attrs(rect: &Rect) -> String {
+    format!(
+        "x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\"",
+        num(rect.x),
+        num(rect.y),
+        num(rect.width),
+        num(rect.height)
+    )
+}
+
+/// `#rrggbb` for a color, ignoring alpha.
+fn hex(color: &Color) -> String {
+    let channel = |c: f32| (c.clamp(0.0, 1.0) * 255.0).round() as u8;
+    format!(
+        "#{:02x}{:02x}{:02x}",
+        channel(color.r),
+        channel(color.g),
+        channel(color.b)
+    )
+}
+
+/// Fill or stroke attributes for a solid color.
+fn paint_attrs(attr: &str, color: &Color) -> String {
+    if color.a <= 0.0 {
+        return format!("{attr}=\"none\"");
+    }
+    if color.a >= 1.0 {
+        return format!("{attr}=\"{}\"", hex(color));
+    }
+    format!(
+        "{attr}=\"{}\" {attr}-opacity=\"{}\"",
+        hex(color),
+        num(color.a)
+    )
+}
+
+/// SVG `matrix(...)` for the 2D part of a transform.
+fn matrix(transform: &Transform) -> String {
+    let d = &transform.data;
+    format!(
+        "matrix({} {} {} {} {} {})",
+        d[0],
+        d[4],
+        d[1],
+        d[5],
+        num(d[3]),
+        num(d[7])
+    )
+}
+
+/// Map a screen-space offset back through a transform's linear part.
+fn untransform_offset(transform: &Transform, (x, y): (f32, f32)) -> (f32, f32) {
+    let d = &transform.data;
+    let (a, b, c, e) = (d[0], d[1], d[4], d[5]);
+    let det = a * e - b * c;
+    if det.abs() > f32::EPSILON {
+        ((e * x - b * y) / det, (a * y - c * x) / det)
+    } else {
+        (x, y)
+    }
+}
+
+fn font_family_name(family: &FontFamily) -> String {
+    match family {
+        FontFamily::SansSerif => "sans-serif".to_string(),
+        FontFamily::Serif => "serif".to_string(),
+        FontFamily::Monospace => "monospace".to_string(),
+        FontFamily::Cursive => "cursive".to_string(),
+        FontFamily::Fantasy => "fantasy".to_string(),
+        FontFamily::Name(name) => escape(name),
+    }
+}
+
+/// Escape text for use in SVG content and attributes.
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Embed an image source as a base64 data URI.
+fn data_uri(source: &ImageSource) -> Option<String> {
+    let (bytes, svg) = match source {
+        ImageSource::Path(path) => (std::fs::read(path).ok()?, false),
+        ImageSource::SvgPath(path) => (std::fs::read(path).ok()?, true),
+        ImageSource::Bytes(bytes) => (bytes.to_vec(), false),
+        ImageSource::SvgBytes(bytes) => (bytes.to_vec(), true),
+    };
+    let mime = if svg {
+        "image/svg+xml"
+    } else if bytes.starts_with(b"\x89PNG") {
+        "image/png"
+    } else if bytes.starts_with(&[0xff, 0xd8]) {
+        "image/jpeg"
+    } else if bytes.starts_with(b"GIF8") {
+        "image/gif"
+    } else if bytes.len() > 12 && &bytes[8..12] == b"WEBP" {
+        "image/webp"
+    } else {
+        return None;
+    };
+    Some(format!("data:{mime};base64,{}", base64(&bytes)))
+}
+
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[(n >> (18 - 6 * i) & 63) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::container;
+
+    #[test]
+    fn test_card_renders_shapes() {
+        let card = container()
+            .padding(10.0)
+            .background(Color::rgb(1.0, 0.0, 0.0))
+            .corner_radius(8.0)
+            .border(2.0, Color::rgba(0.0, 0.0, 1.0, 0.5))
+            .child(
+                container()
+                    .width(40.0)
+                    .height(20.0)
+                    .background(Color::WHITE),
+            );
+        let svg = render_widget_to_svg(card, 200.0, 100.0);
+
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"200\""));
+        assert!(svg.contains(
+            "<rect x=\"0\" y=\"0\" width=\"60\" height=\"40\" rx=\"8\" fill=\"#ff0000\"/>"
+        ));
+        // The border is inset by half its width
+        assert!(svg.contains(
+            "<rect x=\"1\" y=\"1\" width=\"58\" height=\"38\" rx=\"7\" fill=\"none\" \
+             stroke=\"#0000ff\" stroke-opacity=\"0.5\" stroke-width=\"2\"/>"
+        ));
+        assert!(svg.contains("width=\"40\" height=\"20\" rx=\"0\" fill=\"#ffffff\""));
+        assert!(svg.trim_end().ends_with("</svg>"));
+    }
+
+    #[test]
+    fn test_base64_and_escape() {
+        assert_eq!(base64(b"Man"), "TWFu");
+        assert_eq!(base64(b"Ma"), "TWE=");
+        assert_eq!(base64(b"M"), "TQ==");
+        assert_eq!(escape("a < b & \"c\""), "a &lt; b &amp; &quot;c&quot;");
+    }
+}
@@ -1,14 +1,16 @@
 use std::collections::{HashMap, HashSet};
 use std::hash::{Hash, Hasher};
 
+use glyphon::cosmic_text::{CacheKeyFlags, Fallback, PlatformFallback, Wrap};
 use glyphon::{
     Attrs, Buffer, Cache, Color as GlyphonColor, ColorMode, FontSystem, Metrics, Resolution,
     Shaping, SwashCache, TextArea, TextAtlas, TextBounds, TextRenderer, Viewport,
 };
 use wgpu::{Device, MultisampleState, Queue};
 
-use crate::widgets::font::FontWeight;
+use crate::widgets::font::{FontWeight, WrapMode};
 
+use super::font_fallback::FallbackFamilies;
 use super::types::TextEntry;
 
 /// Compute a cache key for a text buffer based on content and styling.
@@ -18,7 +20,10 @@ fn text_buffer_key(entry: &TextEntry, scale_factor: f32) -> u64 {
     entry.text.hash(&mut hasher);
     (entry.font_size * scale_factor).to_bits().hash(&mut hasher);
     entry.font_weight.hash(&mut hasher);
+    entry.font_style.hash(&mut hasher);
     entry.font_family.hash(&mut hasher);
+    entry.tab_width.hash(&mut hasher);
+    entry.wrap.hash(&mut hasher);
     ((entry.rect.width.max(200.0)) * scale_factor)
         .to_bits()
         .hash(&mut hasher);
@@ -28,6 +33,97 @@ fn text_buffer_key(entry: &TextEntry, scale_factor: f32) -> u64 {
     hasher.finish()
 }
 
+/// Create a FontSystem with the registered custom fonts and fallback families.
+pub(super) fn new_font_system() -> FontSystem {
+    let mut font_system = FontSystem::new();
+    for data in crate::get_registered_fonts() {
+        font_system
+            .db_mut()
+            .load_font_source(glyphon::fontdb::Source::Binary(data));
+    }
+    let Some(fallback) =
+        FallbackFamilies::resolve(font_system.db(), PlatformFallback.common_fallback())
+    else {
+        return font_system;
+    };
+    let (locale, db) = font_system.into_locale_and_db();
+    FontSystem::new_with_locale_and_db_and_fallback(locale, db, fallback)
+}
+
+/// Load custom fonts registered after the first `loaded` ones.
+///
+/// Returns `true` when new fonts were added, so callers can drop text
+/// shaped with a fallback face.
+pub(super) fn sync_font_system(font_system: &mut FontSystem, loaded: &mut usize) -> bool {
+    let fonts = crate::registered_fonts_since(*loaded);
+    if fonts.is_empty() {
+        return false;
+    }
+    *loaded += fonts.len();
+    for data in fonts {
+        font_system
+            .db_mut()
+            .load_font_source(glyphon::fontdb::Source::Binary(data));
+    }
+    true
+}
+
+/// Shaping attributes for a text entry.
+///
+/// Italic/oblique text whose family lacks a slanted face is shaped upright
+/// and skewed at rasterization instead.
+pub(super) fn text_attrs<'a>(font_system: &FontSystem, entry: &'a TextEntry) -> Attrs<'a> {
+    let weight = if entry.font_weight == FontWeight::default() {
+        FontWeight::NORMAL
+    } else {
+        entry.font_weight
+    };
+    let style = entry
+        .font_style
+        .resolve(font_system.db(), &entry.font_family, weight);
+    let flags = if style != entry.font_style.to_cosmic() {
+        CacheKeyFlags::FAKE_ITALIC
+    } else {
+        CacheKeyFlags::empty()
+    };
+    Attrs::new()
+        .family(entry.font_family.to_cosmic())
+        .weight(weight.to_cosmic())
+        .style(style)
+        .cache_key_flags(flags)
+}
+
+/// Line wrapping for glyphon buffers.
+///
+/// glyphon has its own copy of cosmic-text, so [`WrapMode::to_cosmic`]'s
+/// type doesn't fit its buffers.
+pub(super) fn glyphon_wrap(mode: WrapMode) -> Wrap {
+    match mode {
+        WrapMode::NoWrap | WrapMode::Pre => Wrap::None,
+        WrapMode::Word => Wrap::Word,
+        WrapMode::BreakWord => Wrap::WordOrGlyph,
+    }
+}
+
+/// Align right-to-left lines to the right edge of the text's own rect.
+///
+/// Buffers are sized wider than their rect so that rounding at the render
+/// scale never re-wraps a line, but the shaper aligns right-to-left lines to
+/// the buffer's right edge. Text with such lines is reshaped at the rect
+/// width, with half a pixel of slack.
+pub(super) fn fit_rtl_lines(
+    font_system: &mut FontSystem,
+    buffer: &mut Buffer,
+    width: f32,
+    height: f32,
+) {
+    if !buffer.layout_runs().any(|run| run.rtl) {
+        return;
+    }
+    buffer.set_size(font_system, Some(width + 0.5), Some(height));
+    buffer.shape_until_scroll(font_system, true);
+}
+
 pub struct TextRenderState {
     font_system: FontSystem,
     swash_cache: SwashCache,
@@ -43,21 +139,27 @@ pub struct TextRenderState {
     /// Keys for current frame's buffers (parallel to `self.buffers`), used to
     /// repopulate `buffer_cache` at the start of the next frame.
     frame_keys: Vec<u64>,
+    /// Number of registered custom fonts loaded into `font_system`.
+    loaded_fonts: usize,
 }
 
 impl TextRenderState {
-    pub fn new(device: &Device, queue: &Queue, format: wgpu::TextureFormat) -> Self {
-        let mut font_system = FontSystem::new();
-        for data in crate::get_registered_fonts() {
-            font_system
-                .db_mut()
-                .load_font_source(glyphon::fontdb::Source::Binary(data));
-        }
+    pub fn new(
+        device: &Device,
+        queue: &Queue,
+        format: wgpu::TextureFormat,
+        sample_count: u32,
+    ) -> Self {
+        let font_system = new_font_system();
+        let loaded_fonts = crate::registered_font_count();
         let swash_cache = SwashCache::new();
         let cache = Cache::new(device);
         let mut atlas = TextAtlas::with_color_mode(device, queue, &cache, format, ColorMode::Web);
-        let text_renderer =
-            TextRenderer::new(&mut atlas, device, MultisampleState::default(), None);
+        let multisample = MultisampleState {
+            count: sample_count,
+            ..Default::default()
+        };
+        let text_renderer = TextRenderer::new(&mut atlas, device, multisample, None);
         let viewport = Viewport::new(device, &cache);
 
         Self {
@@ -70,11 +172,13 @@ impl TextRenderState {
             viewport,
             buffer_cache: HashMap::new(),
             frame_keys: Vec::new(),
+            loaded_fonts,
         }
     }
 
     /// Prepare non-transformed text for rendering directly to screen.
-    /// Returns a list of indices of texts that have transforms and need special handling.
+    /// Returns a list of indices of texts that have transforms or gradient
+    /// fills and need special handling.
     pub fn prepare_text(
         &mut self,
         device: &Device,
@@ -84,9 +188,13 @@ impl TextRenderState {
         screen_height: u32,
         scale_factor: f32,
     ) -> Vec<usize> {
-        // Move last frame's buffers into cache for reuse
+        // Move last frame's buffers into cache for reuse, unless fonts were
+        // added since: cached buffers may have been shaped with a fallback face
+        let fonts_changed = sync_font_system(&mut self.font_system, &mut self.loaded_fonts);
         for (key, buffer) in self.frame_keys.drain(..).zip(self.buffers.drain(..)) {
-            self.buffer_cache.insert(key, buffer);
+            if !fonts_changed {
+                self.buffer_cache.insert(key, buffer);
+            }
         }
 
         // Collect indices of texts that have non-trivial transforms (for texture-based rendering)
@@ -138,7 +246,10 @@ impl TextRenderState {
 
             // Route all non-translation transforms (rotation, scale) to TextQuadRenderer.
             // This keeps the glyphon atlas stable — only identity/translation text goes through it.
-            if !entry.transform.is_identity() && !entry.transform.is_translation_only() {
+            // Gradient fills are applied to the quad too: glyphon only has per-glyph colors.
+            if (!entry.transform.is_identity() && !entry.transform.is_translation_only())
+                || entry.gradient.is_some()
+            {
                 transformed_indices.push(idx);
                 continue; // Skip transformed text in direct rendering
             }
@@ -159,21 +270,23 @@ impl TextRenderState {
                     Some((entry.rect.width.max(200.0)) * scale_factor),
                     Some((entry.rect.height.max(50.0)) * scale_factor),
                 );
-                let weight = if entry.font_weight == FontWeight::default() {
-                    FontWeight::NORMAL
-                } else {
-                    entry.font_weight
-                };
+                buffer.set_tab_width(&mut self.font_system, entry.tab_width);
+                buffer.set_wrap(&mut self.font_system, glyphon_wrap(entry.wrap));
+                let attrs = text_attrs(&self.font_system, entry);
                 buffer.set_text(
                     &mut self.font_system,
                     &entry.text,
-                    &Attrs::new()
-                        .family(entry.font_family.to_cosmic())
-                        .weight(weight.to_cosmic()),
+                    &attrs,
                     Shaping::Advanced,
                     None,
                 );
                 buffer.shape_until_scroll(&mut self.font_system, true);
+                fit_rtl_lines(
+                    &mut self.font_system,
+                    &mut buffer,
+                    entry.rect.width * scale_factor,
+                    entry.rect.height.max(50.0) * scale_factor,
+                );
                 buffer
             };
             self.frame_keys.push(key);
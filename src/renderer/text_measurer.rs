@@ -1,8 +1,12 @@
 use crate::layout::Size;
-use crate::widgets::font::{FontFamily, FontWeight};
-use cosmic_text::{Attrs, Buffer, FontSystem, Metrics, Shaping};
+use crate::widgets::Rect;
+use crate::widgets::font::{FontFamily, FontStyle, FontWeight, WrapMode};
+use cosmic_text::{Attrs, Buffer, Fallback, FontSystem, Metrics, PlatformFallback, Shaping};
 use std::cell::RefCell;
 use std::collections::HashMap;
+use std::ops::Range;
+
+use super::font_fallback::FallbackFamilies;
 
 /// Cache key for measurement results.
 /// Uses f32::to_bits() for hashable floats.
@@ -12,26 +16,203 @@ struct MeasureCacheKey {
     font_size_bits: u32,
     font_family: FontFamily,
     font_weight: FontWeight,
+    font_style: FontStyle,
     max_width_bits: Option<u32>,
+    tab_width: u16,
+    wrap: WrapMode,
+}
+
+/// Maximum number of cached measurements. When full, the least recently
+/// used quarter is evicted.
+const MEASURE_CACHE_CAPACITY: usize = 2048;
+
+/// A cached measurement with its last-use stamp for LRU eviction.
+struct CachedMetrics {
+    size: Size,
+    baseline: f32,
+    last_used: u64,
+}
+
+/// Default tab stop width, in spaces.
+pub const DEFAULT_TAB_WIDTH: u16 = 4;
+
+/// Font and layout settings for measuring text.
+///
+/// Starts from the defaults a plain `Text` uses (sans-serif, normal weight
+/// and style, [`DEFAULT_TAB_WIDTH`], [`WrapMode::Word`], no width limit)
+/// and is adjusted with the builder methods:
+///
+/// ```ignore
+/// let params = TextMeasureParams::new(14.0)
+///     .max_width(Some(200.0))
+///     .font_weight(FontWeight::BOLD);
+/// let size = measure_text_with_params("Hello", &params);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextMeasureParams {
+    pub font_size: f32,
+    /// Width to wrap at; `None` lays the text out at its natural width.
+    pub max_width: Option<f32>,
+    pub font_family: FontFamily,
+    pub font_weight: FontWeight,
+    pub font_style: FontStyle,
+    /// Tab stop width, in spaces.
+    pub tab_width: u16,
+    pub wrap: WrapMode,
+}
+
+impl TextMeasureParams {
+    pub fn new(font_size: f32) -> Self {
+        Self {
+            font_size,
+            max_width: None,
+            font_family: FontFamily::default(),
+            font_weight: FontWeight::NORMAL,
+            font_style: FontStyle::Normal,
+            tab_width: DEFAULT_TAB_WIDTH,
+            wrap: WrapMode::default(),
+        }
+    }
+
+    pub fn max_width(mut self, max_width: Option<f32>) -> Self {
+        self.max_width = max_width;
+        self
+    }
+
+    pub fn font_family(mut self, font_family: FontFamily) -> Self {
+        self.font_family = font_family;
+        self
+    }
+
+    pub fn font_weight(mut self, font_weight: FontWeight) -> Self {
+        self.font_weight = font_weight;
+        self
+    }
+
+    pub fn font_style(mut self, font_style: FontStyle) -> Self {
+        self.font_style = font_style;
+        self
+    }
+
+    pub fn tab_width(mut self, tab_width: u16) -> Self {
+        self.tab_width = tab_width;
+        self
+    }
+
+    pub fn wrap(mut self, wrap: WrapMode) -> Self {
+        self.wrap = wrap;
+        self
+    }
+}
+
+/// Result of hit-testing a point against laid-out text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TextHit {
+    /// Character index in the whole string (caret insertion position).
+    pub index: usize,
+    /// Zero-based logical line (lines are separated by `\n`).
+    pub line: usize,
+    /// Character column within the logical line.
+    pub column: usize,
+}
+
+/// Appended to the last visible line of clamped text
+const ELLIPSIS: &str = "\u{2026}";
+
+/// The longest prefix of `line` that `fits` once followed by an ellipsis,
+/// with the ellipsis appended.
+///
+/// Trailing whitespace before the ellipsis is dropped. `fits` is assumed to
+/// be monotonic (a shorter prefix never fits worse), so a binary search
+/// finds the cut with few measurements.
+fn fit_with_ellipsis(line: &str, mut fits: impl FnMut(&str) -> bool) -> String {
+    let ends: Vec<usize> = line
+        .char_indices()
+        .map(|(i, _)| i)
+        .skip(1)
+        .chain(std::iter::once(line.len()))
+        .collect();
+    let candidate = |chars: usize| {
+        let end = if chars == 0 { 0 } else { ends[chars - 1] };
+        format!("{}{}", line[..end].trim_end(), ELLIPSIS)
+    };
+    // Largest char count whose candidate fits (zero chars always "fits")
+    let (mut lo, mut hi) = (0, ends.len());
+    while lo < hi {
+        let mid = (lo + hi).div_ceil(2);
+        if fits(&candidate(mid)) {
+            lo = mid;
+        } else {
+            hi = mid - 1;
+        }
+    }
+    candidate(lo)
+}
+
+/// Convert a character index into (logical line, byte offset within that line).
+fn line_byte_for_char(text: &str, char_index: usize) -> (usize, usize) {
+    let mut line = 0;
+    let mut line_start = 0;
+    for (count, (byte, ch)) in text.char_indices().enumerate() {
+        if count == char_index {
+            return (line, byte - line_start);
+        }
+        if ch == '\n' {
+            line += 1;
+            line_start = byte + 1;
+        }
+    }
+    (line, text.len() - line_start)
 }
 
 pub struct TextMeasurer {
     font_system: FontSystem,
-    measure_cache: HashMap<MeasureCacheKey, Size>,
+    /// Measured size and first-line baseline, keyed by text and style.
+    measure_cache: HashMap<MeasureCacheKey, CachedMetrics>,
+    /// Monotonic counter stamped on cache entries when used.
+    cache_tick: u64,
+    /// Number of registered custom fonts loaded into `font_system`.
+    loaded_fonts: usize,
 }
 
 impl TextMeasurer {
     pub fn new() -> Self {
         let mut font_system = FontSystem::new();
-        for data in crate::get_registered_fonts() {
+        let fonts = crate::get_registered_fonts();
+        let loaded_fonts = fonts.len();
+        for data in fonts {
             font_system
                 .db_mut()
                 .load_font_source(cosmic_text::fontdb::Source::Binary(data));
         }
+        if let Some(fallback) =
+            FallbackFamilies::resolve(font_system.db(), PlatformFallback.common_fallback())
+        {
+            let (locale, db) = font_system.into_locale_and_db();
+            font_system = FontSystem::new_with_locale_and_db_and_fallback(locale, db, fallback);
+        }
         Self {
             font_system,
             measure_cache: HashMap::new(),
+            cache_tick: 0,
+            loaded_fonts,
+        }
+    }
+
+    /// Load fonts registered since construction (see `add_font_runtime`)
+    /// and drop cached measurements, which may have used a fallback face.
+    pub fn sync_fonts(&mut self) {
+        let fonts = crate::registered_fonts_since(self.loaded_fonts);
+        if fonts.is_empty() {
+            return;
+        }
+        self.loaded_fonts += fonts.len();
+        for data in fonts {
+            self.font_system
+                .db_mut()
+                .load_font_source(cosmic_text::fontdb::Source::Binary(data));
         }
+        self.measure_cache.clear();
     }
 
     pub fn measure(&mut self, text: &str, font_size: f32, max_width: Option<f32>) -> Size {
@@ -52,54 +233,338 @@ impl TextMeasurer {
         font_family: &FontFamily,
         font_weight: FontWeight,
     ) -> Size {
+        let params = TextMeasureParams::new(font_size)
+            .max_width(max_width)
+            .font_family(font_family.clone())
+            .font_weight(font_weight);
+        self.measure_with_params(text, &params)
+    }
+
+    /// Measure text with the full set of font and layout settings.
+    pub fn measure_with_params(&mut self, text: &str, params: &TextMeasureParams) -> Size {
+        self.metrics(text, params).0
+    }
+
+    /// Distance from the text origin to the first line's baseline.
+    pub fn baseline(&mut self, text: &str, params: &TextMeasureParams) -> f32 {
+        self.metrics(text, params).1
+    }
+
+    /// Size and first-line baseline of the text, cached by content and style.
+    fn metrics(&mut self, text: &str, params: &TextMeasureParams) -> (Size, f32) {
         // Build cache key
         let cache_key = MeasureCacheKey {
             text: text.to_string(),
-            font_size_bits: font_size.to_bits(),
-            font_family: font_family.clone(),
-            font_weight,
-            max_width_bits: max_width.map(|w| w.to_bits()),
+            font_size_bits: params.font_size.to_bits(),
+            font_family: params.font_family.clone(),
+            font_weight: params.font_weight,
+            font_style: params.font_style,
+            max_width_bits: params.max_width.map(|w| w.to_bits()),
+            tab_width: params.tab_width,
+            wrap: params.wrap,
         };
 
         // Check cache first
-        if let Some(&cached_size) = self.measure_cache.get(&cache_key) {
-            return cached_size;
+        self.cache_tick += 1;
+        if let Some(cached) = self.measure_cache.get_mut(&cache_key) {
+            cached.last_used = self.cache_tick;
+            crate::render_stats::record_text_measure_cached();
+            return (cached.size, cached.baseline);
         }
+        crate::render_stats::record_text_measure_shaped();
 
         // Measure text
+        let buffer = self.shape(text, params);
+
+        let mut width = 0.0f32;
+        let mut height = 0.0f32;
+        let mut baseline = None;
+        for run in buffer.layout_runs() {
+            width = width.max(run.line_w);
+            height += run.line_height;
+            baseline.get_or_insert(run.line_y);
+        }
+
+        // Ensure minimum height for empty text
+        if height == 0.0 {
+            height = params.font_size * 1.2;
+        }
+
+        let size = Size::new(width, height);
+        // Empty text has no runs; approximate the baseline of a default line
+        let baseline = baseline.unwrap_or(params.font_size);
+
+        // Cache the result
+        if self.measure_cache.len() >= MEASURE_CACHE_CAPACITY {
+            self.evict_least_recently_used();
+        }
+        self.measure_cache.insert(
+            cache_key,
+            CachedMetrics {
+                size,
+                baseline,
+                last_used: self.cache_tick,
+            },
+        );
+
+        (size, baseline)
+    }
+
+    /// Drop the least recently used quarter of the measurement cache.
+    ///
+    /// Evicting in bulk keeps the cost amortized: a full scan happens once
+    /// every `MEASURE_CACHE_CAPACITY / 4` insertions.
+    fn evict_least_recently_used(&mut self) {
+        let mut stamps: Vec<u64> = self.measure_cache.values().map(|c| c.last_used).collect();
+        let cutoff_index = stamps.len() / 4;
+        let (_, &mut cutoff, _) = stamps.select_nth_unstable(cutoff_index);
+        self.measure_cache.retain(|_, c| c.last_used > cutoff);
+    }
+
+    /// Shape `text` into a buffer using the same settings as measurement.
+    fn shape(&mut self, text: &str, params: &TextMeasureParams) -> Buffer {
+        let TextMeasureParams {
+            font_size,
+            max_width,
+            ref font_family,
+            font_weight,
+            font_style,
+            tab_width,
+            wrap,
+        } = *params;
+        // A synthesized slant doesn't change advances, so shape it upright
+        let style = font_style.resolve(self.font_system.db(), font_family, font_weight);
         let metrics = Metrics::new(font_size, font_size * 1.2);
         let mut buffer = Buffer::new(&mut self.font_system, metrics);
-
         buffer.set_size(&mut self.font_system, max_width, None);
+        buffer.set_tab_width(&mut self.font_system, tab_width);
+        buffer.set_wrap(&mut self.font_system, wrap.to_cosmic());
         buffer.set_text(
             &mut self.font_system,
             text,
             &Attrs::new()
                 .family(font_family.to_cosmic())
-                .weight(font_weight.to_cosmic()),
-            Shaping::Basic,
+                .weight(font_weight.to_cosmic())
+                .style(style),
+            // Advanced shaping performs font fallback, matching the renderer
+            Shaping::Advanced,
             None,
         );
         buffer.shape_until_scroll(&mut self.font_system, true);
+        buffer
+    }
 
-        let mut width = 0.0f32;
-        let mut height = 0.0f32;
-        for run in buffer.layout_runs() {
-            width = width.max(run.line_w);
-            height += run.line_height;
+    /// Map a point (relative to the text origin) to a caret position.
+    pub fn hit_test(&mut self, text: &str, params: &TextMeasureParams, x: f32, y: f32) -> TextHit {
+        let buffer = self.shape(text, params);
+        let Some(cursor) = buffer.hit(x.max(0.0), y.max(0.0)) else {
+            return TextHit::default();
+        };
+
+        let mut index = 0;
+        for (line, line_text) in text.split('\n').enumerate() {
+            if line == cursor.line {
+                let byte = cursor.index.min(line_text.len());
+                let column = line_text[..byte].chars().count();
+                return TextHit {
+                    index: index + column,
+                    line,
+                    column,
+                };
+            }
+            // +1 for the newline separator
+            index += line_text.chars().count() + 1;
         }
+        TextHit {
+            index: text.chars().count(),
+            line: cursor.line,
+            column: 0,
+        }
+    }
 
-        // Ensure minimum height for empty text
-        if height == 0.0 {
-            height = font_size * 1.2;
+    /// Bounding rect of the character at `char_index`, relative to the text origin.
+    ///
+    /// Returns `None` for out-of-range indices and for characters that produce
+    /// no glyph (e.g. newlines).
+    pub fn char_rect(
+        &mut self,
+        text: &str,
+        params: &TextMeasureParams,
+        char_index: usize,
+    ) -> Option<Rect> {
+        if char_index >= text.chars().count() {
+            return None;
         }
+        let (line, byte) = line_byte_for_char(text, char_index);
+        let buffer = self.shape(text, params);
+        buffer
+            .layout_runs()
+            .filter(|run| run.line_i == line)
+            .find_map(|run| {
+                run.glyphs
+                    .iter()
+                    .find(|g| g.start <= byte && byte < g.end)
+                    .map(|g| Rect::new(g.x, run.line_top, g.w, run.line_height))
+            })
+    }
 
-        let size = Size::new(width, height);
+    /// Rects covering the characters in `range`, one per visual line,
+    /// relative to the text origin.
+    pub fn range_rects(
+        &mut self,
+        text: &str,
+        params: &TextMeasureParams,
+        range: Range<usize>,
+    ) -> Vec<Rect> {
+        if range.is_empty() {
+            return Vec::new();
+        }
+        // (hard line, byte in line) bounds, compared in text order
+        let start = line_byte_for_char(text, range.start);
+        let end = line_byte_for_char(text, range.end);
+        let buffer = self.shape(text, params);
+        buffer
+            .layout_runs()
+            .filter_map(|run| {
+                let (left, right) = run
+                    .glyphs
+                    .iter()
+                    .filter(|g| (start..end).contains(&(run.line_i, g.start)))
+                    .fold(None, |span: Option<(f32, f32)>, g| {
+                        let (left, right) = span.unwrap_or((g.x, g.x + g.w));
+                        Some((left.min(g.x), right.max(g.x + g.w)))
+                    })?;
+                Some(Rect::new(left, run.line_top, right - left, run.line_height))
+            })
+            .collect()
+    }
 
-        // Cache the result
-        self.measure_cache.insert(cache_key, size);
+    /// Caret position `(x, line_top)` before the character at `char_index`,
+    /// relative to the text origin. An index past the end of a line places
+    /// the caret after its last character.
+    pub fn caret_position(
+        &mut self,
+        text: &str,
+        params: &TextMeasureParams,
+        char_index: usize,
+    ) -> (f32, f32) {
+        let (line, byte) = line_byte_for_char(text, char_index);
+        let buffer = self.shape(text, params);
+        let mut line_end = None;
+        for run in buffer.layout_runs().filter(|run| run.line_i == line) {
+            if let Some(glyph) = run.glyphs.iter().find(|g| g.start <= byte && byte < g.end) {
+                let x = if run.rtl { glyph.x + glyph.w } else { glyph.x };
+                return (x, run.line_top);
+            }
+            line_end = Some((run.line_w, run.line_top));
+        }
+        line_end.unwrap_or((0.0, line as f32 * params.font_size * 1.2))
+    }
+
+    /// Caret x positions for every character index of a single line of text.
+    ///
+    /// Entry `i` is where the caret sits before character `i` and the last
+    /// entry is the position after the last character, so the result has one
+    /// more entry than the text has characters. Positions follow the visual
+    /// order of the shaped glyphs, so they are not sorted for bidirectional
+    /// text. Also returns whether the line's paragraph direction is
+    /// right-to-left. The text is laid out on one line whatever the wrap
+    /// settings of `params`.
+    pub fn caret_offsets(&mut self, text: &str, params: &TextMeasureParams) -> (Vec<f32>, bool) {
+        let params = params.clone().max_width(None).wrap(WrapMode::NoWrap);
+        let buffer = self.shape(text, &params);
+        // (byte range, leading edge, trailing edge) of each glyph
+        let mut glyphs = Vec::new();
+        let mut rtl = false;
+        let mut line_w = 0.0f32;
+        for run in buffer.layout_runs().filter(|run| run.line_i == 0) {
+            rtl = run.rtl;
+            line_w = line_w.max(run.line_w);
+            glyphs.extend(run.glyphs.iter().map(|g| {
+                if g.level.is_rtl() {
+                    (g.start..g.end, g.x + g.w, g.x)
+                } else {
+                    (g.start..g.end, g.x, g.x + g.w)
+                }
+            }));
+        }
+        let glyph_at = |byte: usize| glyphs.iter().find(|(range, _, _)| range.contains(&byte));
+
+        let start = if rtl { line_w } else { 0.0 };
+        let mut offsets = Vec::with_capacity(text.chars().count() + 1);
+        let mut end = start;
+        for (byte, _) in text.char_indices() {
+            match glyph_at(byte) {
+                Some(&(_, leading, trailing)) => {
+                    offsets.push(leading);
+                    end = trailing;
+                }
+                // Characters without a glyph sit where the previous one ended
+                None => offsets.push(end),
+            }
+        }
+        offsets.push(end);
+        (offsets, rtl)
+    }
 
-        size
+    /// Break text into its visual lines, relative to the text origin.
+    ///
+    /// Each entry holds the line's text and its rect (`y` is the line top,
+    /// `width` the line's advance). Hard breaks (`\n`) and wrapping at
+    /// `max_width` both start a new line; empty lines yield an empty string.
+    pub fn wrapped_lines(&mut self, text: &str, params: &TextMeasureParams) -> Vec<(String, Rect)> {
+        let buffer = self.shape(text, params);
+        buffer
+            .layout_runs()
+            .map(|run| {
+                let start = run.glyphs.iter().map(|g| g.start).min().unwrap_or(0);
+                let end = run.glyphs.iter().map(|g| g.end).max().unwrap_or(0);
+                let line = run.text.get(start..end).unwrap_or_default().to_string();
+                let rect = Rect::new(0.0, run.line_top, run.line_w, run.line_height);
+                (line, rect)
+            })
+            .collect()
+    }
+
+    /// Text clamped to `max_lines` visual lines, or `None` if it already fits.
+    ///
+    /// The kept lines are joined with hard breaks and the last one is cut so
+    /// that it fits `max_width` with an ellipsis appended.
+    pub fn clamp_lines(
+        &mut self,
+        text: &str,
+        params: &TextMeasureParams,
+        max_lines: usize,
+    ) -> Option<String> {
+        let buffer = self.shape(text, params);
+        let mut lines: Vec<String> = buffer
+            .layout_runs()
+            .map(|run| {
+                let start = run.glyphs.iter().map(|g| g.start).min().unwrap_or(0);
+                let end = run.glyphs.iter().map(|g| g.end).max().unwrap_or(0);
+                run.text.get(start..end).unwrap_or_default().to_string()
+            })
+            .collect();
+        if lines.len() <= max_lines {
+            return None;
+        }
+        lines.truncate(max_lines.max(1));
+        let last = lines.pop().unwrap_or_default();
+        // Candidates are measured on a single line against the wrap width
+        let single_line = params.clone().max_width(None).wrap(WrapMode::NoWrap);
+        let last = fit_with_ellipsis(&last, |candidate| {
+            let Some(max_width) = params.max_width else {
+                return true;
+            };
+            let buffer = self.shape(candidate, &single_line);
+            let width = buffer
+                .layout_runs()
+                .map(|run| run.line_w)
+                .fold(0.0f32, f32::max);
+            width <= max_width
+        });
+        lines.push(last);
+        Some(lines.join("\n"))
     }
 
     /// Measure text width up to a specific character index.
@@ -199,12 +664,46 @@ impl TextMeasurer {
 
         left.min(char_count)
     }
+
+    /// Sorted, de-duplicated family names of every loaded font face.
+    pub fn available_fonts(&self) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .font_system
+            .db()
+            .faces()
+            .filter_map(|face| face.families.first().map(|(name, _)| name.clone()))
+            .collect();
+        names.sort_unstable();
+        names.dedup();
+        names
+    }
+
+    /// Whether any loaded font face belongs to the given family (case-insensitive).
+    pub fn font_exists(&self, family: &str) -> bool {
+        self.font_system.db().faces().any(|face| {
+            face.families
+                .iter()
+                .any(|(name, _)| name.eq_ignore_ascii_case(family))
+        })
+    }
 }
 
 thread_local! {
     static TEXT_MEASURER: RefCell<TextMeasurer> = RefCell::new(TextMeasurer::new());
 }
 
+/// Load fonts added at runtime into the measurement font system.
+pub(crate) fn sync_measurer_fonts() {
+    TEXT_MEASURER.with_borrow_mut(|m| m.sync_fonts());
+}
+
+/// Changes whenever fonts are loaded into the measurement font system, so
+/// results derived from measurements outside the measurer's own cache (such
+/// as clamped text) can tell they are stale.
+pub(crate) fn measurer_font_generation() -> usize {
+    TEXT_MEASURER.with_borrow(|m| m.loaded_fonts)
+}
+
 /// Measure text dimensions using the font system
 pub fn measure_text(text: &str, font_size: f32, max_width: Option<f32>) -> Size {
     TEXT_MEASURER.with_borrow_mut(|m| m.measure(text, font_size, max_width))
@@ -222,6 +721,27 @@ pub fn measure_text_styled(
         .with_borrow_mut(|m| m.measure_styled(text, font_size, max_width, font_family, font_weight))
 }
 
+/// Measure text dimensions with the full set of font and layout settings
+pub fn measure_text_with_params(text: &str, params: &TextMeasureParams) -> Size {
+    TEXT_MEASURER.with_borrow_mut(|m| m.measure_with_params(text, params))
+}
+
+/// Distance from the text origin to the first line's baseline
+pub fn text_baseline(text: &str, params: &TextMeasureParams) -> f32 {
+    TEXT_MEASURER.with_borrow_mut(|m| m.baseline(text, params))
+}
+
+/// Clamp text to `max_lines` visual lines, ending the last one with an ellipsis.
+///
+/// Returns `None` when the text already fits in `max_lines` lines.
+pub fn clamp_text_lines(
+    text: &str,
+    params: &TextMeasureParams,
+    max_lines: usize,
+) -> Option<String> {
+    TEXT_MEASURER.with_borrow_mut(|m| m.clamp_lines(text, params, max_lines))
+}
+
 /// Measure text width up to a specific character index (for cursor positioning)
 pub fn measure_text_to_char(text: &str, font_size: f32, char_index: usize) -> f32 {
     TEXT_MEASURER.with_borrow_mut(|m| m.measure_to_char(text, font_size, char_index))
@@ -245,6 +765,50 @@ pub fn char_index_from_x(text: &str, font_size: f32, x: f32) -> usize {
     TEXT_MEASURER.with_borrow_mut(|m| m.char_from_x(text, font_size, x))
 }
 
+/// Hit-test a point against laid-out text (for custom editors).
+///
+/// `x`/`y` are relative to the text origin; the text is laid out exactly
+/// like [`measure_text_with_params`] with the same `params`. Returns the
+/// caret insertion index together with its logical line and column.
+pub fn hit_test_text(text: &str, params: &TextMeasureParams, x: f32, y: f32) -> TextHit {
+    TEXT_MEASURER.with_borrow_mut(|m| m.hit_test(text, params, x, y))
+}
+
+/// Caret position `(x, line_top)` for a character index, relative to the text origin
+pub fn caret_position(text: &str, params: &TextMeasureParams, char_index: usize) -> (f32, f32) {
+    TEXT_MEASURER.with_borrow_mut(|m| m.caret_position(text, params, char_index))
+}
+
+/// Caret x positions for each character index of a single line of text,
+/// and whether its paragraph is right-to-left.
+///
+/// The positions have one more entry than the text has characters (the last
+/// one is after the end) and follow the visual glyph order, so mixed-direction
+/// text yields unsorted positions.
+pub fn caret_offsets(text: &str, params: &TextMeasureParams) -> (Vec<f32>, bool) {
+    TEXT_MEASURER.with_borrow_mut(|m| m.caret_offsets(text, params))
+}
+
+/// Visual lines of wrapped text as `(line text, rect)`, relative to the text origin.
+///
+/// Uses the same shaping as [`measure_text_with_params`], so the lines match
+/// what a `Text` with the same settings draws. Useful for gutters such as
+/// line numbers next to wrapped text.
+pub fn measure_text_wrapped(text: &str, params: &TextMeasureParams) -> Vec<(String, Rect)> {
+    TEXT_MEASURER.with_borrow_mut(|m| m.wrapped_lines(text, params))
+}
+
+/// Rects behind the characters in `range`, one per visual line, relative to
+/// the text origin. Lines wrap the same way a `Text` with these settings does.
+pub fn text_range_rects(text: &str, params: &TextMeasureParams, range: Range<usize>) -> Vec<Rect> {
+    TEXT_MEASURER.with_borrow_mut(|m| m.range_rects(text, params, range))
+}
+
+/// Bounding rect of a character, relative to the text origin
+pub fn char_rect(text: &str, params: &TextMeasureParams, char_index: usize) -> Option<Rect> {
+    TEXT_MEASURER.with_borrow_mut(|m| m.char_rect(text, params, char_index))
+}
+
 /// Find character index from x-coordinate with font styling
 pub fn char_index_from_x_styled(
     text: &str,
@@ -256,3 +820,155 @@ pub fn char_index_from_x_styled(
     TEXT_MEASURER
         .with_borrow_mut(|m| m.char_from_x_styled(text, font_size, x, font_family, font_weight))
 }
+
+/// List the font families available for `FontFamily::Name`.
+///
+/// Includes system fonts and fonts registered with `load_font`. Call it after
+/// loading custom fonts: the font database is built on first use.
+pub fn available_fonts() -> Vec<String> {
+    TEXT_MEASURER.with_borrow(|m| m.available_fonts())
+}
+
+/// Check whether a font family is installed or was loaded with `load_font`.
+pub fn font_exists(family: &str) -> bool {
+    TEXT_MEASURER.with_borrow(|m| m.font_exists(family))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fit_with_ellipsis_keeps_longest_prefix() {
+        // One unit per char, room for 6
+        let fits = |s: &str| s.chars().count() <= 6;
+        assert_eq!(fit_with_ellipsis("hello world", fits), "hello\u{2026}");
+        assert_eq!(fit_with_ellipsis("héllo", fits), "héllo\u{2026}");
+        // Whitespace before the ellipsis is dropped
+        assert_eq!(fit_with_ellipsis("abcd efgh", fits), "abcd\u{2026}");
+        // Nothing fits: the ellipsis alone
+        assert_eq!(fit_with_ellipsis("abcdef", |_| false), "\u{2026}");
+    }
+
+    #[test]
+    fn test_caret_queries_use_measure_params() {
+        let narrow = TextMeasureParams::new(14.0).tab_width(2);
+        let wide = narrow.clone().tab_width(8);
+        // The caret after a tab lands on the configured tab stop
+        let (narrow_x, _) = caret_position("\tx", &narrow, 1);
+        let (wide_x, _) = caret_position("\tx", &wide, 1);
+        assert!(wide_x > narrow_x + 1.0, "{narrow_x} {wide_x}");
+        // Hit-testing and char rects follow the same layout
+        assert_eq!(hit_test_text("\tx", &wide, wide_x + 1.0, 1.0).index, 1);
+        let rect = char_rect("\tx", &wide, 1).unwrap();
+        assert!((rect.x - wide_x).abs() < 0.5, "{rect:?} {wide_x}");
+    }
+
+    #[test]
+    fn test_hit_test_reports_line_and_column() {
+        let params = TextMeasureParams::new(14.0);
+        let text = "ab\ncdef";
+        // Caret before 'e' on the second line, and a click on that spot
+        let (x, top) = caret_position(text, &params, 5);
+        assert!(top > 0.0);
+        let hit = hit_test_text(text, &params, x + 0.5, top + 2.0);
+        assert_eq!(
+            hit,
+            TextHit {
+                index: 5,
+                line: 1,
+                column: 2
+            }
+        );
+        // Past the end of a line lands at its end, not on the next line
+        let hit = hit_test_text(text, &params, 500.0, 2.0);
+        assert_eq!((hit.index, hit.line, hit.column), (2, 0, 2));
+        // Newlines have no glyph to bound
+        assert_eq!(char_rect(text, &params, 2), None);
+        assert!(char_rect(text, &params, 3).is_some());
+    }
+
+    #[test]
+    fn test_params_default_to_text_wrap_and_caret_offsets_stay_on_one_line() {
+        // Same default as a plain `Text`
+        assert_eq!(TextMeasureParams::new(14.0).wrap, WrapMode::default());
+
+        let params = TextMeasureParams::new(14.0).max_width(Some(10.0));
+        let (offsets, rtl) = caret_offsets("ab cd", &params);
+        assert!(!rtl);
+        assert_eq!(offsets.len(), 6);
+        // A narrow width does not wrap the line: offsets keep growing
+        assert!(offsets.windows(2).all(|w| w[1] >= w[0]), "{offsets:?}");
+        let width = measure_text_with_params("ab cd", &params.clone().max_width(None)).width;
+        assert!((offsets[5] - width).abs() < 0.5, "{offsets:?} {width}");
+    }
+
+    #[test]
+    fn test_measure_cache_evicts_least_recently_used() {
+        let mut measurer = TextMeasurer::new();
+        let params = TextMeasureParams::new(14.0);
+        let cached = |m: &TextMeasurer, text: &str| m.measure_cache.keys().any(|k| k.text == text);
+        for i in 0..MEASURE_CACHE_CAPACITY {
+            measurer.measure_with_params(&i.to_string(), &params);
+        }
+        assert_eq!(measurer.measure_cache.len(), MEASURE_CACHE_CAPACITY);
+
+        // Measuring the oldest entry again makes it the most recently used
+        measurer.measure_with_params("0", &params);
+        measurer.measure_with_params("overflow", &params);
+
+        let len = measurer.measure_cache.len();
+        assert!(len <= MEASURE_CACHE_CAPACITY * 3 / 4 + 1, "{len}");
+        assert!(cached(&measurer, "0"));
+        assert!(cached(&measurer, "overflow"));
+        assert!(!cached(&measurer, "1"));
+        // The newest entries survive
+        assert!(cached(&measurer, &(MEASURE_CACHE_CAPACITY - 1).to_string()));
+    }
+
+    #[test]
+    fn test_wrapped_lines_match_the_measured_paragraph() {
+        let text = "The quick brown fox jumps over the lazy dog and keeps running";
+        let params = TextMeasureParams::new(14.0)
+            .wrap(WrapMode::Word)
+            .max_width(Some(200.0));
+        let lines = measure_text_wrapped(text, &params);
+        assert!(lines.len() > 1, "{lines:?}");
+
+        // Every word lands on exactly one line, in order
+        let words: Vec<&str> = lines
+            .iter()
+            .flat_map(|(l, _)| l.split_whitespace())
+            .collect();
+        assert_eq!(words, text.split_whitespace().collect::<Vec<_>>());
+        for pair in lines.windows(2) {
+            assert!(pair[1].1.y > pair[0].1.y, "{lines:?}");
+        }
+        for (_, rect) in &lines {
+            assert!(rect.width <= 200.0, "{lines:?}");
+        }
+        // The lines stack up to the measured height
+        let (_, last) = lines.last().unwrap();
+        let size = measure_text_with_params(text, &params);
+        assert!((last.y + last.height - size.height).abs() < 0.5, "{size:?}");
+    }
+
+    #[test]
+    fn test_range_rects_follow_glyphs_and_lines() {
+        let params = TextMeasureParams::new(14.0).wrap(WrapMode::Word);
+        let rects = |text: &str, range: Range<usize>| text_range_rects(text, &params, range);
+
+        let marked = rects("hello world", 6..11);
+        assert_eq!(marked.len(), 1);
+        let start = measure_text_to_char("hello world", 14.0, 6);
+        let end = measure_text_to_char("hello world", 14.0, 11);
+        assert!((marked[0].x - start).abs() < 0.5, "{marked:?} {start}");
+        assert!((marked[0].width - (end - start)).abs() < 0.5);
+
+        // One rect per line, the second below the first
+        let marked = rects("ab\ncd", 1..5);
+        assert_eq!(marked.len(), 2);
+        assert!(marked[1].y > marked[0].y);
+        assert!(rects("ab", 1..1).is_empty());
+    }
+}
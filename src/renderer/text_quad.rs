@@ -5,13 +5,17 @@
 //! that glyphon cannot handle directly.
 //!
 //! Vertex positions are computed on the CPU and passed as pre-computed
-//! NDC coordinates to the shader.
+//! NDC coordinates to the shader. Rasterized textures are cached by string
+//! and style, so text that doesn't change between frames (or only moves,
+//! rotates or scales) is not re-rendered.
 
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
 
 use glyphon::{
-    Attrs, Buffer, Cache, Color as GlyphonColor, ColorMode, FontSystem, Metrics, Resolution,
-    Shaping, SwashCache, TextArea, TextAtlas, TextBounds, TextRenderer, Viewport,
+    Buffer, Cache, Color as GlyphonColor, ColorMode, FontSystem, Metrics, Resolution, Shaping,
+    SwashCache, TextArea, TextAtlas, TextBounds, TextRenderer, Viewport,
 };
 use wgpu::util::DeviceExt;
 use wgpu::{
@@ -22,9 +26,12 @@ use wgpu::{
 
 use super::constants::{TEXT_BUFFER_MARGIN_MULTIPLIER, TEXT_TEXTURE_PADDING};
 use super::gpu::NO_CLIP_RECT;
-use super::textured_vertex::{TexturedVertex, to_ndc};
-use super::types::TextEntry;
-use crate::widgets::font::FontWeight;
+use super::textured_vertex::{
+    NO_TINT, QuadVertexBuffer, TexturedVertex, draw_quad_run, quad_indices, quad_runs, to_ndc,
+};
+use super::types::{Gradient, GradientDir, TextEntry};
+use crate::widgets::Color;
+use crate::widgets::font::{FontFamily, FontStyle, FontWeight, WrapMode};
 
 /// Quality multiplier for supersampling text textures.
 const QUALITY_MULTIPLIER: f32 = 2.0;
@@ -33,12 +40,98 @@ const QUALITY_MULTIPLIER: f32 = 2.0;
 const TEXT_MARGIN: f32 = TEXT_BUFFER_MARGIN_MULTIPLIER;
 
 /// A prepared text quad ready for rendering.
+///
+/// Its vertices live in the renderer's frame vertex buffer, at the quad's
+/// position in the prepared list.
 pub struct PreparedTextQuad {
+    texture: Arc<CachedTextTexture>,
+}
+
+/// A rasterized text texture.
+struct CachedTextTexture {
     #[allow(dead_code)] // Kept alive for GPU usage
     texture: Texture,
     bind_group: BindGroup,
-    /// Vertex buffer with pre-computed vertices in NDC
-    vertex_buffer: WgpuBuffer,
+    width: u32,
+    height: u32,
+    /// Padding around the text, in texture pixels
+    padding: f32,
+    /// Last frame this texture was used. Atomic so it can be stamped while
+    /// prepared quads still share the texture.
+    last_used_frame: AtomicU64,
+}
+
+/// Cache key for text textures: everything that affects rasterization.
+///
+/// The transform is deliberately left out — it is applied to the quad vertices.
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct TextTextureKey {
+    text: String,
+    font_size_bits: u32,
+    font_family: FontFamily,
+    font_weight: FontWeight,
+    font_style: FontStyle,
+    tab_width: u16,
+    wrap: WrapMode,
+    color_bits: [u32; 4],
+    width_bits: u32,
+    height_bits: u32,
+    scale_factor_bits: u32,
+}
+
+impl TextTextureKey {
+    fn new(entry: &TextEntry, scale_factor: f32) -> Self {
+        let color = text_color(entry);
+        Self {
+            text: entry.text.clone(),
+            font_size_bits: entry.font_size.to_bits(),
+            font_family: entry.font_family.clone(),
+            font_weight: entry.font_weight,
+            font_style: entry.font_style,
+            tab_width: entry.tab_width,
+            wrap: entry.wrap,
+            color_bits: [
+                color.r.to_bits(),
+                color.g.to_bits(),
+                color.b.to_bits(),
+                color.a.to_bits(),
+            ],
+            width_bits: entry.rect.width.to_bits(),
+            height_bits: entry.rect.height.to_bits(),
+            scale_factor_bits: scale_factor.to_bits(),
+        }
+    }
+}
+
+/// Color the text is rasterized in.
+///
+/// Gradient text is rasterized in white and colored by the quad's tint.
+fn text_color(entry: &TextEntry) -> Color {
+    if entry.gradient.is_some() {
+        Color::WHITE
+    } else {
+        entry.color
+    }
+}
+
+/// Color of a linear gradient at `(u, v)`, relative to the rect it spans.
+///
+/// Positions outside the rect extrapolate the gradient instead of clamping,
+/// so interpolating between quad corners beyond the rect stays exact inside it.
+fn gradient_tint(gradient: &Gradient, u: f32, v: f32) -> [f32; 4] {
+    let t = match gradient.direction {
+        GradientDir::Horizontal => u,
+        GradientDir::Vertical => v,
+        GradientDir::Diagonal => (u + v) / 2.0,
+        GradientDir::DiagonalReverse => (u + (1.0 - v)) / 2.0,
+    };
+    let (start, end) = (gradient.start_color, gradient.end_color);
+    [
+        start.r + (end.r - start.r) * t,
+        start.g + (end.g - start.g) * t,
+        start.b + (end.b - start.b) * t,
+        start.a + (end.a - start.a) * t,
+    ]
 }
 
 /// Renderer for transformed text as textured quads.
@@ -57,8 +150,10 @@ pub struct TextQuadRenderer {
     bind_group_layout: BindGroupLayout,
     sampler: Sampler,
 
-    // Shared index buffer (vertices are per-quad)
+    // Shared index buffer for up to MAX_QUADS_PER_DRAW quads
     index_buffer: WgpuBuffer,
+    // Vertices of all quads prepared this frame
+    vertex_buffer: QuadVertexBuffer,
 
     // Texture format
     format: TextureFormat,
@@ -66,17 +161,23 @@ pub struct TextQuadRenderer {
     // Screen dimensions for NDC conversion
     screen_width: f32,
     screen_height: f32,
+
+    // Number of registered custom fonts loaded into `font_system`
+    loaded_fonts: usize,
+
+    // Texture cache
+    texture_cache: HashMap<TextTextureKey, Arc<CachedTextTexture>>,
+    current_frame: u64,
+    max_cache_size: usize,
 }
 
 impl TextQuadRenderer {
-    pub fn new(device: &Device, queue: &Queue, format: TextureFormat) -> Self {
+    /// `sample_count` applies to drawing the quads into the frame; the text
+    /// textures themselves are rendered without multisampling.
+    pub fn new(device: &Device, queue: &Queue, format: TextureFormat, sample_count: u32) -> Self {
         // Initialize text rendering components
-        let mut font_system = FontSystem::new();
-        for data in crate::get_registered_fonts() {
-            font_system
-                .db_mut()
-                .load_font_source(glyphon::fontdb::Source::Binary(data));
-        }
+        let font_system = super::text::new_font_system();
+        let loaded_fonts = crate::registered_font_count();
         let swash_cache = SwashCache::new();
         let cache = Cache::new(device);
         let mut atlas = TextAtlas::with_color_mode(device, queue, &cache, format, ColorMode::Web);
@@ -161,7 +262,10 @@ impl TextQuadRenderer {
                 conservative: false,
             },
             depth_stencil: None,
-            multisample: wgpu::MultisampleState::default(),
+            multisample: wgpu::MultisampleState {
+                count: sample_count,
+                ..Default::default()
+            },
             multiview_mask: None,
             cache: None,
         });
@@ -179,10 +283,9 @@ impl TextQuadRenderer {
         });
 
         // Create index buffer
-        let indices: [u16; 6] = [0, 1, 2, 1, 3, 2];
         let index_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
             label: Some("TextQuad Index Buffer"),
-            contents: bytemuck::cast_slice(&indices),
+            contents: bytemuck::cast_slice(&quad_indices()),
             usage: wgpu::BufferUsages::INDEX,
         });
 
@@ -197,9 +300,14 @@ impl TextQuadRenderer {
             bind_group_layout,
             sampler,
             index_buffer,
+            vertex_buffer: QuadVertexBuffer::new("TextQuad Vertex Buffer"),
             format,
             screen_width: 800.0,
             screen_height: 600.0,
+            loaded_fonts,
+            texture_cache: HashMap::new(),
+            current_frame: 0,
+            max_cache_size: 64,
         }
     }
 
@@ -218,23 +326,76 @@ impl TextQuadRenderer {
         indices: &[usize],
         scale_factor: f32,
     ) -> Vec<PreparedTextQuad> {
-        indices
+        if super::text::sync_font_system(&mut self.font_system, &mut self.loaded_fonts) {
+            // Cached textures may have been rasterized with a fallback face
+            self.texture_cache.clear();
+        }
+        self.current_frame += 1;
+        if self.texture_cache.len() > self.max_cache_size {
+            self.evict_oldest();
+        }
+        let mut vertices = Vec::with_capacity(indices.len() * 4);
+        let quads = indices
             .iter()
             .map(|&idx| {
                 let entry = &entries[idx];
-                self.render_text_to_quad(device, queue, entry, scale_factor)
+                let (quad, quad_vertices) =
+                    self.render_text_to_quad(device, queue, entry, scale_factor);
+                vertices.extend_from_slice(&quad_vertices);
+                quad
             })
-            .collect()
+            .collect();
+        self.vertex_buffer.write(device, queue, &vertices);
+        quads
     }
 
-    /// Render a single text entry to a textured quad.
-    fn render_text_to_quad(
+    /// Evict the least recently used textures until under half the limit.
+    fn evict_oldest(&mut self) {
+        let target_size = self.max_cache_size / 2;
+        while self.texture_cache.len() > target_size {
+            let oldest_key = self
+                .texture_cache
+                .iter()
+                .min_by_key(|(_, v)| v.last_used_frame.load(Ordering::Relaxed))
+                .map(|(k, _)| k.clone());
+
+            if let Some(key) = oldest_key {
+                self.texture_cache.remove(&key);
+            } else {
+                break;
+            }
+        }
+    }
+
+    /// Get the cached texture for a text entry, rasterizing it on a miss.
+    fn get_or_render_texture(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        entry: &TextEntry,
+        scale_factor: f32,
+    ) -> Arc<CachedTextTexture> {
+        let key = TextTextureKey::new(entry, scale_factor);
+        if let Some(cached) = self.texture_cache.get(&key) {
+            cached
+                .last_used_frame
+                .store(self.current_frame, Ordering::Relaxed);
+            return cached.clone();
+        }
+
+        let cached = Arc::new(self.render_text_texture(device, queue, entry, scale_factor));
+        self.texture_cache.insert(key, cached.clone());
+        cached
+    }
+
+    /// Rasterize a text entry into an offscreen texture.
+    fn render_text_texture(
         &mut self,
         device: &Arc<Device>,
         queue: &Arc<Queue>,
         entry: &TextEntry,
         scale_factor: f32,
-    ) -> PreparedTextQuad {
+    ) -> CachedTextTexture {
         // Rasterize at fixed resolution: scale_factor * QUALITY_MULTIPLIER.
         // The transform's scale/rotation is applied via GPU quad vertices, not baked into the texture.
         // This prevents atlas churn during scale animations (each frame would otherwise create new entries).
@@ -258,22 +419,24 @@ impl TextQuadRenderer {
             Some(buffer_width),
             Some(buffer_height),
         );
+        buffer.set_tab_width(&mut self.font_system, entry.tab_width);
+        buffer.set_wrap(&mut self.font_system, super::text::glyphon_wrap(entry.wrap));
 
-        let weight = if entry.font_weight == FontWeight::default() {
-            FontWeight::NORMAL
-        } else {
-            entry.font_weight
-        };
+        let attrs = super::text::text_attrs(&self.font_system, entry);
         buffer.set_text(
             &mut self.font_system,
             &entry.text,
-            &Attrs::new()
-                .family(entry.font_family.to_cosmic())
-                .weight(weight.to_cosmic()),
+            &attrs,
             Shaping::Advanced,
             None,
         );
         buffer.shape_until_scroll(&mut self.font_system, true);
+        super::text::fit_rtl_lines(
+            &mut self.font_system,
+            &mut buffer,
+            entry.rect.width * effective_scale,
+            buffer_height,
+        );
 
         // Calculate texture size with padding
         let padding = TEXT_TEXTURE_PADDING * effective_scale;
@@ -319,12 +482,15 @@ impl TextQuadRenderer {
                 right: tex_width as i32,
                 bottom: tex_height as i32,
             },
-            default_color: GlyphonColor::rgba(
-                (entry.color.r * 255.0) as u8,
-                (entry.color.g * 255.0) as u8,
-                (entry.color.b * 255.0) as u8,
-                (entry.color.a * 255.0) as u8,
-            ),
+            default_color: {
+                let color = text_color(entry);
+                GlyphonColor::rgba(
+                    (color.r * 255.0) as u8,
+                    (color.g * 255.0) as u8,
+                    (color.b * 255.0) as u8,
+                    (color.a * 255.0) as u8,
+                )
+            },
             custom_glyphs: &[],
         };
 
@@ -386,6 +552,27 @@ impl TextQuadRenderer {
             ],
         });
 
+        CachedTextTexture {
+            texture,
+            bind_group,
+            width: tex_width,
+            height: tex_height,
+            padding,
+            last_used_frame: AtomicU64::new(self.current_frame),
+        }
+    }
+
+    /// Render a single text entry to a textured quad.
+    fn render_text_to_quad(
+        &mut self,
+        device: &Arc<Device>,
+        queue: &Arc<Queue>,
+        entry: &TextEntry,
+        scale_factor: f32,
+    ) -> (PreparedTextQuad, [TexturedVertex; 4]) {
+        let texture = self.get_or_render_texture(device, queue, entry, scale_factor);
+        let (tex_width, tex_height, padding) = (texture.width, texture.height, texture.padding);
+
         // The entry.rect is in LOCAL coordinates. We need to apply the world_transform
         // to get screen coordinates. The world_transform already includes everything:
         // parent translations, rotations, scales, and center_at adjustments.
@@ -446,6 +633,16 @@ impl TextQuadRenderer {
             (NO_CLIP_RECT, [0.0, 1.0, 0.0, 0.0])
         };
 
+        // Gradient colors at each corner, relative to the text rect
+        let tints = local_corners.map(|(x, y)| match entry.gradient {
+            Some(ref gradient) => gradient_tint(
+                gradient,
+                (x - entry.rect.x) / entry.rect.width.max(f32::EPSILON),
+                (y - entry.rect.y) / entry.rect.height.max(f32::EPSILON),
+            ),
+            None => NO_TINT,
+        });
+
         // Convert to NDC and create vertices with clip data
         let vertices = [
             TexturedVertex {
@@ -459,6 +656,7 @@ impl TextQuadRenderer {
                 screen_pos: [screen_corners[0].0, screen_corners[0].1],
                 clip_rect,
                 clip_params,
+                tint: tints[0],
             },
             TexturedVertex {
                 position: to_ndc(
@@ -471,6 +669,7 @@ impl TextQuadRenderer {
                 screen_pos: [screen_corners[1].0, screen_corners[1].1],
                 clip_rect,
                 clip_params,
+                tint: tints[1],
             },
             TexturedVertex {
                 position: to_ndc(
@@ -483,6 +682,7 @@ impl TextQuadRenderer {
                 screen_pos: [screen_corners[2].0, screen_corners[2].1],
                 clip_rect,
                 clip_params,
+                tint: tints[2],
             },
             TexturedVertex {
                 position: to_ndc(
@@ -495,36 +695,56 @@ impl TextQuadRenderer {
                 screen_pos: [screen_corners[3].0, screen_corners[3].1],
                 clip_rect,
                 clip_params,
+                tint: tints[3],
             },
         ];
 
-        // Create vertex buffer with the vertices already initialized
-        let vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-            label: Some("TextQuad Vertex Buffer"),
-            contents: bytemuck::cast_slice(&vertices),
-            usage: wgpu::BufferUsages::VERTEX,
-        });
-
-        PreparedTextQuad {
-            texture,
-            bind_group,
-            vertex_buffer,
-        }
+        (PreparedTextQuad { texture }, vertices)
     }
 
     /// Render the prepared text quads.
     pub fn render<'a>(&'a self, render_pass: &mut RenderPass<'a>, quads: &'a [PreparedTextQuad]) {
+        let Some(vertex_buffer) = self.vertex_buffer.buffer() else {
+            return;
+        };
         if quads.is_empty() {
             return;
         }
 
         render_pass.set_pipeline(&self.pipeline);
         render_pass.set_index_buffer(self.index_buffer.slice(..), wgpu::IndexFormat::Uint16);
+        render_pass.set_vertex_buffer(0, vertex_buffer.slice(..));
 
-        for quad in quads {
-            render_pass.set_bind_group(0, &quad.bind_group, &[]);
-            render_pass.set_vertex_buffer(0, quad.vertex_buffer.slice(..));
-            render_pass.draw_indexed(0..6, 0, 0..1);
+        // Consecutive quads showing the same cached texture share one draw call
+        for run in quad_runs(quads.len(), |a, b| {
+            Arc::ptr_eq(&quads[a].texture, &quads[b].texture)
+        }) {
+            draw_quad_run(render_pass, &quads[run.start].texture.bind_group, run);
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gradient_tint_extrapolates() {
+        let gradient = Gradient {
+            start_color: Color::rgba(0.0, 0.0, 0.0, 1.0),
+            end_color: Color::rgba(1.0, 1.0, 1.0, 0.0),
+            direction: GradientDir::Vertical,
+        };
+        assert_eq!(gradient_tint(&gradient, 0.3, 0.0), [0.0, 0.0, 0.0, 1.0]);
+        assert_eq!(gradient_tint(&gradient, 0.3, 1.0), [1.0, 1.0, 1.0, 0.0]);
+        // Beyond the rect (e.g. the texture padding) the gradient keeps going
+        assert_eq!(gradient_tint(&gradient, 0.0, 1.5), [1.5, 1.5, 1.5, -0.5]);
+
+        let diagonal = Gradient {
+            direction: GradientDir::DiagonalReverse,
+            ..gradient
+        };
+        assert_eq!(gradient_tint(&diagonal, 0.0, 1.0)[3], 1.0);
+        assert_eq!(gradient_tint(&diagonal, 1.0, 0.0)[3], 0.0);
+    }
+}
@@ -11,6 +11,7 @@ struct VertexInput {
     @location(2) screen_pos: vec2<f32>,
     @location(3) clip_rect: vec4<f32>,
     @location(4) clip_params: vec4<f32>,
+    @location(5) tint: vec4<f32>,
 }
 
 // === Vertex Output ===
@@ -21,6 +22,7 @@ struct VertexOutput {
     @location(1) screen_pos: vec2<f32>,
     @location(2) clip_rect: vec4<f32>,
     @location(3) clip_params: vec2<f32>,
+    @location(4) tint: vec4<f32>,
 }
 
 // === Texture Bindings ===
@@ -38,6 +40,7 @@ fn vs_main(in: VertexInput) -> VertexOutput {
     out.screen_pos = in.screen_pos;
     out.clip_rect = in.clip_rect;
     out.clip_params = in.clip_params.xy;
+    out.tint = in.tint;
     return out;
 }
 
@@ -67,6 +70,14 @@ fn rounded_rect_sdf(pos: vec2<f32>, rect: vec4<f32>, radius: f32) -> f32 {
 fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
     var color = textureSample(t_texture, s_sampler, in.uv);
 
+    // Tinted images (e.g. monochrome icons) and gradient text keep only the
+    // texture's alpha. Gradient tints are extrapolated past the text rect, so
+    // they can leave the 0..1 range (including a zero alpha) near the edges.
+    if (any(in.tint != vec4<f32>(0.0))) {
+        let tint = clamp(in.tint, vec4<f32>(0.0), vec4<f32>(1.0));
+        color = vec4<f32>(tint.rgb, color.a * tint.a);
+    }
+
     // Apply clipping if enabled (negative width/height = no clip sentinel)
     if (in.clip_rect.z >= 0.0 && in.clip_rect.w >= 0.0) {
         let clip_dist = rounded_rect_sdf(
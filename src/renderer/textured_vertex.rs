@@ -1,9 +1,19 @@
 //! Shared textured vertex types for quad rendering.
 //!
 //! This module provides the common vertex format used by both text quad and image quad
-//! rendering pipelines.
+//! rendering pipelines, plus helpers to draw many quads in few draw calls.
+//!
+//! Each frame's quads share one vertex buffer (four vertices per quad, in draw
+//! order) and a shared index buffer. Consecutive quads that sample the same
+//! texture are merged into a single draw call. The vertex buffer persists
+//! across frames and is only reallocated when a frame needs more room.
+
+use std::ops::Range;
 
-use wgpu::{VertexAttribute, VertexBufferLayout, VertexFormat, VertexStepMode};
+use wgpu::{
+    BindGroup, Buffer, BufferUsages, Device, Queue, RenderPass, VertexAttribute,
+    VertexBufferLayout, VertexFormat, VertexStepMode,
+};
 
 /// Vertex with pre-computed NDC position, UV coordinates, and clip data.
 #[repr(C)]
@@ -19,8 +29,14 @@ pub struct TexturedVertex {
     pub clip_rect: [f32; 4],
     /// Clip parameters [corner_radius, curvature, 0, 0]
     pub clip_params: [f32; 4],
+    /// Color replacing the texture color, keeping its alpha ([`NO_TINT`] for none).
+    /// Interpolated across the quad, so corners with different tints draw a gradient.
+    pub tint: [f32; 4],
 }
 
+/// Tint value that leaves the texture colors unchanged.
+pub const NO_TINT: [f32; 4] = [0.0; 4];
+
 impl TexturedVertex {
     pub fn desc() -> VertexBufferLayout<'static> {
         VertexBufferLayout {
@@ -57,6 +73,12 @@ impl TexturedVertex {
                     shader_location: 4,
                     format: VertexFormat::Float32x4,
                 },
+                // tint
+                VertexAttribute {
+                    offset: 56,
+                    shader_location: 5,
+                    format: VertexFormat::Float32x4,
+                },
             ],
         }
     }
@@ -70,3 +92,94 @@ pub fn to_ndc(x: f32, y: f32, screen_width: f32, screen_height: f32) -> [f32; 2]
         1.0 - (y / screen_height) * 2.0,
     ]
 }
+
+/// Maximum number of quads covered by one indexed draw call.
+///
+/// Indices are `u16`; each draw is offset with `base_vertex`, so only the
+/// quads within a single run need to be addressable.
+pub const MAX_QUADS_PER_DRAW: usize = 4096;
+
+/// Index buffer contents for `MAX_QUADS_PER_DRAW` quads, two triangles each.
+pub fn quad_indices() -> Vec<u16> {
+    (0..MAX_QUADS_PER_DRAW as u16)
+        .flat_map(|quad| {
+            let v = quad * 4;
+            [v, v + 1, v + 2, v + 1, v + 3, v + 2]
+        })
+        .collect()
+}
+
+/// Split `len` quads into runs of consecutive quads for which `same_texture`
+/// holds between neighbours, preserving draw order.
+pub fn quad_runs(len: usize, same_texture: impl Fn(usize, usize) -> bool) -> Vec<Range<usize>> {
+    let mut runs = Vec::new();
+    let mut start = 0;
+    while start < len {
+        let mut end = start + 1;
+        while end < len && end - start < MAX_QUADS_PER_DRAW && same_texture(end - 1, end) {
+            end += 1;
+        }
+        runs.push(start..end);
+        start = end;
+    }
+    runs
+}
+
+/// Draw a run of quads with one call.
+///
+/// Expects the pipeline, shared index buffer and frame vertex buffer to be bound.
+pub fn draw_quad_run(render_pass: &mut RenderPass<'_>, bind_group: &BindGroup, run: Range<usize>) {
+    render_pass.set_bind_group(0, bind_group, &[]);
+    render_pass.draw_indexed(0..(run.len() * 6) as u32, (run.start * 4) as i32, 0..1);
+}
+
+/// Persistent vertex buffer for a frame's quads.
+///
+/// Refilled each frame with `queue.write_buffer`; grows (at least doubling)
+/// only when a frame has more quads than ever before, so steady-state
+/// rendering allocates no GPU buffers.
+pub struct QuadVertexBuffer {
+    label: &'static str,
+    buffer: Option<Buffer>,
+    /// Capacity in vertices
+    capacity: usize,
+    /// Vertices written this frame
+    len: usize,
+}
+
+impl QuadVertexBuffer {
+    pub fn new(label: &'static str) -> Self {
+        Self {
+            label,
+            buffer: None,
+            capacity: 0,
+            len: 0,
+        }
+    }
+
+    /// Replace the buffer contents with this frame's vertices.
+    pub fn write(&mut self, device: &Device, queue: &Queue, vertices: &[TexturedVertex]) {
+        self.len = vertices.len();
+        if vertices.is_empty() {
+            return;
+        }
+        if vertices.len() > self.capacity {
+            let new_capacity = (self.capacity * 2).max(vertices.len());
+            self.buffer = Some(device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some(self.label),
+                size: (new_capacity * std::mem::size_of::<TexturedVertex>()) as u64,
+                usage: BufferUsages::VERTEX | BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            }));
+            self.capacity = new_capacity;
+        }
+        if let Some(ref buffer) = self.buffer {
+            queue.write_buffer(buffer, 0, bytemuck::cast_slice(vertices));
+        }
+    }
+
+    /// The buffer holding this frame's vertices, if any were written.
+    pub fn buffer(&self) -> Option<&Buffer> {
+        self.buffer.as_ref().filter(|_| self.len > 0)
+    }
+}
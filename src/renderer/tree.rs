@@ -9,7 +9,7 @@ use crate::transform_origin::TransformOrigin;
 use crate::widgets::Rect;
 
 use super::commands::DrawCommand;
-use super::flatten::FlattenedCommand;
+use super::flatten::{FlattenedCommand, WorldClip};
 
 /// Clip region for a render node (in local coordinates).
 ///
@@ -32,13 +32,16 @@ pub type NodeId = u64;
 ///
 /// Stored on each RenderNode after flattening, enabling incremental
 /// flatten: clean subtrees reuse their cached commands with a
-/// translation offset instead of re-flattening.
+/// translation offset instead of re-flattening. Subtrees under an
+/// ancestor clip are reused only in place, under the same clip.
 #[derive(Debug, Clone)]
 pub struct CachedFlatten {
     /// The flattened commands produced by this subtree.
     pub commands: Vec<FlattenedCommand>,
     /// The world transform at the time of caching.
     pub world_transform: Transform,
+    /// The clip inherited from ancestors at the time of caching.
+    pub parent_clip: Option<WorldClip>,
 }
 
 /// A node in the render tree representing a widget's visual output.
@@ -90,6 +93,9 @@ pub struct RenderNode {
     /// without affecting child content.
     pub overlay_clip: Option<ClipRegion>,
 
+    /// Whether this node's shapes are snapped to the physical pixel grid.
+    pub pixel_snap: bool,
+
     /// Whether this node was freshly painted (true) or reused from cache (false).
     /// The flattener uses this to decide whether to reuse cached flatten output.
     pub repainted: bool,
@@ -119,6 +125,7 @@ impl RenderNode {
             overlay_commands: SmallVec::new(),
             clip: None,
             overlay_clip: None,
+            pixel_snap: false,
             repainted: true,
             partial: false,
             cached_flatten: None,
@@ -143,6 +150,7 @@ impl RenderNode {
         self.overlay_commands.clear();
         self.clip = None;
         self.overlay_clip = None;
+        self.pixel_snap = false;
         self.repainted = true;
         self.partial = false;
         self.cached_flatten = None;
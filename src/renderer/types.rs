@@ -1,7 +1,7 @@
 //! Shared types for the renderer.
 
 use crate::transform::Transform;
-use crate::widgets::font::{FontFamily, FontWeight};
+use crate::widgets::font::{FontFamily, FontStyle, FontWeight, WrapMode};
 use crate::widgets::image::{ContentFit, ImageSource};
 use crate::widgets::{Color, Rect};
 
@@ -82,6 +82,14 @@ pub struct TextEntry {
     pub font_family: FontFamily,
     /// The font weight
     pub font_weight: FontWeight,
+    /// The font style (upright or slanted)
+    pub font_style: FontStyle,
+    /// Tab stop width in spaces
+    pub tab_width: u16,
+    /// How lines break at the rect width
+    pub wrap: WrapMode,
+    /// Gradient filling the glyphs across `rect` instead of `color`
+    pub gradient: Option<Gradient>,
     /// Optional clip rectangle to constrain text rendering
     pub clip_rect: Option<Rect>,
     /// Transform to apply to this text
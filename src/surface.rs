@@ -34,12 +34,27 @@
 //! // Later, to close the surface:
 //! handle.close();
 //! ```
+//!
+//! # Responsive Layouts
+//!
+//! ```ignore
+//! // Inside a surface's widget factory:
+//! let narrow = breakpoint().is_narrow(600);
+//! text(move || if narrow.get() { "⚙" } else { "⚙ Settings" }.to_string())
+//! ```
 
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::rc::Rc;
 use std::sync::atomic::{AtomicU64, Ordering};
 
 use crate::platform::{Anchor, KeyboardInteractivity, Layer};
-use crate::widgets::{Color, Widget};
+use crate::reactive::{
+    IntoSignal, Memo, OwnerId, RwSignal, Signal, create_derived, create_effect, create_memo,
+    create_signal, dispose_owner, on_cleanup, with_owner, without_owner,
+};
+use crate::widgets::{Color, Padding, Widget};
 
 /// Unique identifier for each surface in the application.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -90,6 +105,10 @@ pub struct SurfaceConfig {
     pub background_color: Color,
     /// Exclusive zone (reserves screen space). None means use height.
     pub exclusive_zone: Option<i32>,
+    /// Size the surface to its content, up to this maximum (width, height).
+    pub size_to_content: Option<(u32, u32)>,
+    /// Keep the exclusive zone equal to the root widget's laid-out size.
+    pub auto_exclusive_zone: bool,
 }
 
 impl Default for SurfaceConfig {
@@ -103,6 +122,8 @@ impl Default for SurfaceConfig {
             namespace: "guido-surface".to_string(),
             background_color: Color::rgb(0.1, 0.1, 0.15),
             exclusive_zone: None,
+            size_to_content: None,
+            auto_exclusive_zone: false,
         }
     }
 }
@@ -156,6 +177,24 @@ impl SurfaceConfig {
         self
     }
 
+    /// Keep the exclusive zone in sync with the content size.
+    ///
+    /// After every layout the zone is set to the root widget's laid-out
+    /// height (or width, for a bar anchored to the left or right edge), so a
+    /// bar that grows or shrinks always reserves exactly the space it covers.
+    /// Overrides [`exclusive_zone`](Self::exclusive_zone) once laid out.
+    ///
+    /// ```ignore
+    /// SurfaceConfig::new()
+    ///     .height(64)
+    ///     .anchor(Anchor::TOP | Anchor::LEFT | Anchor::RIGHT)
+    ///     .auto_exclusive_zone(true)
+    /// ```
+    pub fn auto_exclusive_zone(mut self, enabled: bool) -> Self {
+        self.auto_exclusive_zone = enabled;
+        self
+    }
+
     /// Set the keyboard interactivity mode.
     ///
     /// - `KeyboardInteractivity::None`: Surface never receives keyboard focus.
@@ -165,6 +204,27 @@ impl SurfaceConfig {
         self.keyboard_interactivity = mode;
         self
     }
+
+    /// Size the surface to fit its content, up to the given maximum.
+    ///
+    /// The root widget is laid out with at most `max_width` x `max_height`
+    /// and the surface requests the measured size from the compositor. The
+    /// first frame is only presented once the surface has its content size,
+    /// and the surface follows the content as it grows or shrinks.
+    ///
+    /// The `width`/`height` set on this config are only used as the initial
+    /// request before the first layout.
+    ///
+    /// ```ignore
+    /// // A menu popup exactly as tall as its items, up to 400px
+    /// SurfaceConfig::new()
+    ///     .size_to_content(240, 400)
+    ///     .layer(Layer::Overlay)
+    /// ```
+    pub fn size_to_content(mut self, max_width: u32, max_height: u32) -> Self {
+        self.size_to_content = Some((max_width, max_height));
+        self
+    }
 }
 
 /// Handle to a spawned surface for controlling it from widget code.
@@ -233,10 +293,21 @@ impl SurfaceHandle {
     /// The exclusive zone reserves screen space so other windows don't
     /// overlap. Pass 0 for no exclusive zone, or a positive value for
     /// the number of pixels to reserve.
+    ///
+    /// Turns off [`set_auto_exclusive_zone`](Self::set_auto_exclusive_zone).
     pub fn set_exclusive_zone(&self, zone: i32) {
         push_surface_command(SurfaceCommand::SetExclusiveZone { id: self.id, zone });
     }
 
+    /// Make the exclusive zone follow the content size (see
+    /// [`SurfaceConfig::auto_exclusive_zone`]).
+    pub fn set_auto_exclusive_zone(&self, enabled: bool) {
+        push_surface_command(SurfaceCommand::SetAutoExclusiveZone {
+            id: self.id,
+            enabled,
+        });
+    }
+
     /// Set the margin for this surface.
     ///
     /// Margins add space between the surface and the screen edge it's
@@ -250,6 +321,181 @@ impl SurfaceHandle {
             left,
         });
     }
+
+    /// Set the namespace of this surface, following `namespace` when it is a
+    /// signal.
+    ///
+    /// Compositors match their per-surface rules (blur, animations) on the
+    /// namespace. The layer shell protocol fixes it when the layer surface is
+    /// created, so each change recreates the layer surface. The widgets and
+    /// their state are kept. The binding lasts as long as the current owner.
+    ///
+    /// ```ignore
+    /// bar.set_namespace(move || format!("bar-{}", mode.get()));
+    /// ```
+    pub fn set_namespace<M>(&self, namespace: impl IntoSignal<String, M>) {
+        let id = self.id;
+        let namespace = namespace.into_signal();
+        create_effect(move || {
+            push_surface_command(SurfaceCommand::SetNamespace {
+                id,
+                namespace: namespace.get(),
+            });
+        })
+        .detach();
+    }
+
+    /// Pause or resume rendering of this surface.
+    ///
+    /// While disabled the surface keeps its last frame, and its widgets'
+    /// animations stop advancing, so a hidden popup costs no CPU. Signal
+    /// changes still mark widgets dirty and are drawn once rendering resumes.
+    /// Surfaces the compositor stops showing are paused the same way
+    /// automatically.
+    pub fn set_rendering_enabled(&self, enabled: bool) {
+        push_surface_command(SurfaceCommand::SetRenderingEnabled {
+            id: self.id,
+            enabled,
+        });
+    }
+
+    /// Repaint the whole surface on the next frame.
+    ///
+    /// Bypasses fine-grained dirty tracking: every widget is repainted even if
+    /// none of its signals changed. Use after changing state the UI reads
+    /// without signals (e.g. a theme swap). See [`request_full_repaint`] for
+    /// all surfaces.
+    pub fn request_full_repaint(&self) {
+        push_surface_command(SurfaceCommand::FullRepaint(Some(self.id)));
+    }
+
+    /// Save the next frame of this surface as a PNG at `path`.
+    ///
+    /// The surface is repainted and the frame is read back after drawing,
+    /// so the image matches what is on screen, including transparency. The
+    /// frame is still presented as usual. Failures are logged.
+    ///
+    /// ```ignore
+    /// button("Screenshot").on_click(move || bar.capture_png("bar.png"))
+    /// ```
+    pub fn capture_png(&self, path: impl Into<PathBuf>) {
+        push_surface_command(SurfaceCommand::CapturePng {
+            id: self.id,
+            path: path.into(),
+        });
+    }
+
+    /// Reactive size of this surface, for responsive layouts.
+    pub fn breakpoint(&self) -> Breakpoint {
+        Breakpoint {
+            size: surface_size_signal(self.id).read_only(),
+        }
+    }
+
+    /// Memo that is `true` while this surface has keyboard focus.
+    ///
+    /// ```ignore
+    /// let active = handle.is_active();
+    /// container().background(move || if active.get() { ACCENT } else { MUTED })
+    /// ```
+    pub fn is_active(&self) -> Memo<bool> {
+        let id = self.id;
+        let active = active_surface_signal();
+        create_memo(move || active.get() == Some(id))
+    }
+
+    /// Reactive number of device pixels per logical pixel on this surface.
+    ///
+    /// Combines the display's HiDPI scale (which can be fractional) with the
+    /// app's [`ui_scale`](crate::ui_scale).
+    pub fn scale_factor(&self) -> Signal<f32> {
+        surface_scale_signal(self.id).read_only()
+    }
+
+    /// Reactive width of one device pixel on this surface, in logical pixels.
+    ///
+    /// See [`hairline()`].
+    pub fn hairline(&self) -> Memo<f32> {
+        let scale = surface_scale_signal(self.id);
+        create_memo(move || 1.0 / scale.get())
+    }
+
+    /// Reactive insets of the areas of this surface covered by exclusive zones.
+    ///
+    /// See [`safe_area()`].
+    pub fn safe_area(&self) -> Signal<Padding> {
+        surface_insets_signal(self.id).read_only()
+    }
+}
+
+/// Reactive surface size with helpers for responsive layouts.
+///
+/// The size is in logical pixels and updates whenever the compositor resizes
+/// the surface. Get one with [`breakpoint()`] while building a surface's
+/// widgets, or with [`SurfaceHandle::breakpoint`] for a specific surface.
+///
+/// # Example
+///
+/// ```ignore
+/// let bp = breakpoint();
+/// let narrow = bp.is_narrow(600);
+///
+/// container().child(text(move || {
+///     if narrow.get() { "⚙".to_string() } else { "⚙ Settings".to_string() }
+/// }))
+/// ```
+#[derive(Clone, Copy)]
+pub struct Breakpoint {
+    size: Signal<(u32, u32)>,
+}
+
+impl Breakpoint {
+    /// Current `(width, height)` (tracked).
+    pub fn size(&self) -> (u32, u32) {
+        self.size.get()
+    }
+
+    /// Current width (tracked).
+    pub fn width(&self) -> u32 {
+        self.size.get().0
+    }
+
+    /// Current height (tracked).
+    pub fn height(&self) -> u32 {
+        self.size.get().1
+    }
+
+    /// The `(width, height)` signal.
+    pub fn size_signal(&self) -> Signal<(u32, u32)> {
+        self.size
+    }
+
+    /// A signal of the surface width.
+    pub fn width_signal(&self) -> Signal<u32> {
+        let size = self.size;
+        create_derived(move || size.get().0)
+    }
+
+    /// A signal of the surface height.
+    pub fn height_signal(&self) -> Signal<u32> {
+        let size = self.size;
+        create_derived(move || size.get().1)
+    }
+
+    /// Memo that is `true` while the surface is narrower than `max_width`.
+    ///
+    /// Only changes when the threshold is crossed, so dependents don't
+    /// re-run on every resize.
+    pub fn is_narrow(&self, max_width: u32) -> Memo<bool> {
+        let size = self.size;
+        create_memo(move || size.get().0 < max_width)
+    }
+
+    /// Memo that is `true` while the surface is at least `min_width` wide.
+    pub fn is_wide(&self, min_width: u32) -> Memo<bool> {
+        let size = self.size;
+        create_memo(move || size.get().0 >= min_width)
+    }
 }
 
 /// Commands for dynamic surface creation/destruction and property modification.
@@ -280,6 +526,8 @@ pub(crate) enum SurfaceCommand {
     },
     /// Set the exclusive zone for a surface.
     SetExclusiveZone { id: SurfaceId, zone: i32 },
+    /// Make a surface's exclusive zone follow its content size.
+    SetAutoExclusiveZone { id: SurfaceId, enabled: bool },
     /// Set the margin for a surface.
     SetMargin {
         id: SurfaceId,
@@ -288,6 +536,16 @@ pub(crate) enum SurfaceCommand {
         bottom: i32,
         left: i32,
     },
+    /// Set the namespace of a surface, recreating its layer surface.
+    SetNamespace { id: SurfaceId, namespace: String },
+    /// Enable or disable rendering of a surface.
+    SetRenderingEnabled { id: SurfaceId, enabled: bool },
+    /// Repaint every widget of a surface (`None` = all surfaces).
+    FullRepaint(Option<SurfaceId>),
+    /// Re-run layout and paint for every widget on all surfaces.
+    FullRelayout,
+    /// Save the next frame of a surface as a PNG.
+    CapturePng { id: SurfaceId, path: PathBuf },
 }
 
 // Thread-local storage for the surface command queue.
@@ -304,11 +562,364 @@ fn push_surface_command(cmd: SurfaceCommand) {
     crate::jobs::request_frame();
 }
 
-/// Reset the surface command queue.
+/// Reset the surface command queue and size signals.
 ///
-/// Called during `App::drop()` to clear stale surface commands.
+/// Called during `App::drop()` to clear stale surface state.
 pub(crate) fn reset_surface_commands() {
     SURFACE_COMMANDS.with(|cmds| cmds.borrow_mut().clear());
+    SURFACE_SIZES.with(|sizes| sizes.borrow_mut().clear());
+    SURFACE_SCALES.with(|scales| scales.borrow_mut().clear());
+    SURFACE_INSETS.with(|insets| insets.borrow_mut().clear());
+    CURRENT_SURFACE.with(|current| current.set(None));
+    ACTIVE_SURFACE.with(|active| active.borrow_mut().take());
+    POINTER_POSITION.with(|position| position.borrow_mut().take());
+    ESCAPE_HANDLERS.with(|handlers| handlers.borrow_mut().clear());
+}
+
+/// A per-surface signal and the owner disposing it when the surface closes
+type SurfaceSignal<T> = (RwSignal<T>, OwnerId);
+
+thread_local! {
+    static SURFACE_SIZES: RefCell<HashMap<SurfaceId, SurfaceSignal<(u32, u32)>>> =
+        RefCell::new(HashMap::new());
+    static SURFACE_SCALES: RefCell<HashMap<SurfaceId, SurfaceSignal<f32>>> =
+        RefCell::new(HashMap::new());
+    static SURFACE_INSETS: RefCell<HashMap<SurfaceId, SurfaceSignal<Padding>>> =
+        RefCell::new(HashMap::new());
+    static CURRENT_SURFACE: Cell<Option<SurfaceId>> = const { Cell::new(None) };
+    static ACTIVE_SURFACE: RefCell<Option<RwSignal<Option<SurfaceId>>>> =
+        const { RefCell::new(None) };
+    static POINTER_POSITION: RefCell<Option<RwSignal<(f32, f32)>>> =
+        const { RefCell::new(None) };
+}
+
+/// Create a per-surface signal under an owner of its own.
+///
+/// The signal lives as long as the surface rather than the scope that first
+/// asked for it, and is disposed with its owner when the surface closes.
+fn create_surface_signal<T: Clone + PartialEq + Send + 'static>(
+    value: T,
+) -> (RwSignal<T>, OwnerId) {
+    without_owner(|| with_owner(|| create_signal(value)))
+}
+
+/// Get (or create) the size signal for a surface.
+fn surface_size_signal(id: SurfaceId) -> RwSignal<(u32, u32)> {
+    SURFACE_SIZES.with(|sizes| {
+        sizes
+            .borrow_mut()
+            .entry(id)
+            .or_insert_with(|| create_surface_signal((0, 0)))
+            .0
+    })
+}
+
+/// Record the current logical size of a surface, notifying breakpoints if it changed.
+///
+/// The size is converted to UI-scaled units, matching the widgets' layout.
+pub(crate) fn set_surface_size(id: SurfaceId, width: u32, height: u32) {
+    let ui_scale = crate::ui_scale();
+    let (width, height) = if ui_scale == 1.0 {
+        (width, height)
+    } else {
+        (
+            (width as f32 / ui_scale).round() as u32,
+            (height as f32 / ui_scale).round() as u32,
+        )
+    };
+    let size = surface_size_signal(id);
+    if size.get_untracked() != (width, height) {
+        size.set((width, height));
+    }
+}
+
+/// Get (or create) the scale signal for a surface.
+fn surface_scale_signal(id: SurfaceId) -> RwSignal<f32> {
+    SURFACE_SCALES.with(|scales| {
+        scales
+            .borrow_mut()
+            .entry(id)
+            .or_insert_with(|| create_surface_signal(1.0))
+            .0
+    })
+}
+
+/// Record the device pixels per logical pixel of a surface (HiDPI scale
+/// times UI scale), notifying hairlines if it changed.
+pub(crate) fn set_surface_scale(id: SurfaceId, scale: f32) {
+    let signal = surface_scale_signal(id);
+    if signal.get_untracked() != scale {
+        signal.set(scale);
+    }
+}
+
+/// Get (or create) the safe-area insets signal for a surface.
+fn surface_insets_signal(id: SurfaceId) -> RwSignal<Padding> {
+    SURFACE_INSETS.with(|insets| {
+        insets
+            .borrow_mut()
+            .entry(id)
+            .or_insert_with(|| create_surface_signal(Padding::all(0.0)))
+            .0
+    })
+}
+
+/// Record the safe-area insets of a surface, in UI-scaled units.
+pub(crate) fn set_surface_insets(id: SurfaceId, insets: Padding) {
+    let signal = surface_insets_signal(id);
+    if signal.get_untracked() != insets {
+        signal.set(insets);
+    }
+}
+
+/// Get (or create) the signal holding the keyboard-focused surface.
+fn active_surface_signal() -> RwSignal<Option<SurfaceId>> {
+    ACTIVE_SURFACE.with(|active| {
+        *active
+            .borrow_mut()
+            .get_or_insert_with(|| create_signal(None))
+    })
+}
+
+/// The surface that has keyboard focus, or `None` when no surface of the
+/// app does.
+///
+/// Updates when the compositor moves keyboard focus between surfaces, so
+/// global shortcuts can be routed to the active surface and widgets can
+/// style themselves (see [`SurfaceHandle::is_active`]).
+///
+/// ```ignore
+/// let active = active_surface();
+/// create_effect(move || log::info!("focused surface: {:?}", active.get()));
+/// ```
+pub fn active_surface() -> Signal<Option<SurfaceId>> {
+    active_surface_signal().read_only()
+}
+
+/// Record that `id` gained or lost keyboard focus.
+///
+/// Losing focus only clears the active surface if it is still `id`, so a
+/// focus move is correct whichever surface's events are handled first.
+pub(crate) fn set_surface_active(id: SurfaceId, active: bool) {
+    let signal = active_surface_signal();
+    let current = signal.get_untracked();
+    if active && current != Some(id) {
+        signal.set(Some(id));
+    } else if !active && current == Some(id) {
+        signal.set(None);
+    }
+}
+
+/// Get (or create) the signal holding the last pointer position.
+fn pointer_position_signal() -> RwSignal<(f32, f32)> {
+    POINTER_POSITION.with(|position| {
+        *position
+            .borrow_mut()
+            .get_or_insert_with(|| create_signal((0.0, 0.0)))
+    })
+}
+
+/// The last known pointer position, in logical pixels relative to the
+/// surface the pointer is over.
+///
+/// Updated at most once per frame from the pointer motion of that frame, and
+/// kept when the pointer stops or leaves the surface. Starts at `(0.0, 0.0)`
+/// before the first move.
+///
+/// ```ignore
+/// let pointer = pointer_position();
+/// container()
+///     .width(120)
+///     .height(120)
+///     .corner_radius(60)
+///     .background(Color::rgba(1.0, 1.0, 1.0, 0.1))
+///     .translate(move || pointer.get().0 - 60.0, move || pointer.get().1 - 60.0)
+/// ```
+pub fn pointer_position() -> Signal<(f32, f32)> {
+    pointer_position_signal().read_only()
+}
+
+/// Record the pointer position reached during a frame.
+pub(crate) fn set_pointer_position(x: f32, y: f32) {
+    let signal = pointer_position_signal();
+    if signal.get_untracked() != (x, y) {
+        signal.set((x, y));
+    }
+}
+
+/// Dispose the size, scale and inset signals of a closed surface.
+pub(crate) fn remove_surface_size(id: SurfaceId) {
+    let owners = [
+        SURFACE_SIZES.with(|sizes| sizes.borrow_mut().remove(&id).map(|(_, owner)| owner)),
+        SURFACE_SCALES.with(|scales| scales.borrow_mut().remove(&id).map(|(_, owner)| owner)),
+        SURFACE_INSETS.with(|insets| insets.borrow_mut().remove(&id).map(|(_, owner)| owner)),
+    ];
+    for owner in owners.into_iter().flatten() {
+        dispose_owner(owner);
+    }
+}
+
+/// Run `f` with `id` as the surface that [`breakpoint()`] refers to.
+pub(crate) fn with_current_surface<T>(id: SurfaceId, f: impl FnOnce() -> T) -> T {
+    let prev = CURRENT_SURFACE.with(|current| current.replace(Some(id)));
+    let result = f();
+    CURRENT_SURFACE.with(|current| current.set(prev));
+    result
+}
+
+/// Reactive size of the surface whose widgets are being built or updated.
+///
+/// Call it inside a surface's widget factory (or event handlers and dynamic
+/// children of that surface). Use [`SurfaceHandle::breakpoint`] to target a
+/// specific surface from elsewhere.
+///
+/// # Panics
+///
+/// Panics when called outside of any surface.
+pub fn breakpoint() -> Breakpoint {
+    let id = CURRENT_SURFACE
+        .with(|current| current.get())
+        .expect("breakpoint() called outside of a surface; use SurfaceHandle::breakpoint()");
+    surface_handle(id).breakpoint()
+}
+
+/// Width of one device pixel, in logical pixels, on the surface whose widgets
+/// are being built or updated.
+///
+/// Use it for borders and dividers that should be exactly one physical pixel
+/// thick at any scale (1 at 1x, 0.5 at 2x, 0.8 at 1.25x), together with
+/// [`Container::pixel_snap`](crate::widgets::Container::pixel_snap) so the
+/// line also starts on a pixel boundary.
+///
+/// ```ignore
+/// container()
+///     .border(hairline(), Color::rgba(1.0, 1.0, 1.0, 0.2))
+///     .pixel_snap()
+/// ```
+///
+/// # Panics
+///
+/// Panics when called outside of any surface, like [`breakpoint()`].
+pub fn hairline() -> Memo<f32> {
+    let id = CURRENT_SURFACE
+        .with(|current| current.get())
+        .expect("hairline() called outside of a surface; use SurfaceHandle::hairline()");
+    surface_handle(id).hairline()
+}
+
+/// Insets of the areas of the current surface that other bars reserve with
+/// their exclusive zones.
+///
+/// Surfaces that respect exclusive zones (a zone of 0 or more) are already
+/// placed by the compositor outside of them, so their insets stay at zero. A
+/// surface that ignores them (`exclusive_zone(Some(-1))`), like a fullscreen
+/// overlay, covers the reserved areas and can pad its content with the
+/// insets instead:
+///
+/// ```ignore
+/// let safe_area = safe_area();
+/// container()
+///     .width(fill())
+///     .height(fill())
+///     .padding(move || safe_area.get())
+///     .child(launcher_grid())
+/// ```
+///
+/// Only the exclusive zones of this app's own surfaces are known; the layer
+/// shell protocol doesn't report those of other clients. An edge counts when
+/// the current surface is anchored to it.
+///
+/// # Panics
+///
+/// Panics when called outside of any surface, like [`breakpoint()`].
+pub fn safe_area() -> Signal<Padding> {
+    let id = CURRENT_SURFACE
+        .with(|current| current.get())
+        .expect("safe_area() called outside of a surface; use SurfaceHandle::safe_area()");
+    surface_handle(id).safe_area()
+}
+
+/// An Escape handler and the surface it belongs to (`None` for any surface).
+struct EscapeHandler {
+    key: u64,
+    surface: Option<SurfaceId>,
+    callback: Rc<dyn Fn()>,
+}
+
+thread_local! {
+    static ESCAPE_HANDLERS: RefCell<Vec<EscapeHandler>> = const { RefCell::new(Vec::new()) };
+    static NEXT_ESCAPE_KEY: Cell<u64> = const { Cell::new(0) };
+}
+
+/// Call `f` when Escape is pressed on the current surface and no widget
+/// handles it (a focused text input, for example, keeps its own Escape).
+///
+/// Only the most recently registered handler of a surface fires, so nested
+/// overlays close one at a time. The handler is removed when the current
+/// owner is cleaned up, e.g. when the overlay's dynamic child is removed.
+/// Outside of a surface it applies to every surface.
+///
+/// ```ignore
+/// let search_open = create_signal(false);
+///
+/// container().child(move || {
+///     search_open.get().then(|| {
+///         on_escape(move || search_open.set(false));
+///         search_overlay()
+///     })
+/// })
+/// ```
+pub fn on_escape(f: impl Fn() + 'static) {
+    let key = NEXT_ESCAPE_KEY.with(|next| next.replace(next.get() + 1));
+    let surface = CURRENT_SURFACE.with(|current| current.get());
+    ESCAPE_HANDLERS.with(|handlers| {
+        handlers.borrow_mut().push(EscapeHandler {
+            key,
+            surface,
+            callback: Rc::new(f),
+        })
+    });
+    on_cleanup(move || {
+        ESCAPE_HANDLERS.with(|handlers| handlers.borrow_mut().retain(|h| h.key != key));
+    });
+}
+
+/// Run the latest Escape handler for surface `id`, if any.
+pub(crate) fn dispatch_escape(id: SurfaceId) {
+    let callback = ESCAPE_HANDLERS.with(|handlers| {
+        handlers
+            .borrow()
+            .iter()
+            .rev()
+            .find(|h| h.surface.is_none_or(|s| s == id))
+            .map(|h| h.callback.clone())
+    });
+    if let Some(callback) = callback {
+        callback();
+    }
+}
+
+/// Repaint every surface on the next frame.
+///
+/// Every widget is repainted even if none of its signals changed, which is
+/// useful after swapping state that isn't tracked reactively, such as a
+/// theme reload. Use [`SurfaceHandle::request_full_repaint`] for a single
+/// surface.
+///
+/// # Example
+///
+/// ```ignore
+/// THEME.with(|t| *t.borrow_mut() = Theme::light());
+/// request_full_repaint();
+/// ```
+pub fn request_full_repaint() {
+    push_surface_command(SurfaceCommand::FullRepaint(None));
+}
+
+/// Re-layout and repaint every surface, e.g. after the available fonts or
+/// the default font family changed.
+pub(crate) fn request_full_relayout() {
+    push_surface_command(SurfaceCommand::FullRelayout);
 }
 
 /// Drain all pending surface commands. Called by the main event loop.
@@ -391,3 +1002,140 @@ where
 pub fn surface_handle(id: SurfaceId) -> SurfaceHandle {
     SurfaceHandle { id }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn namespaces() -> Vec<String> {
+        drain_surface_commands()
+            .into_iter()
+            .filter_map(|cmd| match cmd {
+                SurfaceCommand::SetNamespace { namespace, .. } => Some(namespace),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_set_namespace_follows_signal() {
+        let mode = create_signal("idle");
+        let handle = SurfaceHandle {
+            id: SurfaceId::next(),
+        };
+        handle.set_namespace(move || format!("bar-{}", mode.get()));
+        assert_eq!(namespaces(), ["bar-idle"]);
+
+        mode.set("busy");
+        assert_eq!(namespaces(), ["bar-busy"]);
+        reset_surface_commands();
+    }
+
+    #[test]
+    fn test_full_repaint_targets_one_or_all_surfaces() {
+        let handle = SurfaceHandle {
+            id: SurfaceId::next(),
+        };
+        handle.request_full_repaint();
+        request_full_repaint();
+
+        let targets: Vec<_> = drain_surface_commands()
+            .into_iter()
+            .filter_map(|cmd| match cmd {
+                SurfaceCommand::FullRepaint(target) => Some(target),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(targets, [Some(handle.id), None]);
+        reset_surface_commands();
+    }
+
+    #[test]
+    fn test_active_surface_follows_keyboard_focus() {
+        let (bar, popup) = (SurfaceId::next(), SurfaceId::next());
+        let active = active_surface();
+        // Memos live in a widget's owner
+        let (bar_active, owner) = with_owner(|| SurfaceHandle { id: bar }.is_active());
+        assert_eq!(active.get_untracked(), None);
+
+        set_surface_active(bar, true);
+        assert_eq!(active.get_untracked(), Some(bar));
+        assert!(bar_active.get());
+
+        // Focus moving to the popup, whichever event comes first
+        set_surface_active(popup, true);
+        set_surface_active(bar, false);
+        assert_eq!(active.get_untracked(), Some(popup));
+        assert!(!bar_active.get());
+
+        set_surface_active(popup, false);
+        assert_eq!(active.get_untracked(), None);
+        dispose_owner(owner);
+        reset_surface_commands();
+    }
+
+    #[test]
+    fn test_pointer_position_keeps_the_last_move() {
+        let pointer = pointer_position();
+        assert_eq!(pointer.get_untracked(), (0.0, 0.0));
+        let updates = Rc::new(Cell::new(0));
+        let count = updates.clone();
+        let (_effect, owner) = with_owner(|| {
+            create_effect(move || {
+                pointer.get();
+                count.set(count.get() + 1);
+            })
+        });
+
+        set_pointer_position(12.0, 30.5);
+        assert_eq!(pointer.get_untracked(), (12.0, 30.5));
+        // Frames without motion report the same spot without notifying
+        set_pointer_position(12.0, 30.5);
+        assert_eq!(updates.get(), 2);
+        dispose_owner(owner);
+        reset_surface_commands();
+    }
+
+    #[test]
+    fn test_size_signal_lives_with_the_surface() {
+        use crate::reactive::storage::has_signal;
+
+        let handle = SurfaceHandle {
+            id: SurfaceId::next(),
+        };
+        // First asked for from a component scope that goes away
+        let (breakpoint, scope) = with_owner(|| handle.breakpoint());
+        dispose_owner(scope);
+        set_surface_size(handle.id, 300, 40);
+        assert_eq!(breakpoint.size_signal().get_untracked(), (300, 40));
+
+        // Closing the surface disposes it
+        let signal = surface_size_signal(handle.id);
+        remove_surface_size(handle.id);
+        assert!(!has_signal(signal.id()));
+        reset_surface_commands();
+    }
+
+    #[test]
+    fn test_latest_escape_handler_of_the_surface_fires() {
+        let closed = Rc::new(RefCell::new(Vec::new()));
+        let (bar, popup) = (SurfaceId::next(), SurfaceId::next());
+        let handler = |name: &'static str| {
+            let closed = closed.clone();
+            move || closed.borrow_mut().push(name)
+        };
+
+        with_current_surface(bar, || on_escape(handler("menu")));
+        let ((), submenu) =
+            with_owner(|| with_current_surface(bar, || on_escape(handler("submenu"))));
+        with_current_surface(popup, || on_escape(handler("popup")));
+
+        // Nested overlays close one at a time, innermost first
+        dispatch_escape(bar);
+        dispose_owner(submenu);
+        dispatch_escape(bar);
+        dispatch_escape(popup);
+        assert_eq!(*closed.borrow(), ["submenu", "menu", "popup"]);
+        reset_surface_commands();
+    }
+}
@@ -4,16 +4,18 @@
 //! including GPU initialization and widget layout.
 
 use std::collections::HashMap;
+use std::path::PathBuf;
 
 use smithay_client_toolkit::reexports::client::Connection;
 
+use crate::jobs;
 use crate::layout::Constraints;
-use crate::platform::{WaylandState, WaylandWindowWrapper};
+use crate::platform::{Anchor, WaylandState, WaylandWindowWrapper};
 use crate::reactive::owner::{OwnerId, dispose_owner};
 use crate::renderer::{FlattenedCommand, GpuContext, RenderNode, RenderTree, SurfaceState};
 use crate::surface::{SurfaceConfig, SurfaceId};
 use crate::tree::{Tree, WidgetId};
-use crate::widgets::Widget;
+use crate::widgets::{Padding, Widget};
 
 /// A surface with unified GPU lifecycle management.
 ///
@@ -38,6 +40,90 @@ pub struct ManagedSurface {
     pub root_node: RenderNode,
     /// Flattened commands buffer (reused across frames to avoid allocation)
     pub flattened_commands: Vec<FlattenedCommand>,
+    /// Last size requested from the compositor for content-sized surfaces
+    requested_content_size: Option<(u32, u32)>,
+    /// Last exclusive zone set for surfaces with an automatic zone
+    pub(crate) requested_exclusive_zone: Option<i32>,
+    /// Rendering switch set through `SurfaceHandle::set_rendering_enabled`
+    pub rendering_enabled: bool,
+    /// Where to save the next rendered frame, set through `SurfaceHandle::capture_png`
+    pub pending_capture: Option<PathBuf>,
+    /// Whether rendering and animations are currently paused
+    suspended: bool,
+    /// Screen reader connection, created with the GPU surface
+    #[cfg(feature = "accesskit")]
+    access: Option<crate::platform::atspi::AccessAdapter>,
+}
+
+/// Whether the exclusive zone of a surface with `anchor` is measured along
+/// its width: a vertical bar attached to exactly one of the left and right
+/// edges. Other surfaces reserve space along their height.
+fn reserves_width(anchor: Anchor) -> bool {
+    let horizontal = anchor.contains(Anchor::LEFT) != anchor.contains(Anchor::RIGHT);
+    let vertical = anchor.contains(Anchor::TOP) != anchor.contains(Anchor::BOTTOM);
+    horizontal && !vertical
+}
+
+/// The screen edge a surface with `anchor` reserves its exclusive zone along:
+/// the single anchored edge, or the edge a bar spanning the perpendicular axis
+/// is attached to. Surfaces anchored to a corner, to opposite edges only, or
+/// to nothing reserve no edge.
+fn exclusive_edge(anchor: Anchor) -> Option<Anchor> {
+    let spans_width = anchor.contains(Anchor::LEFT | Anchor::RIGHT);
+    let spans_height = anchor.contains(Anchor::TOP | Anchor::BOTTOM);
+    [Anchor::TOP, Anchor::BOTTOM, Anchor::LEFT, Anchor::RIGHT]
+        .into_iter()
+        .find(|&edge| {
+            let opposite = match edge {
+                Anchor::TOP => Anchor::BOTTOM,
+                Anchor::BOTTOM => Anchor::TOP,
+                Anchor::LEFT => Anchor::RIGHT,
+                _ => Anchor::LEFT,
+            };
+            let horizontal_edge = edge == Anchor::TOP || edge == Anchor::BOTTOM;
+            let spans_perpendicular = if horizontal_edge {
+                spans_width
+            } else {
+                spans_height
+            };
+            let others = anchor - edge - opposite;
+            anchor.contains(edge)
+                && !anchor.contains(opposite)
+                && (others.is_empty() || spans_perpendicular)
+        })
+}
+
+/// Insets (in logical pixels) of a surface with `anchor` and `zone` covered
+/// by the exclusive zones of other surfaces, given as `(anchor, zone)` pairs.
+///
+/// Surfaces that respect exclusive zones (`zone >= 0`) are placed outside of
+/// them by the compositor and get no insets. Edges the surface isn't
+/// anchored to are skipped, since its distance to them is unknown.
+fn reserved_insets(
+    anchor: Anchor,
+    zone: i32,
+    others: impl IntoIterator<Item = (Anchor, i32)>,
+) -> Padding {
+    let mut insets = Padding::all(0.0);
+    if zone >= 0 {
+        return insets;
+    }
+    for (other_anchor, other_zone) in others {
+        if other_zone <= 0 {
+            continue;
+        }
+        let Some(edge) = exclusive_edge(other_anchor).filter(|&e| anchor.contains(e)) else {
+            continue;
+        };
+        let side = match edge {
+            Anchor::TOP => &mut insets.top,
+            Anchor::BOTTOM => &mut insets.bottom,
+            Anchor::LEFT => &mut insets.left,
+            _ => &mut insets.right,
+        };
+        *side += other_zone as f32;
+    }
+    insets
 }
 
 impl ManagedSurface {
@@ -68,6 +154,13 @@ impl ManagedSurface {
             render_tree: RenderTree::new(),
             root_node: RenderNode::new(widget_id.as_u64()),
             flattened_commands: Vec::new(),
+            requested_content_size: None,
+            requested_exclusive_zone: None,
+            rendering_enabled: true,
+            pending_capture: None,
+            suspended: false,
+            #[cfg(feature = "accesskit")]
+            access: None,
         }
     }
 
@@ -106,6 +199,10 @@ impl ManagedSurface {
             gpu_context.create_surface(window_handle, physical_width, physical_height);
         self.wgpu_surface = Some(wgpu_surface);
         self.previous_scale_factor = scale_factor;
+        #[cfg(feature = "accesskit")]
+        {
+            self.access = Some(crate::platform::atspi::AccessAdapter::new());
+        }
 
         // Perform initial layout
         self.layout_widget(tree, width as f32, height as f32);
@@ -113,14 +210,103 @@ impl ManagedSurface {
         true
     }
 
+    /// Pause the surface when rendering is disabled or the compositor is not
+    /// showing it (`occluded`), and resume it otherwise. Returns whether the
+    /// surface is paused.
+    pub fn update_suspended(&mut self, occluded: bool, tree: &mut Tree) -> bool {
+        let suspended = !self.rendering_enabled || occluded;
+        if suspended != self.suspended {
+            self.suspended = suspended;
+            log::debug!(
+                "Surface {:?} rendering {}",
+                self.id,
+                if suspended { "paused" } else { "resumed" }
+            );
+            jobs::set_root_suspended(self.widget_id, suspended);
+            if !suspended {
+                // Frames were skipped; redraw everything once
+                tree.mark_subtree_needs_paint(self.widget_id);
+            }
+        }
+        suspended
+    }
+
     /// Check if GPU is initialized.
     pub fn is_gpu_ready(&self) -> bool {
         self.wgpu_surface.is_some()
     }
 
+    /// Root layout constraints for the given surface dimensions.
+    ///
+    /// Content-sized surfaces lay out against their configured maximum so the
+    /// root widget reports its natural size instead of the current surface size.
+    ///
+    /// Sizes are in surface units and are converted to the UI-scaled units
+    /// widgets are laid out in (see [`crate::set_ui_scale`]).
+    pub fn root_constraints(&self, width: f32, height: f32) -> Constraints {
+        let ui_scale = crate::ui_scale();
+        match self.config.size_to_content {
+            Some((max_width, max_height)) => Constraints::new(
+                0.0,
+                0.0,
+                max_width as f32 / ui_scale,
+                max_height as f32 / ui_scale,
+            ),
+            None => Constraints::new(0.0, 0.0, width / ui_scale, height / ui_scale),
+        }
+    }
+
+    /// For content-sized surfaces, return the measured content size if it
+    /// differs from the last size requested from the compositor.
+    pub fn take_content_size_change(&mut self, tree: &Tree) -> Option<(u32, u32)> {
+        self.config.size_to_content?;
+        let size = tree.cached_size(self.widget_id)?;
+        let ui_scale = crate::ui_scale();
+        let content_size = (
+            ((size.width * ui_scale).ceil() as u32).max(1),
+            ((size.height * ui_scale).ceil() as u32).max(1),
+        );
+        if self.requested_content_size == Some(content_size) {
+            return None;
+        }
+        self.requested_content_size = Some(content_size);
+        Some(content_size)
+    }
+
+    /// The exclusive zone currently requested from the compositor.
+    pub fn exclusive_zone(&self) -> i32 {
+        match self.requested_exclusive_zone {
+            Some(zone) if self.config.auto_exclusive_zone => zone,
+            _ => self
+                .config
+                .exclusive_zone
+                .unwrap_or(self.config.height as i32),
+        }
+    }
+
+    /// For surfaces with an automatic exclusive zone, return the zone matching
+    /// the root widget's laid-out size if it differs from the last one set.
+    pub fn take_exclusive_zone_change(&mut self, tree: &Tree) -> Option<i32> {
+        if !self.config.auto_exclusive_zone {
+            return None;
+        }
+        let size = tree.cached_size(self.widget_id)?;
+        let extent = if reserves_width(self.config.anchor) {
+            size.width
+        } else {
+            size.height
+        };
+        let zone = (extent * crate::ui_scale()).ceil() as i32;
+        if self.requested_exclusive_zone == Some(zone) {
+            return None;
+        }
+        self.requested_exclusive_zone = Some(zone);
+        Some(zone)
+    }
+
     /// Perform widget layout with the given dimensions.
     pub fn layout_widget(&self, tree: &mut Tree, width: f32, height: f32) {
-        let constraints = Constraints::new(0.0, 0.0, width, height);
+        let constraints = self.root_constraints(width, height);
 
         tree.with_widget_mut(self.widget_id, |widget, id, tree| {
             widget.layout(tree, id, constraints);
@@ -156,7 +342,11 @@ impl SurfaceManager {
 
     /// Remove a surface by ID.
     pub fn remove(&mut self, id: SurfaceId) -> Option<ManagedSurface> {
-        self.surfaces.remove(&id)
+        let surface = self.surfaces.remove(&id)?;
+        if surface.suspended {
+            jobs::set_root_suspended(surface.widget_id, false);
+        }
+        Some(surface)
     }
 
     /// Get a mutable surface by ID.
@@ -174,6 +364,58 @@ impl SurfaceManager {
         self.surfaces.is_empty()
     }
 
+    /// Update every surface's safe-area insets from the exclusive zones of
+    /// the other surfaces.
+    pub fn update_safe_areas(&self) {
+        let ui_scale = crate::ui_scale();
+        for (id, surface) in &self.surfaces {
+            let others = self
+                .surfaces
+                .iter()
+                .filter(|(other_id, _)| *other_id != id)
+                .map(|(_, other)| (other.config.anchor, other.exclusive_zone()));
+            let insets = reserved_insets(surface.config.anchor, surface.exclusive_zone(), others);
+            crate::surface::set_surface_insets(
+                *id,
+                Padding {
+                    top: insets.top / ui_scale,
+                    right: insets.right / ui_scale,
+                    bottom: insets.bottom / ui_scale,
+                    left: insets.left / ui_scale,
+                },
+            );
+        }
+    }
+
+    /// Perform the actions screen readers requested since the last frame
+    /// (clicking or focusing a widget).
+    pub fn perform_access_actions(&mut self, tree: &mut Tree) {
+        #[cfg(feature = "accesskit")]
+        for surface in self.surfaces.values_mut() {
+            if let Some(access) = surface.access.as_mut() {
+                access.perform_actions(tree, surface.widget_id);
+            }
+        }
+        #[cfg(not(feature = "accesskit"))]
+        let _ = tree;
+    }
+
+    /// Send each surface's accessibility tree and keyboard focus to
+    /// listening screen readers. Trees that didn't change are skipped.
+    pub fn publish_access_trees(&mut self, tree: &Tree) {
+        #[cfg(feature = "accesskit")]
+        {
+            let active = crate::surface::active_surface().get_untracked();
+            for (id, surface) in self.surfaces.iter_mut() {
+                if let Some(access) = surface.access.as_mut() {
+                    access.update(tree, surface.widget_id, active == Some(*id));
+                }
+            }
+        }
+        #[cfg(not(feature = "accesskit"))]
+        let _ = tree;
+    }
+
     /// Initialize GPU for surfaces that need it.
     ///
     /// This iterates over all surfaces and initializes GPU for any
@@ -218,3 +460,153 @@ impl Default for SurfaceManager {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reactive::{create_signal, with_owner};
+    use crate::widgets::container;
+
+    fn managed(
+        config: SurfaceConfig,
+        widget: impl Widget + 'static,
+        tree: &mut Tree,
+    ) -> ManagedSurface {
+        let ((), owner_id) = with_owner(|| {});
+        ManagedSurface::new(SurfaceId::next(), config, Box::new(widget), owner_id, tree)
+    }
+
+    #[test]
+    fn test_content_sized_surface_follows_its_content() {
+        let height = create_signal(30.0f32);
+        let mut tree = Tree::new();
+        let mut surface = managed(
+            SurfaceConfig::new().size_to_content(240, 400),
+            container().width(100.0).height(move || height.get()),
+            &mut tree,
+        );
+
+        // Laid out against the maximum, not the current surface size
+        surface.layout_widget(&mut tree, 800.0, 20.0);
+        assert_eq!(surface.take_content_size_change(&tree), Some((100, 30)));
+        // Only changes are reported
+        assert_eq!(surface.take_content_size_change(&tree), None);
+
+        // Growing past the maximum is capped
+        height.set(1000.0);
+        tree.mark_needs_layout(surface.widget_id);
+        surface.layout_widget(&mut tree, 100.0, 30.0);
+        assert_eq!(surface.take_content_size_change(&tree), Some((100, 400)));
+
+        // Fixed-size surfaces never resize to their content
+        let mut fixed = managed(SurfaceConfig::new(), container().width(100.0), &mut tree);
+        fixed.layout_widget(&mut tree, 800.0, 20.0);
+        assert_eq!(fixed.take_content_size_change(&tree), None);
+    }
+
+    #[test]
+    fn test_auto_exclusive_zone_follows_the_bar_size() {
+        let height = create_signal(30.0f32);
+        let mut tree = Tree::new();
+        // A content-sized bar, so it can grow
+        let mut bar = managed(
+            SurfaceConfig::new()
+                .anchor(Anchor::TOP | Anchor::LEFT | Anchor::RIGHT)
+                .size_to_content(800, 100)
+                .auto_exclusive_zone(true),
+            container().height(move || height.get()),
+            &mut tree,
+        );
+        bar.layout_widget(&mut tree, 800.0, 30.0);
+        assert_eq!(bar.take_exclusive_zone_change(&tree), Some(30));
+        assert_eq!(bar.take_exclusive_zone_change(&tree), None);
+
+        height.set(42.5);
+        tree.mark_needs_layout(bar.widget_id);
+        bar.layout_widget(&mut tree, 800.0, 30.0);
+        assert_eq!(bar.take_exclusive_zone_change(&tree), Some(43));
+
+        // Vertical bars reserve their width
+        let mut side = managed(
+            SurfaceConfig::new()
+                .anchor(Anchor::LEFT | Anchor::TOP | Anchor::BOTTOM)
+                .auto_exclusive_zone(true),
+            container().width(48.0),
+            &mut tree,
+        );
+        side.layout_widget(&mut tree, 48.0, 600.0);
+        assert_eq!(side.take_exclusive_zone_change(&tree), Some(48));
+
+        // Without the option the configured zone is left alone
+        let mut fixed = managed(SurfaceConfig::new(), container().height(30.0), &mut tree);
+        fixed.layout_widget(&mut tree, 800.0, 30.0);
+        assert_eq!(fixed.take_exclusive_zone_change(&tree), None);
+    }
+
+    #[test]
+    fn test_ui_scale_shrinks_layout_space_and_grows_content() {
+        use crate::layout::{Size, fill};
+
+        let mut tree = Tree::new();
+        let mut surface = managed(
+            SurfaceConfig::new().size_to_content(500, 500),
+            container().width(fill()).height(40.0),
+            &mut tree,
+        );
+        crate::set_ui_scale(1.25);
+        // Invalid factors keep the current one
+        crate::set_ui_scale(0.0);
+        assert_eq!(crate::ui_scale(), 1.25);
+
+        let constraints = surface.root_constraints(500.0, 250.0);
+        assert_eq!(
+            (constraints.max_width, constraints.max_height),
+            (400.0, 400.0)
+        );
+        // Widgets fill 400 units, shown 25% larger on the 500px surface
+        surface.layout_widget(&mut tree, 500.0, 250.0);
+        assert_eq!(
+            tree.cached_size(surface.widget_id),
+            Some(Size::new(400.0, 40.0))
+        );
+        assert_eq!(surface.take_content_size_change(&tree), Some((500, 50)));
+
+        crate::set_ui_scale(1.0);
+        crate::surface::reset_surface_commands();
+    }
+
+    #[test]
+    fn test_exclusive_edge() {
+        let bar = Anchor::TOP | Anchor::LEFT | Anchor::RIGHT;
+        assert_eq!(exclusive_edge(bar), Some(Anchor::TOP));
+        assert_eq!(exclusive_edge(Anchor::LEFT), Some(Anchor::LEFT));
+        assert_eq!(
+            exclusive_edge(Anchor::RIGHT | Anchor::TOP | Anchor::BOTTOM),
+            Some(Anchor::RIGHT)
+        );
+        assert_eq!(exclusive_edge(Anchor::TOP | Anchor::LEFT), None);
+        assert_eq!(exclusive_edge(Anchor::all()), None);
+        assert_eq!(exclusive_edge(Anchor::empty()), None);
+    }
+
+    #[test]
+    fn test_reserved_insets() {
+        let top_bar = (Anchor::TOP | Anchor::LEFT | Anchor::RIGHT, 32);
+        let dock = (Anchor::BOTTOM, 48);
+        let popup = (Anchor::TOP | Anchor::RIGHT, 300);
+
+        // A fullscreen overlay ignoring exclusive zones
+        let insets = reserved_insets(Anchor::all(), -1, [top_bar, dock, popup]);
+        assert_eq!(insets.top, 32.0);
+        assert_eq!(insets.bottom, 48.0);
+        assert_eq!((insets.left, insets.right), (0.0, 0.0));
+
+        // Only edges the surface is anchored to count
+        let insets = reserved_insets(Anchor::TOP, -1, [top_bar, dock]);
+        assert_eq!((insets.top, insets.bottom), (32.0, 0.0));
+
+        // The compositor already keeps zone-respecting surfaces clear
+        let insets = reserved_insets(Anchor::all(), 0, [top_bar, dock]);
+        assert_eq!((insets.top, insets.bottom), (0.0, 0.0));
+    }
+}
@@ -22,6 +22,11 @@
 //! - **Partial Layout**: When a widget is marked dirty, the dirty flag
 //!   bubbles up to the nearest relayout boundary, which is added to the
 //!   layout queue. Only dirty subtrees are re-laid out.
+//!
+//! - **Inspection**: [`Tree::walk`] and [`Tree::snapshot`] give read-only
+//!   access to the hierarchy, bounds and dirty flags for debugging tools, and
+//!   [`Tree::debug_layout`] and [`Tree::format_layout`] show the constraints
+//!   each widget was laid out with.
 
 use smallvec::SmallVec;
 
@@ -32,6 +37,10 @@ use crate::widgets::{Rect, Widget};
 /// so this avoids a heap allocation for the common case.
 type ChildrenVec = SmallVec<[WidgetId; 4]>;
 
+/// Most disjoint rects a [`DamageRegion`] tracks before collapsing them
+/// into one bounding rect.
+const MAX_DAMAGE_RECTS: usize = 8;
+
 /// Accumulated damage region for a frame.
 #[derive(Debug, Clone)]
 pub enum DamageRegion {
@@ -39,10 +48,39 @@ pub enum DamageRegion {
     None,
     /// Partial damage — only the given rect needs redraw.
     Partial(Rect),
+    /// Partial damage in several disjoint areas, e.g. a clock and a battery
+    /// icon at opposite ends of a bar.
+    Multiple(Vec<Rect>),
     /// Full damage — the entire surface needs redraw.
     Full,
 }
 
+/// Read-only view of a widget subtree, produced by [`Tree::snapshot`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct WidgetSnapshot {
+    pub id: WidgetId,
+    pub parent: Option<WidgetId>,
+    /// Widget type name from [`Widget::debug_name`]
+    pub name: &'static str,
+    /// Name set with `test_id`, if any
+    pub test_id: Option<String>,
+    /// Surface-relative bounds from the last layout
+    pub bounds: Rect,
+    pub needs_layout: bool,
+    pub needs_paint: bool,
+    pub children: Vec<WidgetSnapshot>,
+}
+
+impl WidgetSnapshot {
+    /// Find the snapshot for `id` in this subtree.
+    pub fn find(&self, id: WidgetId) -> Option<&WidgetSnapshot> {
+        if self.id == id {
+            return Some(self);
+        }
+        self.children.iter().find_map(|child| child.find(id))
+    }
+}
+
 /// Unique identifier for a widget in the tree.
 ///
 /// Uses a generational index design:
@@ -101,10 +139,14 @@ struct Node {
     needs_paint: bool,
     /// Whether this widget is a relayout boundary
     is_relayout_boundary: bool,
+    /// Whether this widget is a repaint boundary (isolated from ancestor relayouts)
+    is_repaint_boundary: bool,
     /// Cached constraints from last layout
     cached_constraints: Option<Constraints>,
     /// Cached size from last layout
     cached_size: Option<Size>,
+    /// Distance from the widget's top edge to its first text baseline
+    baseline: Option<f32>,
     /// Widget origin (set after layout by parent)
     origin: (f32, f32),
     /// Back-pointer to sparse array index (for swap-remove fixup)
@@ -173,8 +215,10 @@ impl Tree {
             needs_layout: false,
             needs_paint: true,
             is_relayout_boundary: false,
+            is_repaint_boundary: false,
             cached_constraints: None,
             cached_size: None,
+            baseline: None,
             origin: (0.0, 0.0),
             sparse_index,
             cached_paint: None,
@@ -314,6 +358,15 @@ impl Tree {
             .and_then(|idx| self.dense[idx].parent)
     }
 
+    /// Get the root ancestor of a widget (the widget itself if it has no parent).
+    pub fn root_of(&self, id: WidgetId) -> WidgetId {
+        let mut root = id;
+        while let Some(parent) = self.get_parent(root) {
+            root = parent;
+        }
+        root
+    }
+
     /// Get the children of a widget (returns a slice to avoid heap allocation).
     pub fn get_children(&self, id: WidgetId) -> &[WidgetId] {
         self.get_dense_index(id)
@@ -376,6 +429,22 @@ impl Tree {
             .unwrap_or(false)
     }
 
+    /// Mark a widget and all its descendants as needing layout.
+    ///
+    /// Used when something every widget may depend on changes (e.g. the
+    /// loaded fonts). Does not bubble to ancestors or return a layout root:
+    /// the caller schedules `widget_id` itself.
+    pub fn mark_subtree_needs_layout(&mut self, widget_id: WidgetId) {
+        let mut stack = vec![widget_id];
+        while let Some(id) = stack.pop() {
+            let Some(dense_idx) = self.get_dense_index(id) else {
+                continue;
+            };
+            self.dense[dense_idx].needs_layout = true;
+            stack.extend_from_slice(&self.dense[dense_idx].children);
+        }
+    }
+
     /// Mark a widget as needing paint, propagating up to the root.
     ///
     /// Similar to `mark_needs_layout`, this bubbles the paint-dirty flag
@@ -428,6 +497,57 @@ impl Tree {
         self.mark_subtree_needs_paint_inner(widget_id);
     }
 
+    /// Mark a subtree that was just laid out as needing paint.
+    ///
+    /// Unlike [`mark_subtree_needs_paint`](Self::mark_subtree_needs_paint),
+    /// this skips descendant repaint boundaries: their paint cache stays valid
+    /// unless their own layout ran (in which case they mark themselves). The
+    /// dirty flag also bubbles to ancestors so they re-composite the subtree.
+    ///
+    /// A repaint boundary clips its content, so when `widget_id` is one the
+    /// damage is limited to its bounds; otherwise the bounds of every widget in
+    /// the subtree are unioned into the damage, since children may overflow.
+    pub fn mark_relayout_needs_paint(&mut self, widget_id: WidgetId) {
+        if self.is_repaint_boundary(widget_id) {
+            if self.paints_outside_bounds(widget_id)
+                || self.ancestor_paints_outside_bounds(widget_id)
+            {
+                self.damage = DamageRegion::Full;
+            } else if let Some(bounds) = self.get_surface_relative_bounds(widget_id) {
+                self.expand_damage_rect(bounds);
+            }
+        } else {
+            self.add_subtree_damage(widget_id);
+        }
+
+        let mut stack = vec![widget_id];
+        while let Some(id) = stack.pop() {
+            let Some(dense_idx) = self.get_dense_index(id) else {
+                continue;
+            };
+            if id != widget_id && self.dense[dense_idx].is_repaint_boundary {
+                continue;
+            }
+            self.dense[dense_idx].needs_paint = true;
+            stack.extend_from_slice(&self.dense[dense_idx].children);
+        }
+
+        // Bubble to ancestors (no extra damage — the subtree already covers it)
+        let mut current = self
+            .get_dense_index(widget_id)
+            .and_then(|idx| self.dense[idx].parent);
+        while let Some(id) = current {
+            let Some(dense_idx) = self.get_dense_index(id) else {
+                return;
+            };
+            if self.dense[dense_idx].needs_paint {
+                return;
+            }
+            self.dense[dense_idx].needs_paint = true;
+            current = self.dense[dense_idx].parent;
+        }
+    }
+
     fn mark_subtree_needs_paint_inner(&mut self, widget_id: WidgetId) {
         // Iterative DFS to avoid cloning children SmallVecs for borrow checker
         let mut stack = vec![widget_id];
@@ -464,18 +584,119 @@ impl Tree {
         Some(Rect::new(x, y, size.width, size.height))
     }
 
-    /// Union a rect into the accumulated damage region.
+    /// Union the surface-relative bounds of a widget and its descendants into
+    /// the damage region.
+    ///
+    /// Called before a subtree is re-laid out to damage where its widgets were,
+    /// and by [`mark_relayout_needs_paint`](Self::mark_relayout_needs_paint)
+    /// for where they end up. Repaint boundaries clip their content, so their
+    /// descendants are skipped.
+    ///
+    /// Layout bounds don't account for transforms or shadows, so if the
+    /// subtree or one of its ancestors [paints outside its
+    /// bounds](Widget::paints_outside_bounds) the whole surface is damaged.
+    pub fn add_subtree_damage(&mut self, widget_id: WidgetId) {
+        let Some(idx) = self.get_dense_index(widget_id) else {
+            return;
+        };
+        if self.ancestor_paints_outside_bounds(widget_id) {
+            self.damage = DamageRegion::Full;
+            return;
+        }
+        // Surface-relative position of the parent; origins are parent-relative
+        let (base_x, base_y) = match self.dense[idx].parent {
+            Some(parent) => self
+                .get_surface_relative_bounds(parent)
+                .map(|b| (b.x, b.y))
+                .unwrap_or_default(),
+            None => (0.0, 0.0),
+        };
+
+        let mut stack = vec![(widget_id, base_x, base_y)];
+        while let Some((id, parent_x, parent_y)) = stack.pop() {
+            let Some(dense_idx) = self.get_dense_index(id) else {
+                continue;
+            };
+            let node = &self.dense[dense_idx];
+            if node.widget.paints_outside_bounds() {
+                self.damage = DamageRegion::Full;
+                return;
+            }
+            let x = parent_x + node.origin.0;
+            let y = parent_y + node.origin.1;
+            let rect = node
+                .cached_size
+                .map(|size| Rect::new(x, y, size.width, size.height));
+            if id == widget_id || !node.is_repaint_boundary {
+                stack.extend(node.children.iter().map(|&child| (child, x, y)));
+            }
+            if let Some(rect) = rect {
+                self.expand_damage_rect(rect);
+            }
+        }
+    }
+
+    /// Whether the widget may draw outside its layout bounds.
+    fn paints_outside_bounds(&self, widget_id: WidgetId) -> bool {
+        self.get_dense_index(widget_id)
+            .is_some_and(|idx| self.dense[idx].widget.paints_outside_bounds())
+    }
+
+    /// Whether any ancestor of the widget may draw outside its layout bounds,
+    /// which also displaces or inflates everything inside it.
+    fn ancestor_paints_outside_bounds(&self, widget_id: WidgetId) -> bool {
+        let mut current = self
+            .get_dense_index(widget_id)
+            .and_then(|idx| self.dense[idx].parent);
+        while let Some(id) = current {
+            let Some(idx) = self.get_dense_index(id) else {
+                return false;
+            };
+            if self.dense[idx].widget.paints_outside_bounds() {
+                return true;
+            }
+            current = self.dense[idx].parent;
+        }
+        false
+    }
+
+    /// Add a rect to the accumulated damage region.
+    ///
+    /// Overlapping rects are merged, so each reported rect covers a disjoint
+    /// area. Past [`MAX_DAMAGE_RECTS`] the rects collapse into their bounding
+    /// rect, which is cheaper for the compositor than many tiny ones.
+    ///
+    /// Empty rects (e.g. collapsed or hidden widgets) are ignored so they
+    /// don't stretch the damage towards their origin.
     fn expand_damage_rect(&mut self, rect: Rect) {
-        self.damage = match &self.damage {
-            DamageRegion::None => DamageRegion::Partial(rect),
-            DamageRegion::Partial(existing) => {
-                let min_x = existing.x.min(rect.x);
-                let min_y = existing.y.min(rect.y);
-                let max_x = (existing.x + existing.width).max(rect.x + rect.width);
-                let max_y = (existing.y + existing.height).max(rect.y + rect.height);
-                DamageRegion::Partial(Rect::new(min_x, min_y, max_x - min_x, max_y - min_y))
+        if rect.width <= 0.0 || rect.height <= 0.0 {
+            return;
+        }
+        let mut rects = match std::mem::replace(&mut self.damage, DamageRegion::None) {
+            DamageRegion::None => Vec::new(),
+            DamageRegion::Partial(existing) => vec![existing],
+            DamageRegion::Multiple(existing) => existing,
+            DamageRegion::Full => {
+                self.damage = DamageRegion::Full;
+                return;
             }
-            DamageRegion::Full => DamageRegion::Full,
+        };
+
+        // Absorb every rect the growing one touches; a merge can make it
+        // reach rects it didn't overlap before, so rescan until stable.
+        let mut merged = rect;
+        while let Some(pos) = rects.iter().position(|r| r.intersects(&merged)) {
+            merged = merged.union(&rects.swap_remove(pos));
+        }
+        rects.push(merged);
+
+        if rects.len() > MAX_DAMAGE_RECTS {
+            let bounds = rects.iter().skip(1).fold(rects[0], |acc, r| acc.union(r));
+            rects = vec![bounds];
+        }
+        self.damage = match rects.len() {
+            1 => DamageRegion::Partial(rects[0]),
+            _ => DamageRegion::Multiple(rects),
         };
     }
 
@@ -503,6 +724,20 @@ impl Tree {
             .unwrap_or(false)
     }
 
+    /// Set whether a widget is a repaint boundary.
+    pub fn set_repaint_boundary(&mut self, id: WidgetId, is_boundary: bool) {
+        if let Some(idx) = self.get_dense_index(id) {
+            self.dense[idx].is_repaint_boundary = is_boundary;
+        }
+    }
+
+    /// Check if a widget is a repaint boundary.
+    pub fn is_repaint_boundary(&self, id: WidgetId) -> bool {
+        self.get_dense_index(id)
+            .map(|idx| self.dense[idx].is_repaint_boundary)
+            .unwrap_or(false)
+    }
+
     /// Cache the constraints and size for a widget.
     pub fn cache_layout(&mut self, id: WidgetId, constraints: Constraints, size: Size) {
         if let Some(idx) = self.get_dense_index(id) {
@@ -523,6 +758,67 @@ impl Tree {
             .and_then(|idx| self.dense[idx].cached_size)
     }
 
+    /// Get the constraints a widget was last laid out with and the size it
+    /// returned, for debugging layout.
+    ///
+    /// `None` until the widget has been laid out.
+    pub fn debug_layout(&self, id: WidgetId) -> Option<(Constraints, Size)> {
+        let idx = self.get_dense_index(id)?;
+        let node = &self.dense[idx];
+        Some((node.cached_constraints?, node.cached_size?))
+    }
+
+    /// Describe the layout of the subtree under `root`, one widget per line,
+    /// indented by depth: type name, `test_id`, the constraints it received
+    /// and the size it returned.
+    ///
+    /// ```text
+    /// Container#3 [0..400 x 0..32] -> 400x32
+    ///   Text#4 "clock" [0..400 x 0..32] -> 42x17
+    /// ```
+    pub fn format_layout(&self, root: WidgetId) -> String {
+        let mut out = String::new();
+        self.walk(root, |id, depth| {
+            let name = self.debug_name(id).unwrap_or("?");
+            out.push_str(&format!(
+                "{:indent$}{}#{}",
+                "",
+                name,
+                id.index,
+                indent = depth * 2
+            ));
+            if let Some(test_id) = self
+                .with_widget(id, |w| w.test_id().map(String::from))
+                .flatten()
+            {
+                out.push_str(&format!(" {:?}", test_id));
+            }
+            match self.debug_layout(id) {
+                Some((c, size)) => out.push_str(&format!(
+                    " [{}..{} x {}..{}] -> {}x{}\n",
+                    c.min_width, c.max_width, c.min_height, c.max_height, size.width, size.height
+                )),
+                None => out.push_str(" (not laid out)\n"),
+            }
+        });
+        out
+    }
+
+    /// Set a widget's baseline: the distance from its top edge to its first
+    /// text baseline, or `None` if it has no text. Set during layout and read
+    /// by `CrossAlignment::Baseline`.
+    pub fn set_baseline(&mut self, id: WidgetId, baseline: Option<f32>) {
+        if let Some(idx) = self.get_dense_index(id) {
+            self.dense[idx].baseline = baseline;
+        }
+    }
+
+    /// Get a widget's baseline from its last layout.
+    pub fn baseline(&self, id: WidgetId) -> Option<f32> {
+        self.get_dense_index(id)
+            .and_then(|idx| self.dense[idx].baseline)
+    }
+
     /// Set the origin (position) for a widget.
     pub fn set_origin(&mut self, id: WidgetId, x: f32, y: f32) {
         if let Some(idx) = self.get_dense_index(id) {
@@ -572,6 +868,73 @@ impl Tree {
     pub fn widget_count(&self) -> usize {
         self.dense.len()
     }
+
+    /// IDs of all widgets without a parent (one per surface root, plus any
+    /// detached widgets).
+    pub fn roots(&self) -> Vec<WidgetId> {
+        self.dense
+            .iter()
+            .filter(|node| node.parent.is_none())
+            .filter_map(|node| {
+                self.sparse[node.sparse_index as usize]
+                    .as_ref()
+                    .map(|entry| WidgetId::new(node.sparse_index, entry.generation))
+            })
+            .collect()
+    }
+
+    /// Get the widget's type name (see [`Widget::debug_name`]).
+    pub fn debug_name(&self, id: WidgetId) -> Option<&'static str> {
+        self.with_widget(id, |widget| widget.debug_name())
+    }
+
+    /// Find the widget tagged with `test_id` (see [`Widget::test_id`]).
+    ///
+    /// If several widgets share the name, which one is returned is unspecified.
+    pub fn find_by_test_id(&self, test_id: &str) -> Option<WidgetId> {
+        self.dense
+            .iter()
+            .find(|node| node.widget.test_id() == Some(test_id))
+            .and_then(|node| {
+                self.sparse[node.sparse_index as usize]
+                    .as_ref()
+                    .map(|entry| WidgetId::new(node.sparse_index, entry.generation))
+            })
+    }
+
+    /// Visit `root` and its descendants depth-first in paint order.
+    ///
+    /// The callback receives each widget ID and its depth below `root`.
+    pub fn walk(&self, root: WidgetId, mut f: impl FnMut(WidgetId, usize)) {
+        if !self.contains(root) {
+            return;
+        }
+        let mut stack = vec![(root, 0)];
+        while let Some((id, depth)) = stack.pop() {
+            f(id, depth);
+            stack.extend(self.get_children(id).iter().rev().map(|&c| (c, depth + 1)));
+        }
+    }
+
+    /// Capture the subtree under `root` as an owned [`WidgetSnapshot`].
+    pub fn snapshot(&self, root: WidgetId) -> Option<WidgetSnapshot> {
+        let idx = self.get_dense_index(root)?;
+        let node = &self.dense[idx];
+        Some(WidgetSnapshot {
+            id: root,
+            parent: node.parent,
+            name: node.widget.debug_name(),
+            test_id: node.widget.test_id().map(String::from),
+            bounds: self.get_surface_relative_bounds(root).unwrap_or_default(),
+            needs_layout: node.needs_layout,
+            needs_paint: node.needs_paint,
+            children: node
+                .children
+                .iter()
+                .filter_map(|&child| self.snapshot(child))
+                .collect(),
+        })
+    }
 }
 
 impl Default for Tree {
@@ -601,6 +964,21 @@ mod tests {
         fn paint(&self, _tree: &Tree, _id: WidgetId, _ctx: &mut crate::renderer::PaintContext) {}
     }
 
+    // Mock widget drawing past its bounds, like a transformed or shadowed container
+    struct OverflowingWidget;
+
+    impl Widget for OverflowingWidget {
+        fn layout(&mut self, _tree: &mut Tree, _id: WidgetId, constraints: Constraints) -> Size {
+            Size::new(constraints.max_width, constraints.max_height)
+        }
+
+        fn paint(&self, _tree: &Tree, _id: WidgetId, _ctx: &mut crate::renderer::PaintContext) {}
+
+        fn paints_outside_bounds(&self) -> bool {
+            true
+        }
+    }
+
     #[test]
     fn test_tree_register_unregister() {
         let mut tree = Tree::new();
@@ -721,6 +1099,28 @@ mod tests {
         assert_eq!(layout_root, None);
     }
 
+    #[test]
+    fn test_mark_subtree_needs_layout() {
+        let mut tree = Tree::new();
+        let root_id = tree.register(Box::new(MockWidget::new()));
+        let child_id = tree.register(Box::new(MockWidget::new()));
+        let grandchild_id = tree.register(Box::new(MockWidget::new()));
+        let sibling_id = tree.register(Box::new(MockWidget::new()));
+
+        tree.set_parent(child_id, root_id);
+        tree.set_parent(grandchild_id, child_id);
+        tree.set_parent(sibling_id, root_id);
+
+        tree.mark_subtree_needs_layout(child_id);
+        assert!(tree.needs_layout(child_id));
+        assert!(tree.needs_layout(grandchild_id));
+        assert!(!tree.needs_layout(sibling_id));
+        assert!(!tree.needs_layout(root_id));
+
+        // A later bubbling mark stops at the already-dirty child
+        assert_eq!(tree.mark_needs_layout(grandchild_id), None);
+    }
+
     #[test]
     fn test_tree_with_widget() {
         let mut tree = Tree::new();
@@ -846,4 +1246,318 @@ mod tests {
         assert!(tree.needs_paint(child2_id));
         assert!(tree.needs_paint(grandchild_id));
     }
+
+    #[test]
+    fn test_mark_relayout_needs_paint_skips_repaint_boundaries() {
+        let mut tree = Tree::new();
+        let root_id = tree.register(Box::new(MockWidget::new()));
+        let clock_id = tree.register(Box::new(MockWidget::new()));
+        let logo_id = tree.register(Box::new(MockWidget::new()));
+        let logo_child_id = tree.register(Box::new(MockWidget::new()));
+
+        tree.set_parent(clock_id, root_id);
+        tree.set_parent(logo_id, root_id);
+        tree.set_parent(logo_child_id, logo_id);
+        tree.set_repaint_boundary(logo_id, true);
+
+        for id in [root_id, clock_id, logo_id, logo_child_id] {
+            tree.clear_needs_paint(id);
+        }
+        tree.take_damage();
+
+        let size = Size::new(200.0, 30.0);
+        tree.cache_layout(root_id, Constraints::tight(size), size);
+        let size = Size::new(60.0, 20.0);
+        tree.cache_layout(clock_id, Constraints::tight(size), size);
+        tree.set_origin(clock_id, 10.0, 5.0);
+
+        // Relayout of the root repaints everything except the boundary's subtree
+        tree.mark_relayout_needs_paint(root_id);
+        assert!(tree.needs_paint(root_id));
+        assert!(tree.needs_paint(clock_id));
+        assert!(!tree.needs_paint(logo_id));
+        assert!(!tree.needs_paint(logo_child_id));
+        match tree.take_damage() {
+            DamageRegion::Partial(rect) => {
+                assert_eq!(rect, Rect::new(0.0, 0.0, 200.0, 30.0))
+            }
+            other => panic!("expected partial damage, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_damage_coalesces_small_changes() {
+        let mut tree = Tree::new();
+        let root_id = tree.register(Box::new(MockWidget::new()));
+        let panel_id = tree.register(Box::new(MockWidget::new()));
+        let badge_id = tree.register(Box::new(MockWidget::new()));
+        let label_id = tree.register(Box::new(MockWidget::new()));
+        let hidden_id = tree.register(Box::new(MockWidget::new()));
+
+        tree.set_parent(panel_id, root_id);
+        tree.set_parent(badge_id, panel_id);
+        tree.set_parent(label_id, panel_id);
+        tree.set_parent(hidden_id, root_id);
+
+        let size = Size::new(1920.0, 1080.0);
+        tree.cache_layout(root_id, Constraints::tight(size), size);
+        let size = Size::new(100.0, 40.0);
+        tree.cache_layout(panel_id, Constraints::tight(size), size);
+        tree.set_origin(panel_id, 1800.0, 20.0);
+        let size = Size::new(16.0, 16.0);
+        tree.cache_layout(badge_id, Constraints::tight(size), size);
+        tree.set_origin(badge_id, 4.0, 4.0);
+        let size = Size::new(50.0, 20.0);
+        tree.cache_layout(label_id, Constraints::tight(size), size);
+        tree.set_origin(label_id, 12.0, 10.0);
+        tree.cache_layout(hidden_id, Constraints::tight(Size::zero()), Size::zero());
+
+        for id in [root_id, panel_id, badge_id, label_id, hidden_id] {
+            tree.clear_needs_paint(id);
+        }
+        tree.take_damage();
+
+        // Two overlapping changes in the top-right corner, plus a collapsed widget
+        tree.mark_needs_paint(badge_id);
+        tree.mark_needs_paint(label_id);
+        tree.mark_needs_paint(hidden_id);
+        match tree.take_damage() {
+            DamageRegion::Partial(rect) => {
+                assert_eq!(rect, Rect::new(1804.0, 24.0, 58.0, 26.0))
+            }
+            other => panic!("expected partial damage, got {other:?}"),
+        }
+
+        // Relayout of the panel damages the panel, not the surface
+        tree.mark_relayout_needs_paint(panel_id);
+        match tree.take_damage() {
+            DamageRegion::Partial(rect) => {
+                assert_eq!(rect, Rect::new(1800.0, 20.0, 100.0, 40.0))
+            }
+            other => panic!("expected partial damage, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_damage_keeps_disjoint_rects_separate() {
+        let mut tree = Tree::new();
+        let root_id = tree.register(Box::new(MockWidget::new()));
+        let size = Size::new(1920.0, 32.0);
+        tree.cache_layout(root_id, Constraints::tight(size), size);
+
+        let mut add = |x: f32, y: f32, w: f32, h: f32| {
+            let id = tree.register(Box::new(MockWidget::new()));
+            tree.set_parent(id, root_id);
+            let size = Size::new(w, h);
+            tree.cache_layout(id, Constraints::tight(size), size);
+            tree.set_origin(id, x, y);
+            tree.clear_needs_paint(id);
+            id
+        };
+        let battery_id = add(8.0, 8.0, 16.0, 16.0);
+        let clock_id = add(1840.0, 6.0, 72.0, 20.0);
+        let clock_icon_id = add(1830.0, 8.0, 16.0, 16.0);
+        let workspace_ids: Vec<_> = (0..MAX_DAMAGE_RECTS)
+            .map(|i| add(100.0 + i as f32 * 40.0, 4.0, 24.0, 24.0))
+            .collect();
+        tree.clear_needs_paint(root_id);
+        tree.take_damage();
+
+        // A clock on the right and a battery icon on the left stay apart
+        tree.mark_needs_paint(battery_id);
+        tree.mark_needs_paint(clock_id);
+        match tree.take_damage() {
+            DamageRegion::Multiple(rects) => {
+                assert_eq!(rects.len(), 2);
+                assert!(rects.contains(&Rect::new(8.0, 8.0, 16.0, 16.0)));
+                assert!(rects.contains(&Rect::new(1840.0, 6.0, 72.0, 20.0)));
+            }
+            other => panic!("expected multiple damage rects, got {other:?}"),
+        }
+
+        // Overlapping rects merge
+        tree.mark_needs_paint(clock_id);
+        tree.mark_needs_paint(clock_icon_id);
+        match tree.take_damage() {
+            DamageRegion::Partial(rect) => {
+                assert_eq!(rect, Rect::new(1830.0, 6.0, 82.0, 20.0))
+            }
+            other => panic!("expected partial damage, got {other:?}"),
+        }
+
+        // Too many rects collapse into their bounds
+        tree.mark_needs_paint(battery_id);
+        for &id in &workspace_ids {
+            tree.mark_needs_paint(id);
+        }
+        match tree.take_damage() {
+            DamageRegion::Partial(rect) => {
+                let right = 100.0 + (MAX_DAMAGE_RECTS - 1) as f32 * 40.0 + 24.0;
+                assert_eq!(rect, Rect::new(8.0, 4.0, right - 8.0, 24.0))
+            }
+            other => panic!("expected partial damage, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_mark_relayout_needs_paint_on_boundary_damages_its_bounds() {
+        let mut tree = Tree::new();
+        let root_id = tree.register(Box::new(MockWidget::new()));
+        let boundary_id = tree.register(Box::new(MockWidget::new()));
+        let child_id = tree.register(Box::new(MockWidget::new()));
+
+        tree.set_parent(boundary_id, root_id);
+        tree.set_parent(child_id, boundary_id);
+        tree.set_repaint_boundary(boundary_id, true);
+        let size = Size::new(80.0, 20.0);
+        tree.cache_layout(boundary_id, Constraints::tight(size), size);
+        tree.set_origin(boundary_id, 100.0, 4.0);
+
+        for id in [root_id, boundary_id, child_id] {
+            tree.clear_needs_paint(id);
+        }
+        tree.take_damage();
+
+        tree.mark_relayout_needs_paint(boundary_id);
+        assert!(tree.needs_paint(boundary_id));
+        assert!(tree.needs_paint(child_id));
+        // Ancestors re-composite the boundary
+        assert!(tree.needs_paint(root_id));
+        match tree.take_damage() {
+            DamageRegion::Partial(rect) => {
+                assert_eq!(rect, Rect::new(100.0, 4.0, 80.0, 20.0))
+            }
+            other => panic!("expected partial damage, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_subtree_damage_falls_back_to_full_when_painting_outside_bounds() {
+        let mut tree = Tree::new();
+        let root_id = tree.register(Box::new(MockWidget::new()));
+        let panel_id = tree.register(Box::new(MockWidget::new()));
+        let shadowed_id = tree.register(Box::new(OverflowingWidget));
+        let label_id = tree.register(Box::new(MockWidget::new()));
+
+        tree.set_parent(panel_id, root_id);
+        tree.set_parent(shadowed_id, panel_id);
+        tree.set_parent(label_id, shadowed_id);
+        let size = Size::new(50.0, 20.0);
+        for id in [panel_id, shadowed_id, label_id] {
+            tree.cache_layout(id, Constraints::tight(size), size);
+        }
+        tree.take_damage();
+
+        // The shadow inside the relaid-out panel reaches past the layout bounds
+        tree.add_subtree_damage(panel_id);
+        assert!(matches!(tree.take_damage(), DamageRegion::Full));
+
+        // So does the transform of an ancestor of the relaid-out label
+        tree.add_subtree_damage(label_id);
+        assert!(matches!(tree.take_damage(), DamageRegion::Full));
+    }
+
+    #[test]
+    fn test_tree_walk_and_snapshot() {
+        let mut tree = Tree::new();
+        let root = tree.register(Box::new(MockWidget::new()));
+        let first = tree.register(Box::new(MockWidget::new()));
+        let leaf = tree.register(Box::new(MockWidget::new()));
+        let second = tree.register(Box::new(MockWidget::new()));
+        tree.set_parent(first, root);
+        tree.set_parent(leaf, first);
+        tree.set_parent(second, root);
+        tree.set_origin(first, 10.0, 20.0);
+        tree.cache_layout(
+            leaf,
+            Constraints::tight(Size::new(5.0, 5.0)),
+            Size::new(5.0, 5.0),
+        );
+        tree.set_origin(leaf, 1.0, 2.0);
+
+        assert_eq!(tree.roots(), vec![root]);
+
+        let mut visited = Vec::new();
+        tree.walk(root, |id, depth| visited.push((id, depth)));
+        assert_eq!(visited, vec![(root, 0), (first, 1), (leaf, 2), (second, 1)]);
+
+        let snapshot = tree.snapshot(root).unwrap();
+        assert_eq!(snapshot.name, "MockWidget");
+        let leaf_snapshot = snapshot.find(leaf).unwrap();
+        assert_eq!(leaf_snapshot.parent, Some(first));
+        assert_eq!(leaf_snapshot.bounds, Rect::new(11.0, 22.0, 5.0, 5.0));
+    }
+
+    #[test]
+    fn test_find_by_test_id_and_click_center() {
+        use crate::widgets::{Event, MouseButton, container};
+        use std::cell::Cell;
+        use std::rc::Rc;
+
+        let clicked = Rc::new(Cell::new(false));
+        let clicked_flag = clicked.clone();
+        let mut tree = Tree::new();
+        let root = tree.register(Box::new(
+            container().padding(10.0).child(
+                container()
+                    .test_id("submit-button")
+                    .width(40.0)
+                    .height(20.0)
+                    .on_click(move || clicked_flag.set(true)),
+            ),
+        ));
+        tree.with_widget_mut(root, |widget, id, tree| {
+            widget.register_children(tree, id);
+            widget.layout(tree, id, Constraints::loose(Size::new(200.0, 100.0)))
+        });
+
+        let button = tree.find_by_test_id("submit-button").unwrap();
+        assert_eq!(tree.find_by_test_id("missing"), None);
+        let bounds = tree.get_surface_relative_bounds(button).unwrap();
+        assert_eq!(bounds, Rect::new(10.0, 10.0, 40.0, 20.0));
+
+        let (x, y) = bounds.center();
+        for event in [
+            Event::MouseDown {
+                x,
+                y,
+                button: MouseButton::Left,
+            },
+            Event::MouseUp {
+                x,
+                y,
+                button: MouseButton::Left,
+            },
+        ] {
+            tree.with_widget_mut(root, |widget, id, tree| widget.event(tree, id, &event));
+        }
+        assert!(clicked.get());
+    }
+
+    #[test]
+    fn test_debug_layout() {
+        let mut tree = Tree::new();
+        let root_id = tree.register(Box::new(MockWidget::new()));
+        let child_id = tree.register(Box::new(MockWidget::new()));
+        tree.set_parent(child_id, root_id);
+        assert_eq!(tree.debug_layout(root_id), None);
+
+        let constraints = Constraints::new(0.0, 0.0, 400.0, 32.0);
+        tree.cache_layout(root_id, constraints, Size::new(400.0, 32.0));
+        assert_eq!(
+            tree.debug_layout(root_id),
+            Some((constraints, Size::new(400.0, 32.0)))
+        );
+
+        let lines: Vec<String> = tree
+            .format_layout(root_id)
+            .lines()
+            .map(String::from)
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].ends_with("[0..400 x 0..32] -> 400x32"));
+        // Children are indented, and ones without a layout say so
+        assert!(lines[1].starts_with("  "));
+        assert!(lines[1].ends_with("(not laid out)"));
+    }
 }
@@ -2,14 +2,22 @@
 //!
 //! Attach a `WidgetRef` to a `Container` via `.widget_ref(r)` to track its
 //! bounding rect after layout. The rect is exposed as a `Signal<Rect>` that
-//! updates automatically each frame.
+//! updates automatically each frame. For scrollable containers the ref also
+//! tracks the scroll offset and scroll fraction, and can snap the container's
+//! animated properties to their targets without a transition.
+//!
+//! A ref can also inject synthetic input into its widget ([`WidgetRef::click`],
+//! [`WidgetRef::hover`], [`WidgetRef::dispatch`]) for tests and scripted UI,
+//! and detect presses outside of it with [`on_click_outside`].
 
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::collections::HashMap;
+use std::rc::Rc;
 
-use crate::reactive::{RwSignal, Signal, create_signal};
+use crate::jobs::{JobRequest, RequiredJob, request_frame, request_job};
+use crate::reactive::{RwSignal, Signal, create_signal, on_cleanup};
 use crate::tree::{Tree, WidgetId};
-use crate::widgets::Rect;
+use crate::widgets::{Event, MouseButton, Rect};
 
 /// A handle to a widget's surface-relative bounding rect.
 ///
@@ -18,6 +26,9 @@ use crate::widgets::Rect;
 #[derive(Clone, Copy)]
 pub struct WidgetRef {
     signal: RwSignal<Rect>,
+    scroll_offset: RwSignal<(f32, f32)>,
+    scroll_fraction: RwSignal<(f32, f32)>,
+    snap: RwSignal<bool>,
 }
 
 impl WidgetRef {
@@ -26,6 +37,82 @@ impl WidgetRef {
         self.signal.read_only()
     }
 
+    /// The scroll offset `(x, y)` in logical pixels (read-only).
+    ///
+    /// Only updates for scrollable containers; stays `(0.0, 0.0)` otherwise.
+    pub fn scroll_offset_signal(&self) -> Signal<(f32, f32)> {
+        self.scroll_offset.read_only()
+    }
+
+    /// How far the container is scrolled `(x, y)`, from 0.0 (start) to 1.0 (end).
+    ///
+    /// An axis without overflow reports 0.0. Handy for scroll progress indicators.
+    pub fn scroll_fraction_signal(&self) -> Signal<(f32, f32)> {
+        self.scroll_fraction.read_only()
+    }
+
+    /// Apply the next change to the container's animated properties immediately
+    /// instead of transitioning.
+    ///
+    /// Call it alongside the change, e.g. when resetting a panel's width after a
+    /// layout change. Changes made later animate as usual.
+    ///
+    /// ```ignore
+    /// panel_ref.snap_animations();
+    /// panel_width.set(240.0);
+    /// ```
+    pub fn snap_animations(&self) {
+        self.snap.set(true);
+        if let Some(id) = widget_id_for(self.signal) {
+            // Make sure the container advances and re-lays out next frame,
+            // consuming the snap even if only paint properties changed
+            request_job(id, JobRequest::Animation(RequiredJob::Layout));
+        }
+    }
+
+    /// Click the widget at its center, as a left mouse press and release.
+    ///
+    /// The events are dispatched from the surface's root like real input, so
+    /// ancestors, pressed state, ripples and `on_click` behave as for a real
+    /// click, and a widget covering the center receives it instead. They are
+    /// delivered at the start of the next frame.
+    pub fn click(&self) {
+        queue_synthetic(self.signal, SyntheticEvent::Click);
+    }
+
+    /// Move the pointer onto the widget's center (`true`) or off the surface
+    /// (`false`).
+    pub fn hover(&self, hovered: bool) {
+        queue_synthetic(self.signal, SyntheticEvent::Hover(hovered));
+    }
+
+    /// Deliver an arbitrary event to the widget's surface at the start of the
+    /// next frame.
+    ///
+    /// The event is dispatched from the surface's root like real input. Pointer
+    /// coordinates are surface-relative, the same space as [`rect`](Self::rect).
+    pub fn dispatch(&self, event: Event) {
+        queue_synthetic(self.signal, SyntheticEvent::Event(event));
+    }
+
+    /// Internal: whether a snap is pending for the next frame.
+    pub(crate) fn snap_pending(&self) -> bool {
+        self.snap.get_untracked()
+    }
+
+    /// Internal: consume a pending snap.
+    pub(crate) fn clear_snap(&self) {
+        if self.snap.get_untracked() {
+            self.snap.set(false);
+        }
+    }
+
+    /// Internal: update the scroll signals after the offset or content size changed.
+    pub(crate) fn set_scroll(&self, offset: (f32, f32), fraction: (f32, f32)) {
+        self.scroll_offset.set(offset);
+        self.scroll_fraction.set(fraction);
+    }
+
     /// Internal: get the read-write signal for updating bounds after layout.
     pub(crate) fn rw_signal(&self) -> RwSignal<Rect> {
         self.signal
@@ -36,6 +123,9 @@ impl WidgetRef {
 pub fn create_widget_ref() -> WidgetRef {
     WidgetRef {
         signal: create_signal(Rect::default()),
+        scroll_offset: create_signal((0.0, 0.0)),
+        scroll_fraction: create_signal((0.0, 0.0)),
+        snap: create_signal(false),
     }
 }
 
@@ -46,6 +136,24 @@ pub fn create_widget_ref() -> WidgetRef {
 thread_local! {
     static WIDGET_REF_REGISTRY: RefCell<HashMap<WidgetId, RwSignal<Rect>>> =
         RefCell::new(HashMap::new());
+    static SYNTHETIC_EVENTS: RefCell<Vec<(RwSignal<Rect>, SyntheticEvent)>> =
+        const { RefCell::new(Vec::new()) };
+    static OUTSIDE_HANDLERS: RefCell<Vec<OutsideHandler>> = const { RefCell::new(Vec::new()) };
+    static NEXT_OUTSIDE_KEY: Cell<u64> = const { Cell::new(0) };
+}
+
+/// A callback registered with [`on_click_outside`].
+struct OutsideHandler {
+    key: u64,
+    signal: RwSignal<Rect>,
+    callback: Rc<dyn Fn()>,
+}
+
+/// Input queued through a `WidgetRef`, delivered on the next frame.
+enum SyntheticEvent {
+    Click,
+    Hover(bool),
+    Event(Event),
 }
 
 /// Register (or re-register) a widget ref mapping.
@@ -58,11 +166,132 @@ pub(crate) fn register_widget_ref(id: WidgetId, signal: RwSignal<Rect>) {
     });
 }
 
+/// Find the widget a ref's bounds signal is registered for.
+fn widget_id_for(signal: RwSignal<Rect>) -> Option<WidgetId> {
+    WIDGET_REF_REGISTRY.with(|reg| {
+        reg.borrow()
+            .iter()
+            .find_map(|(&id, &registered)| (registered == signal).then_some(id))
+    })
+}
+
 /// Reset the widget ref registry.
 ///
 /// Called during `App::drop()` to clear stale widget ref entries.
 pub(crate) fn reset_widget_refs() {
     WIDGET_REF_REGISTRY.with(|r| r.borrow_mut().clear());
+    SYNTHETIC_EVENTS.with(|q| q.borrow_mut().clear());
+    OUTSIDE_HANDLERS.with(|h| h.borrow_mut().clear());
+}
+
+/// Call `f` whenever a mouse button is pressed outside the ref's widget.
+///
+/// Presses on other surfaces of the app count as outside. Nothing fires while
+/// the widget isn't laid out (e.g. a closed menu), and the handler is removed
+/// when the current owner is cleaned up, so register it next to the widget:
+///
+/// ```ignore
+/// let open = create_signal(false);
+///
+/// container().child(move || {
+///     open.get().then(|| {
+///         let menu = create_widget_ref();
+///         on_click_outside(menu, move || open.set(false));
+///         container().widget_ref(menu).child(menu_items())
+///     })
+/// })
+/// ```
+///
+/// The press is still delivered to the widgets under the pointer.
+pub fn on_click_outside(widget_ref: WidgetRef, f: impl Fn() + 'static) {
+    let key = NEXT_OUTSIDE_KEY.with(|next| next.replace(next.get() + 1));
+    OUTSIDE_HANDLERS.with(|handlers| {
+        handlers.borrow_mut().push(OutsideHandler {
+            key,
+            signal: widget_ref.signal,
+            callback: Rc::new(f),
+        })
+    });
+    on_cleanup(move || {
+        OUTSIDE_HANDLERS.with(|handlers| handlers.borrow_mut().retain(|h| h.key != key));
+    });
+}
+
+/// Run the [`on_click_outside`] handlers of widgets that don't contain a
+/// press at `(x, y)` on the surface whose root widget is `root`.
+///
+/// Called after the `MouseDown` was dispatched to the surface's widgets.
+pub(crate) fn dispatch_click_outside(tree: &Tree, root: WidgetId, x: f32, y: f32) {
+    let callbacks: Vec<Rc<dyn Fn()>> = OUTSIDE_HANDLERS.with(|handlers| {
+        handlers
+            .borrow()
+            .iter()
+            .filter(|handler| {
+                let Some(id) = widget_id_for(handler.signal) else {
+                    return false;
+                };
+                tree.root_of(id) != root
+                    || tree
+                        .get_surface_relative_bounds(id)
+                        .is_some_and(|bounds| !bounds.contains(x, y))
+            })
+            .map(|handler| handler.callback.clone())
+            .collect()
+    });
+    for callback in callbacks {
+        callback();
+    }
+}
+
+fn queue_synthetic(signal: RwSignal<Rect>, event: SyntheticEvent) {
+    SYNTHETIC_EVENTS.with(|q| q.borrow_mut().push((signal, event)));
+    request_frame();
+}
+
+/// Deliver events queued through [`WidgetRef::click`], [`WidgetRef::hover`]
+/// and [`WidgetRef::dispatch`].
+///
+/// Events enter at the root of the ref's surface, in surface coordinates, and
+/// take the same path as real input: ancestors see them first, the widget on
+/// top at the target point receives them, and presses run the
+/// [`on_click_outside`] handlers. Refs that haven't been laid out yet have no
+/// widget and are dropped. Called once per frame before jobs are processed.
+pub(crate) fn dispatch_synthetic_events(tree: &mut Tree) {
+    let queued = SYNTHETIC_EVENTS.with(|q| std::mem::take(&mut *q.borrow_mut()));
+    for (signal, synthetic) in queued {
+        let Some(id) = widget_id_for(signal) else {
+            log::warn!("Dropping synthetic event for a WidgetRef that is not laid out");
+            continue;
+        };
+        let Some(bounds) = tree.get_surface_relative_bounds(id) else {
+            continue;
+        };
+        let root = tree.root_of(id);
+        let (x, y) = bounds.center();
+        let events = match synthetic {
+            SyntheticEvent::Click => vec![
+                Event::MouseDown {
+                    x,
+                    y,
+                    button: MouseButton::Left,
+                },
+                Event::MouseUp {
+                    x,
+                    y,
+                    button: MouseButton::Left,
+                },
+            ],
+            SyntheticEvent::Hover(true) => vec![Event::MouseMove { x, y }],
+            SyntheticEvent::Hover(false) => vec![Event::MouseLeave],
+            SyntheticEvent::Event(event) => vec![event],
+        };
+        for event in &events {
+            tree.with_widget_mut(root, |widget, id, tree| widget.event(tree, id, event));
+            if let Event::MouseDown { x, y, .. } = *event {
+                dispatch_click_outside(tree, root, x, y);
+            }
+        }
+    }
 }
 
 /// Update all registered widget ref signals with current bounds from `tree`.
@@ -82,3 +311,88 @@ pub(crate) fn update_widget_refs(tree: &Tree) {
         });
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout::{Constraints, Size};
+    use crate::widgets::container;
+
+    #[test]
+    fn test_widget_ref_click_reaches_on_click() {
+        let clicks = Rc::new(Cell::new(0));
+        let click_count = clicks.clone();
+        let outside = Rc::new(Cell::new(false));
+        let outside_flag = outside.clone();
+        let button_ref = create_widget_ref();
+        let other_ref = create_widget_ref();
+        on_click_outside(other_ref, move || outside_flag.set(true));
+        let mut tree = Tree::new();
+        let root = tree.register(Box::new(
+            container()
+                .padding(10.0)
+                .child(
+                    container()
+                        .widget_ref(button_ref)
+                        .width(40.0)
+                        .height(20.0)
+                        .on_click(move || click_count.set(click_count.get() + 1)),
+                )
+                .child(container().widget_ref(other_ref).width(10.0).height(10.0)),
+        ));
+        tree.with_widget_mut(root, |widget, id, tree| {
+            widget.register_children(tree, id);
+            widget.layout(tree, id, Constraints::loose(Size::new(200.0, 100.0)))
+        });
+
+        button_ref.click();
+        assert_eq!(clicks.get(), 0);
+        dispatch_synthetic_events(&mut tree);
+        assert_eq!(clicks.get(), 1);
+        // The press went through the surface's normal dispatch
+        assert!(outside.get());
+        reset_widget_refs();
+    }
+
+    #[test]
+    fn test_click_outside_fires_only_for_presses_outside() {
+        use crate::reactive::{dispose_owner, with_owner};
+
+        let presses = Rc::new(Cell::new(0));
+        let menu_ref = create_widget_ref();
+        let closed_ref = create_widget_ref();
+        let count = presses.clone();
+        let ((), owner) = with_owner(|| {
+            on_click_outside(menu_ref, move || count.set(count.get() + 1));
+        });
+        // A ref whose widget isn't mounted never fires
+        let count = presses.clone();
+        on_click_outside(closed_ref, move || count.set(count.get() + 100));
+
+        let mut tree = Tree::new();
+        let root = tree.register(Box::new(
+            container()
+                .padding(10.0)
+                .child(container().widget_ref(menu_ref).width(40.0).height(20.0)),
+        ));
+        let other_surface = tree.register(Box::new(container()));
+        tree.with_widget_mut(root, |widget, id, tree| {
+            widget.register_children(tree, id);
+            widget.layout(tree, id, Constraints::loose(Size::new(200.0, 100.0)))
+        });
+
+        dispatch_click_outside(&tree, root, 20.0, 15.0);
+        assert_eq!(presses.get(), 0);
+        dispatch_click_outside(&tree, root, 80.0, 15.0);
+        assert_eq!(presses.get(), 1);
+        // Presses on another surface are outside too
+        dispatch_click_outside(&tree, other_surface, 20.0, 15.0);
+        assert_eq!(presses.get(), 2);
+
+        // Cleaning up the owner removes the handler
+        dispose_owner(owner);
+        dispatch_click_outside(&tree, root, 80.0, 15.0);
+        assert_eq!(presses.get(), 2);
+        reset_widget_refs();
+    }
+}
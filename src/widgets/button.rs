@@ -0,0 +1,219 @@
+//! Ready-made button component.
+//!
+//! [`button`] builds a Material-style button out of a container: padding,
+//! rounded corners, hover and pressed state layers, a ripple and a focus ring.
+//! All of them follow the variant and colors, even when those are signals.
+//! Every default can be overridden through the builder.
+//!
+//! # Example
+//!
+//! ```ignore
+//! button("Save").on_click(move || save(document));
+//!
+//! button("Cancel").outlined().color(Color::rgb(0.9, 0.4, 0.4));
+//! ```
+
+use crate::component;
+use crate::reactive::{IntoSignal, create_signal};
+
+use super::container::container;
+use super::state_layer::{RippleConfig, StateStyle};
+use super::text::text;
+use super::widget::{Color, Padding};
+
+/// Default accent color of buttons
+pub const DEFAULT_BUTTON_COLOR: Color = Color::rgb(0.36, 0.52, 0.92);
+
+/// Default color of the focus ring
+const FOCUS_RING_COLOR: Color = Color::rgba(1.0, 1.0, 1.0, 0.7);
+
+/// Visual style of a [`Button`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ButtonVariant {
+    /// Solid accent background with a contrasting label
+    #[default]
+    Filled,
+    /// Transparent background with an accent outline and label
+    Outlined,
+    /// Transparent background with an accent label only
+    Text,
+}
+
+// The component macro generates a `button()` constructor without arguments;
+// it stays private so the public `button(label)` can take the label directly.
+mod component {
+    use super::*;
+
+    #[component]
+    pub fn button(
+        label: String,
+        #[prop(default = "ButtonVariant::Filled")] variant: ButtonVariant,
+        #[prop(default = "DEFAULT_BUTTON_COLOR")] color: Color,
+        #[prop(default = "Color::WHITE")] label_color: Color,
+        #[prop(default = "FOCUS_RING_COLOR")] focus_ring_color: Color,
+        #[prop(default = "Padding::symmetric(20.0, 10.0)")] padding: Padding,
+        #[prop(default = "20.0")] corner_radius: f32,
+        #[prop(default = "14.0")] font_size: f32,
+        #[prop(callback)] on_click: (),
+    ) -> impl Widget {
+        // Hover and press tints follow `variant` and `color`, so they are
+        // derived here rather than fixed in state styles
+        let hovered = create_signal(false);
+        let pressed = create_signal(false);
+
+        container()
+            .padding(padding)
+            .corner_radius(corner_radius)
+            .background(move || {
+                let tint = color.get();
+                match variant.get() {
+                    ButtonVariant::Filled if pressed.get() => tint.darker(0.05),
+                    ButtonVariant::Filled if hovered.get() => tint.lighter(0.1),
+                    ButtonVariant::Filled => tint,
+                    ButtonVariant::Outlined | ButtonVariant::Text if pressed.get() => {
+                        tint.with_alpha(0.12)
+                    }
+                    ButtonVariant::Outlined | ButtonVariant::Text if hovered.get() => {
+                        tint.with_alpha(0.08)
+                    }
+                    ButtonVariant::Outlined | ButtonVariant::Text => Color::TRANSPARENT,
+                }
+            })
+            .border(
+                move || match variant.get() {
+                    ButtonVariant::Outlined => 1.0,
+                    ButtonVariant::Filled | ButtonVariant::Text => 0.0,
+                },
+                move || match variant.get() {
+                    ButtonVariant::Outlined => color.get(),
+                    // Only visible as the focus ring
+                    ButtonVariant::Filled | ButtonVariant::Text => focus_ring_color.get(),
+                },
+            )
+            .pressed_state(|s: StateStyle| s.ripple())
+            .ripple_color(move || match variant.get() {
+                ButtonVariant::Filled => RippleConfig::default().color,
+                ButtonVariant::Outlined | ButtonVariant::Text => color.get().with_alpha(0.25),
+            })
+            .focused_state(|s: StateStyle| s.border_width(2.0))
+            .on_hover(move |h| {
+                hovered.set(h);
+                if !h {
+                    pressed.set(false);
+                }
+            })
+            .on_mouse_down(move |_, _| pressed.set(true))
+            .on_mouse_up(move |_, _| pressed.set(false))
+            .focusable()
+            .on_click_option(on_click.clone())
+            .child(
+                text(label)
+                    .font_size(font_size)
+                    .color(move || match variant.get() {
+                        ButtonVariant::Filled => label_color.get(),
+                        ButtonVariant::Outlined | ButtonVariant::Text => color.get(),
+                    }),
+            )
+    }
+}
+
+pub use component::Button;
+
+impl Button {
+    /// Use the solid [`ButtonVariant::Filled`] style (the default).
+    pub fn filled(self) -> Self {
+        self.variant(ButtonVariant::Filled)
+    }
+
+    /// Use the [`ButtonVariant::Outlined`] style.
+    pub fn outlined(self) -> Self {
+        self.variant(ButtonVariant::Outlined)
+    }
+
+    /// Use the borderless [`ButtonVariant::Text`] style.
+    pub fn text(self) -> Self {
+        self.variant(ButtonVariant::Text)
+    }
+}
+
+/// Create a button showing `label`.
+///
+/// Hovering lightens it, pressing plays a ripple, and once pressed it keeps
+/// keyboard focus (shown with a focus ring) so Enter or Space click it again.
+pub fn button<M>(label: impl IntoSignal<String, M>) -> Button {
+    Button::new().label(label)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    use super::*;
+    use crate::layout::{Constraints, Size};
+    use crate::renderer::{Border, DrawCommand, PaintContext, RenderNode};
+    use crate::tree::{Tree, WidgetId};
+    use crate::widgets::widget::{Event, MouseButton, Widget};
+
+    fn mount(widget: impl Widget + 'static) -> (Tree, WidgetId) {
+        let mut tree = Tree::new();
+        let id = tree.register(Box::new(widget));
+        tree.with_widget_mut(id, |widget, id, tree| widget.register_children(tree, id));
+        tree.with_widget_mut(id, |widget, id, tree| {
+            widget.layout(tree, id, Constraints::loose(Size::new(200.0, 100.0)))
+        });
+        (tree, id)
+    }
+
+    /// Fill color and border of the painted button, if it draws a shape
+    fn background(tree: &Tree, id: WidgetId) -> Option<(Color, Option<Border>)> {
+        let mut node = RenderNode::new(id.as_u64());
+        tree.with_widget(id, |widget| {
+            widget.paint(tree, id, &mut PaintContext::new(&mut node))
+        });
+        node.commands.iter().find_map(|cmd| match cmd.as_ref() {
+            DrawCommand::RoundedRect { color, border, .. } => Some((*color, *border)),
+            _ => None,
+        })
+    }
+
+    fn fill(tree: &Tree, id: WidgetId) -> Color {
+        background(tree, id).unwrap().0
+    }
+
+    fn send(tree: &mut Tree, id: WidgetId, event: Event) {
+        tree.with_widget_mut(id, |widget, id, tree| widget.event(tree, id, &event));
+    }
+
+    #[test]
+    fn test_filled_button_clicks_and_follows_hover_and_press() {
+        let clicks = Rc::new(Cell::new(0));
+        let counter = clicks.clone();
+        let (mut tree, id) = mount(button("Save").on_click(move || counter.set(counter.get() + 1)));
+        // Clear of the rounded corners
+        let (x, y, button) = (30.0, 15.0, MouseButton::Left);
+        assert_eq!(fill(&tree, id), DEFAULT_BUTTON_COLOR);
+
+        send(&mut tree, id, Event::MouseMove { x, y });
+        assert_eq!(fill(&tree, id), DEFAULT_BUTTON_COLOR.lighter(0.1));
+        send(&mut tree, id, Event::MouseDown { x, y, button });
+        assert_eq!(fill(&tree, id), DEFAULT_BUTTON_COLOR.darker(0.05));
+        send(&mut tree, id, Event::MouseUp { x, y, button });
+        assert_eq!(clicks.get(), 1);
+        assert_eq!(fill(&tree, id), DEFAULT_BUTTON_COLOR.lighter(0.1));
+    }
+
+    #[test]
+    fn test_outlined_and_text_variants_use_the_accent_color() {
+        let accent = Color::rgb(0.9, 0.4, 0.4);
+        let (tree, id) = mount(button("Cancel").outlined().color(accent));
+        // Only the outline is drawn
+        let (fill, border) = background(&tree, id).unwrap();
+        assert_eq!(fill, Color::TRANSPARENT);
+        let border = border.unwrap();
+        assert_eq!((border.width, border.color), (1.0, accent));
+
+        let (tree, id) = mount(button("More").text().color(accent));
+        assert!(background(&tree, id).is_none());
+    }
+}
@@ -2,13 +2,11 @@ use std::collections::HashMap;
 use std::rc::Rc;
 
 use crate::jobs::{JobRequest, JobType, request_job};
-use crate::layout::{Constraints, Size};
 use crate::reactive::{OwnerId, dispose_owner, with_signal_tracking};
-use crate::renderer::PaintContext;
 use crate::tree::{Tree, WidgetId};
 
 use super::Widget;
-use super::widget::{Event, EventResponse};
+use super::widget::delegate_widget;
 
 /// Segment metadata - tracks what kind of source each segment is
 enum SegmentType {
@@ -424,31 +422,5 @@ impl Drop for OwnedWidget {
 }
 
 impl Widget for OwnedWidget {
-    fn advance_animations(&mut self, tree: &mut Tree, id: WidgetId) -> bool {
-        self.inner.advance_animations(tree, id)
-    }
-
-    fn reconcile_children(&mut self, tree: &mut Tree, id: WidgetId) -> bool {
-        self.inner.reconcile_children(tree, id)
-    }
-
-    fn register_children(&mut self, tree: &mut Tree, id: WidgetId) {
-        self.inner.register_children(tree, id)
-    }
-
-    fn layout(&mut self, tree: &mut Tree, id: WidgetId, constraints: Constraints) -> Size {
-        self.inner.layout(tree, id, constraints)
-    }
-
-    fn paint(&self, tree: &Tree, id: WidgetId, ctx: &mut PaintContext) {
-        self.inner.paint(tree, id, ctx)
-    }
-
-    fn event(&mut self, tree: &mut Tree, id: WidgetId, event: &Event) -> EventResponse {
-        self.inner.event(tree, id, event)
-    }
-
-    fn has_focus_descendant(&self, tree: &Tree, focused_id: WidgetId) -> bool {
-        self.inner.has_focus_descendant(tree, focused_id)
-    }
+    delegate_widget!(inner);
 }
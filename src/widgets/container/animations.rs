@@ -1,5 +1,4 @@
-use std::time::Instant;
-
+use crate::animation::clock::Stopwatch;
 use crate::animation::{Animatable, SpringState, Transition, TransitionConfig};
 
 /// Result of advancing an animation, indicating whether the value changed
@@ -28,14 +27,16 @@ pub struct AnimationState<T: Animatable> {
     start: T,
     /// Progress from 0.0 to 1.0 (or beyond for overshoot)
     progress: f32,
-    /// Time when animation started
-    start_time: Instant,
+    /// Animation time since the animation started
+    stopwatch: Stopwatch,
     /// Forward transition (used when value increases or no reverse is set)
     transition: Transition,
     /// Optional reverse transition (used when value decreases)
     reverse_transition: Option<Transition>,
     /// Whether the current animation is using the reverse transition
     using_reverse: bool,
+    /// Transitions set at creation, kept while a state transition overrides them
+    base_transition: Option<TransitionConfig>,
     /// Spring state (for spring timing functions)
     spring_state: Option<SpringState>,
     /// Whether the animation has been initialized with its first real value
@@ -60,10 +61,11 @@ impl<T: Animatable> AnimationState<T> {
             target: initial_value,
             start: initial_value,
             progress: 1.0, // Start completed
-            start_time: Instant::now(),
+            stopwatch: Stopwatch::start(),
             transition: config.forward,
             reverse_transition: config.reverse,
             using_reverse: false,
+            base_transition: None,
             spring_state,
             initialized: false, // Not yet initialized with real content-based value
             prev_value: None,
@@ -96,10 +98,20 @@ impl<T: Animatable> AnimationState<T> {
             crate::animation::TimingFunction::Spring(_)
         );
 
+        // Targets changed while finishing animations settle right away
+        if crate::animation::finishing_animations() {
+            self.current = new_target;
+            self.start = new_target;
+            self.target = new_target;
+            self.progress = 1.0;
+            self.spring_state = None;
+            return;
+        }
+
         self.start = self.current;
         self.target = new_target;
         self.progress = 0.0;
-        self.start_time = Instant::now();
+        self.stopwatch = Stopwatch::start();
         self.spring_state = if is_spring {
             Some(SpringState::new())
         } else {
@@ -107,12 +119,55 @@ impl<T: Animatable> AnimationState<T> {
         };
     }
 
+    /// Start animating to a new target value with a different transition.
+    ///
+    /// `transition` replaces the one the animation was created with until a
+    /// later call passes `None`, which restores it. Like
+    /// [`animate_to`](Self::animate_to), this does nothing while the target is
+    /// unchanged, so a running animation keeps its transition.
+    pub fn animate_to_with(&mut self, new_target: T, transition: Option<&TransitionConfig>) {
+        if new_target == self.target {
+            return;
+        }
+        let config = match transition {
+            Some(config) => {
+                if self.base_transition.is_none() {
+                    self.base_transition = Some(TransitionConfig {
+                        forward: self.transition.clone(),
+                        reverse: self.reverse_transition.clone(),
+                    });
+                }
+                Some(config.clone())
+            }
+            None => self.base_transition.take(),
+        };
+        if let Some(config) = config {
+            self.transition = config.forward;
+            self.reverse_transition = config.reverse;
+        }
+        self.animate_to(new_target);
+    }
+
     /// Advance the animation and return whether the value changed
     pub fn advance(&mut self) -> AdvanceResult<T> {
         if self.progress >= 1.0 && self.spring_state.is_none() {
             return AdvanceResult::NoChange;
         }
 
+        if crate::animation::skip_animations() {
+            // Resolve instantly instead of transitioning
+            self.progress = 1.0;
+            self.spring_state = None;
+            self.current = self.target;
+            let changed = self.prev_value.as_ref() != Some(&self.target);
+            self.prev_value = Some(self.target);
+            return if changed {
+                AdvanceResult::Changed(self.target)
+            } else {
+                AdvanceResult::NoChange
+            };
+        }
+
         // Extract scalar transition values upfront to avoid borrow conflicts
         // with self.spring_state. Copy SpringConfig (which is Copy) instead of
         // cloning the entire TimingFunction (which may contain an Arc).
@@ -124,7 +179,8 @@ impl<T: Animatable> AnimationState<T> {
             _ => None,
         };
 
-        let elapsed = self.start_time.elapsed().as_secs_f32() * 1000.0; // Convert to ms
+        // Capped per frame, so a stalled loop doesn't jump the animation ahead
+        let elapsed = self.stopwatch.tick().as_secs_f32() * 1000.0; // Convert to ms
         let adjusted_elapsed = (elapsed - delay_ms).max(0.0);
 
         if adjusted_elapsed <= 0.0 {
@@ -265,8 +321,12 @@ macro_rules! advance_anim {
     };
     // Paint animation with target update
     ($self:expr, $anim:ident, $target_expr:expr, $id:expr, $any_animating:expr, paint) => {
+        $crate::advance_anim!($self, $anim, $target_expr, None, $id, $any_animating, paint)
+    };
+    // Paint animation with target update, using a state transition when given
+    ($self:expr, $anim:ident, $target_expr:expr, $transition:expr, $id:expr, $any_animating:expr, paint) => {
         if let Some(ref mut anim) = $self.$anim {
-            anim.animate_to($target_expr);
+            anim.animate_to_with($target_expr, $transition);
             if anim.is_animating() {
                 $any_animating = true;
                 let required = if anim.advance().is_changed() {
@@ -292,10 +352,18 @@ pub fn get_animated_value<T: Animatable + Copy>(
     }
 }
 
+/// Helper to jump an optional animation straight to its target
+#[inline]
+pub fn snap_anim<T: Animatable>(anim: &mut Option<AnimationState<T>>, target: T) {
+    if let Some(anim) = anim {
+        anim.set_immediate(target);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::animation::TimingFunction;
+    use crate::animation::{SpringConfig, TimingFunction, clock};
 
     #[test]
     fn test_animation_state_new() {
@@ -325,11 +393,11 @@ mod tests {
         let mut state = AnimationState::new(0.0f32, transition);
 
         state.animate_to(100.0);
-        let first_start_time = state.start_time;
+        let first_stopwatch = state.stopwatch;
 
         // Animate to same target should not restart
         state.animate_to(100.0);
-        assert_eq!(state.start_time, first_start_time);
+        assert_eq!(state.stopwatch, first_stopwatch);
     }
 
     #[test]
@@ -366,6 +434,98 @@ mod tests {
         assert_eq!(value, 42.0);
     }
 
+    #[test]
+    fn test_virtual_clock_steps_transition() {
+        clock::pause_clock();
+        let transition = Transition::new(200.0, TimingFunction::EaseInOut);
+        let mut state = AnimationState::new(0.0f32, transition);
+        state.set_immediate(0.0);
+        state.animate_to(100.0);
+
+        clock::advance_clock(std::time::Duration::from_millis(100));
+        assert!(state.advance().is_changed());
+        let midpoint = TimingFunction::EaseInOut.evaluate(0.5) * 100.0;
+        assert!((*state.current() - midpoint).abs() < 1e-4);
+        assert!(state.is_animating());
+
+        clock::advance_clock(std::time::Duration::from_millis(100));
+        state.advance();
+        assert_eq!(*state.current(), 100.0);
+        assert!(!state.is_animating());
+        clock::resume_clock();
+    }
+
+    #[test]
+    fn test_animate_to_with_restores_base_transition() {
+        let mut state =
+            AnimationState::new(0.0f32, Transition::new(1000.0, TimingFunction::Linear));
+        state.set_immediate(0.0);
+
+        let fast = TransitionConfig::from(Transition::new(100.0, TimingFunction::Linear));
+        state.animate_to_with(100.0, Some(&fast));
+        assert_eq!(state.active_transition().duration_ms, 100.0);
+
+        // Without a state transition the original one comes back
+        state.animate_to_with(0.0, None);
+        assert_eq!(state.active_transition().duration_ms, 1000.0);
+        state.animate_to_with(50.0, None);
+        assert_eq!(state.active_transition().duration_ms, 1000.0);
+    }
+
+    #[test]
+    fn test_stall_resumes_without_jumping() {
+        clock::pause_clock();
+        let transition = Transition::new(1000.0, TimingFunction::Linear);
+        let mut state = AnimationState::new(0.0f32, transition);
+        state.set_immediate(0.0);
+        state.animate_to(100.0);
+
+        clock::advance_clock(std::time::Duration::from_millis(100));
+        state.advance();
+        assert!((*state.current() - 10.0).abs() < 1e-3);
+
+        // Frozen for a second: only one capped step is applied
+        clock::advance_clock(std::time::Duration::from_secs(1));
+        state.advance();
+        assert!((*state.current() - 20.0).abs() < 1e-3);
+        assert!(state.is_animating());
+        clock::resume_clock();
+    }
+
+    #[test]
+    fn test_finish_all_animations_settles_springs() {
+        let mut state = AnimationState::new(0.0f32, Transition::spring(SpringConfig::BOUNCY));
+        state.set_immediate(0.0);
+        state.animate_to(100.0);
+        assert!(state.is_animating());
+
+        crate::animation::finish_all_animations();
+        assert_eq!(state.advance(), AdvanceResult::Changed(100.0));
+        assert!(!state.is_animating());
+        // Targets set during the same frame settle too
+        state.animate_to(50.0);
+        assert_eq!(*state.current(), 50.0);
+        assert!(!state.is_animating());
+
+        // Later changes animate again
+        crate::animation::end_finish_animations();
+        state.animate_to(0.0);
+        assert!(state.is_animating());
+    }
+
+    #[test]
+    fn test_reduce_motion_resolves_instantly() {
+        crate::animation::set_reduce_motion(true);
+        let mut state = AnimationState::new(0.0f32, Transition::spring(SpringConfig::BOUNCY));
+        state.set_immediate(0.0);
+        state.animate_to(100.0);
+
+        assert_eq!(state.advance(), AdvanceResult::Changed(100.0));
+        assert_eq!(*state.current(), 100.0);
+        assert!(!state.is_animating());
+        crate::animation::set_reduce_motion(false);
+    }
+
     #[test]
     fn test_get_animated_value_with_none() {
         let value = get_animated_value::<f32>(None, || 99.0);
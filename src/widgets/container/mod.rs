@@ -4,19 +4,26 @@ mod animations;
 mod ripple;
 mod scrollable;
 
+use animations::snap_anim;
 pub use animations::{AdvanceResult, AnimationState, get_animated_value};
 pub use ripple::RippleState;
+use scrollable::ScrollbarFade;
 
 use std::borrow::Cow;
 use std::rc::Rc;
 
+use crate::accessibility::{AccessInfo, Role};
 use crate::advance_anim;
 use crate::animation::TransitionConfig;
 use crate::jobs::{JobRequest, JobType, RequiredJob, request_job};
-use crate::layout::{Constraints, Flex, Layout, Length, Size};
+use crate::layout::{
+    Constrained, Constraints, CrossAlignment, Flex, IntoF32, Layout, Length, MainAlignment,
+    Overlay, Size,
+};
 use crate::reactive::{
-    IntoSignal, OptionSignalExt, Signal, create_derived, create_stored, focused_widget,
-    with_signal_tracking,
+    CursorIcon, IntoSignal, OptionSignalExt, Signal, clear_focus, create_derived, create_stored,
+    enter_hover_cursor, focused_widget, has_focus, leave_hover_cursor, release_focus,
+    request_focus, with_signal_tracking,
 };
 use crate::renderer::{GradientDir, PaintContext, Shadow};
 use crate::transform::Transform;
@@ -26,12 +33,15 @@ use crate::widget_ref::{WidgetRef, register_widget_ref};
 
 use super::children::ChildrenSource;
 use super::into_child::{IntoChild, IntoChildren};
+use super::list_navigation::{ListNavigation, NavOutcome};
 use super::scroll::{
-    ScrollAxis, ScrollState, ScrollbarBuilder, ScrollbarConfig, ScrollbarVisibility,
+    ScrollAxis, ScrollState, ScrollbarBuilder, ScrollbarConfig, ScrollbarVisibility, SnapAlign,
 };
-use super::state_layer::{StateStyle, resolve_background};
+use super::state_layer::{RippleConfig, StateStyle, resolve_background};
+use super::style::WidgetStyle;
 use super::widget::{
-    Color, Event, EventResponse, LayoutHints, MouseButton, Padding, Rect, ScrollSource, Widget,
+    Color, Event, EventResponse, Key, KeyEvent, LayoutHints, MouseButton, Padding, Rect,
+    ScrollSource, Widget,
 };
 
 /// Callback for click events
@@ -40,12 +50,22 @@ pub type ClickCallback = Rc<dyn Fn()>;
 pub type HoverCallback = Rc<dyn Fn(bool)>;
 /// Callback for scroll events (delta_x, delta_y, source)
 pub type ScrollCallback = Rc<dyn Fn(f32, f32, ScrollSource)>;
+/// Callback for zoom gestures (scale factor, center in container-local coords)
+pub type ZoomCallback = Rc<dyn Fn(f32, (f32, f32))>;
 /// Callback for pointer move events (x, y in container-local coords)
 pub type PointerMoveCallback = Rc<dyn Fn(f32, f32)>;
 /// Callback for mouse down events (x, y in container-local coords)
 pub type MouseDownCallback = Rc<dyn Fn(f32, f32)>;
 /// Callback for mouse up events (x, y in container-local coords)
 pub type MouseUpCallback = Rc<dyn Fn(f32, f32)>;
+/// Callback for key presses while the container or a descendant is focused
+pub type KeyCallback = Rc<dyn Fn(&KeyEvent) -> EventResponse>;
+
+/// Zoom rate for Ctrl+scroll: one 40px wheel notch scales by about 10%
+const ZOOM_PER_PIXEL: f32 = 0.0024;
+
+/// Scrim dimming disabled containers that have no `disabled_state`
+const DISABLED_SCRIM: Color = Color::rgba(0.0, 0.0, 0.0, 0.4);
 
 /// Gradient direction for linear gradients
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -97,6 +117,16 @@ impl LinearGradient {
     }
 }
 
+impl From<&LinearGradient> for crate::renderer::Gradient {
+    fn from(gradient: &LinearGradient) -> Self {
+        Self {
+            start_color: gradient.start_color,
+            end_color: gradient.end_color,
+            direction: gradient.direction.into(),
+        }
+    }
+}
+
 /// Border definition
 #[derive(Debug, Clone, Copy)]
 pub struct Border {
@@ -135,6 +165,8 @@ pub(super) struct ContainerAnims {
     pub(super) border_width: Option<AnimationState<f32>>,
     pub(super) border_color: Option<AnimationState<Color>>,
     pub(super) transform: Option<AnimationState<Transform>>,
+    /// Height animates toward the natural content height (see `animate_height_auto`)
+    pub(super) height_auto: bool,
 }
 
 /// Interaction state (callbacks, hover/press tracking, state styles, ripple).
@@ -143,15 +175,35 @@ pub(super) struct InteractionState {
     pub(super) on_click: Option<ClickCallback>,
     pub(super) on_hover: Option<HoverCallback>,
     pub(super) on_scroll: Option<ScrollCallback>,
+    pub(super) on_zoom: Option<ZoomCallback>,
     pub(super) on_pointer_move: Option<PointerMoveCallback>,
+    /// Pointer moves over the container (see `on_hover_move`)
+    pub(super) on_hover_move: Option<PointerMoveCallback>,
     pub(super) on_mouse_down: Option<MouseDownCallback>,
     pub(super) on_mouse_up: Option<MouseUpCallback>,
+    pub(super) on_key: Option<KeyCallback>,
+    /// Cursor shown while hovered (see `cursor`)
+    pub(super) cursor: Option<Signal<CursorIcon>>,
     pub(super) is_hovered: bool,
     pub(super) is_pressed: bool,
     pub(super) hover_state: Option<StateStyle>,
     pub(super) pressed_state: Option<StateStyle>,
     pub(super) focused_state: Option<StateStyle>,
+    pub(super) disabled_state: Option<StateStyle>,
+    /// Ignore input and show the disabled style (see `disabled`)
+    pub(super) disabled: Option<Signal<bool>>,
     pub(super) ripple: RippleState,
+    /// Ripple set with `ripple`, independent of the pressed state
+    pub(super) ripple_config: Option<RippleConfig>,
+    /// Reactive ripple color set with `ripple_color`
+    pub(super) ripple_color: Option<Signal<Color>>,
+    /// Minimum pointer target size, centered on the visual bounds
+    pub(super) min_hit_size: Option<Size>,
+    /// Take keyboard focus when pressed (see `focusable`)
+    pub(super) focusable: bool,
+    /// Own id, recorded by focusable containers so focused styling can
+    /// check for focus on the container itself
+    pub(super) focus_id: Option<WidgetId>,
 }
 
 impl Default for InteractionState {
@@ -160,31 +212,77 @@ impl Default for InteractionState {
             on_click: None,
             on_hover: None,
             on_scroll: None,
+            on_zoom: None,
             on_pointer_move: None,
+            on_hover_move: None,
             on_mouse_down: None,
             on_mouse_up: None,
+            on_key: None,
+            cursor: None,
             is_hovered: false,
             is_pressed: false,
             hover_state: None,
             pressed_state: None,
             focused_state: None,
+            disabled_state: None,
+            disabled: None,
             ripple: RippleState::new(),
+            ripple_config: None,
+            ripple_color: None,
+            min_hit_size: None,
+            focusable: false,
+            focus_id: None,
         }
     }
 }
 
+impl InteractionState {
+    /// The ripple played on press: the one set with `ripple`, or else the
+    /// pressed state's.
+    fn ripple_config(&self) -> Option<&RippleConfig> {
+        self.ripple_config.as_ref().or_else(|| {
+            self.pressed_state
+                .as_ref()
+                .and_then(|state| state.ripple.as_ref())
+        })
+    }
+}
+
+/// Callback fired when a scrollable container is scrolled near its end.
+pub(super) struct ScrollEndHandler {
+    /// Distance from the end (logical pixels) that counts as "at the end"
+    pub(super) threshold: f32,
+    pub(super) callback: Rc<dyn Fn()>,
+    /// Already fired; re-armed once scrolled (or grown) away from the end
+    pub(super) fired: bool,
+}
+
+/// Gradient fades drawn at the viewport edges of a scrollable container.
+pub(super) struct ScrollFade {
+    pub(super) size: f32,
+    /// Defaults to the container background
+    pub(super) color: Option<Signal<Color>>,
+}
+
 /// Scroll state and configuration, boxed to avoid bloating Container.
 /// Only allocated when `.scrollable()` is called.
 pub(super) struct ScrollData {
     pub(super) scrollbar_visibility: ScrollbarVisibility,
     pub(super) scrollbar_config: ScrollbarConfig,
     pub(super) scroll_state: ScrollState,
+    pub(super) snap: Option<SnapAlign>,
+    pub(super) on_scroll_end: Option<ScrollEndHandler>,
     pub(super) v_scrollbar_track_id: Option<WidgetId>,
     pub(super) v_scrollbar_handle_id: Option<WidgetId>,
     pub(super) v_scrollbar_scale_anim: Option<AnimationState<f32>>,
     pub(super) h_scrollbar_track_id: Option<WidgetId>,
     pub(super) h_scrollbar_handle_id: Option<WidgetId>,
     pub(super) h_scrollbar_scale_anim: Option<AnimationState<f32>>,
+    /// Only set for `ScrollbarVisibility::AutoHide`
+    pub(super) scrollbar_fade: Option<ScrollbarFade>,
+    pub(super) edge_fade: Option<ScrollFade>,
+    /// Children marked `sticky`, in content order
+    pub(super) sticky_children: Vec<WidgetId>,
 }
 
 impl Default for ScrollData {
@@ -193,12 +291,17 @@ impl Default for ScrollData {
             scrollbar_visibility: ScrollbarVisibility::Always,
             scrollbar_config: ScrollbarConfig::default(),
             scroll_state: ScrollState::default(),
+            snap: None,
+            on_scroll_end: None,
             v_scrollbar_track_id: None,
             v_scrollbar_handle_id: None,
             v_scrollbar_scale_anim: None,
             h_scrollbar_track_id: None,
             h_scrollbar_handle_id: None,
             h_scrollbar_scale_anim: None,
+            scrollbar_fade: None,
+            edge_fade: None,
+            sticky_children: Vec::new(),
         }
     }
 }
@@ -210,19 +313,30 @@ pub struct Container {
 
     // Styling properties
     pub(super) padding: Option<Signal<Padding>>,
+    pub(super) margin: Option<Signal<Padding>>,
     pub(super) background: Option<Signal<Color>>,
     pub(super) gradient: Option<LinearGradient>,
     pub(super) corner_radius: Option<Signal<f32>>,
     pub(super) corner_curvature: Option<Signal<f32>>,
     pub(super) border_width: Option<Signal<f32>>,
     pub(super) border_color: Option<Signal<Color>>,
+    pub(super) border_gradient: Option<LinearGradient>,
     pub(super) elevation: Option<Signal<f32>>,
+    pub(super) shadow_color: Option<Signal<Color>>,
     pub(super) width: Option<Signal<Length>>,
     pub(super) height: Option<Signal<Length>>,
+    pub(super) aspect_ratio: Option<Signal<f32>>,
+    pub(super) fit_content: bool,
     pub(super) overflow: Overflow,
+    pub(super) clip_circle: bool,
+    pub(super) repaint_boundary: bool,
+    pub(super) pixel_snap: bool,
     pub(super) visible: Option<Signal<bool>>,
+    pub(super) z_index: Option<Signal<i32>>,
+    pub(super) access_info: Option<Signal<AccessInfo>>,
     pub(super) transform: Option<Signal<Transform>>,
     pub(super) transform_origin: Option<Signal<TransformOrigin>>,
+    pub(super) pivot_transforms: Vec<PivotTransform>,
 
     // Interaction state (callbacks, hover/press, state styles, ripple)
     // Only allocated when interaction features are used
@@ -231,12 +345,21 @@ pub struct Container {
     // Widget ref for reactive bounds tracking
     pub(super) widget_ref: Option<WidgetRef>,
 
+    // Stable name for locating the container in tests and inspectors
+    pub(super) test_id: Option<String>,
+
+    // Pinned to the top of a scrollable parent's viewport (see `sticky`)
+    pub(super) sticky: bool,
+
     // Animation state (boxed to save ~400 bytes per non-animated container)
     pub(super) anims: Option<Box<ContainerAnims>>,
 
     // Scroll configuration
     pub(super) scroll_axis: ScrollAxis,
     pub(super) scroll_data: Option<Box<ScrollData>>,
+
+    // Keyboard list navigation (roving focus over children)
+    pub(super) list_nav: Option<Box<ListNavigation>>,
 }
 
 impl Container {
@@ -246,24 +369,38 @@ impl Container {
             layout: Box::new(Flex::column()),
             children_source,
             padding: None,
+            margin: None,
             background: None,
             gradient: None,
             corner_radius: None,
             corner_curvature: None,
             border_width: None,
             border_color: None,
+            border_gradient: None,
             elevation: None,
+            shadow_color: None,
             width: None,
             height: None,
+            aspect_ratio: None,
+            fit_content: false,
             overflow: Overflow::Visible,
+            clip_circle: false,
+            repaint_boundary: false,
+            pixel_snap: false,
             visible: None,
+            z_index: None,
+            access_info: None,
             transform: None,
             transform_origin: None,
+            pivot_transforms: Vec::new(),
             interaction: None,
             widget_ref: None,
+            test_id: None,
+            sticky: false,
             anims: None,
             scroll_axis: ScrollAxis::None,
             scroll_data: None,
+            list_nav: None,
         }
     }
 
@@ -300,6 +437,31 @@ impl Container {
         self
     }
 
+    /// Set the spacing between children of the current layout.
+    ///
+    /// Shorthand for [`Flex::spacing`]; call it after [`layout`](Self::layout),
+    /// which replaces the layout. Overlay layouts ignore it.
+    pub fn gap<M>(mut self, gap: impl IntoSignal<f32, M>) -> Self {
+        self.layout.set_spacing(gap.into_signal());
+        self
+    }
+
+    /// Set the main axis alignment of the current layout.
+    ///
+    /// Shorthand for [`Flex::main_alignment`]; call it after [`layout`](Self::layout).
+    pub fn main_align<M>(mut self, alignment: impl IntoSignal<MainAlignment, M>) -> Self {
+        self.layout.set_main_alignment(alignment.into_signal());
+        self
+    }
+
+    /// Set the cross axis alignment of the current layout.
+    ///
+    /// Shorthand for [`Flex::cross_alignment`]; call it after [`layout`](Self::layout).
+    pub fn cross_align<M>(mut self, alignment: impl IntoSignal<CrossAlignment, M>) -> Self {
+        self.layout.set_cross_alignment(alignment.into_signal());
+        self
+    }
+
     /// Add a single child (static or dynamic)
     pub fn child<M>(mut self, child: impl IntoChild<M>) -> Self {
         child.add_to_container(&mut self.children_source);
@@ -326,6 +488,61 @@ impl Container {
         self
     }
 
+    /// Apply a shared [`WidgetStyle`].
+    ///
+    /// Only properties the container doesn't set itself are taken from the
+    /// style, so inline builders win whether they are called before or after
+    /// this. State styles are taken as a whole: an inline
+    /// [`hover_state`](Self::hover_state) replaces the style's.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let card_style = WidgetStyle::new()
+    ///     .padding(16.0)
+    ///     .background(Color::rgb(0.15, 0.15, 0.2))
+    ///     .corner_radius(8.0);
+    ///
+    /// container().style(&card_style).child(text("Card"))
+    /// ```
+    pub fn style(mut self, style: &WidgetStyle) -> Self {
+        fn fill<T>(field: &mut Option<T>, value: &Option<T>)
+        where
+            T: Clone,
+        {
+            if field.is_none() {
+                field.clone_from(value);
+            }
+        }
+        fill(&mut self.padding, &style.padding);
+        fill(&mut self.background, &style.background);
+        fill(&mut self.corner_radius, &style.corner_radius);
+        fill(&mut self.border_width, &style.border_width);
+        fill(&mut self.border_color, &style.border_color);
+        fill(&mut self.elevation, &style.elevation);
+        fill(&mut self.shadow_color, &style.shadow_color);
+        fill(&mut self.width, &style.width);
+        fill(&mut self.height, &style.height);
+
+        // A state set inline replaces the style's
+        let ix = self.interaction.as_deref();
+        let unset = |state: &Option<StateStyle>, inline: fn(&InteractionState) -> bool| {
+            state.clone().filter(|_| !ix.is_some_and(inline))
+        };
+        let hover = unset(&style.hover_state, |ix| ix.hover_state.is_some());
+        let pressed = unset(&style.pressed_state, |ix| ix.pressed_state.is_some());
+        let focused = unset(&style.focused_state, |ix| ix.focused_state.is_some());
+        if let Some(state) = hover {
+            self = self.hover_state(|_| state);
+        }
+        if let Some(state) = pressed {
+            self = self.pressed_state(|_| state);
+        }
+        if let Some(state) = focused {
+            self = self.focused_state(|_| state);
+        }
+        self
+    }
+
     /// Set padding in logical pixels.
     ///
     /// Accepts multiple formats via `From` conversions:
@@ -339,6 +556,95 @@ impl Container {
         self
     }
 
+    /// Set padding per side as `top, right, bottom, left`.
+    ///
+    /// Each side accepts a static value, signal or closure independently;
+    /// changing any of them re-lays out the container.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// container().padding_each(8, 16, 8, move || if wide.get() { 32.0 } else { 16.0 })
+    /// ```
+    pub fn padding_each<M1, M2, M3, M4>(
+        mut self,
+        top: impl IntoSignal<f32, M1>,
+        right: impl IntoSignal<f32, M2>,
+        bottom: impl IntoSignal<f32, M3>,
+        left: impl IntoSignal<f32, M4>,
+    ) -> Self {
+        let (top, right, bottom, left) = (
+            top.into_signal(),
+            right.into_signal(),
+            bottom.into_signal(),
+            left.into_signal(),
+        );
+        self.padding = Some(create_derived(move || Padding {
+            top: top.get(),
+            right: right.get(),
+            bottom: bottom.get(),
+            left: left.get(),
+        }));
+        self
+    }
+
+    /// Set the outer margin in logical pixels.
+    ///
+    /// The parent `Flex` or `Overlay` layout keeps this much extra space
+    /// around the container. Margins of adjacent children add up (they do not
+    /// collapse), and background, borders and hit-testing stay within the
+    /// container's own bounds. Accepts the same formats as [`padding`](Self::padding).
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // 16px between the two cards, 8px from the column's edges
+    /// container()
+    ///     .layout(Flex::column())
+    ///     .child(container().margin(8).child(card_a))
+    ///     .child(container().margin(8).child(card_b))
+    /// ```
+    pub fn margin<M>(mut self, value: impl IntoSignal<Padding, M>) -> Self {
+        self.margin = Some(value.into_signal());
+        self
+    }
+
+    /// Set the horizontal (left/right) and vertical (top/bottom) margin.
+    pub fn margin_xy<M1, M2>(
+        mut self,
+        horizontal: impl IntoSignal<f32, M1>,
+        vertical: impl IntoSignal<f32, M2>,
+    ) -> Self {
+        let (horizontal, vertical) = (horizontal.into_signal(), vertical.into_signal());
+        self.margin = Some(create_derived(move || {
+            Padding::symmetric(horizontal.get(), vertical.get())
+        }));
+        self
+    }
+
+    /// Set the margin per side as `top, right, bottom, left`.
+    pub fn margin_each<M1, M2, M3, M4>(
+        mut self,
+        top: impl IntoSignal<f32, M1>,
+        right: impl IntoSignal<f32, M2>,
+        bottom: impl IntoSignal<f32, M3>,
+        left: impl IntoSignal<f32, M4>,
+    ) -> Self {
+        let (top, right, bottom, left) = (
+            top.into_signal(),
+            right.into_signal(),
+            bottom.into_signal(),
+            left.into_signal(),
+        );
+        self.margin = Some(create_derived(move || Padding {
+            top: top.get(),
+            right: right.get(),
+            bottom: bottom.get(),
+            left: left.get(),
+        }));
+        self
+    }
+
     /// Set the background fill color.
     ///
     /// Supports RGBA transparency. Use [`Color::TRANSPARENT`] for no background.
@@ -407,6 +713,26 @@ impl Container {
         self
     }
 
+    /// Set a border whose color follows a linear gradient.
+    ///
+    /// The gradient runs across the container's bounds, so the outline fades
+    /// between the two colors while following the corner radius.
+    ///
+    /// ```ignore
+    /// container()
+    ///     .corner_radius(24.0)
+    ///     .gradient_border(3.0, LinearGradient::horizontal(Color::rgb(1.0, 0.3, 0.5), Color::rgb(0.3, 0.5, 1.0)))
+    /// ```
+    pub fn gradient_border<M>(
+        mut self,
+        width: impl IntoSignal<f32, M>,
+        gradient: LinearGradient,
+    ) -> Self {
+        self.border_width = Some(width.into_signal());
+        self.border_gradient = Some(gradient);
+        self
+    }
+
     /// Set a linear gradient background
     pub fn gradient(mut self, gradient: LinearGradient) -> Self {
         self.gradient = Some(gradient);
@@ -443,6 +769,92 @@ impl Container {
         self
     }
 
+    /// Clip this container and its children to a circle (a stadium when not square).
+    ///
+    /// The corner radius follows the size (half the smaller side), so the shape
+    /// stays round while the container resizes. Typical for avatars:
+    ///
+    /// ```ignore
+    /// container()
+    ///     .width(48.0)
+    ///     .height(48.0)
+    ///     .clip_circle()
+    ///     .child(image("avatar.png").content_fit(ContentFit::Cover))
+    /// ```
+    pub fn clip_circle(mut self) -> Self {
+        self.clip_circle = true;
+        self.overflow = Overflow::Hidden;
+        self
+    }
+
+    /// Isolate this container's subtree from its ancestors' repaints.
+    ///
+    /// Normally a relayout repaints the whole re-laid-out subtree. A repaint
+    /// boundary keeps its cached paint (and flattened commands) unless its own
+    /// content changes, so a frequently updating neighbor doesn't invalidate
+    /// it.
+    ///
+    /// A boundary always clips its content to its bounds, whatever
+    /// [`overflow`](Self::overflow) is set to: damage for its updates covers
+    /// only those bounds, so anything drawn outside would go stale.
+    ///
+    /// Give the boundary a fixed size so it is also a relayout boundary: its
+    /// own updates then only damage its bounds instead of the whole surface.
+    ///
+    /// ```ignore
+    /// container()
+    ///     .layout(Flex::row())
+    ///     .child(repaint_boundary(logo()))
+    ///     .child(container().width(80.0).height(24.0).repaint_boundary().child(clock()))
+    /// ```
+    pub fn repaint_boundary(mut self) -> Self {
+        self.repaint_boundary = true;
+        self
+    }
+
+    /// Size the content to the widest child's intrinsic width.
+    ///
+    /// Children are first measured without a width limit, with
+    /// [`fill()`](crate::layout::fill) widths treated as shrink-to-fit, and
+    /// then laid out within the widest measured width. Stretched and filling
+    /// children therefore line up with the widest one instead of the available
+    /// space, which suits menus and tooltips. Has no effect with an exact
+    /// width or horizontal scrolling.
+    ///
+    /// ```ignore
+    /// container()
+    ///     .fit_content()
+    ///     .layout(Flex::column())
+    ///     .child(menu_item("Open").width(fill()))
+    ///     .child(menu_item("Open Recent…").width(fill()))
+    /// ```
+    pub fn fit_content(mut self) -> Self {
+        self.fit_content = true;
+        self
+    }
+
+    /// Snap the background and border to the physical pixel grid.
+    ///
+    /// Keeps thin lines crisp instead of blurring them across two device
+    /// pixels at fractional positions or scales. Edges are rounded to whole
+    /// pixels, so use it for dividers and hairline borders rather than on
+    /// animated or transformed containers. Pair it with [`hairline`] for
+    /// lines exactly one device pixel thick:
+    ///
+    /// ```ignore
+    /// let line = hairline();
+    /// container()
+    ///     .height(move || line.get())
+    ///     .background(Color::rgba(1.0, 1.0, 1.0, 0.1))
+    ///     .pixel_snap()
+    /// ```
+    ///
+    /// [`hairline`]: crate::surface::hairline
+    pub fn pixel_snap(mut self) -> Self {
+        self.pixel_snap = true;
+        self
+    }
+
     /// Set visibility of this container.
     ///
     /// When `visible` is false, the container takes up no space in layout,
@@ -452,6 +864,40 @@ impl Container {
         self
     }
 
+    /// Set the stacking order of this container among its siblings.
+    ///
+    /// Siblings with a higher z-index are painted on top and receive pointer
+    /// events first, regardless of declaration order. Most useful with the
+    /// [`Overlay`](crate::layout::Overlay) layout. Defaults to 0.
+    pub fn z_index<M>(mut self, z: impl IntoSignal<i32, M>) -> Self {
+        self.z_index = Some(z.into_signal());
+        self
+    }
+
+    /// Describe this container for assistive technology, replacing the
+    /// default `Button` (clickable) or `Group` role.
+    ///
+    /// ```ignore
+    /// container()
+    ///     .on_click(move || enabled.update(|e| *e = !*e))
+    ///     .access_info(move || AccessInfo::new(Role::RadioButton).checked(enabled.get()))
+    /// ```
+    pub fn access_info<M>(mut self, info: impl IntoSignal<AccessInfo, M>) -> Self {
+        self.access_info = Some(info.into_signal());
+        self
+    }
+
+    /// Make this container a keyboard-navigable list of its children.
+    ///
+    /// Clicking the container gives it keyboard focus (one tab stop for the
+    /// whole group). While focused, Up/Down/Home/End move the highlighted index
+    /// and Enter activates the highlighted child. Scrollable containers scroll
+    /// the highlighted child into view. See [`ListNavigation`].
+    pub fn list_navigation(mut self, nav: ListNavigation) -> Self {
+        self.list_nav = Some(Box::new(nav));
+        self
+    }
+
     /// Enable scrolling on this container.
     pub fn scrollable(mut self, axis: ScrollAxis) -> Self {
         self.scroll_axis = axis;
@@ -461,12 +907,71 @@ impl Container {
         self
     }
 
+    /// Snap the scroll offset to a child when scrolling settles.
+    ///
+    /// After a wheel scroll, touch fling or scrollbar drag ends, the content
+    /// animates so the nearest child is aligned according to `align`.
+    /// Call after [`scrollable`](Self::scrollable).
+    pub fn scroll_snap(mut self, align: SnapAlign) -> Self {
+        self.scroll_or_init().snap = Some(align);
+        self
+    }
+
+    /// Call `callback` when the user scrolls within `threshold` pixels of the end.
+    ///
+    /// Fires once, then re-arms when the offset moves back beyond the threshold
+    /// (e.g. after more items were appended). Uses the vertical axis when it
+    /// scrolls, otherwise the horizontal one. Call after
+    /// [`scrollable`](Self::scrollable).
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// container()
+    ///     .scrollable(ScrollAxis::Vertical)
+    ///     .on_scroll_end(200.0, move || load_more(items))
+    /// ```
+    pub fn on_scroll_end<F: Fn() + 'static>(mut self, threshold: f32, callback: F) -> Self {
+        self.scroll_or_init().on_scroll_end = Some(ScrollEndHandler {
+            threshold: threshold.max(0.0),
+            callback: Rc::new(callback),
+            fired: false,
+        });
+        self
+    }
+
     /// Configure scrollbar visibility.
     pub fn scrollbar_visibility(mut self, visibility: ScrollbarVisibility) -> Self {
         self.scroll_or_init().scrollbar_visibility = visibility;
         self
     }
 
+    /// Fade content out over `size` pixels at viewport edges with more content
+    /// beyond them.
+    ///
+    /// The fade blends into the container background; set
+    /// [`scroll_fade_color`](Self::scroll_fade_color) when the background is
+    /// transparent. Call after [`scrollable`](Self::scrollable).
+    pub fn scroll_fade(mut self, size: f32) -> Self {
+        let sd = self.scroll_or_init();
+        match sd.edge_fade {
+            Some(ref mut fade) => fade.size = size,
+            None => sd.edge_fade = Some(ScrollFade { size, color: None }),
+        }
+        self
+    }
+
+    /// Color the [`scroll_fade`](Self::scroll_fade) edges blend into.
+    pub fn scroll_fade_color<M>(mut self, color: impl IntoSignal<Color, M>) -> Self {
+        let color = Some(color.into_signal());
+        let sd = self.scroll_or_init();
+        match sd.edge_fade {
+            Some(ref mut fade) => fade.color = color,
+            None => sd.edge_fade = Some(ScrollFade { size: 0.0, color }),
+        }
+        self
+    }
+
     /// Customize scrollbar appearance.
     pub fn scrollbar<F>(mut self, f: F) -> Self
     where
@@ -477,6 +982,46 @@ impl Container {
         self
     }
 
+    /// Expand the hover/click target to at least `width` x `height`.
+    ///
+    /// The extra area is centered on the container and only affects pointer
+    /// hit-testing; layout and painting keep the visual size. Useful for
+    /// small icon buttons.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// container()
+    ///     .width(16)
+    ///     .height(16)
+    ///     .min_hit_size(40, 40)
+    ///     .on_click(close)
+    ///     .child(image(CLOSE_ICON))
+    /// ```
+    pub fn min_hit_size(mut self, width: impl IntoF32, height: impl IntoF32) -> Self {
+        self.interact_mut().min_hit_size = Some(Size::new(width.into_f32(), height.into_f32()));
+        self
+    }
+
+    /// Show `cursor` while the pointer is over this container.
+    ///
+    /// The cursor is restored when the pointer leaves. When nested containers
+    /// declare cursors, the innermost hovered one wins, and leaving it shows
+    /// its hovered ancestor's cursor again.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// container()
+    ///     .cursor(CursorIcon::Pointer)
+    ///     .on_click(open_docs)
+    ///     .child(text("Documentation").color(Color::rgb(0.4, 0.6, 1.0)))
+    /// ```
+    pub fn cursor<M>(mut self, cursor: impl IntoSignal<CursorIcon, M>) -> Self {
+        self.interact_mut().cursor = Some(cursor.into_signal());
+        self
+    }
+
     pub fn on_click<F: Fn() + 'static>(mut self, callback: F) -> Self {
         self.interact_mut().on_click = Some(Rc::new(callback));
         self
@@ -500,11 +1045,66 @@ impl Container {
         self
     }
 
+    /// Interpret Ctrl+scroll and touchpad pinches over this container as a zoom gesture.
+    ///
+    /// The callback receives a multiplicative scale factor (> 1 zooms in) and
+    /// the pointer position in container-local coordinates, so content can
+    /// zoom toward the cursor. Plain scrolling is unaffected.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let zoom = create_signal(1.0f32);
+    /// let pan = create_signal((0.0f32, 0.0f32));
+    /// container()
+    ///     .on_zoom(move |factor, (cx, cy)| {
+    ///         // Keep the point under the cursor fixed while scaling
+    ///         let (px, py) = pan.get();
+    ///         pan.set((cx - (cx - px) * factor, cy - (cy - py) * factor));
+    ///         zoom.update(|z| *z *= factor);
+    ///     })
+    ///     .child(
+    ///         container()
+    ///             .transform(move || {
+    ///                 let (px, py) = pan.get();
+    ///                 Transform::translate(px, py).then(&Transform::scale(zoom.get()))
+    ///             })
+    ///             .transform_origin(TransformOrigin::TOP_LEFT)
+    ///             .child(image("map.png")),
+    ///     )
+    /// ```
+    pub fn on_zoom<F: Fn(f32, (f32, f32)) + 'static>(mut self, callback: F) -> Self {
+        self.interact_mut().on_zoom = Some(Rc::new(callback));
+        self
+    }
+
     pub fn on_pointer_move<F: Fn(f32, f32) + 'static>(mut self, callback: F) -> Self {
         self.interact_mut().on_pointer_move = Some(Rc::new(callback));
         self
     }
 
+    /// Track the pointer while it hovers the container.
+    ///
+    /// The callback receives the position in container-local coordinates
+    /// when the pointer enters and on every move over the container. Unlike
+    /// [`on_pointer_move`](Self::on_pointer_move), it doesn't fire while a
+    /// press drags the pointer outside.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let spot = create_signal((0.0, 0.0));
+    ///
+    /// container()
+    ///     .layout(Overlay::new())
+    ///     .on_hover_move(move |x, y| spot.set((x, y)))
+    ///     .child(highlight(spot))
+    ///     .child(card_content())
+    /// ```
+    pub fn on_hover_move<F: Fn(f32, f32) + 'static>(mut self, callback: F) -> Self {
+        self.interact_mut().on_hover_move = Some(Rc::new(callback));
+        self
+    }
+
     pub fn on_mouse_down<F: Fn(f32, f32) + 'static>(mut self, callback: F) -> Self {
         self.interact_mut().on_mouse_down = Some(Rc::new(callback));
         self
@@ -515,17 +1115,92 @@ impl Container {
         self
     }
 
+    /// Handle key presses while this container or one of its descendants
+    /// has keyboard focus.
+    ///
+    /// Focused descendants see the key first; the handler only runs for keys
+    /// they ignore. Return [`EventResponse::Handled`] to stop the key there.
+    /// Pair it with [`focusable`](Self::focusable) so clicking the container
+    /// focuses it.
+    ///
+    /// ```ignore
+    /// container()
+    ///     .focusable()
+    ///     .on_key(move |e| match e.key {
+    ///         Key::Left => { board.update(|b| b.move_left()); EventResponse::Handled }
+    ///         Key::Right => { board.update(|b| b.move_right()); EventResponse::Handled }
+    ///         _ => EventResponse::Ignored,
+    ///     })
+    /// ```
+    pub fn on_key<F: Fn(&KeyEvent) -> EventResponse + 'static>(mut self, callback: F) -> Self {
+        self.interact_mut().on_key = Some(Rc::new(callback));
+        self
+    }
+
     /// Attach a [`WidgetRef`] to track this container's surface-relative bounds.
     pub fn widget_ref(mut self, r: WidgetRef) -> Self {
         self.widget_ref = Some(r);
         self
     }
 
+    /// Tag this container with a stable name.
+    ///
+    /// Tests and debugging tools can look it up with
+    /// [`Tree::find_by_test_id`] instead of relying on coordinates.
+    pub fn test_id(mut self, id: impl Into<String>) -> Self {
+        self.test_id = Some(id.into());
+        self
+    }
+
+    /// Pin this container to the top of its scrollable parent's viewport.
+    ///
+    /// Once scrolled past, a sticky child stays at the top until the next
+    /// sticky sibling pushes it out, like section headers in a grouped list.
+    /// Only vertically scrollable parents honor it.
+    pub fn sticky(mut self) -> Self {
+        self.sticky = true;
+        self
+    }
+
+    /// Keep this container at a fixed width:height ratio.
+    ///
+    /// One dimension is derived from the other: an explicit width or height
+    /// drives the other side, otherwise the container takes the largest size
+    /// with this ratio that fits the available space.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // 16:9 thumbnail that scales with the parent's width
+    /// container().aspect_ratio(16.0 / 9.0).background(Color::BLACK)
+    /// ```
+    pub fn aspect_ratio<M>(mut self, ratio: impl IntoSignal<f32, M>) -> Self {
+        self.aspect_ratio = Some(ratio.into_signal());
+        self
+    }
+
     pub fn elevation<M>(mut self, level: impl IntoSignal<f32, M>) -> Self {
         self.elevation = Some(level.into_signal());
         self
     }
 
+    /// Tint the elevation shadow with a custom color instead of the default black.
+    ///
+    /// The color is used as-is, including its alpha.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // Accent-colored glow under a card
+    /// container()
+    ///     .elevation(4.0)
+    ///     .shadow_color(accent.with_alpha(0.4))
+    /// ```
+    pub fn shadow_color<M>(mut self, color: impl IntoSignal<Color, M>) -> Self {
+        self.shadow_color = Some(color.into_signal());
+        self
+    }
+
     /// Set the transform for this container
     pub fn transform<M>(mut self, t: impl IntoSignal<Transform, M>) -> Self {
         self.transform = Some(t.into_signal());
@@ -588,19 +1263,74 @@ impl Container {
         self
     }
 
-    /// Enable animation for width changes
-    pub fn animate_width(mut self, transition: impl Into<TransitionConfig>) -> Self {
-        let initial = self
-            .width
-            .as_ref()
-            .map(|w| {
-                let len = w.get();
-                len.exact.or(len.min).unwrap_or(0.0)
-            })
-            .unwrap_or(0.0);
-        self.anims_mut().width = Some(AnimationState::new(initial, transition));
-        self
-    }
+    /// Apply a transform around its own pivot point instead of the shared
+    /// [`transform_origin`](Self::transform_origin).
+    ///
+    /// Pivoted transforms compose in the order they are added, after the
+    /// transform built by [`transform`](Self::transform), [`rotate`](Self::rotate),
+    /// [`scale`](Self::scale) and [`translate`](Self::translate) (so call those
+    /// first). They are not affected by state layers or `animate_transform`.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // Swing around the top-left corner, then grow from the center
+    /// container()
+    ///     .rotate_around(TransformOrigin::TOP_LEFT, 45.0)
+    ///     .scale_around(TransformOrigin::CENTER, 1.2)
+    /// ```
+    pub fn transform_around<M1, M2>(
+        mut self,
+        origin: impl IntoSignal<TransformOrigin, M1>,
+        t: impl IntoSignal<Transform, M2>,
+    ) -> Self {
+        self.pivot_transforms.push(PivotTransform {
+            transform: t.into_signal(),
+            origin: origin.into_signal(),
+        });
+        self
+    }
+
+    /// Rotate this container by the given angle in degrees around `origin`.
+    ///
+    /// See [`transform_around`](Self::transform_around).
+    pub fn rotate_around<M1, M2>(
+        self,
+        origin: impl IntoSignal<TransformOrigin, M1>,
+        degrees: impl IntoSignal<f32, M2>,
+    ) -> Self {
+        let degrees = degrees.into_signal();
+        self.transform_around(
+            origin,
+            create_derived(move || Transform::rotate_degrees(degrees.get())),
+        )
+    }
+
+    /// Scale this container uniformly around `origin`.
+    ///
+    /// See [`transform_around`](Self::transform_around).
+    pub fn scale_around<M1, M2>(
+        self,
+        origin: impl IntoSignal<TransformOrigin, M1>,
+        s: impl IntoSignal<f32, M2>,
+    ) -> Self {
+        let s = s.into_signal();
+        self.transform_around(origin, create_derived(move || Transform::scale(s.get())))
+    }
+
+    /// Enable animation for width changes
+    pub fn animate_width(mut self, transition: impl Into<TransitionConfig>) -> Self {
+        let initial = self
+            .width
+            .as_ref()
+            .map(|w| {
+                let len = w.get();
+                len.exact.or(len.min).unwrap_or(0.0)
+            })
+            .unwrap_or(0.0);
+        self.anims_mut().width = Some(AnimationState::new(initial, transition));
+        self
+    }
 
     /// Enable animation for height changes
     pub fn animate_height(mut self, transition: impl Into<TransitionConfig>) -> Self {
@@ -616,6 +1346,29 @@ impl Container {
         self
     }
 
+    /// Animate the height toward the natural height of the content.
+    ///
+    /// Children are always laid out at their full height and the container
+    /// animates toward it whenever the content grows or shrinks. An exact
+    /// height (typically `0`) still wins, so switching between
+    /// [`auto()`](crate::layout::auto) and `0` expands and collapses the
+    /// container. Children are clipped to the animated height.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let open = create_signal(false);
+    ///
+    /// container()
+    ///     .height(move || if open.get() { auto() } else { Length::exact(0) })
+    ///     .animate_height_auto(Transition::new(250.0, TimingFunction::EaseInOut))
+    ///     .child(details())
+    /// ```
+    pub fn animate_height_auto(mut self, transition: impl Into<TransitionConfig>) -> Self {
+        self = self.animate_height(transition);
+        self.anims_mut().height_auto = true;
+        self
+    }
+
     /// Enable animation for background color changes
     pub fn animate_background(mut self, transition: impl Into<TransitionConfig>) -> Self {
         let initial = self.background.get_or(Color::TRANSPARENT);
@@ -663,7 +1416,9 @@ impl Container {
     where
         F: FnOnce(StateStyle) -> StateStyle,
     {
-        self.interact_mut().hover_state = Some(f(StateStyle::new()));
+        let style = f(StateStyle::new());
+        self.install_state_transitions(&style);
+        self.interact_mut().hover_state = Some(style);
         self
     }
 
@@ -672,7 +1427,90 @@ impl Container {
     where
         F: FnOnce(StateStyle) -> StateStyle,
     {
-        self.interact_mut().pressed_state = Some(f(StateStyle::new()));
+        let style = f(StateStyle::new());
+        self.install_state_transitions(&style);
+        self.interact_mut().pressed_state = Some(style);
+        self
+    }
+
+    /// Play a ripple when the container is pressed.
+    ///
+    /// Works without a [`pressed_state`](Self::pressed_state) and takes
+    /// precedence over a ripple set there. Use [`RippleConfig::unbounded`] for
+    /// a ripple that spreads from the center past the container's bounds.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// container()
+    ///     .width(32)
+    ///     .height(32)
+    ///     .on_click(toggle_mute)
+    ///     .ripple(RippleConfig::unbounded(accent.with_alpha(0.25)))
+    ///     .child(image(SPEAKER_ICON))
+    /// ```
+    pub fn ripple(mut self, config: RippleConfig) -> Self {
+        self.interact_mut().ripple_config = Some(config);
+        self
+    }
+
+    /// Color the press ripple with `color` instead of its configured color.
+    ///
+    /// Unlike the color of a [`RippleConfig`], this can follow a signal, so
+    /// the ripple keeps up with a changing accent. It has no effect unless a
+    /// ripple is set, either with [`ripple`](Self::ripple) or in the
+    /// [`pressed_state`](Self::pressed_state).
+    pub fn ripple_color<M>(mut self, color: impl IntoSignal<Color, M>) -> Self {
+        self.interact_mut().ripple_color = Some(color.into_signal());
+        self
+    }
+
+    /// Disable the container while `disabled` is true.
+    ///
+    /// A disabled container and its children ignore all input: clicks, hover,
+    /// presses and keys. Focus held inside it is released. Hover, pressed and
+    /// focused styles and the ripple are suppressed, and
+    /// [`disabled_state`](Self::disabled_state) applies instead; without one,
+    /// the container is dimmed.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let submitting = create_signal(false);
+    ///
+    /// container()
+    ///     .background(Color::rgb(0.3, 0.5, 0.9))
+    ///     .hover_state(|s| s.lighter(0.1))
+    ///     .pressed_state(|s| s.ripple())
+    ///     .disabled(submitting)
+    ///     .on_click(move || submit())
+    ///     .child(text("Submit"))
+    /// ```
+    pub fn disabled<M>(mut self, disabled: impl IntoSignal<bool, M>) -> Self {
+        self.interact_mut().disabled = Some(disabled.into_signal());
+        self
+    }
+
+    /// Set style overrides for the disabled state.
+    ///
+    /// Replaces the default dimming of [`disabled`](Self::disabled) containers.
+    pub fn disabled_state<F>(mut self, f: F) -> Self
+    where
+        F: FnOnce(StateStyle) -> StateStyle,
+    {
+        let style = f(StateStyle::new());
+        self.install_state_transitions(&style);
+        self.interact_mut().disabled_state = Some(style);
+        self
+    }
+
+    /// Let the container take keyboard focus when pressed.
+    ///
+    /// While focused, Enter and Space activate `on_click` and
+    /// [`focused_state`](Self::focused_state) applies. Pressing outside
+    /// releases focus.
+    pub fn focusable(mut self) -> Self {
+        self.interact_mut().focusable = true;
         self
     }
 
@@ -691,14 +1529,65 @@ impl Container {
     where
         F: FnOnce(StateStyle) -> StateStyle,
     {
-        self.interact_mut().focused_state = Some(f(StateStyle::new()));
+        let style = f(StateStyle::new());
+        self.install_state_transitions(&style);
+        self.interact_mut().focused_state = Some(style);
         self
     }
 
-    /// Check if any child widget has focus
+    /// Create animations for the properties a state style has a transition for.
+    ///
+    /// Properties that already animate (through `animate_*` or another state)
+    /// keep their animation; the state's transition is swapped in while the
+    /// state is active.
+    fn install_state_transitions(&mut self, style: &StateStyle) {
+        let anims = self.anims.as_deref();
+        let missing_background = anims.is_none_or(|a| a.background.is_none());
+        let missing_border_width = anims.is_none_or(|a| a.border_width.is_none());
+        let missing_border_color = anims.is_none_or(|a| a.border_color.is_none());
+        let missing_corner_radius = anims.is_none_or(|a| a.corner_radius.is_none());
+        let missing_transform = anims.is_none_or(|a| a.transform.is_none());
+
+        if let Some(ref transition) = style.background_transition
+            && missing_background
+        {
+            let initial = self.background.get_or(Color::TRANSPARENT);
+            self.anims_mut().background = Some(AnimationState::new(initial, transition.clone()));
+        }
+        if let Some(ref transition) = style.border_transition {
+            if missing_border_width {
+                let initial = self.border_width.get_or(0.0);
+                self.anims_mut().border_width =
+                    Some(AnimationState::new(initial, transition.clone()));
+            }
+            if missing_border_color {
+                let initial = self.border_color.get_or(Color::TRANSPARENT);
+                self.anims_mut().border_color =
+                    Some(AnimationState::new(initial, transition.clone()));
+            }
+        }
+        if let Some(ref transition) = style.corner_radius_transition
+            && missing_corner_radius
+        {
+            let initial = self.corner_radius.get_or(0.0);
+            self.anims_mut().corner_radius = Some(AnimationState::new(initial, transition.clone()));
+        }
+        if let Some(ref transition) = style.transform_transition
+            && missing_transform
+        {
+            let initial = self.transform.get_or(Transform::IDENTITY);
+            self.anims_mut().transform = Some(AnimationState::new(initial, transition.clone()));
+        }
+    }
+
+    /// Check if this focusable container or any child widget has focus
     fn has_child_focus(&self, tree: &Tree) -> bool {
         if let Some(focused_id) = focused_widget() {
-            return self.widget_has_focus(tree, focused_id);
+            let own_focus = self
+                .interaction
+                .as_ref()
+                .is_some_and(|ix| ix.focus_id == Some(focused_id));
+            return own_focus || self.widget_has_focus(tree, focused_id);
         }
         false
     }
@@ -734,6 +1623,12 @@ impl Container {
             return false;
         }
 
+        // The parent positions this widget by its margin, so a margin change
+        // must reach the parent's layout
+        if self.margin.is_some() {
+            return false;
+        }
+
         // Widget is a boundary if its size doesn't depend on children.
         // This happens when:
         // 1. It has explicit fixed width AND height
@@ -748,8 +1643,28 @@ impl Container {
         (has_fixed_width || tight_width) && (has_fixed_height || tight_height)
     }
 
+    /// Whether the container is currently disabled (see `disabled`).
+    fn is_disabled(&self) -> bool {
+        self.interaction
+            .as_ref()
+            .is_some_and(|ix| ix.disabled.get_or(false))
+    }
+
+    /// Whether content overflowing the bounds is hidden, either by request or
+    /// because the container is a repaint boundary.
+    fn hides_overflow(&self) -> bool {
+        self.overflow == Overflow::Hidden || self.repaint_boundary
+    }
+
+    /// Whether children are clipped to the container's (rounded) bounds.
+    fn clips_children(&self) -> bool {
+        self.hides_overflow()
+            || self.scroll_axis != ScrollAxis::None
+            || self.anims.as_ref().is_some_and(|a| a.height_auto)
+    }
+
     // State layer resolution helper
-    // Priority: pressed > focused > hovered
+    // Priority: disabled (exclusive) > pressed > focused > hovered
     fn resolve_state_value<T: Clone>(
         &self,
         tree: &Tree,
@@ -759,6 +1674,13 @@ impl Container {
         let Some(ref ix) = self.interaction else {
             return base;
         };
+        if ix.disabled.get_or(false) {
+            return ix
+                .disabled_state
+                .as_ref()
+                .and_then(extractor)
+                .unwrap_or(base);
+        }
         if ix.is_pressed
             && let Some(ref state) = ix.pressed_state
             && let Some(value) = extractor(state)
@@ -883,6 +1805,25 @@ impl Container {
         )
     }
 
+    /// Get the full user transform and the origin it is applied around,
+    /// including the pivoted transforms.
+    fn user_transform(&self, tree: &Tree, bounds: Rect) -> (Transform, TransformOrigin) {
+        let transform = self.animated_transform(tree);
+        let origin = self.transform_origin.get_or(TransformOrigin::CENTER);
+        if self.pivot_transforms.is_empty() {
+            return (transform, origin);
+        }
+        let steps: Vec<(Transform, TransformOrigin)> = self
+            .pivot_transforms
+            .iter()
+            .map(|step| (step.transform.get(), step.origin.get()))
+            .collect();
+        (
+            compose_pivoted(transform, origin, &steps, bounds.width, bounds.height),
+            origin,
+        )
+    }
+
     /// Check if any state layer properties have animations enabled
     fn has_animated_state_properties(&self) -> bool {
         self.anims.as_ref().is_some_and(|a| {
@@ -901,10 +1842,162 @@ impl Container {
             request_job(id, JobRequest::Paint);
         }
     }
+
+    /// Drop hover and press state, e.g. when the container becomes disabled
+    /// under the pointer.
+    fn clear_pointer_state(&mut self, tree: &Tree, id: WidgetId) {
+        let Some(ref mut ix) = self.interaction else {
+            return;
+        };
+        if !ix.is_hovered && !ix.is_pressed {
+            return;
+        }
+        let was_hovered = ix.is_hovered;
+        ix.is_hovered = false;
+        ix.is_pressed = false;
+        if was_hovered {
+            update_hover_cursor(ix, tree, id);
+            if let Some(ref callback) = ix.on_hover {
+                callback(false);
+            }
+        }
+        self.request_state_change_repaint(id);
+    }
+
+    /// Order `children` by z-index (ascending, or descending for hit-testing).
+    ///
+    /// Returns `None` when every child has the default z-index, so the common
+    /// case keeps using the declaration-order slice without allocating.
+    /// The sort is stable: siblings with equal z-index keep declaration order.
+    fn children_by_z(
+        tree: &Tree,
+        children: &[WidgetId],
+        descending: bool,
+    ) -> Option<Vec<WidgetId>> {
+        let z_of = |child_id: WidgetId| tree.with_widget(child_id, |w| w.z_index()).unwrap_or(0);
+        if children.iter().all(|&child_id| z_of(child_id) == 0) {
+            return None;
+        }
+        let mut ordered: Vec<(i32, WidgetId)> = children
+            .iter()
+            .map(|&child_id| (z_of(child_id), child_id))
+            .collect();
+        if descending {
+            ordered.sort_by_key(|&(z, _)| std::cmp::Reverse(z));
+        } else {
+            ordered.sort_by_key(|&(z, _)| z);
+        }
+        Some(ordered.into_iter().map(|(_, child_id)| child_id).collect())
+    }
+}
+
+/// Corner radius that turns `bounds` into a circle (or stadium).
+fn circle_radius(bounds: Rect) -> f32 {
+    bounds.width.min(bounds.height) / 2.0
+}
+
+/// Show or release the container's declared cursor after its hover state changed.
+fn update_hover_cursor(ix: &InteractionState, tree: &Tree, id: WidgetId) {
+    let Some(cursor) = ix.cursor else {
+        return;
+    };
+    if ix.is_hovered {
+        let depth = std::iter::successors(tree.get_parent(id), |&p| tree.get_parent(p)).count();
+        enter_hover_cursor(id, depth, cursor.get_untracked());
+    } else {
+        leave_hover_cursor(id);
+    }
+}
+
+/// Grow `bounds` around its center so it is at least `min` in each dimension.
+fn expand_to_min_size(bounds: Rect, min: Size) -> Rect {
+    let grow_x = (min.width - bounds.width).max(0.0);
+    let grow_y = (min.height - bounds.height).max(0.0);
+    Rect::new(
+        bounds.x - grow_x / 2.0,
+        bounds.y - grow_y / 2.0,
+        bounds.width + grow_x,
+        bounds.height + grow_y,
+    )
 }
 
-/// Convert elevation level to shadow parameters
-fn elevation_to_shadow(level: f32) -> Shadow {
+/// Derive exact width/height from a width:height `ratio`.
+///
+/// If one dimension is exact, the other is derived from it. Otherwise the
+/// largest size satisfying the ratio within the available space is used.
+/// Both dimensions are then kept within their min/max limits, even if that
+/// breaks the ratio. Leaves the lengths untouched when the ratio is invalid, both dimensions are
+/// already exact, or the available space is unbounded in both directions.
+fn resolve_aspect_ratio(
+    ratio: f32,
+    width: &mut Length,
+    height: &mut Length,
+    constraints: Constraints,
+) {
+    if !ratio.is_finite() || ratio <= 0.0 {
+        return;
+    }
+    let max_width = width
+        .max
+        .unwrap_or(f32::INFINITY)
+        .min(constraints.max_width);
+    let max_height = height
+        .max
+        .unwrap_or(f32::INFINITY)
+        .min(constraints.max_height);
+    let min_width = width.min.unwrap_or(0.0).max(constraints.min_width);
+    let min_height = height.min.unwrap_or(0.0).max(constraints.min_height);
+
+    let (w, h) = match (width.exact, height.exact) {
+        (Some(_), Some(_)) => return,
+        (Some(w), None) => (w, w / ratio),
+        (None, Some(h)) => (h * ratio, h),
+        (None, None) => {
+            if max_width.is_finite() && max_width / ratio <= max_height {
+                (max_width, max_width / ratio)
+            } else if max_height.is_finite() {
+                (max_height * ratio, max_height)
+            } else {
+                return;
+            }
+        }
+    };
+    width.exact = Some(w.max(min_width).min(max_width));
+    height.exact = Some(h.max(min_height).min(max_height));
+}
+
+/// A transform applied around its own pivot, added by
+/// [`Container::transform_around`].
+pub(super) struct PivotTransform {
+    transform: Signal<Transform>,
+    origin: Signal<TransformOrigin>,
+}
+
+/// Compose `transform` (applied around `origin`) with pivoted `steps` for a
+/// `width` x `height` container.
+///
+/// The result is expressed around `origin` again, so the paint and event
+/// paths can keep centering a single transform at the container's origin.
+fn compose_pivoted(
+    transform: Transform,
+    origin: TransformOrigin,
+    steps: &[(Transform, TransformOrigin)],
+    width: f32,
+    height: f32,
+) -> Transform {
+    let local = Rect::new(0.0, 0.0, width, height);
+    let (ox, oy) = origin.resolve(local);
+    let full = steps
+        .iter()
+        .fold(transform.center_at(ox, oy), |full, (step, pivot)| {
+            let (px, py) = pivot.resolve(local);
+            full.then(&step.center_at(px, py))
+        });
+    full.center_at(-ox, -oy)
+}
+
+/// Convert elevation level to shadow parameters, optionally tinted with `color`
+fn elevation_to_shadow(level: f32, color: Option<Color>) -> Shadow {
     if level <= 0.0 {
         return Shadow::none();
     }
@@ -927,7 +2020,7 @@ fn elevation_to_shadow(level: f32) -> Shadow {
         (0.0, offset_y),
         blur,
         0.0,
-        Color::rgba(0.0, 0.0, 0.0, alpha),
+        color.unwrap_or(Color::rgba(0.0, 0.0, 0.0, alpha)),
     )
 }
 
@@ -954,7 +2047,28 @@ impl Widget for Container {
             let corner_radius_target = self.effective_corner_radius_target(tree);
             let border_color_target = self.effective_border_color_target(tree);
             let transform_target = self.effective_transform_target(tree);
+            // Transitions of the active state, if it sets any
+            let state_transition = |extractor: fn(&StateStyle) -> &Option<TransitionConfig>| {
+                self.resolve_state_value(tree, None, |state| {
+                    extractor(state).as_ref().map(|t| Some(t.clone()))
+                })
+            };
+            let bg_transition = state_transition(|s| &s.background_transition);
+            let border_transition = state_transition(|s| &s.border_transition);
+            let corner_radius_transition = state_transition(|s| &s.corner_radius_transition);
+            let transform_transition = state_transition(|s| &s.transform_transition);
+            let snap = self.widget_ref.is_some_and(|r| r.snap_pending());
             let anims = self.anims.as_mut().unwrap();
+            if snap {
+                // Width and height snap during layout, where their targets are known
+                snap_anim(&mut anims.padding, padding_target);
+                snap_anim(&mut anims.border_width, border_width_target);
+                snap_anim(&mut anims.background, bg_target);
+                snap_anim(&mut anims.corner_radius, corner_radius_target);
+                snap_anim(&mut anims.border_color, border_color_target);
+                snap_anim(&mut anims.transform, transform_target);
+                request_job(id, JobRequest::Paint);
+            }
             // Layout-affecting animations: width, height, padding
             advance_anim!(anims, width, id, any_animating, layout);
             advance_anim!(anims, height, id, any_animating, layout);
@@ -965,15 +2079,25 @@ impl Widget for Container {
                 anims,
                 border_width,
                 border_width_target,
+                border_transition.as_ref(),
+                id,
+                any_animating,
+                paint
+            );
+            advance_anim!(
+                anims,
+                background,
+                bg_target,
+                bg_transition.as_ref(),
                 id,
                 any_animating,
                 paint
             );
-            advance_anim!(anims, background, bg_target, id, any_animating, paint);
             advance_anim!(
                 anims,
                 corner_radius,
                 corner_radius_target,
+                corner_radius_transition.as_ref(),
                 id,
                 any_animating,
                 paint
@@ -982,20 +2106,29 @@ impl Widget for Container {
                 anims,
                 border_color,
                 border_color_target,
+                border_transition.as_ref(),
+                id,
+                any_animating,
+                paint
+            );
+            advance_anim!(
+                anims,
+                transform,
+                transform_target,
+                transform_transition.as_ref(),
                 id,
                 any_animating,
                 paint
             );
-            advance_anim!(anims, transform, transform_target, id, any_animating, paint);
         }
 
         // Advance ripple animation
         if let Some(ref mut ix) = self.interaction
             && ix.ripple.is_active()
-            && let Some(ref state) = ix.pressed_state
-            && let Some(ref config) = state.ripple
+            && let Some(config) = ix.ripple_config()
         {
-            let ripple_animating = ix.ripple.advance(config);
+            let config = config.clone();
+            let ripple_animating = ix.ripple.advance(&config);
             if ripple_animating {
                 // Ripple is paint-only, request animation continuation with paint
                 request_job(id, JobRequest::Animation(RequiredJob::Paint));
@@ -1010,13 +2143,25 @@ impl Widget for Container {
             if has_scroll_velocity {
                 let scroll_animating = sd.scroll_state.advance_momentum();
                 if scroll_animating {
+                    self.reveal_scrollbars(id);
                     // Kinetic scroll is paint-only, request animation continuation with paint
                     request_job(id, JobRequest::Animation(RequiredJob::Paint));
                 }
                 any_animating = any_animating || scroll_animating;
+                self.sync_scroll_observers(true);
             }
         }
 
+        // Snap to the nearest child once scrolling settles
+        if self
+            .scroll_data
+            .as_ref()
+            .is_some_and(|sd| sd.snap.is_some())
+            && self.advance_scroll_snap(tree, id)
+        {
+            any_animating = true;
+        }
+
         // Update scrollbar handle positions based on current scroll offset
         // (scroll is paint-only, so layout may not run during scrolling)
         if self.scroll_axis != ScrollAxis::None {
@@ -1029,6 +2174,11 @@ impl Widget for Container {
             any_animating = true;
         }
 
+        // Fade auto-hiding scrollbars in or out
+        if self.advance_scrollbar_fade(id) {
+            any_animating = true;
+        }
+
         // Note: No final Animation push needed here - each animation source
         // (advance_anim! macro, ripple, kinetic scroll) handles its own continuation
 
@@ -1056,14 +2206,18 @@ impl Widget for Container {
         LayoutHints {
             fill_width: self.width.as_ref().map(|w| w.get().fill).unwrap_or(false),
             fill_height: self.height.as_ref().map(|h| h.get().fill).unwrap_or(false),
+            margin: self.margin.get_or(Padding::default()),
         }
     }
 
     fn layout(&mut self, tree: &mut Tree, id: WidgetId, constraints: Constraints) -> Size {
+        tree.set_repaint_boundary(id, self.repaint_boundary);
+
         // Check visibility with signal tracking so changes trigger re-layout
         let is_visible = with_signal_tracking(id, JobType::Layout, || self.visible.get_or(true));
         if !is_visible {
             tree.set_relayout_boundary(id, true);
+            tree.set_baseline(id, None);
             let size = Size::zero();
             tree.cache_layout(id, constraints, size);
             tree.clear_needs_layout(id);
@@ -1101,18 +2255,37 @@ impl Widget for Container {
         // Clear dirty flag since we're doing layout now
         tree.clear_needs_layout(id);
 
+        // Focus can't stay inside a disabled subtree, or key input would
+        // still reach the focused widget. Tracked so disabling re-runs this.
+        let disabled = with_signal_tracking(id, JobType::Layout, || self.is_disabled());
+        if disabled && self.has_child_focus(tree) {
+            clear_focus();
+        }
+
         // Auto-track signal reads for layout properties.
         // Any signals read here (including closures) will register this widget
         // as a Layout subscriber so future changes trigger re-layout.
-        let (padding, width_length, height_length) =
+        let (padding, mut width_length, mut height_length, aspect_ratio) =
             with_signal_tracking(id, JobType::Layout, || {
+                // The parent applies the margin; track it so a change re-lays
+                // out this (non-boundary) widget and therefore the parent
+                let _ = self.margin.get_or(Padding::default());
+                // Gap and alignment changes re-lay out the children
+                self.layout.track_signals();
                 (
                     self.animated_padding(),
                     self.width.as_ref().map(|w| w.get()).unwrap_or_default(),
                     self.height.as_ref().map(|h| h.get()).unwrap_or_default(),
+                    self.aspect_ratio.as_ref().map(|r| r.get()),
                 )
             });
 
+        // Resolve the aspect ratio into exact dimensions so the rest of layout
+        // treats the container as fixed-size.
+        if let Some(ratio) = aspect_ratio {
+            resolve_aspect_ratio(ratio, &mut width_length, &mut height_length, constraints);
+        }
+
         // Calculate dimensions for child layout constraints.
         // When a layout animation is active and the width/height is exact, use
         // the animated current value so children are positioned within the actual
@@ -1137,23 +2310,27 @@ impl Widget for Container {
                 }
             };
 
-        let child_layout_height =
-            if let Some(anim) = self.anims.as_ref().and_then(|a| a.height.as_ref()) {
-                if !anim.is_initial() && height_length.exact.is_some() {
-                    *anim.current()
-                } else {
-                    height_length.exact.unwrap_or(constraints.max_height)
-                }
-            } else if let Some(exact) = height_length.exact {
-                exact
+        // Auto height animations measure the content at its natural height
+        let height_auto = self.anims.as_ref().is_some_and(|a| a.height_auto);
+        let child_layout_height = if height_auto {
+            let h = constraints.max_height;
+            height_length.max.map_or(h, |max| h.min(max))
+        } else if let Some(anim) = self.anims.as_ref().and_then(|a| a.height.as_ref()) {
+            if !anim.is_initial() && height_length.exact.is_some() {
+                *anim.current()
             } else {
-                let h = constraints.max_height;
-                if let Some(max) = height_length.max {
-                    h.min(max)
-                } else {
-                    h
-                }
-            };
+                height_length.exact.unwrap_or(constraints.max_height)
+            }
+        } else if let Some(exact) = height_length.exact {
+            exact
+        } else {
+            let h = constraints.max_height;
+            if let Some(max) = height_length.max {
+                h.min(max)
+            } else {
+                h
+            }
+        };
 
         // Child constraints with padding
         let mut child_max_width = (child_layout_width - padding.horizontal()).max(0.0);
@@ -1173,18 +2350,21 @@ impl Widget for Container {
             }
         }
 
-        let child_min_width = if width_length.exact.is_some() || width_length.fill {
-            child_max_width
-        } else {
-            // Propagate the effective minimum so layouts like Center/End know
-            // how much space they actually have to position children within.
-            // Sources of minimum: explicit at_least(min) or parent constraints.
-            let effective_min = width_length.min.unwrap_or(0.0).max(constraints.min_width);
-            (effective_min - padding.horizontal())
-                .max(0.0)
-                .min(child_max_width)
-        };
-        let child_min_height = if height_length.exact.is_some() || height_length.fill {
+        let child_min_width =
+            if width_length.exact.is_some() || (width_length.fill && child_max_width.is_finite()) {
+                child_max_width
+            } else {
+                // Propagate the effective minimum so layouts like Center/End know
+                // how much space they actually have to position children within.
+                // Sources of minimum: explicit at_least(min) or parent constraints.
+                let effective_min = width_length.min.unwrap_or(0.0).max(constraints.min_width);
+                (effective_min - padding.horizontal())
+                    .max(0.0)
+                    .min(child_max_width)
+            };
+        let child_min_height = if height_length.exact.is_some()
+            || (height_length.fill && child_max_height.is_finite())
+        {
             child_max_height
         } else {
             let effective_min = height_length.min.unwrap_or(0.0).max(constraints.min_height);
@@ -1196,7 +2376,7 @@ impl Widget for Container {
         // For scrollable containers, use unbounded constraints in scroll direction
         let scroll_axis = self.scroll_axis;
 
-        let child_constraints = match scroll_axis {
+        let mut child_constraints = match scroll_axis {
             ScrollAxis::Vertical => Constraints {
                 min_width: 0.0,
                 min_height: 0.0,
@@ -1231,6 +2411,32 @@ impl Widget for Container {
         // Reconcile and get children IDs
         let children = self.children_source.reconcile_and_get(tree);
 
+        // Measurement pass: lay the children out without a width limit to find
+        // their intrinsic width, then constrain the real pass to it
+        if self.fit_content
+            && width_length.exact.is_none()
+            && !scroll_axis.allows_horizontal()
+            && !children.is_empty()
+        {
+            let measure_constraints = Constraints {
+                min_width: 0.0,
+                max_width: f32::INFINITY,
+                ..child_constraints
+            };
+            let measured = self
+                .layout
+                .layout(
+                    tree,
+                    children,
+                    measure_constraints,
+                    (child_origin_x, child_origin_y),
+                )
+                .width
+                .min(child_constraints.max_width);
+            child_constraints.max_width = measured;
+            child_constraints.min_width = child_constraints.min_width.min(measured);
+        }
+
         let content_size = if !children.is_empty() {
             self.layout.layout(
                 tree,
@@ -1241,6 +2447,28 @@ impl Widget for Container {
         } else {
             Size::zero()
         };
+        let sticky_children: Vec<WidgetId> = if scroll_axis.allows_vertical() {
+            children
+                .iter()
+                .copied()
+                .filter(|&child| tree.with_widget(child, |w| w.is_sticky()).unwrap_or(false))
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        // Inherit the baseline of the first child that has one, so nested
+        // text still lines up under CrossAlignment::Baseline
+        let baseline = if scroll_axis.allows_vertical() {
+            None
+        } else {
+            children.iter().find_map(|&child| {
+                let child_baseline = tree.baseline(child)?;
+                let (_, y) = tree.get_origin(child)?;
+                Some(y + child_baseline)
+            })
+        };
+        tree.set_baseline(id, baseline);
 
         // Update scroll state with the viewport dimensions available for children.
         if scroll_axis != ScrollAxis::None {
@@ -1250,12 +2478,14 @@ impl Widget for Container {
             sd.scroll_state.viewport_width = child_max_width;
             sd.scroll_state.viewport_height = child_max_height;
             sd.scroll_state.clamp_offsets();
+            sd.sticky_children = sticky_children;
         }
 
         let content_width = content_size.width + padding.horizontal();
         let content_height = content_size.height + padding.vertical();
 
         // Update animation targets
+        let snap = self.widget_ref.is_some_and(|r| r.snap_pending());
         if let Some(ref mut anims) = self.anims {
             if let Some(ref mut anim) = anims.width {
                 let effective_target = if let Some(exact) = width_length.exact {
@@ -1264,7 +2494,7 @@ impl Widget for Container {
                     let min_w = width_length.min.unwrap_or(0.0);
                     content_width.max(min_w)
                 };
-                if anim.is_initial() {
+                if anim.is_initial() || snap {
                     // Always mark as initialized on first layout so subsequent
                     // changes animate rather than snap.
                     anim.set_immediate(effective_target);
@@ -1286,7 +2516,7 @@ impl Widget for Container {
                     let min_h = height_length.min.unwrap_or(0.0);
                     content_height.max(min_h)
                 };
-                if anim.is_initial() {
+                if anim.is_initial() || snap {
                     anim.set_immediate(effective_target);
                 } else if (effective_target - *anim.target()).abs() > 0.001 {
                     anim.animate_to(effective_target);
@@ -1373,11 +2603,11 @@ impl Widget for Container {
             .is_some_and(|a| a.is_animating());
         let has_exact_width = width_length.exact.is_some();
         let has_exact_height = height_length.exact.is_some();
-        let allow_shrink_width = self.overflow == Overflow::Hidden
+        let allow_shrink_width = self.hides_overflow()
             || width_animating
             || has_exact_width
             || self.scroll_axis.allows_horizontal();
-        let allow_shrink_height = self.overflow == Overflow::Hidden
+        let allow_shrink_height = self.hides_overflow()
             || height_animating
             || has_exact_height
             || self.scroll_axis.allows_vertical();
@@ -1391,7 +2621,7 @@ impl Widget for Container {
             }
         } else if let Some(exact) = width_length.exact {
             exact
-        } else if width_length.fill {
+        } else if width_length.fill && constraints.max_width.is_finite() {
             constraints.max_width
         } else {
             content_width
@@ -1413,7 +2643,7 @@ impl Widget for Container {
             }
         } else if let Some(exact) = height_length.exact {
             exact
-        } else if height_length.fill {
+        } else if height_length.fill && constraints.max_height.is_finite() {
             constraints.max_height
         } else {
             content_height
@@ -1461,9 +2691,21 @@ impl Widget for Container {
         // Cache constraints and size for partial layout
         tree.cache_layout(id, constraints, size);
 
+        // A repaint boundary skipped by its ancestors' relayout repaint
+        // must repaint itself when its own layout ran
+        if self.repaint_boundary {
+            tree.mark_relayout_needs_paint(id);
+        }
+
         // Register widget ref so update_widget_refs() can refresh bounds
         if let Some(ref wr) = self.widget_ref {
             register_widget_ref(id, wr.rw_signal());
+            wr.clear_snap();
+        }
+
+        // Content size may have changed the scroll range (and clamped the offset)
+        if self.scroll_axis != ScrollAxis::None {
+            self.sync_scroll_observers(false);
         }
 
         size
@@ -1473,19 +2715,28 @@ impl Widget for Container {
         if !self.visible.get_or(true) {
             return EventResponse::Ignored;
         }
+        if self.is_disabled() {
+            self.clear_pointer_state(tree, id);
+            return EventResponse::Ignored;
+        }
 
         // Get bounds from Tree (single source of truth)
         let bounds = tree.get_bounds(id).unwrap_or_default();
 
-        let transform = self.animated_transform(tree);
-        let transform_origin = self.transform_origin.get_or(TransformOrigin::CENTER);
-        let corner_radius = self.animated_corner_radius(tree);
+        let (transform, transform_origin) = self.user_transform(tree, bounds);
+        let corner_radius = if self.clip_circle {
+            circle_radius(bounds)
+        } else {
+            self.animated_corner_radius(tree)
+        };
 
         // Transform event coordinates to local space
         let local_event: Cow<'_, Event> = if !transform.is_identity() {
             if let Some((x, y)) = event.coords() {
                 let (origin_x, origin_y) = transform_origin.resolve(bounds);
-                // Transform is in screen space, simply center and invert
+                // Event coordinates are in the parent's local space, the same
+                // space the transform is applied in. Each ancestor has already
+                // inverted its own transform, so nested transforms compose.
                 let screen_space_transform = transform.center_at(origin_x, origin_y);
                 let (local_x, local_y) = screen_space_transform.inverse().transform_point(x, y);
                 Cow::Owned(event.with_coords(local_x, local_y))
@@ -1496,11 +2747,33 @@ impl Widget for Container {
             Cow::Borrowed(event)
         };
 
+        // Pointer target for hover/click, expanded by min_hit_size
+        let hit_bounds = match self.interaction.as_ref().and_then(|ix| ix.min_hit_size) {
+            Some(min) => expand_to_min_size(bounds, min),
+            None => bounds,
+        };
+
         // Handle scrollbar events first
         if let Some(response) = self.handle_scrollbar_event(tree, id, bounds, &local_event) {
             return response;
         }
 
+        // Pre-dispatch: list navigation containers take keyboard focus when
+        // pressed (even if a child handles the click) and drop it on outside presses.
+        let focusable = self.interaction.as_ref().is_some_and(|ix| ix.focusable);
+        if focusable && let Some(ref mut ix) = self.interaction {
+            ix.focus_id = Some(id);
+        }
+        if (self.list_nav.is_some() || focusable)
+            && let Event::MouseDown { x, y, .. } = local_event.as_ref()
+        {
+            if bounds.contains(*x, *y) {
+                request_focus(id);
+            } else if has_focus(id) {
+                release_focus(id);
+            }
+        }
+
         // Pre-dispatch: update hover state and fire pointer move callback
         // before children get the event. This ensures parent hover tracking
         // works even when a child container handles the MouseMove/MouseEnter.
@@ -1514,31 +2787,42 @@ impl Widget for Container {
                 }
             };
             match local_event.as_ref() {
-                Event::MouseEnter { x, y } => {
-                    if bounds.contains_rounded(*x, *y, corner_radius) && !ix.is_hovered {
-                        ix.is_hovered = true;
-                        if ix.hover_state.is_some() {
-                            request_repaint(id);
-                        }
-                        if let Some(ref callback) = ix.on_hover {
-                            callback(true);
-                        }
+                Event::MouseEnter { x, y }
+                    if hit_bounds.contains_rounded(*x, *y, corner_radius) && !ix.is_hovered =>
+                {
+                    ix.is_hovered = true;
+                    if ix.hover_state.is_some() {
+                        request_repaint(id);
+                    }
+                    update_hover_cursor(ix, tree, id);
+                    if let Some(ref callback) = ix.on_hover {
+                        callback(true);
+                    }
+                    if let Some(ref callback) = ix.on_hover_move {
+                        callback(*x - bounds.x, *y - bounds.y);
                     }
                 }
                 Event::MouseMove { x, y } => {
+                    let inside = hit_bounds.contains_rounded(*x, *y, corner_radius);
                     if let Some(ref callback) = ix.on_pointer_move
-                        && (bounds.contains_rounded(*x, *y, corner_radius) || ix.is_pressed)
+                        && (inside || ix.is_pressed)
+                    {
+                        callback(*x - bounds.x, *y - bounds.y);
+                    }
+                    if let Some(ref callback) = ix.on_hover_move
+                        && inside
                     {
                         callback(*x - bounds.x, *y - bounds.y);
                     }
 
                     let was_hovered = ix.is_hovered;
-                    ix.is_hovered = bounds.contains_rounded(*x, *y, corner_radius);
+                    ix.is_hovered = inside;
 
                     if was_hovered != ix.is_hovered {
                         if ix.hover_state.is_some() {
                             request_repaint(id);
                         }
+                        update_hover_cursor(ix, tree, id);
                         if let Some(ref callback) = ix.on_hover {
                             callback(ix.is_hovered);
                         }
@@ -1570,19 +2854,43 @@ impl Widget for Container {
             local_event.clone()
         };
 
-        // When overflow is hidden, children outside the container's bounds are
-        // clipped and invisible. Skip dispatching pointer events to them so that
-        // invisible children (e.g. inside a 0-height collapsed submenu) cannot
-        // steal clicks from siblings positioned below.
-        let skip_child_dispatch = (self.overflow == Overflow::Hidden
-            || self.scroll_axis != ScrollAxis::None)
+        // When overflow is hidden, children outside the container's clip are
+        // invisible. Skip dispatching pointer events to them so that invisible
+        // children (e.g. inside a 0-height collapsed submenu, scrolled out of
+        // view, or behind a rounded corner) cannot steal clicks from siblings.
+        let skip_child_dispatch = self.clips_children()
             && local_event
                 .coords()
-                .is_some_and(|(x, y)| !bounds.contains(x, y));
+                .is_some_and(|(x, y)| !bounds.contains_rounded(x, y, corner_radius));
 
-        // Let children handle first (layout already reconciled)
+        // Let children handle first (layout already reconciled).
+        // Higher z-index children are hit first since they are painted on top.
         if !skip_child_dispatch {
-            for &child_id in self.children_source.get() {
+            // A pinned sticky child sits on top, shifted from its layout position
+            let pinned = if self.scroll_axis != ScrollAxis::None {
+                self.pinned_sticky_child(tree)
+            } else {
+                None
+            };
+            if let Some((pinned_id, shift)) = pinned {
+                let pinned_event = match child_event.coords() {
+                    Some((x, y)) => Cow::Owned(child_event.with_coords(x, y - shift)),
+                    None => child_event.clone(),
+                };
+                if tree.with_widget_mut(pinned_id, |child, child_id, tree| {
+                    child.event(tree, child_id, &pinned_event)
+                }) == Some(EventResponse::Handled)
+                {
+                    return EventResponse::Handled;
+                }
+            }
+
+            let z_ordered = Self::children_by_z(tree, self.children_source.get(), true);
+            let hit_order = z_ordered.as_deref().unwrap_or(self.children_source.get());
+            for &child_id in hit_order {
+                if pinned.is_some_and(|(pinned_id, _)| pinned_id == child_id) {
+                    continue;
+                }
                 if let Some(response) = tree.with_widget_mut(child_id, |child, child_id, tree| {
                     child.event(tree, child_id, &child_event)
                 }) && response == EventResponse::Handled
@@ -1592,6 +2900,18 @@ impl Widget for Container {
             }
         }
 
+        // Keys ignored by focused descendants go to the key handler
+        if let Event::KeyDown { key, modifiers } = local_event.as_ref()
+            && let Some(callback) = self.interaction.as_ref().and_then(|ix| ix.on_key.clone())
+            && (has_focus(id) || self.has_child_focus(tree))
+            && callback(&KeyEvent {
+                key: *key,
+                modifiers: *modifiers,
+            }) == EventResponse::Handled
+        {
+            return EventResponse::Handled;
+        }
+
         // Handle our own events
         match local_event.as_ref() {
             // Hover tracking already handled in pre-dispatch above.
@@ -1599,7 +2919,7 @@ impl Widget for Container {
             // sibling containers from tracking their own hover state.
             Event::MouseEnter { .. } | Event::MouseMove { .. } => {}
             Event::MouseDown { x, y, button } => {
-                if bounds.contains_rounded(*x, *y, corner_radius)
+                if hit_bounds.contains_rounded(*x, *y, corner_radius)
                     && *button == MouseButton::Left
                     && let Some(ref mut ix) = self.interaction
                 {
@@ -1607,11 +2927,12 @@ impl Widget for Container {
                     ix.is_pressed = true;
 
                     // Start ripple animation if configured
-                    let has_ripple = ix
-                        .pressed_state
-                        .as_ref()
-                        .is_some_and(|s| s.ripple.is_some());
-                    if has_ripple {
+                    let bounded_ripple = ix.ripple_config().map(|config| config.bounded);
+                    if bounded_ripple == Some(false) {
+                        // Unbounded ripples always spread from the center
+                        ix.ripple.start(bounds.width / 2.0, bounds.height / 2.0);
+                        request_job(id, JobRequest::Animation(RequiredJob::Paint));
+                    } else if bounded_ripple == Some(true) {
                         // Convert screen coords to local coords accounting for transform
                         let (screen_x, screen_y) = event.coords().unwrap_or((*x, *y));
                         let (local_x, local_y) = if !transform.is_identity() {
@@ -1654,7 +2975,11 @@ impl Widget for Container {
                     ix.is_pressed = false;
 
                     // Start ripple fade animation
-                    if ix.ripple.is_active() {
+                    let bounded_ripple = ix.ripple_config().is_none_or(|config| config.bounded);
+                    if ix.ripple.is_active() && !bounded_ripple {
+                        ix.ripple.start_fade_to_center(bounds.width, bounds.height);
+                        request_job(id, JobRequest::Animation(RequiredJob::Paint));
+                    } else if ix.ripple.is_active() {
                         // Convert screen coords to local coords accounting for transform
                         let (screen_x, screen_y) = event.coords().unwrap_or((*x, *y));
                         let (local_x, local_y) = if !transform.is_identity() {
@@ -1683,7 +3008,7 @@ impl Widget for Container {
                         handled = true;
                     }
                     if let Some(ref ix) = self.interaction
-                        && bounds.contains_rounded(*x, *y, corner_radius)
+                        && hit_bounds.contains_rounded(*x, *y, corner_radius)
                         && let Some(ref callback) = ix.on_click
                     {
                         callback();
@@ -1700,6 +3025,7 @@ impl Widget for Container {
                     let was_pressed = ix.is_pressed;
                     if ix.is_hovered {
                         ix.is_hovered = false;
+                        update_hover_cursor(ix, tree, id);
                         if let Some(ref callback) = ix.on_hover {
                             callback(false);
                         }
@@ -1726,11 +3052,27 @@ impl Widget for Container {
                 delta_x,
                 delta_y,
                 source,
+                modifiers,
             } => {
                 if bounds.contains_rounded(*x, *y, corner_radius) {
+                    if modifiers.ctrl
+                        && let Some(ref ix) = self.interaction
+                        && let Some(ref callback) = ix.on_zoom
+                    {
+                        // Scrolling up (negative delta) zooms in
+                        let factor = (-*delta_y * ZOOM_PER_PIXEL).exp();
+                        if factor != 1.0 {
+                            callback(factor, (*x - bounds.x, *y - bounds.y));
+                        }
+                        return EventResponse::Handled;
+                    }
+
                     if self.scroll_axis != ScrollAxis::None {
                         let consumed = self.apply_scroll(*delta_x, *delta_y, *source);
                         if consumed {
+                            self.schedule_scroll_snap(id);
+                            self.reveal_scrollbars(id);
+                            self.sync_scroll_observers(true);
                             // Kinetic scrolling needs Animation + Paint if has velocity
                             let sd = self.scroll();
                             let has_velocity = sd.scroll_state.velocity_x.abs() > 0.5
@@ -1752,13 +3094,60 @@ impl Widget for Container {
                     }
                 }
             }
-            // Keyboard and focus events are handled by focused widgets, not containers
+            Event::Pinch { x, y, scale, .. } => {
+                if bounds.contains_rounded(*x, *y, corner_radius)
+                    && let Some(ref ix) = self.interaction
+                    && let Some(ref callback) = ix.on_zoom
+                {
+                    if *scale != 1.0 {
+                        callback(*scale, (*x - bounds.x, *y - bounds.y));
+                    }
+                    return EventResponse::Handled;
+                }
+            }
+            // Swipes are only handled by widgets that opt in
+            Event::Swipe { .. } => {}
+            Event::KeyDown { key, .. } if has_focus(id) => {
+                if let Some(ref nav) = self.list_nav {
+                    let children = self.children_source.get();
+                    match nav.handle_key(key, children.len()) {
+                        NavOutcome::Moved(index) => {
+                            let child_id = children[index];
+                            if self.scroll_axis != ScrollAxis::None
+                                && self.scroll_child_into_view(tree, bounds, child_id)
+                            {
+                                self.sync_scroll_observers(true);
+                                request_job(id, JobRequest::Paint);
+                            }
+                            return EventResponse::Handled;
+                        }
+                        NavOutcome::Activated => return EventResponse::Handled,
+                        NavOutcome::Ignored => {}
+                    }
+                }
+                if matches!(key, Key::Enter | Key::Char(' '))
+                    && let Some(ref ix) = self.interaction
+                    && ix.focusable
+                    && let Some(ref callback) = ix.on_click
+                {
+                    callback();
+                    return EventResponse::Handled;
+                }
+            }
+            Event::FocusOut if has_focus(id) => {
+                release_focus(id);
+            }
+            // Other keyboard and focus events are handled by focused widgets
             Event::KeyDown { .. } | Event::KeyUp { .. } | Event::FocusIn | Event::FocusOut => {}
         }
 
         EventResponse::Ignored
     }
 
+    fn z_index(&self) -> i32 {
+        self.z_index.get_or(0)
+    }
+
     fn has_focus_descendant(&self, tree: &Tree, focused_id: WidgetId) -> bool {
         if !self.visible.get_or(true) {
             return false;
@@ -1766,6 +3155,45 @@ impl Widget for Container {
         self.widget_has_focus(tree, focused_id)
     }
 
+    fn test_id(&self) -> Option<&str> {
+        self.test_id.as_deref()
+    }
+
+    fn is_sticky(&self) -> bool {
+        self.sticky
+    }
+
+    fn accessibility(&self) -> Option<AccessInfo> {
+        if let Some(info) = self.access_info {
+            return Some(info.get_untracked());
+        }
+        // Clickable containers act as buttons; others are plain groups
+        self.interaction
+            .as_ref()
+            .filter(|ix| ix.on_click.is_some())
+            .map(|_| AccessInfo::new(Role::Button))
+    }
+
+    fn paints_outside_bounds(&self) -> bool {
+        // Conservative: any configured transform or shadow may reach past the
+        // layout bounds, whether or not it is currently in effect
+        let state_overflows = self.interaction.as_ref().is_some_and(|ix| {
+            [
+                &ix.hover_state,
+                &ix.pressed_state,
+                &ix.focused_state,
+                &ix.disabled_state,
+            ]
+            .into_iter()
+            .flatten()
+            .any(|state| state.transform.is_some() || state.elevation.is_some())
+        });
+        self.transform.is_some()
+            || !self.pivot_transforms.is_empty()
+            || self.elevation.is_some()
+            || state_overflows
+    }
+
     fn paint(&self, tree: &Tree, id: WidgetId, ctx: &mut PaintContext) {
         let is_visible = with_signal_tracking(id, JobType::Paint, || self.visible.get_or(true));
         if !is_visible {
@@ -1783,8 +3211,8 @@ impl Widget for Container {
             corner_radius,
             corner_curvature,
             elevation_level,
-            user_transform,
-            transform_origin,
+            shadow_color,
+            (user_transform, transform_origin),
             border_width,
             border_color,
         ) = with_signal_tracking(id, JobType::Paint, || {
@@ -1793,13 +3221,15 @@ impl Widget for Container {
                 self.animated_corner_radius(tree),
                 self.corner_curvature.get_or(1.0),
                 self.effective_elevation(tree),
-                self.animated_transform(tree),
-                self.transform_origin.get_or(TransformOrigin::CENTER),
+                self.shadow_color.map(|c| c.get()),
+                self.user_transform(tree, bounds),
                 self.animated_border_width(tree),
                 self.animated_border_color(tree),
             )
         });
 
+        let disabled = with_signal_tracking(id, JobType::Paint, || self.is_disabled());
+
         // When animations exist, also track raw signal reads for Animation jobs.
         // This ensures signal changes trigger advance_animations() to update targets.
         // (The animated_* methods above may read from the animation cache instead of the signal,
@@ -1821,14 +3251,23 @@ impl Widget for Container {
                 if let Some(s) = &self.transform {
                     let _ = s.get();
                 }
+                let _ = self.is_disabled();
             });
         }
 
-        let shadow = elevation_to_shadow(elevation_level);
+        let shadow = elevation_to_shadow(elevation_level, shadow_color);
+        let (corner_radius, corner_curvature) = if self.clip_circle {
+            (circle_radius(bounds), 1.0)
+        } else {
+            (corner_radius, corner_curvature)
+        };
 
         // LOCAL bounds (0,0 is widget origin) - all drawing uses these
         let local_bounds = Rect::new(0.0, 0.0, bounds.width, bounds.height);
         ctx.set_bounds(local_bounds);
+        if self.pixel_snap {
+            ctx.set_pixel_snap(true);
+        }
 
         // Apply user transform (rotation, scale, user-specified translate)
         // Position is handled by the parent via set_transform before calling paint
@@ -1841,11 +3280,7 @@ impl Widget for Container {
         if let Some(ref gradient) = self.gradient {
             ctx.draw_gradient_rect(
                 local_bounds,
-                crate::renderer::Gradient {
-                    start_color: gradient.start_color,
-                    end_color: gradient.end_color,
-                    direction: gradient.direction.into(),
-                },
+                gradient.into(),
                 corner_radius,
                 corner_curvature,
             );
@@ -1870,13 +3305,23 @@ impl Widget for Container {
 
         // Draw border using LOCAL coordinates (values captured above in with_signal_tracking)
         if border_width > 0.0 {
-            ctx.draw_border_frame_with_curvature(
-                local_bounds,
-                border_color,
-                corner_radius,
-                border_width,
-                corner_curvature,
-            );
+            if let Some(ref gradient) = self.border_gradient {
+                ctx.draw_gradient_border_frame(
+                    local_bounds,
+                    gradient.into(),
+                    corner_radius,
+                    border_width,
+                    corner_curvature,
+                );
+            } else {
+                ctx.draw_border_frame_with_curvature(
+                    local_bounds,
+                    border_color,
+                    corner_radius,
+                    border_width,
+                    corner_curvature,
+                );
+            }
         }
 
         // Determine if we need to clip children
@@ -1884,7 +3329,7 @@ impl Widget for Container {
 
         // Set clip region for scrollable or overflow:hidden containers
         // This clips all children to the container bounds
-        if is_scrollable || self.overflow == Overflow::Hidden {
+        if self.clips_children() {
             ctx.set_clip(local_bounds, corner_radius, corner_curvature);
         }
 
@@ -1960,7 +3405,24 @@ impl Widget for Container {
             all_children
         };
 
+        // Paint in ascending z-index order so higher z children end up on top.
+        // Reading child z-index signals here makes z changes repaint this container.
+        let z_ordered = with_signal_tracking(id, JobType::Paint, || {
+            Self::children_by_z(tree, visible_children, false)
+        });
+        let visible_children = z_ordered.as_deref().unwrap_or(visible_children);
+
+        // A pinned sticky child is painted last, on top of the content
+        let pinned = if is_scrollable {
+            self.pinned_sticky_child(tree)
+        } else {
+            None
+        };
+
         for &child_id in visible_children {
+            if pinned.is_some_and(|(pinned_id, _)| pinned_id == child_id) {
+                continue;
+            }
             // Get child bounds from Tree - these are in LOCAL coordinates (relative to parent)
             let child_bounds = tree
                 .get_bounds(child_id)
@@ -2060,6 +3522,22 @@ impl Widget for Container {
             crate::render_stats::record_paint_child_painted();
         }
 
+        if let Some((child_id, shift)) = pinned {
+            self.paint_sticky_child(tree, child_id, shift, ctx);
+        }
+
+        // Fade edges with more content beyond them
+        if is_scrollable {
+            self.paint_scroll_fade(
+                id,
+                ctx,
+                local_bounds,
+                background,
+                corner_radius,
+                corner_curvature,
+            );
+        }
+
         // Draw scrollbar containers
         if is_scrollable {
             self.paint_scrollbar_containers(tree, id, ctx);
@@ -2067,32 +3545,1070 @@ impl Widget for Container {
 
         // Draw ripple effect as overlay (ripple.center is already in local coordinates)
         if let Some(ref ix) = self.interaction
+            && !disabled
             && let Some((local_cx, local_cy)) = ix.ripple.center
-            && let Some(ref pressed_state) = ix.pressed_state
-            && let Some(ref ripple_config) = pressed_state.ripple
+            && let Some(ripple_config) = ix.ripple_config()
             && ix.ripple.opacity > 0.0
         {
             // Set overlay clip to container bounds with rounded corners
             // This clips the ripple without affecting children
-            ctx.set_overlay_clip(local_bounds, corner_radius, corner_curvature);
+            if ripple_config.bounded {
+                ctx.set_overlay_clip(local_bounds, corner_radius, corner_curvature);
+            }
 
             let max_dist_x = local_cx.max(bounds.width - local_cx);
             let max_dist_y = local_cy.max(bounds.height - local_cy);
             let max_radius = (max_dist_x * max_dist_x + max_dist_y * max_dist_y).sqrt();
             let current_radius = max_radius * ix.ripple.progress;
 
-            let ripple_color = Color::rgba(
-                ripple_config.color.r,
-                ripple_config.color.g,
-                ripple_config.color.b,
-                ripple_config.color.a * ix.ripple.opacity,
-            );
+            let color = ix.ripple_color.map_or(ripple_config.color, |color| {
+                with_signal_tracking(id, JobType::Paint, || color.get())
+            });
+            let ripple_color = Color::rgba(color.r, color.g, color.b, color.a * ix.ripple.opacity);
 
             ctx.draw_overlay_circle(local_cx, local_cy, current_radius, ripple_color);
         }
+
+        // Dim disabled containers over their content, unless they style it themselves
+        if disabled
+            && self
+                .interaction
+                .as_ref()
+                .is_some_and(|ix| ix.disabled_state.is_none())
+        {
+            // Clip to the container's own shape so the scrim never spills
+            // past its corners onto neighbouring content
+            ctx.set_overlay_clip(local_bounds, corner_radius, corner_curvature);
+            ctx.draw_overlay_rounded_rect(local_bounds, DISABLED_SCRIM, corner_radius);
+        }
     }
 }
 
 pub fn container() -> Container {
     Container::new()
 }
+
+/// Wrap a widget in a [repaint boundary](Container::repaint_boundary).
+///
+/// ```ignore
+/// repaint_boundary(logo())
+/// ```
+pub fn repaint_boundary<M>(child: impl IntoChild<M>) -> Container {
+    Container::new().repaint_boundary().child(child)
+}
+
+/// Lay out `child` with its width limited to `min_width..=max_width` and its
+/// height to `min_height..=max_height`, within what the parent allows (see
+/// [`Constrained`]). The container sizes to the child.
+///
+/// Use `f32::INFINITY` for a maximum that is left to the parent.
+///
+/// ```ignore
+/// // Wraps at 300px even in a wider parent
+/// constrained(0.0, 300.0, 0.0, f32::INFINITY, text(description))
+/// ```
+pub fn constrained<M>(
+    min_width: f32,
+    max_width: f32,
+    min_height: f32,
+    max_height: f32,
+    child: impl IntoChild<M>,
+) -> Container {
+    Container::new()
+        .layout(Constrained::new(
+            min_width, max_width, min_height, max_height,
+        ))
+        .child(child)
+}
+
+/// Create a container laying out `children` in a [row](Flex::row).
+///
+/// ```ignore
+/// row([a, b, c]).gap(8.0).main_align(MainAlignment::SpaceBetween)
+/// ```
+pub fn row<M>(children: impl IntoChildren<M>) -> Container {
+    Container::new().layout(Flex::row()).children(children)
+}
+
+/// Create a container laying out `children` in a [column](Flex::column).
+pub fn column<M>(children: impl IntoChildren<M>) -> Container {
+    Container::new().layout(Flex::column()).children(children)
+}
+
+/// Create a container stacking `children` on top of each other with an
+/// [`Overlay`] layout.
+pub fn stack<M>(children: impl IntoChildren<M>) -> Container {
+    Container::new().layout(Overlay::new()).children(children)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::animation::{TimingFunction, Transition};
+    use crate::layout::{Constraints, Length, Size, at_least, at_most, fill};
+    use crate::widgets::widget::MouseButton;
+    use std::cell::Cell;
+
+    fn click(tree: &mut Tree, id: WidgetId, (x, y): (f32, f32)) {
+        let button = MouseButton::Left;
+        for event in [
+            Event::MouseDown { x, y, button },
+            Event::MouseUp { x, y, button },
+        ] {
+            tree.with_widget_mut(id, |widget, id, tree| widget.event(tree, id, &event));
+        }
+    }
+
+    fn layout(tree: &mut Tree, id: WidgetId) {
+        tree.with_widget_mut(id, |widget, id, tree| widget.register_children(tree, id));
+        tree.with_widget_mut(id, |widget, id, tree| {
+            widget.layout(tree, id, Constraints::loose(Size::new(400.0, 400.0)))
+        });
+    }
+
+    #[test]
+    fn test_pivoted_transforms_use_own_origins() {
+        // Rotating a 100x50 box a quarter turn around its top-left corner,
+        // then scaling it 2x around its center
+        let rotate = Transform::rotate_degrees(90.0);
+        let scale = Transform::scale(2.0);
+        let steps = [
+            (rotate, TransformOrigin::TOP_LEFT),
+            (scale, TransformOrigin::CENTER),
+        ];
+        let composed = compose_pivoted(
+            Transform::IDENTITY,
+            TransformOrigin::CENTER,
+            &steps,
+            100.0,
+            50.0,
+        );
+        let expected = rotate.then(&scale.center_at(50.0, 25.0));
+        // Applied as painted: centered at the container origin
+        let painted = composed.center_at(50.0, 25.0);
+        for (x, y) in [(0.0, 0.0), (50.0, 25.0), (100.0, 50.0)] {
+            let (ax, ay) = painted.transform_point(x, y);
+            let (ex, ey) = expected.transform_point(x, y);
+            assert!((ax - ex).abs() < 1e-3 && (ay - ey).abs() < 1e-3);
+        }
+        // The box's center ends up rotated around the top-left corner
+        let (cx, cy) = painted.transform_point(50.0, 25.0);
+        assert!((cx + 25.0).abs() < 1e-3 && (cy - 50.0).abs() < 1e-3);
+    }
+
+    fn aspect(ratio: f32, width: Length, height: Length, max: Size) -> (Length, Length) {
+        let (mut width, mut height) = (width, height);
+        resolve_aspect_ratio(ratio, &mut width, &mut height, Constraints::loose(max));
+        (width, height)
+    }
+
+    #[test]
+    fn test_aspect_ratio_derives_height_from_width() {
+        let max = Size::new(400.0, 400.0);
+        let (w, h) = aspect(2.0, Length::exact(100.0), Length::default(), max);
+        assert_eq!((w.exact, h.exact), (Some(100.0), Some(50.0)));
+
+        // The derived height respects its own limits and the constraints
+        let (_, h) = aspect(2.0, Length::exact(100.0), at_most(30.0), max);
+        assert_eq!(h.exact, Some(30.0));
+        let (_, h) = aspect(2.0, Length::exact(100.0), at_least(80.0), max);
+        assert_eq!(h.exact, Some(80.0));
+        let (_, h) = aspect(0.1, Length::exact(100.0), Length::default(), max);
+        assert_eq!(h.exact, Some(400.0));
+    }
+
+    #[test]
+    fn test_aspect_ratio_derives_width_from_height() {
+        let max = Size::new(400.0, 400.0);
+        let (w, h) = aspect(2.0, Length::default(), Length::exact(50.0), max);
+        assert_eq!((w.exact, h.exact), (Some(100.0), Some(50.0)));
+
+        let (w, _) = aspect(2.0, at_most(60.0), Length::exact(50.0), max);
+        assert_eq!(w.exact, Some(60.0));
+        let (w, _) = aspect(2.0, at_least(150.0), Length::exact(50.0), max);
+        assert_eq!(w.exact, Some(150.0));
+    }
+
+    #[test]
+    fn test_aspect_ratio_fits_available_space() {
+        // Wide space: the height limits the size
+        let (w, h) = aspect(
+            2.0,
+            Length::default(),
+            Length::default(),
+            Size::new(400.0, 100.0),
+        );
+        assert_eq!((w.exact, h.exact), (Some(200.0), Some(100.0)));
+        // Tall space: the width limits the size
+        let (w, h) = aspect(
+            2.0,
+            Length::default(),
+            Length::default(),
+            Size::new(100.0, 400.0),
+        );
+        assert_eq!((w.exact, h.exact), (Some(100.0), Some(50.0)));
+        // Unbounded in both directions: nothing to derive from
+        let (w, h) = aspect(
+            2.0,
+            Length::default(),
+            Length::default(),
+            Size::new(f32::INFINITY, f32::INFINITY),
+        );
+        assert_eq!((w.exact, h.exact), (None, None));
+    }
+
+    #[test]
+    fn test_aspect_ratio_fitted_size_respects_min_limits() {
+        // Fitting 2:1 into a tall space gives 100x50, below the min height
+        let (w, h) = aspect(
+            2.0,
+            Length::default(),
+            at_least(80.0),
+            Size::new(100.0, 400.0),
+        );
+        assert_eq!((w.exact, h.exact), (Some(100.0), Some(80.0)));
+        // Fitting into a wide space gives 200x100, below the min width
+        let (w, h) = aspect(
+            2.0,
+            at_least(300.0),
+            Length::default(),
+            Size::new(400.0, 100.0),
+        );
+        assert_eq!((w.exact, h.exact), (Some(300.0), Some(100.0)));
+        // Minimum constraints from the parent count too
+        let mut width = Length::default();
+        let mut height = Length::default();
+        let constraints = Constraints::new(0.0, 70.0, 100.0, 400.0);
+        resolve_aspect_ratio(2.0, &mut width, &mut height, constraints);
+        assert_eq!((width.exact, height.exact), (Some(100.0), Some(70.0)));
+    }
+
+    #[test]
+    fn test_aspect_ratio_keeps_exact_dimensions() {
+        let max = Size::new(400.0, 400.0);
+        let (w, h) = aspect(2.0, Length::exact(100.0), Length::exact(100.0), max);
+        assert_eq!((w.exact, h.exact), (Some(100.0), Some(100.0)));
+        // Invalid ratios leave the lengths untouched
+        let (w, h) = aspect(0.0, Length::exact(100.0), Length::default(), max);
+        assert_eq!((w.exact, h.exact), (Some(100.0), None));
+    }
+
+    #[test]
+    fn test_constrained_limits_child() {
+        let mut tree = Tree::new();
+        let id = tree.register(Box::new(constrained(
+            0.0,
+            300.0,
+            0.0,
+            f32::INFINITY,
+            container().width(fill()).height(10.0),
+        )));
+        layout(&mut tree, id);
+
+        let child = tree.get_children(id)[0];
+        assert_eq!(tree.cached_size(child), Some(Size::new(300.0, 10.0)));
+        assert_eq!(tree.cached_size(id), Some(Size::new(300.0, 10.0)));
+    }
+
+    #[test]
+    fn test_padding_each_sets_sides_and_tracks_signals() {
+        use crate::jobs::{Job, JobType, drain_pending_jobs, process_jobs};
+        use crate::reactive::create_signal;
+
+        let left = create_signal(4.0f32);
+        let mut tree = Tree::new();
+        let id = tree.register(Box::new(
+            container()
+                .padding_each(1, 2, 3, left)
+                .child(container().width(10.0).height(10.0)),
+        ));
+        layout(&mut tree, id);
+        let child = tree.get_children(id)[0];
+        assert_eq!(tree.cached_size(id), Some(Size::new(16.0, 14.0)));
+        assert_eq!(tree.get_origin(child), Some((4.0, 1.0)));
+        drain_pending_jobs();
+
+        left.set(10.0);
+        let jobs = drain_pending_jobs();
+        assert!(jobs.contains(&Job {
+            widget_id: id,
+            job_type: JobType::Layout,
+        }));
+        process_jobs(&jobs, &mut tree, &mut Vec::new());
+        layout(&mut tree, id);
+        assert_eq!(tree.cached_size(id), Some(Size::new(22.0, 14.0)));
+        assert_eq!(tree.get_origin(child), Some((10.0, 1.0)));
+    }
+
+    #[test]
+    fn test_row_column_and_stack_match_the_verbose_form() {
+        let items = || [10.0, 20.0, 30.0].map(|size: f32| container().width(size).height(size));
+        let origins = |widget: Container| {
+            let mut tree = Tree::new();
+            let id = tree.register(Box::new(widget.width(200.0).height(100.0)));
+            layout(&mut tree, id);
+            tree.get_children(id)
+                .iter()
+                .map(|&child| tree.get_origin(child).unwrap())
+                .collect::<Vec<_>>()
+        };
+
+        let row = origins(
+            row(items())
+                .gap(8.0)
+                .main_align(MainAlignment::SpaceBetween)
+                .cross_align(CrossAlignment::Center),
+        );
+        let verbose = origins(
+            container()
+                .layout(
+                    Flex::row()
+                        .spacing(8.0)
+                        .main_alignment(MainAlignment::SpaceBetween)
+                        .cross_alignment(CrossAlignment::Center),
+                )
+                .children(items()),
+        );
+        assert_eq!(row, verbose);
+        assert_eq!(row, [(0.0, 45.0), (80.0, 40.0), (170.0, 35.0)]);
+
+        let column = origins(column(items()).gap(8.0));
+        assert_eq!(column, [(0.0, 0.0), (0.0, 18.0), (0.0, 46.0)]);
+        // Overlay ignores the gap
+        let stacked = origins(stack(items()).gap(8.0));
+        assert_eq!(
+            stacked,
+            origins(container().layout(Overlay::new()).children(items()))
+        );
+    }
+
+    #[test]
+    fn test_fit_content_sizes_to_widest_child() {
+        let item = |width: f32| {
+            container()
+                .width(fill())
+                .child(container().width(width).height(10.0))
+        };
+        let mut tree = Tree::new();
+        let id = tree.register(Box::new(
+            container()
+                .fit_content()
+                .layout(Flex::column())
+                .child(item(40.0))
+                .child(item(120.0))
+                .child(item(80.0)),
+        ));
+        layout(&mut tree, id);
+
+        // Filling items align to the widest one instead of the available space
+        for &child in tree.get_children(id) {
+            assert_eq!(tree.cached_size(child), Some(Size::new(120.0, 10.0)));
+        }
+        assert_eq!(tree.cached_size(id), Some(Size::new(120.0, 30.0)));
+    }
+
+    #[test]
+    fn test_animate_height_auto_expands_and_collapses() {
+        use crate::animation::clock;
+        use crate::layout::auto;
+        use crate::reactive::create_signal;
+
+        clock::pause_clock();
+        let open = create_signal(false);
+        let mut tree = Tree::new();
+        let id = tree.register(Box::new(
+            container()
+                .height(move || if open.get() { auto() } else { Length::exact(0) })
+                .animate_height_auto(Transition::new(200.0, TimingFunction::Linear))
+                .child(container().width(10.0).height(60.0)),
+        ));
+        let step = |tree: &mut Tree, ms: u64| {
+            clock::advance_clock(std::time::Duration::from_millis(ms));
+            tree.with_widget_mut(id, |widget, id, tree| widget.advance_animations(tree, id));
+            tree.mark_needs_layout(id);
+            layout(tree, id);
+            tree.cached_size(id).unwrap().height
+        };
+        layout(&mut tree, id);
+        assert_eq!(tree.cached_size(id).unwrap().height, 0.0);
+        // The content keeps its natural height while clipped away
+        let child = tree.get_children(id)[0];
+        assert_eq!(tree.cached_size(child).unwrap().height, 60.0);
+
+        open.set(true);
+        step(&mut tree, 0);
+        assert!((step(&mut tree, 100) - 30.0).abs() < 1e-3);
+        assert!((step(&mut tree, 100) - 60.0).abs() < 1e-3);
+
+        open.set(false);
+        step(&mut tree, 0);
+        assert!((step(&mut tree, 100) - 30.0).abs() < 1e-3);
+        assert_eq!(tree.cached_size(child).unwrap().height, 60.0);
+        assert!(step(&mut tree, 100).abs() < 1e-3);
+        clock::resume_clock();
+    }
+
+    #[test]
+    fn test_state_transition_animates_background() {
+        let base = Color::rgb(0.2, 0.2, 0.3);
+        let hover = Color::rgb(0.4, 0.4, 0.5);
+        let mut tree = Tree::new();
+        let id = tree.register(Box::new(container()));
+        let mut widget = container().background(base).hover_state(|s| {
+            s.background(hover)
+                .transition_background(Transition::new(200.0, TimingFunction::Linear))
+        });
+        assert!(
+            widget
+                .anims
+                .as_ref()
+                .is_some_and(|a| a.background.is_some())
+        );
+
+        widget.interact_mut().is_hovered = true;
+        assert!(widget.advance_animations(&mut tree, id));
+        let anim = widget.anims.as_ref().unwrap().background.as_ref().unwrap();
+        assert_eq!(*anim.target(), hover);
+        assert!(anim.is_animating());
+    }
+
+    #[test]
+    fn test_style_fills_unset_properties() {
+        let card_style = WidgetStyle::new()
+            .padding(16.0)
+            .background(Color::WHITE)
+            .corner_radius(8.0)
+            .hover_state(|s| s.lighter(0.1));
+        let red = Color::rgb(1.0, 0.0, 0.0);
+
+        let plain = container().style(&card_style);
+        assert_eq!(plain.background.get_or(Color::TRANSPARENT), Color::WHITE);
+        assert_eq!(plain.corner_radius.get_or(0.0), 8.0);
+        assert!(plain.interaction.is_some_and(|ix| ix.hover_state.is_some()));
+
+        // Inline values win whichever order they are set in
+        let before = container().background(red).style(&card_style);
+        let after = container().style(&card_style).background(red);
+        for widget in [before, after] {
+            assert_eq!(widget.background.get_or(Color::TRANSPARENT), red);
+            assert_eq!(
+                widget.padding.get_or(Padding::default()),
+                Padding::all(16.0)
+            );
+        }
+    }
+
+    #[test]
+    fn test_container_ripple_overrides_pressed_state() {
+        let mut ix = InteractionState::default();
+        assert!(ix.ripple_config().is_none());
+
+        ix.pressed_state = Some(StateStyle::new().ripple());
+        assert!(ix.ripple_config().is_some_and(|config| config.bounded));
+
+        ix.ripple_config = Some(RippleConfig::unbounded(Color::WHITE));
+        assert!(ix.ripple_config().is_some_and(|config| !config.bounded));
+        // Also plays without any pressed style
+        ix.pressed_state = None;
+        assert!(ix.ripple_config().is_some_and(|config| !config.bounded));
+    }
+
+    #[test]
+    fn test_hover_move_reports_local_position() {
+        let positions = Rc::new(std::cell::RefCell::new(Vec::new()));
+        let log = positions.clone();
+        let mut tree = Tree::new();
+        let id = tree.register(Box::new(
+            container().padding(20.0).child(
+                container()
+                    .width(100.0)
+                    .height(50.0)
+                    .on_hover_move(move |x, y| log.borrow_mut().push((x, y))),
+            ),
+        ));
+        layout(&mut tree, id);
+
+        for (x, y) in [(30.0, 25.0), (110.0, 60.0), (200.0, 200.0)] {
+            let event = Event::MouseMove { x, y };
+            tree.with_widget_mut(id, |widget, id, tree| widget.event(tree, id, &event));
+        }
+        // Outside the card nothing is reported
+        assert_eq!(*positions.borrow(), [(10.0, 5.0), (90.0, 40.0)]);
+    }
+
+    #[test]
+    fn test_clipped_children_ignore_clicks() {
+        let clicks = Rc::new(Cell::new(0));
+        let counter = clicks.clone();
+        // Items 40px tall in a 50px tall scroll view: the second item
+        // overflows into the area below the view
+        let mut tree = Tree::new();
+        let id = tree.register(Box::new(
+            container().child(
+                container()
+                    .width(100.0)
+                    .height(50.0)
+                    .scrollable(ScrollAxis::Vertical)
+                    .child(container().width(100.0).height(40.0))
+                    .child(
+                        container()
+                            .width(100.0)
+                            .height(40.0)
+                            .on_click(move || counter.set(counter.get() + 1)),
+                    ),
+            ),
+        ));
+        layout(&mut tree, id);
+
+        click(&mut tree, id, (50.0, 45.0));
+        assert_eq!(clicks.get(), 1);
+        // Below the view, where the clipped part of the item would be
+        click(&mut tree, id, (50.0, 70.0));
+        assert_eq!(clicks.get(), 1);
+    }
+
+    #[test]
+    fn test_dynamic_child_z_index_is_hit_first() {
+        let hits = Rc::new(std::cell::RefCell::new(Vec::new()));
+        let log = hits.clone();
+        // Two stacked keyed children; the second one is raised above the first
+        let mut tree = Tree::new();
+        let id = tree.register(Box::new(container().layout(Overlay::new()).children(
+            move || {
+                let log = log.clone();
+                [(0u64, 0), (1, 1)].into_iter().map(move |(key, z)| {
+                    let log = log.clone();
+                    (key, move || {
+                        container()
+                            .width(50.0)
+                            .height(50.0)
+                            .z_index(z)
+                            .on_click(move || log.borrow_mut().push(key))
+                    })
+                })
+            },
+        )));
+        layout(&mut tree, id);
+
+        click(&mut tree, id, (25.0, 25.0));
+        assert_eq!(*hits.borrow(), [1]);
+    }
+
+    #[test]
+    fn test_scroll_position_signals_and_scroll_end() {
+        let ends = Rc::new(Cell::new(0));
+        let counter = ends.clone();
+        let list_ref = crate::widget_ref::create_widget_ref();
+        let mut tree = Tree::new();
+        let id = tree.register(Box::new(
+            container()
+                .width(100.0)
+                .height(100.0)
+                .scrollable(ScrollAxis::Vertical)
+                .on_scroll_end(20.0, move || counter.set(counter.get() + 1))
+                .widget_ref(list_ref)
+                .child(container().height(500.0)),
+        ));
+        layout(&mut tree, id);
+        let scroll = |tree: &mut Tree, delta_y: f32| {
+            let event = Event::Scroll {
+                x: 10.0,
+                y: 10.0,
+                delta_x: 0.0,
+                delta_y,
+                source: ScrollSource::Wheel,
+                modifiers: Default::default(),
+            };
+            tree.with_widget_mut(id, |widget, id, tree| widget.event(tree, id, &event));
+        };
+
+        scroll(&mut tree, 200.0);
+        assert_eq!(
+            list_ref.scroll_offset_signal().get_untracked(),
+            (0.0, 200.0)
+        );
+        assert_eq!(
+            list_ref.scroll_fraction_signal().get_untracked(),
+            (0.0, 0.5)
+        );
+        assert_eq!(ends.get(), 0);
+
+        // Within 20px of the end: fires once until scrolled away again
+        scroll(&mut tree, 190.0);
+        scroll(&mut tree, 10.0);
+        assert_eq!(
+            list_ref.scroll_fraction_signal().get_untracked(),
+            (0.0, 1.0)
+        );
+        assert_eq!(ends.get(), 1);
+        scroll(&mut tree, -100.0);
+        scroll(&mut tree, 100.0);
+        assert_eq!(ends.get(), 2);
+        crate::widget_ref::reset_widget_refs();
+    }
+
+    #[test]
+    fn test_snap_animations_applies_one_change_immediately() {
+        use crate::animation::clock;
+        use crate::reactive::create_signal;
+
+        clock::pause_clock();
+        let width = create_signal(100.0f32);
+        let panel_ref = crate::widget_ref::create_widget_ref();
+        let mut tree = Tree::new();
+        let id = tree.register(Box::new(
+            container()
+                .width(move || width.get())
+                .height(10.0)
+                .animate_width(Transition::new(200.0, TimingFunction::Linear))
+                .widget_ref(panel_ref),
+        ));
+        let step = |tree: &mut Tree, ms: u64| {
+            clock::advance_clock(std::time::Duration::from_millis(ms));
+            tree.with_widget_mut(id, |widget, id, tree| widget.advance_animations(tree, id));
+            tree.mark_needs_layout(id);
+            layout(tree, id);
+            tree.cached_size(id).unwrap().width
+        };
+        layout(&mut tree, id);
+        assert_eq!(tree.cached_size(id).unwrap().width, 100.0);
+
+        panel_ref.snap_animations();
+        width.set(300.0);
+        assert_eq!(step(&mut tree, 0), 300.0);
+
+        // The snap is used up: the next change transitions again
+        width.set(100.0);
+        step(&mut tree, 0);
+        assert!((step(&mut tree, 100) - 200.0).abs() < 1e-3);
+        assert!((step(&mut tree, 100) - 100.0).abs() < 1e-3);
+        crate::widget_ref::reset_widget_refs();
+        clock::resume_clock();
+    }
+
+    #[test]
+    fn test_hover_cursor_follows_the_deepest_hovered_container() {
+        use crate::reactive::cursor::{get_current_cursor, reset_cursor};
+
+        let mut tree = Tree::new();
+        let id = tree.register(Box::new(
+            container()
+                .width(200.0)
+                .height(100.0)
+                .cursor(CursorIcon::Pointer)
+                .child(
+                    container()
+                        .width(50.0)
+                        .height(50.0)
+                        .cursor(CursorIcon::Text),
+                ),
+        ));
+        layout(&mut tree, id);
+        let send = |tree: &mut Tree, event: Event| {
+            tree.with_widget_mut(id, |widget, id, tree| widget.event(tree, id, &event));
+            get_current_cursor()
+        };
+
+        assert_eq!(
+            send(&mut tree, Event::MouseMove { x: 10.0, y: 10.0 }),
+            CursorIcon::Text
+        );
+        // Leaving the inner container falls back to the outer one
+        assert_eq!(
+            send(&mut tree, Event::MouseMove { x: 150.0, y: 10.0 }),
+            CursorIcon::Pointer
+        );
+        assert_eq!(
+            send(&mut tree, Event::MouseMove { x: 20.0, y: 20.0 }),
+            CursorIcon::Text
+        );
+        assert_eq!(send(&mut tree, Event::MouseLeave), CursorIcon::Default);
+        reset_cursor();
+    }
+
+    #[test]
+    fn test_ctrl_scroll_and_pinch_zoom_while_plain_scroll_pans() {
+        use crate::widgets::widget::{GesturePhase, Modifiers};
+
+        let zooms = Rc::new(std::cell::RefCell::new(Vec::new()));
+        let log = zooms.clone();
+        let viewer_ref = crate::widget_ref::create_widget_ref();
+        let mut tree = Tree::new();
+        let id = tree.register(Box::new(
+            container().padding(20.0).child(
+                container()
+                    .width(100.0)
+                    .height(100.0)
+                    .scrollable(ScrollAxis::Vertical)
+                    .on_zoom(move |factor, center| log.borrow_mut().push((factor, center)))
+                    .widget_ref(viewer_ref)
+                    .child(container().height(500.0)),
+            ),
+        ));
+        layout(&mut tree, id);
+        let send = |tree: &mut Tree, event: Event| {
+            tree.with_widget_mut(id, |widget, id, tree| widget.event(tree, id, &event));
+        };
+        let scroll = |delta_y: f32, ctrl: bool| Event::Scroll {
+            x: 50.0,
+            y: 40.0,
+            delta_x: 0.0,
+            delta_y,
+            source: ScrollSource::Wheel,
+            modifiers: Modifiers {
+                ctrl,
+                ..Default::default()
+            },
+        };
+
+        // Ctrl+scroll up zooms in around the cursor, in the viewer's coords
+        send(&mut tree, scroll(-50.0, true));
+        send(&mut tree, scroll(50.0, true));
+        send(
+            &mut tree,
+            Event::Pinch {
+                x: 50.0,
+                y: 40.0,
+                scale: 0.5,
+                phase: GesturePhase::Update,
+            },
+        );
+        {
+            let zooms = zooms.borrow();
+            assert_eq!(zooms.len(), 3);
+            assert!(zooms[0].0 > 1.0 && zooms[1].0 < 1.0, "{zooms:?}");
+            assert!((zooms[0].0 * zooms[1].0 - 1.0).abs() < 1e-5);
+            assert_eq!(zooms[2].0, 0.5);
+            assert!(zooms.iter().all(|&(_, center)| center == (30.0, 20.0)));
+        }
+        assert_eq!(
+            viewer_ref.scroll_offset_signal().get_untracked(),
+            (0.0, 0.0)
+        );
+
+        // Plain scroll still pans
+        send(&mut tree, scroll(50.0, false));
+        assert_eq!(zooms.borrow().len(), 3);
+        assert_eq!(
+            viewer_ref.scroll_offset_signal().get_untracked(),
+            (0.0, 50.0)
+        );
+        crate::widget_ref::reset_widget_refs();
+    }
+
+    #[test]
+    fn test_on_key_gets_keys_ignored_by_focused_descendants() {
+        let keys = Rc::new(std::cell::RefCell::new(Vec::new()));
+        let log = keys.clone();
+        let mut tree = Tree::new();
+        let id = tree.register(Box::new(
+            container()
+                .on_key(move |e| {
+                    log.borrow_mut().push(e.key);
+                    if e.key == Key::Left {
+                        EventResponse::Handled
+                    } else {
+                        EventResponse::Ignored
+                    }
+                })
+                .child(
+                    container()
+                        .width(50.0)
+                        .height(20.0)
+                        .focusable()
+                        .on_key(|e| {
+                            if e.key == Key::Enter {
+                                EventResponse::Handled
+                            } else {
+                                EventResponse::Ignored
+                            }
+                        }),
+                ),
+        ));
+        layout(&mut tree, id);
+        let press = |tree: &mut Tree, key: Key| {
+            let event = Event::KeyDown {
+                key,
+                modifiers: Default::default(),
+            };
+            tree.with_widget_mut(id, |widget, id, tree| widget.event(tree, id, &event))
+        };
+
+        // Nothing focused inside: the handler doesn't run
+        assert_eq!(press(&mut tree, Key::Left), Some(EventResponse::Ignored));
+        assert!(keys.borrow().is_empty());
+
+        click(&mut tree, id, (10.0, 10.0));
+        // The focused child keeps Enter; Left bubbles up and is handled
+        assert_eq!(press(&mut tree, Key::Enter), Some(EventResponse::Handled));
+        assert_eq!(press(&mut tree, Key::Left), Some(EventResponse::Handled));
+        assert_eq!(press(&mut tree, Key::Up), Some(EventResponse::Ignored));
+        assert_eq!(*keys.borrow(), [Key::Left, Key::Up]);
+        clear_focus();
+    }
+
+    #[test]
+    fn test_min_hit_size_grows_only_the_hit_area() {
+        let clicks = Rc::new(Cell::new(0));
+        let counter = clicks.clone();
+        let mut tree = Tree::new();
+        let id = tree.register(Box::new(
+            container().padding(50.0).child(
+                container()
+                    .width(16.0)
+                    .height(16.0)
+                    .min_hit_size(40, 40)
+                    .on_click(move || counter.set(counter.get() + 1)),
+            ),
+        ));
+        layout(&mut tree, id);
+        let icon = tree.get_children(id)[0];
+
+        // Layout keeps the visual size
+        assert_eq!(
+            tree.get_bounds(icon),
+            Some(Rect::new(50.0, 50.0, 16.0, 16.0))
+        );
+        assert_eq!(tree.cached_size(id), Some(Size::new(116.0, 116.0)));
+
+        // 10px left of the icon, inside the 40x40 target centered on it
+        click(&mut tree, id, (40.0, 58.0));
+        assert_eq!(clicks.get(), 1);
+        // Outside the target
+        click(&mut tree, id, (30.0, 58.0));
+        assert_eq!(clicks.get(), 1);
+    }
+
+    #[test]
+    fn test_children_paint_in_ascending_z_order() {
+        use crate::renderer::RenderNode;
+
+        let stacked = |z: i32| container().width(50.0).height(50.0).z_index(z);
+        let mut tree = Tree::new();
+        let id = tree.register(Box::new(
+            container()
+                .layout(Overlay::new())
+                .child(stacked(2))
+                .child(stacked(0))
+                .child(stacked(1))
+                .child(stacked(1)),
+        ));
+        layout(&mut tree, id);
+        let children = tree.get_children(id).to_vec();
+
+        let mut node = RenderNode::new(id.as_u64());
+        tree.with_widget(id, |widget| {
+            widget.paint(&tree, id, &mut PaintContext::new(&mut node))
+        });
+        let painted: Vec<u64> = node.children.iter().map(|child| child.id).collect();
+        // Equal z-index keeps declaration order
+        let expected: Vec<u64> = [1, 2, 3, 0].iter().map(|&i| children[i].as_u64()).collect();
+        assert_eq!(painted, expected);
+    }
+
+    #[test]
+    fn test_shadow_color_tints_the_elevation_shadow() {
+        use crate::renderer::{DrawCommand, RenderNode};
+
+        let accent = Color::rgba(0.2, 0.4, 1.0, 0.4);
+        let shadow_of = |card: Container| {
+            let mut tree = Tree::new();
+            let id = tree.register(Box::new(
+                card.width(50.0)
+                    .height(50.0)
+                    .background(Color::WHITE)
+                    .elevation(4.0),
+            ));
+            layout(&mut tree, id);
+            let mut node = RenderNode::new(id.as_u64());
+            tree.with_widget(id, |widget| {
+                widget.paint(&tree, id, &mut PaintContext::new(&mut node))
+            });
+            node.commands.iter().find_map(|cmd| match cmd.as_ref() {
+                DrawCommand::RoundedRect { shadow, .. } => *shadow,
+                _ => None,
+            })
+        };
+
+        let default = shadow_of(container()).unwrap();
+        let tinted = shadow_of(container().shadow_color(accent)).unwrap();
+        assert_eq!(tinted.color, accent);
+        // Only the color changes with the tint
+        assert_ne!(default.color, accent);
+        assert_eq!((tinted.offset, tinted.blur), (default.offset, default.blur));
+    }
+
+    #[test]
+    fn test_hit_test_follows_descending_z_order() {
+        use crate::reactive::create_signal;
+
+        let hits = Rc::new(std::cell::RefCell::new(Vec::new()));
+        let first_z = create_signal(2);
+        let last_z = create_signal(1);
+        let stacked = |name: &'static str, z: Signal<i32>| {
+            let log = hits.clone();
+            container()
+                .width(50.0)
+                .height(50.0)
+                .z_index(z)
+                .on_click(move || log.borrow_mut().push(name))
+        };
+        let mut tree = Tree::new();
+        let id = tree.register(Box::new(
+            container()
+                .layout(Overlay::new())
+                .child(stacked("first", first_z.into()))
+                .child(stacked("middle", 0.into_signal()))
+                .child(stacked("last", last_z.into())),
+        ));
+        layout(&mut tree, id);
+
+        // The highest z-index takes the click, whatever its declaration order
+        click(&mut tree, id, (25.0, 25.0));
+        first_z.set(-1);
+        click(&mut tree, id, (25.0, 25.0));
+        last_z.set(-2);
+        click(&mut tree, id, (25.0, 25.0));
+        assert_eq!(*hits.borrow(), ["first", "last", "middle"]);
+    }
+
+    #[test]
+    fn test_disabling_clears_focus_inside() {
+        use crate::reactive::create_signal;
+        use crate::renderer::RenderNode;
+
+        let disabled = create_signal(false);
+        let mut tree = Tree::new();
+        let id = tree.register(Box::new(
+            container().disabled(disabled).child(
+                container()
+                    .width(50.0)
+                    .height(20.0)
+                    .focusable()
+                    .on_click(|| {}),
+            ),
+        ));
+        layout(&mut tree, id);
+        let field = tree.get_children(id)[0];
+
+        click(&mut tree, id, (10.0, 10.0));
+        assert_eq!(focused_widget(), Some(field));
+
+        // Painting alone keeps the focus
+        disabled.set(true);
+        let mut node = RenderNode::new(id.as_u64());
+        tree.with_widget(id, |widget| {
+            widget.paint(&tree, id, &mut PaintContext::new(&mut node))
+        });
+        assert_eq!(focused_widget(), Some(field));
+
+        // The relayout queued by disabling the form drops the focus it contained
+        tree.mark_needs_layout(id);
+        layout(&mut tree, id);
+        assert_eq!(focused_widget(), None);
+    }
+
+    #[test]
+    fn test_rounded_clip_ignores_corner_clicks() {
+        let clicks = Rc::new(Cell::new(0));
+        let counter = clicks.clone();
+        let mut tree = Tree::new();
+        let id = tree.register(Box::new(
+            container()
+                .width(100.0)
+                .height(100.0)
+                .corner_radius(40.0)
+                .overflow(Overflow::Hidden)
+                .child(
+                    container()
+                        .width(100.0)
+                        .height(100.0)
+                        .on_click(move || counter.set(counter.get() + 1)),
+                ),
+        ));
+        layout(&mut tree, id);
+
+        click(&mut tree, id, (2.0, 2.0));
+        assert_eq!(clicks.get(), 0);
+        click(&mut tree, id, (50.0, 50.0));
+        assert_eq!(clicks.get(), 1);
+    }
+
+    #[test]
+    fn test_repaint_boundary_clips_regardless_of_overflow() {
+        let clicks = Rc::new(Cell::new(0));
+        let counter = clicks.clone();
+        let mut tree = Tree::new();
+        let id = tree.register(Box::new(
+            container()
+                .width(50.0)
+                .height(50.0)
+                .repaint_boundary()
+                .overflow(Overflow::Visible)
+                .child(
+                    container()
+                        .width(100.0)
+                        .height(100.0)
+                        .on_click(move || counter.set(counter.get() + 1)),
+                ),
+        ));
+        layout(&mut tree, id);
+
+        // The child overflows the boundary, which hides that part anyway
+        click(&mut tree, id, (75.0, 75.0));
+        assert_eq!(clicks.get(), 0);
+        click(&mut tree, id, (25.0, 25.0));
+        assert_eq!(clicks.get(), 1);
+    }
+
+    #[test]
+    fn test_nested_transforms_hit_test() {
+        let clicks = Rc::new(Cell::new(0));
+        let counter = clicks.clone();
+        // A 40x40 button rotated 45° inside a rotated, scaled panel inside a
+        // translated, rotated root
+        let mut tree = Tree::new();
+        let id = tree.register(Box::new(
+            container()
+                .width(300.0)
+                .height(300.0)
+                .padding(30.0)
+                .translate(15.0, 5.0)
+                .rotate(30.0)
+                .transform_origin(TransformOrigin::TOP_LEFT)
+                .child(
+                    container()
+                        .width(200.0)
+                        .height(200.0)
+                        .padding(20.0)
+                        .rotate(90.0)
+                        .scale(0.5)
+                        .child(
+                            container()
+                                .width(40.0)
+                                .height(40.0)
+                                .rotate(45.0)
+                                .on_click(move || counter.set(counter.get() + 1)),
+                        ),
+                ),
+        ));
+        layout(&mut tree, id);
+
+        // Maps a point in the panel's local space to the screen, as painted
+        let panel = Transform::translate(15.0, 5.0)
+            .then(&Transform::rotate_degrees(30.0))
+            .then(&Transform::translate(30.0, 30.0))
+            .then(
+                &Transform::rotate_degrees(90.0)
+                    .then(&Transform::scale(0.5))
+                    .center_at(100.0, 100.0),
+            );
+        // Offsets from the button's center (40, 40): the center and a point
+        // towards a rotated corner hit; a point towards an unrotated corner misses
+        for (dx, dy, hits) in [(0.0, 0.0, 1), (26.0, 0.0, 1), (18.0, 18.0, 0)] {
+            let before = clicks.get();
+            click(&mut tree, id, panel.transform_point(40.0 + dx, 40.0 + dy));
+            assert_eq!(clicks.get() - before, hits, "offset ({dx}, {dy})");
+        }
+    }
+}
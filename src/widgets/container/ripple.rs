@@ -1,5 +1,4 @@
-use std::time::Instant;
-
+use crate::animation::clock::Stopwatch;
 use crate::widgets::state_layer::RippleConfig;
 
 /// Ripple animation state for pressed feedback
@@ -15,10 +14,10 @@ pub struct RippleState {
     pub opacity: f32,
     /// Whether the ripple is currently fading out (mouse released)
     pub fading: bool,
-    /// Time when ripple animation started (for smooth animation)
-    pub start_time: Option<Instant>,
-    /// Time when ripple fade/contraction started
-    pub fade_start_time: Option<Instant>,
+    /// Animation time since the ripple started (for smooth animation)
+    pub stopwatch: Option<Stopwatch>,
+    /// Animation time since the ripple fade/contraction started
+    pub fade_stopwatch: Option<Stopwatch>,
     /// Progress at which fading started (for smooth contraction)
     pub fade_start_progress: f32,
     /// Deferred reset flag to ensure final frame is painted before clearing state
@@ -33,12 +32,16 @@ impl RippleState {
     /// Start a ripple animation at the given local coordinates.
     ///
     /// The coordinates should be relative to the container's origin (0,0 = top-left).
+    /// Does nothing while reduced motion is enabled.
     pub fn start(&mut self, local_x: f32, local_y: f32) {
+        if crate::animation::reduce_motion() {
+            return;
+        }
         self.center = Some((local_x, local_y));
         self.progress = 0.0;
         self.opacity = 1.0;
         self.fading = false;
-        self.start_time = Some(Instant::now());
+        self.stopwatch = Some(Stopwatch::start());
     }
 
     /// Start fading the ripple, contracting toward the given local exit point.
@@ -48,7 +51,7 @@ impl RippleState {
         if self.center.is_some() && self.opacity > 0.0 {
             self.exit_center = Some((exit_x, exit_y));
             self.fading = true;
-            self.fade_start_time = Some(Instant::now());
+            self.fade_stopwatch = Some(Stopwatch::start());
             self.fade_start_progress = self.progress;
         }
     }
@@ -58,7 +61,7 @@ impl RippleState {
         if self.center.is_some() && self.opacity > 0.0 {
             self.exit_center = Some((container_width / 2.0, container_height / 2.0));
             self.fading = true;
-            self.fade_start_time = Some(Instant::now());
+            self.fade_stopwatch = Some(Stopwatch::start());
             self.fade_start_progress = self.progress;
         }
     }
@@ -80,8 +83,8 @@ impl RippleState {
         self.progress = 0.0;
         self.opacity = 0.0;
         self.fading = false;
-        self.start_time = None;
-        self.fade_start_time = None;
+        self.stopwatch = None;
+        self.fade_stopwatch = None;
         self.fade_start_progress = 0.0;
         self.pending_reset = false;
     }
@@ -94,21 +97,25 @@ impl RippleState {
             return false;
         }
 
-        let Some(start_time) = self.start_time else {
+        if self.stopwatch.is_none() {
             return false;
-        };
+        }
 
-        let elapsed = start_time.elapsed().as_secs_f32();
+        // Reduced motion was enabled mid-ripple, or animations are finishing
+        if crate::animation::skip_animations() {
+            self.reset();
+            return false;
+        }
 
         // Expansion animation (0.4 seconds base, modified by expand_speed)
         let expand_duration = 0.4 / ripple_config.expand_speed;
 
         if self.fading {
             // Reverse animation: contract toward exit point
-            let Some(fade_start) = self.fade_start_time else {
+            let Some(ref mut fade_stopwatch) = self.fade_stopwatch else {
                 return false;
             };
-            let fade_elapsed = fade_start.elapsed().as_secs_f32();
+            let fade_elapsed = fade_stopwatch.tick().as_secs_f32();
             let fade_duration = 0.3 / ripple_config.fade_speed;
 
             // Calculate contraction progress (0 = just started fading, 1 = fully contracted)
@@ -139,7 +146,10 @@ impl RippleState {
             }
         } else {
             // Expansion animation
-            if self.progress < 1.0 {
+            if self.progress < 1.0
+                && let Some(ref mut stopwatch) = self.stopwatch
+            {
+                let elapsed = stopwatch.tick().as_secs_f32();
                 self.progress = (elapsed / expand_duration).min(1.0);
                 // Use ease-out curve for expansion
                 self.progress = 1.0 - (1.0 - self.progress).powi(3);
@@ -154,6 +164,7 @@ impl RippleState {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::animation::clock;
 
     #[test]
     fn test_ripple_state_new() {
@@ -176,7 +187,7 @@ mod tests {
         assert_eq!(state.progress, 0.0);
         assert_eq!(state.opacity, 1.0);
         assert!(!state.fading);
-        assert!(state.start_time.is_some());
+        assert!(state.stopwatch.is_some());
     }
 
     #[test]
@@ -190,7 +201,7 @@ mod tests {
         assert!(state.fading);
         assert_eq!(state.exit_center, Some((150.0, 250.0)));
         assert_eq!(state.fade_start_progress, 0.5);
-        assert!(state.fade_start_time.is_some());
+        assert!(state.fade_stopwatch.is_some());
     }
 
     #[test]
@@ -232,8 +243,8 @@ mod tests {
         assert_eq!(state.progress, 0.0);
         assert_eq!(state.opacity, 0.0);
         assert!(!state.fading);
-        assert!(state.start_time.is_none());
-        assert!(state.fade_start_time.is_none());
+        assert!(state.stopwatch.is_none());
+        assert!(state.fade_stopwatch.is_none());
         assert_eq!(state.fade_start_progress, 0.0);
         assert!(!state.pending_reset);
     }
@@ -264,4 +275,29 @@ mod tests {
         state.fading = true;
         assert!(state.is_animating()); // fading
     }
+
+    #[test]
+    fn test_ripple_expansion_follows_virtual_clock() {
+        clock::pause_clock();
+        let config = RippleConfig::default();
+        let mut state = RippleState::new();
+        state.start(0.0, 0.0);
+
+        // Halfway through the 0.4s expansion: ease-out cubic gives 0.875
+        for _ in 0..2 {
+            clock::advance_clock(std::time::Duration::from_millis(100));
+            assert!(state.advance(&config));
+        }
+        assert!((state.progress - 0.875).abs() < 1e-4);
+
+        // A stall only advances the ripple by one capped step
+        clock::advance_clock(std::time::Duration::from_secs(1));
+        assert!(state.advance(&config));
+        assert!(state.progress < 1.0);
+
+        clock::advance_clock(std::time::Duration::from_millis(100));
+        assert!(!state.advance(&config));
+        assert_eq!(state.progress, 1.0);
+        clock::resume_clock();
+    }
 }
@@ -1,15 +1,44 @@
 //! Scrollable container functionality.
 
-use crate::animation::{SpringConfig, Transition};
-use crate::jobs::{JobRequest, RequiredJob, request_job};
+use std::time::Instant;
+
+use crate::animation::{SpringConfig, TimingFunction, Transition, clock};
+use crate::jobs::{JobRequest, JobType, RequiredJob, request_job};
 use crate::layout::Constraints;
-use crate::renderer::PaintContext;
+use crate::reactive::{OptionSignalExt, RwSignal, create_signal, with_signal_tracking};
+use crate::renderer::{Gradient, GradientDir, PaintContext};
+use crate::transform::Transform;
 use crate::tree::{Tree, WidgetId};
-use crate::widgets::scroll::{ScrollAxis, ScrollbarAxis, ScrollbarVisibility};
-use crate::widgets::widget::{Event, EventResponse, MouseButton, Rect, ScrollSource};
+use crate::widgets::scroll::{ScrollAxis, ScrollbarAxis, ScrollbarVisibility, pinned_header};
+use crate::widgets::widget::{Color, Event, EventResponse, MouseButton, Rect, ScrollSource};
 
 use super::Container;
-use super::animations::AnimationState;
+use super::animations::{AdvanceResult, AnimationState};
+
+/// Duration of the auto-hide fade in and out
+const SCROLLBAR_FADE_MS: f32 = 200.0;
+
+/// Fade state of auto-hiding scrollbars.
+pub(crate) struct ScrollbarFade {
+    /// Opacity applied to the track and handle backgrounds
+    opacity: RwSignal<f32>,
+    anim: AnimationState<f32>,
+    /// Last scroll or scrollbar hover
+    last_activity: Option<Instant>,
+}
+
+impl ScrollbarFade {
+    fn new() -> Self {
+        Self {
+            opacity: create_signal(0.0),
+            anim: AnimationState::new(
+                0.0,
+                Transition::new(SCROLLBAR_FADE_MS, TimingFunction::EaseOut),
+            ),
+            last_activity: None,
+        }
+    }
+}
 
 impl Container {
     /// Initialize scrollbar containers if scrolling is enabled and they don't exist yet.
@@ -21,10 +50,19 @@ impl Container {
             return;
         }
 
+        if matches!(
+            self.scroll().scrollbar_visibility,
+            ScrollbarVisibility::AutoHide { .. }
+        ) && self.scroll().scrollbar_fade.is_none()
+        {
+            self.scroll_mut().scrollbar_fade = Some(ScrollbarFade::new());
+        }
+        let opacity = self.scroll().scrollbar_fade.as_ref().map(|f| f.opacity);
+
         // Create vertical scrollbar containers if needed
         if self.scroll_axis.allows_vertical() && self.scroll().v_scrollbar_track_id.is_none() {
             let (track, handle, scale_anim) =
-                Self::create_scrollbar_components(&self.scroll().scrollbar_config);
+                Self::create_scrollbar_components(&self.scroll().scrollbar_config, opacity);
 
             // Register track in Tree
             let track_id = tree.register(Box::new(track));
@@ -42,7 +80,7 @@ impl Container {
         // Create horizontal scrollbar containers if needed
         if self.scroll_axis.allows_horizontal() && self.scroll().h_scrollbar_track_id.is_none() {
             let (track, handle, scale_anim) =
-                Self::create_scrollbar_components(&self.scroll().scrollbar_config);
+                Self::create_scrollbar_components(&self.scroll().scrollbar_config, opacity);
 
             // Register track in Tree
             let track_id = tree.register(Box::new(track));
@@ -58,8 +96,11 @@ impl Container {
         }
     }
 
+    /// `opacity` fades the resting track and handle colors of auto-hiding
+    /// scrollbars; hover and pressed colors only show while fully visible.
     fn create_scrollbar_components(
         config: &crate::widgets::scroll::ScrollbarConfig,
+        opacity: Option<RwSignal<f32>>,
     ) -> (Container, Container, AnimationState<f32>) {
         use crate::widgets::state_layer::StateStyle;
 
@@ -72,15 +113,22 @@ impl Container {
         let handle_hover_color = config.handle_hover_color;
         let handle_pressed_color = config.handle_pressed_color;
 
+        let faded = move |color: Color| {
+            move || match opacity {
+                Some(opacity) => color.scale_alpha(opacity.get()),
+                None => color,
+            }
+        };
+
         // Track container
         let track = Container::new()
-            .background(track_color)
+            .background(faded(track_color))
             .corner_radius(track_corner_radius)
             .corner_curvature(track_corner_curvature);
 
         // Handle container with hover state for color change and ripple on press
         let handle = Container::new()
-            .background(handle_color)
+            .background(faded(handle_color))
             .corner_radius(handle_corner_radius)
             .corner_curvature(handle_corner_curvature)
             .hover_state(move |s: StateStyle| s.background(handle_hover_color))
@@ -227,6 +275,54 @@ impl Container {
         }
     }
 
+    /// Show auto-hiding scrollbars and restart their idle timeout.
+    pub(super) fn reveal_scrollbars(&mut self, id: WidgetId) {
+        if let Some(fade) = self
+            .scroll_data
+            .as_mut()
+            .and_then(|sd| sd.scrollbar_fade.as_mut())
+        {
+            fade.last_activity = Some(clock::now());
+            request_job(id, JobRequest::Animation(RequiredJob::Paint));
+        }
+    }
+
+    /// Fade auto-hiding scrollbars in after activity and out once idle.
+    /// Returns true while fading or waiting for the idle timeout.
+    pub(super) fn advance_scrollbar_fade(&mut self, id: WidgetId) -> bool {
+        let Some(ref mut sd) = self.scroll_data else {
+            return false;
+        };
+        let state = &sd.scroll_state;
+        let active = [ScrollbarAxis::Vertical, ScrollbarAxis::Horizontal]
+            .into_iter()
+            .any(|axis| state.is_track_hovered(axis) || state.is_dragging(axis));
+        let visibility = sd.scrollbar_visibility;
+        let Some(ref mut fade) = sd.scrollbar_fade else {
+            return false;
+        };
+
+        if active {
+            fade.last_activity = Some(clock::now());
+        }
+        let shown = fade
+            .last_activity
+            .is_some_and(|t| visibility.shown_after_idle(clock::elapsed_since(t)));
+        fade.anim.animate_to(if shown { 1.0 } else { 0.0 });
+
+        let fading = fade.anim.is_animating();
+        if fading && let AdvanceResult::Changed(opacity) = fade.anim.advance() {
+            fade.opacity.set(opacity);
+        }
+        if fading {
+            request_job(id, JobRequest::Animation(RequiredJob::Paint));
+        } else if shown {
+            // Keep polling until the idle timeout expires
+            request_job(id, JobRequest::Animation(RequiredJob::None));
+        }
+        fading || shown
+    }
+
     /// Advance scrollbar scale animations and apply transforms.
     /// Called from advance_animations since scroll is paint-only and layout
     /// may not run during hover events.
@@ -357,11 +453,14 @@ impl Container {
         _id: WidgetId,
         ctx: &mut PaintContext,
     ) {
-        use crate::transform::Transform;
-
         let sd = self.scroll();
 
-        if sd.scrollbar_visibility == ScrollbarVisibility::Hidden {
+        if sd.scrollbar_visibility == ScrollbarVisibility::Hidden
+            || sd
+                .scrollbar_fade
+                .as_ref()
+                .is_some_and(|fade| fade.opacity.get_untracked() <= 0.0)
+        {
             return;
         }
 
@@ -472,6 +571,121 @@ impl Container {
         }
     }
 
+    /// Draw the `scroll_fade` gradients over the children at edges with
+    /// hidden content, blending into `background` unless a fade color is set.
+    pub(super) fn paint_scroll_fade(
+        &self,
+        id: WidgetId,
+        ctx: &mut PaintContext,
+        local_bounds: Rect,
+        background: Color,
+        corner_radius: f32,
+        corner_curvature: f32,
+    ) {
+        let sd = self.scroll();
+        let Some(ref fade) = sd.edge_fade else {
+            return;
+        };
+        let color = with_signal_tracking(id, JobType::Paint, || fade.color.get_or(background));
+        let [top, bottom, left, right] = sd.scroll_state.edge_fades(self.scroll_axis, fade.size);
+        if color.a <= 0.0 || top + bottom + left + right <= 0.0 {
+            return;
+        }
+
+        let size = fade.size;
+        let Rect { width, height, .. } = local_bounds;
+        let edges = [
+            (
+                top,
+                Rect::new(0.0, 0.0, width, size),
+                GradientDir::Vertical,
+                false,
+            ),
+            (
+                bottom,
+                Rect::new(0.0, height - size, width, size),
+                GradientDir::Vertical,
+                true,
+            ),
+            (
+                left,
+                Rect::new(0.0, 0.0, size, height),
+                GradientDir::Horizontal,
+                false,
+            ),
+            (
+                right,
+                Rect::new(width - size, 0.0, size, height),
+                GradientDir::Horizontal,
+                true,
+            ),
+        ];
+
+        ctx.set_overlay_clip(local_bounds, corner_radius, corner_curvature);
+        for (strength, rect, direction, toward_end) in edges {
+            if strength <= 0.0 {
+                continue;
+            }
+            let solid = color.scale_alpha(strength);
+            let clear = color.with_alpha(0.0);
+            let (start_color, end_color) = if toward_end {
+                (clear, solid)
+            } else {
+                (solid, clear)
+            };
+            ctx.draw_overlay_gradient_rect(
+                rect,
+                Gradient {
+                    start_color,
+                    end_color,
+                    direction,
+                },
+            );
+        }
+    }
+
+    /// The sticky child pinned at the current scroll offset and how far it is
+    /// shifted down from its layout position.
+    pub(super) fn pinned_sticky_child(&self, tree: &Tree) -> Option<(WidgetId, f32)> {
+        let sd = self.scroll_data.as_deref()?;
+        if sd.sticky_children.is_empty() {
+            return None;
+        }
+        let headers: Vec<(f32, f32)> = sd
+            .sticky_children
+            .iter()
+            .map(|&child| {
+                tree.get_bounds(child)
+                    .map_or((0.0, 0.0), |b| (b.y, b.height))
+            })
+            .collect();
+        let (index, shift) = pinned_header(&headers, sd.scroll_state.offset_y)?;
+        Some((sd.sticky_children[index], shift))
+    }
+
+    /// Paint a pinned sticky child `shift` pixels below its layout position.
+    pub(super) fn paint_sticky_child(
+        &self,
+        tree: &Tree,
+        child_id: WidgetId,
+        shift: f32,
+        ctx: &mut PaintContext,
+    ) {
+        let Some(bounds) = tree.get_bounds(child_id) else {
+            return;
+        };
+        let state = &self.scroll().scroll_state;
+        let child_local = Rect::new(0.0, 0.0, bounds.width, bounds.height);
+        let mut child_ctx = ctx.add_child(child_id.as_u64(), child_local);
+        child_ctx.set_transform(Transform::translate(
+            bounds.x - state.offset_x,
+            bounds.y + shift - state.offset_y,
+        ));
+        tree.with_widget(child_id, |child| {
+            child.paint(tree, child_id, &mut child_ctx)
+        });
+    }
+
     /// Handle scrollbar-related events, returns EventResponse if handled
     pub(super) fn handle_scrollbar_event(
         &mut self,
@@ -579,6 +793,7 @@ impl Container {
             Event::MouseUp { button, .. } if *button == MouseButton::Left => {
                 if self.scroll().scroll_state.scrollbar_dragging {
                     self.scroll_mut().scroll_state.scrollbar_dragging = false;
+                    self.schedule_scroll_snap(id);
                     if let Some(handle_id) = self.scroll().v_scrollbar_handle_id {
                         tree.with_widget_mut(handle_id, |widget, widget_id, tree| {
                             widget.event(tree, widget_id, event);
@@ -589,6 +804,7 @@ impl Container {
                 }
                 if self.scroll().scroll_state.h_scrollbar_dragging {
                     self.scroll_mut().scroll_state.h_scrollbar_dragging = false;
+                    self.schedule_scroll_snap(id);
                     if let Some(handle_id) = self.scroll().h_scrollbar_handle_id {
                         tree.with_widget_mut(handle_id, |widget, widget_id, tree| {
                             widget.event(tree, widget_id, event);
@@ -723,6 +939,7 @@ impl Container {
                 let sd = self.scroll_mut();
                 let offset = ratio * sd.scroll_state.max_scroll(axis);
                 sd.scroll_state.set_offset(axis, offset);
+                self.sync_scroll_observers(true);
                 request_job(id, JobRequest::Paint);
             }
             return Some(EventResponse::Handled);
@@ -762,6 +979,7 @@ impl Container {
             let scroll_delta = (delta / available) * max_scroll;
             let new_offset = (start_offset + scroll_delta).clamp(0.0, max_scroll);
             self.scroll_mut().scroll_state.set_offset(axis, new_offset);
+            self.sync_scroll_observers(true);
             // Scrollbar dragging needs Animation + Paint for smooth updates
             request_job(id, JobRequest::Animation(RequiredJob::Paint));
         }
@@ -773,7 +991,7 @@ impl Container {
     fn update_scrollbar_hover(
         &mut self,
         tree: &mut Tree,
-        _id: WidgetId,
+        id: WidgetId,
         bounds: Rect,
         axis: ScrollbarAxis,
         x: f32,
@@ -839,9 +1057,151 @@ impl Container {
             }
         }
 
+        if is_track_hovered && !was_track_hovered {
+            self.reveal_scrollbars(id);
+        }
         needs_repaint
     }
 
+    /// Scroll so that `child_id` is fully visible within the viewport.
+    ///
+    /// Scrolls the minimal distance needed. Returns true if the offset changed.
+    pub(super) fn scroll_child_into_view(
+        &mut self,
+        tree: &Tree,
+        bounds: Rect,
+        child_id: WidgetId,
+    ) -> bool {
+        let Some(child) = tree.get_bounds(child_id) else {
+            return false;
+        };
+        let axis = self.scroll_axis;
+        let sd = self.scroll_mut();
+        let old_x = sd.scroll_state.offset_x;
+        let old_y = sd.scroll_state.offset_y;
+
+        if axis.allows_vertical() {
+            let offset = &mut sd.scroll_state.offset_y;
+            if child.y < *offset {
+                *offset = child.y;
+            } else if child.y + child.height > *offset + bounds.height {
+                *offset = child.y + child.height - bounds.height;
+            }
+        }
+        if axis.allows_horizontal() {
+            let offset = &mut sd.scroll_state.offset_x;
+            if child.x < *offset {
+                *offset = child.x;
+            } else if child.x + child.width > *offset + bounds.width {
+                *offset = child.x + child.width - bounds.width;
+            }
+        }
+        sd.scroll_state.clamp_offsets();
+
+        old_x != sd.scroll_state.offset_x || old_y != sd.scroll_state.offset_y
+    }
+
+    /// Push the current scroll position to the widget ref signals and fire
+    /// `on_scroll_end` if the user scrolled near the end.
+    ///
+    /// `user_scroll` is false for layout-driven changes, which only re-arm
+    /// `on_scroll_end` without firing it.
+    pub(super) fn sync_scroll_observers(&mut self, user_scroll: bool) {
+        let axis = self.scroll_axis;
+        let widget_ref = self.widget_ref;
+        let Some(ref mut sd) = self.scroll_data else {
+            return;
+        };
+        let state = &sd.scroll_state;
+        let (max_x, max_y) = (state.max_scroll_x(), state.max_scroll_y());
+
+        if let Some(wr) = widget_ref {
+            let fraction = |offset: f32, max: f32| if max > 0.0 { offset / max } else { 0.0 };
+            wr.set_scroll(
+                (state.offset_x, state.offset_y),
+                (
+                    fraction(state.offset_x, max_x),
+                    fraction(state.offset_y, max_y),
+                ),
+            );
+        }
+
+        let Some(ref mut handler) = sd.on_scroll_end else {
+            return;
+        };
+        let (offset, max) = if axis.allows_vertical() {
+            (state.offset_y, max_y)
+        } else {
+            (state.offset_x, max_x)
+        };
+        let near_end = max > 0.0 && max - offset <= handler.threshold;
+        if !near_end {
+            handler.fired = false;
+        } else if user_scroll && !handler.fired {
+            handler.fired = true;
+            (handler.callback)();
+        }
+    }
+
+    /// Restart snap detection after the user scrolled (no-op without `scroll_snap`).
+    ///
+    /// Any running snap animation is cancelled; the snap happens once scrolling
+    /// and momentum have settled.
+    pub(super) fn schedule_scroll_snap(&mut self, id: WidgetId) {
+        let Some(ref mut sd) = self.scroll_data else {
+            return;
+        };
+        if sd.snap.is_none() {
+            return;
+        }
+        sd.scroll_state.cancel_snap();
+        sd.scroll_state.snap_pending = true;
+        sd.scroll_state.last_scroll_time = Some(clock::now());
+        request_job(id, JobRequest::Animation(RequiredJob::Paint));
+    }
+
+    /// Start or advance the snap animation. Returns true while snapping is in progress.
+    pub(super) fn advance_scroll_snap(&mut self, tree: &Tree, id: WidgetId) -> bool {
+        let axis = self.scroll_axis;
+        let children = self.children_source.get();
+        let Some(ref mut sd) = self.scroll_data else {
+            return false;
+        };
+        let Some(align) = sd.snap else {
+            return false;
+        };
+        let state = &mut sd.scroll_state;
+
+        if state.should_snap() {
+            state.snap_pending = false;
+            let bounds: Vec<Rect> = children
+                .iter()
+                .filter_map(|&child| tree.get_bounds(child))
+                .collect();
+            if axis.allows_horizontal() {
+                let items: Vec<_> = bounds.iter().map(|b| (b.x, b.width)).collect();
+                state.snap_target_x =
+                    state.nearest_snap_offset(ScrollbarAxis::Horizontal, align, &items);
+            }
+            if axis.allows_vertical() {
+                let items: Vec<_> = bounds.iter().map(|b| (b.y, b.height)).collect();
+                state.snap_target_y =
+                    state.nearest_snap_offset(ScrollbarAxis::Vertical, align, &items);
+            }
+        }
+
+        let animating = if state.snap_target_x.is_some() || state.snap_target_y.is_some() {
+            state.advance_snap()
+        } else {
+            // Keep polling until scrolling settles
+            state.snap_pending
+        };
+        if animating {
+            request_job(id, JobRequest::Animation(RequiredJob::Paint));
+        }
+        animating
+    }
+
     /// Apply scroll delta and return true if any scrolling occurred
     pub(super) fn apply_scroll(
         &mut self,
@@ -882,7 +1242,7 @@ impl Container {
                 }
                 ScrollAxis::None => {}
             }
-            sd.scroll_state.last_scroll_time = Some(std::time::Instant::now());
+            sd.scroll_state.last_scroll_time = Some(clock::now());
         }
 
         old_x != sd.scroll_state.offset_x || old_y != sd.scroll_state.offset_y
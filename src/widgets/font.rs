@@ -1,8 +1,10 @@
-//! Font family and weight types for text styling.
+//! Font family, weight, style and wrapping types for text styling.
 //!
-//! These types allow configuring font family and weight on text widgets.
+//! These types allow configuring font family, weight, style and line wrapping
+//! on text widgets.
 
-use cosmic_text::{Family, Weight};
+use cosmic_text::fontdb::{Database, Query};
+use cosmic_text::{Family, Style, Weight, Wrap};
 
 /// Font family specification.
 ///
@@ -80,6 +82,151 @@ impl FontWeight {
     }
 }
 
+/// Font style (upright or slanted), matching CSS font-style values.
+///
+/// # Examples
+///
+/// ```ignore
+/// text("Hello").font_style(FontStyle::Italic)
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum FontStyle {
+    /// Upright glyphs (default)
+    #[default]
+    Normal,
+    /// The family's italic face
+    Italic,
+    /// The family's oblique (slanted) face
+    Oblique,
+}
+
+impl FontStyle {
+    /// Convert to cosmic-text Style type for rendering.
+    pub fn to_cosmic(self) -> Style {
+        match self {
+            FontStyle::Normal => Style::Normal,
+            FontStyle::Italic => Style::Italic,
+            FontStyle::Oblique => Style::Oblique,
+        }
+    }
+
+    /// The face style to request from `db` for this family and weight.
+    ///
+    /// Returns `Style::Normal` when the family has no slanted face, meaning the
+    /// slant has to be synthesized. Unknown families keep the requested style
+    /// so fallback fonts can still match it.
+    pub(crate) fn resolve(self, db: &Database, family: &FontFamily, weight: FontWeight) -> Style {
+        let style = self.to_cosmic();
+        if self == FontStyle::Normal {
+            return style;
+        }
+        let query = Query {
+            families: &[family.to_cosmic()],
+            weight: weight.to_cosmic(),
+            style,
+            ..Query::default()
+        };
+        match db.query(&query).and_then(|id| db.face(id)) {
+            Some(face) if face.style == Style::Normal => Style::Normal,
+            _ => style,
+        }
+    }
+}
+
+/// How text breaks into lines.
+///
+/// # Examples
+///
+/// ```ignore
+/// text(url).wrap(WrapMode::BreakWord)
+/// text(source_code).mono().wrap(WrapMode::Pre)
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum WrapMode {
+    /// A single line: line breaks show as spaces and overflowing text is
+    /// clipped by the parent
+    NoWrap,
+    /// Wrap between words; a word longer than the line overflows it
+    #[default]
+    Word,
+    /// Wrap between words, breaking words that don't fit on a line of their own
+    BreakWord,
+    /// Keep line breaks and whitespace as written, without wrapping
+    Pre,
+}
+
+impl WrapMode {
+    /// Convert to cosmic-text Wrap type for rendering.
+    pub fn to_cosmic(self) -> Wrap {
+        match self {
+            WrapMode::NoWrap | WrapMode::Pre => Wrap::None,
+            WrapMode::Word => Wrap::Word,
+            WrapMode::BreakWord => Wrap::WordOrGlyph,
+        }
+    }
+
+    /// Whether lines are broken to fit the available width.
+    pub fn wraps(self) -> bool {
+        matches!(self, WrapMode::Word | WrapMode::BreakWord)
+    }
+}
+
+/// Base direction of a paragraph of text.
+///
+/// Mixed-direction text is always reordered by the Unicode bidi algorithm;
+/// the direction decides which side lines start from and how neutral
+/// characters (spaces, punctuation, digits) at the edges are placed.
+///
+/// # Examples
+///
+/// ```ignore
+/// text(arabic_label).direction(TextDirection::Rtl)
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum TextDirection {
+    /// Taken from the first strong character of each paragraph
+    #[default]
+    Auto,
+    /// Left-to-right paragraphs, aligned to the left
+    Ltr,
+    /// Right-to-left paragraphs, aligned to the right
+    Rtl,
+}
+
+impl TextDirection {
+    /// Zero-width mark that sets the direction of the paragraph it starts.
+    fn mark(self) -> Option<char> {
+        match self {
+            TextDirection::Auto => None,
+            TextDirection::Ltr => Some('\u{200E}'),
+            TextDirection::Rtl => Some('\u{200F}'),
+        }
+    }
+
+    /// `text` with every paragraph forced to this direction.
+    ///
+    /// The shaper detects the direction from the first strong character, so
+    /// a direction mark is prepended to each line that doesn't start with
+    /// one already. `Auto` leaves the text unchanged.
+    pub(crate) fn apply(self, text: &str) -> Option<String> {
+        let mark = self.mark()?;
+        if text.split('\n').all(|line| line.starts_with(mark)) {
+            return None;
+        }
+        let lines: Vec<String> = text
+            .split('\n')
+            .map(|line| {
+                if line.starts_with(mark) {
+                    line.to_string()
+                } else {
+                    format!("{mark}{line}")
+                }
+            })
+            .collect();
+        Some(lines.join("\n"))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -94,6 +241,48 @@ mod tests {
         assert_eq!(FontWeight::default(), FontWeight(0));
     }
 
+    #[test]
+    fn font_style_default() {
+        assert_eq!(FontStyle::default(), FontStyle::Normal);
+        assert_eq!(FontStyle::Italic.to_cosmic(), Style::Italic);
+    }
+
+    #[test]
+    fn font_style_resolve_without_faces() {
+        let db = Database::new();
+        let family = FontFamily::Name("Missing".into());
+        assert_eq!(
+            FontStyle::Italic.resolve(&db, &family, FontWeight::NORMAL),
+            Style::Italic
+        );
+        assert_eq!(
+            FontStyle::Normal.resolve(&db, &family, FontWeight::NORMAL),
+            Style::Normal
+        );
+    }
+
+    #[test]
+    fn wrap_mode_default() {
+        assert_eq!(WrapMode::default(), WrapMode::Word);
+        assert_eq!(WrapMode::BreakWord.to_cosmic(), Wrap::WordOrGlyph);
+        assert!(!WrapMode::Pre.wraps());
+    }
+
+    #[test]
+    fn text_direction_marks_each_paragraph() {
+        assert_eq!(TextDirection::Auto.apply("abc"), None);
+        assert_eq!(
+            TextDirection::Rtl.apply("abc\ndef").as_deref(),
+            Some("\u{200F}abc\n\u{200F}def")
+        );
+        // Already marked text is left alone
+        assert_eq!(TextDirection::Rtl.apply("\u{200F}abc"), None);
+        assert_eq!(
+            TextDirection::Ltr.apply("\u{200E}abc\n").as_deref(),
+            Some("\u{200E}abc\n\u{200E}")
+        );
+    }
+
     #[test]
     fn font_weight_constants() {
         assert_eq!(FontWeight::NORMAL.0, 400);
@@ -0,0 +1,218 @@
+//! Ready-made icon button component.
+//!
+//! [`icon_button`] shows a tinted icon inside a circular hit area with hover
+//! and pressed state layers, a ripple and a focus ring. The hit area is never
+//! smaller than 40x40 so small icons stay easy to click.
+//!
+//! # Example
+//!
+//! ```ignore
+//! icon_button("./icons/settings.svg").on_click(move || open_settings());
+//!
+//! icon_button(ImageSource::SvgBytes(CLOSE_SVG.into()))
+//!     .color(Color::rgb(0.8, 0.8, 0.85))
+//!     .hover_color(Color::rgb(0.95, 0.4, 0.4));
+//! ```
+
+use std::sync::Arc;
+
+use crate::component;
+use crate::reactive::{IntoSignal, create_signal};
+
+use super::container::container;
+use super::image::{ImageSource, image};
+use super::state_layer::StateStyle;
+use super::widget::{Color, Padding, Widget, delegate_widget};
+
+/// Default icon tint
+pub const DEFAULT_ICON_COLOR: Color = Color::rgb(0.85, 0.85, 0.9);
+
+/// Smallest hit area of an icon button, in logical pixels
+const MIN_HIT_SIZE: f32 = 40.0;
+
+/// Default color of the focus ring
+const FOCUS_RING_COLOR: Color = Color::rgba(1.0, 1.0, 1.0, 0.7);
+
+// The macro-generated component gives every prop a default and a public
+// `new()`. It stays private behind `IconButton`, which can only be built by
+// `icon_button(source)`, so an icon button always has an icon.
+mod component {
+    use super::*;
+
+    #[component]
+    pub fn icon_button(
+        // Never shown: `icon_button` always sets the source
+        #[prop(default = "ImageSource::SvgBytes(Arc::from([]))")] source: ImageSource,
+        #[prop(default = "DEFAULT_ICON_COLOR")] color: Color,
+        // Transparent means "keep `color` while hovered"
+        #[prop(default = "Color::TRANSPARENT")] hover_color: Color,
+        #[prop(default = "FOCUS_RING_COLOR")] focus_ring_color: Color,
+        #[prop(default = "20.0")] size: f32,
+        #[prop(default = "Padding::all(10.0)")] padding: Padding,
+        #[prop(callback)] on_click: (),
+    ) -> impl Widget {
+        // Hover and press tints follow `color`, so they are derived here
+        // rather than fixed in state styles
+        let hovered = create_signal(false);
+        let pressed = create_signal(false);
+
+        container()
+            .padding(padding)
+            .clip_circle()
+            .min_hit_size(MIN_HIT_SIZE, MIN_HIT_SIZE)
+            .background(move || {
+                if pressed.get() {
+                    color.get().with_alpha(0.12)
+                } else if hovered.get() {
+                    color.get().with_alpha(0.08)
+                } else {
+                    Color::TRANSPARENT
+                }
+            })
+            // Only visible as the focus ring
+            .border(0.0, focus_ring_color)
+            .pressed_state(|s: StateStyle| s.ripple())
+            .ripple_color(move || color.get().with_alpha(0.25))
+            .focused_state(|s: StateStyle| s.border_width(2.0))
+            .focusable()
+            .on_hover(move |h| {
+                hovered.set(h);
+                if !h {
+                    pressed.set(false);
+                }
+            })
+            .on_mouse_down(move |_, _| pressed.set(true))
+            .on_mouse_up(move |_, _| pressed.set(false))
+            .on_click_option(on_click.clone())
+            .child(image(source).size(size).tint(move || {
+                let hover = hover_color.get();
+                if hovered.get() && hover.a > 0.0 {
+                    hover
+                } else {
+                    color.get()
+                }
+            }))
+    }
+}
+
+/// A circular button showing a tinted icon, created with [`icon_button`].
+pub struct IconButton {
+    inner: component::IconButton,
+}
+
+impl IconButton {
+    /// Replace the icon.
+    pub fn source<M>(mut self, source: impl IntoSignal<ImageSource, M>) -> Self {
+        self.inner = self.inner.source(source);
+        self
+    }
+
+    /// Tint of the icon, also used for the hover, press and ripple layers.
+    pub fn color<M>(mut self, color: impl IntoSignal<Color, M>) -> Self {
+        self.inner = self.inner.color(color);
+        self
+    }
+
+    /// Tint of the icon while hovered; transparent keeps [`color`](Self::color).
+    pub fn hover_color<M>(mut self, color: impl IntoSignal<Color, M>) -> Self {
+        self.inner = self.inner.hover_color(color);
+        self
+    }
+
+    /// Color of the ring shown while the button has keyboard focus.
+    pub fn focus_ring_color<M>(mut self, color: impl IntoSignal<Color, M>) -> Self {
+        self.inner = self.inner.focus_ring_color(color);
+        self
+    }
+
+    /// Width and height of the icon in logical pixels.
+    pub fn size<M>(mut self, size: impl IntoSignal<f32, M>) -> Self {
+        self.inner = self.inner.size(size);
+        self
+    }
+
+    /// Space between the icon and the edge of the circle.
+    pub fn padding<M>(mut self, padding: impl IntoSignal<Padding, M>) -> Self {
+        self.inner = self.inner.padding(padding);
+        self
+    }
+
+    /// Run `callback` when the button is clicked.
+    pub fn on_click<F: Fn() + 'static>(mut self, callback: F) -> Self {
+        self.inner = self.inner.on_click(callback);
+        self
+    }
+}
+
+impl Widget for IconButton {
+    delegate_widget!(inner);
+}
+
+/// Create a circular button showing the icon `source`.
+///
+/// The icon is tinted with [`IconButton::color`] (and [`IconButton::hover_color`]
+/// while hovered), so monochrome SVGs follow the surrounding theme.
+pub fn icon_button<M>(source: impl IntoSignal<ImageSource, M>) -> IconButton {
+    IconButton {
+        inner: component::IconButton::new().source(source),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    use super::*;
+    use crate::layout::{Constraints, Size};
+    use crate::renderer::{DrawCommand, PaintContext, RenderNode};
+    use crate::tree::{Tree, WidgetId};
+    use crate::widgets::widget::{Event, MouseButton};
+
+    fn icon_tint(tree: &Tree, id: WidgetId) -> Option<Color> {
+        let mut node = RenderNode::new(id.as_u64());
+        tree.with_widget(id, |widget| {
+            widget.paint(tree, id, &mut PaintContext::new(&mut node))
+        });
+        node.children
+            .iter()
+            .flat_map(|child| child.commands.iter())
+            .find_map(|cmd| match cmd.as_ref() {
+                DrawCommand::Image { tint, .. } => *tint,
+                _ => None,
+            })
+    }
+
+    #[test]
+    fn test_icon_button_has_a_min_target_and_hover_tint() {
+        let (color, hover) = (Color::rgb(0.8, 0.8, 0.85), Color::rgb(0.95, 0.4, 0.4));
+        let clicks = Rc::new(Cell::new(0));
+        let counter = clicks.clone();
+        let mut tree = Tree::new();
+        let id = tree.register(Box::new(
+            icon_button(ImageSource::SvgBytes(Arc::from([])))
+                .size(12.0)
+                .color(color)
+                .hover_color(hover)
+                .on_click(move || counter.set(counter.get() + 1)),
+        ));
+        tree.with_widget_mut(id, |widget, id, tree| widget.register_children(tree, id));
+        tree.with_widget_mut(id, |widget, id, tree| {
+            widget.layout(tree, id, Constraints::loose(Size::new(200.0, 200.0)))
+        });
+        assert_eq!(tree.cached_size(id), Some(Size::new(32.0, 32.0)));
+        assert_eq!(icon_tint(&tree, id), Some(color));
+
+        // Just outside the 32px circle, but within the 40px target
+        let (x, y, button) = (34.0, 16.0, MouseButton::Left);
+        for event in [
+            Event::MouseMove { x, y },
+            Event::MouseDown { x, y, button },
+            Event::MouseUp { x, y, button },
+        ] {
+            tree.with_widget_mut(id, |widget, id, tree| widget.event(tree, id, &event));
+        }
+        assert_eq!(clicks.get(), 1);
+        assert_eq!(icon_tint(&tree, id), Some(hover));
+    }
+}
@@ -6,13 +6,17 @@
 use std::path::PathBuf;
 use std::sync::Arc;
 
+use crate::accessibility::{AccessInfo, Role};
 use crate::jobs::JobType;
 use crate::layout::{Constraints, Size};
 use crate::reactive::{IntoSignal, Signal, with_signal_tracking};
 use crate::renderer::PaintContext;
 use crate::tree::{Tree, WidgetId};
 
-use super::widget::{EventResponse, Rect, Widget};
+use super::widget::{Color, EventResponse, Rect, Widget};
+
+/// Size of an [`icon`]: the line height of default-size text
+pub const ICON_SIZE: f32 = 14.0 * 1.2;
 
 /// Source for an image - can be a file path or in-memory bytes.
 #[derive(Debug, Clone, PartialEq)]
@@ -89,6 +93,9 @@ pub struct Image {
     width: Option<Signal<f32>>,
     height: Option<Signal<f32>>,
     content_fit: ContentFit,
+    tint: Option<Signal<Color>>,
+    /// Text read by screen readers in place of the image
+    alt: Option<Signal<String>>,
     /// Cached intrinsic size from the image source
     intrinsic_size: Option<(u32, u32)>,
     /// Cached source for change detection
@@ -103,6 +110,8 @@ impl Image {
             width: None,
             height: None,
             content_fit: ContentFit::default(),
+            tint: None,
+            alt: None,
             intrinsic_size: None,
             cached_source: None,
         }
@@ -120,12 +129,33 @@ impl Image {
         self
     }
 
+    /// Set the same fixed width and height.
+    pub fn size<M>(self, size: impl IntoSignal<f32, M>) -> Self {
+        let size = size.into_signal();
+        self.width(size).height(size)
+    }
+
+    /// Recolor the image with a single color, keeping its alpha.
+    ///
+    /// Meant for monochrome icons: every visible pixel takes the tint color,
+    /// so the same SVG can follow the theme or change color on hover.
+    pub fn tint<M>(mut self, color: impl IntoSignal<Color, M>) -> Self {
+        self.tint = Some(color.into_signal());
+        self
+    }
+
     /// Set the content fit mode.
     pub fn content_fit(mut self, fit: ContentFit) -> Self {
         self.content_fit = fit;
         self
     }
 
+    /// Describe the image for screen readers (e.g. `"Battery 80%"`).
+    pub fn alt<M>(mut self, text: impl IntoSignal<String, M>) -> Self {
+        self.alt = Some(text.into_signal());
+        self
+    }
+
     /// Get the current intrinsic size if known.
     pub fn intrinsic_size(&self) -> Option<(u32, u32)> {
         self.intrinsic_size
@@ -206,6 +236,14 @@ impl Image {
 }
 
 impl Widget for Image {
+    fn accessibility(&self) -> Option<AccessInfo> {
+        let info = AccessInfo::new(Role::Image);
+        Some(match self.alt {
+            Some(alt) => info.name(alt.get_untracked()),
+            None => info,
+        })
+    }
+
     fn layout(&mut self, tree: &mut Tree, id: WidgetId, constraints: Constraints) -> Size {
         // Images are never relayout boundaries
         tree.set_relayout_boundary(id, false);
@@ -251,7 +289,13 @@ impl Widget for Image {
         if let Some(ref source) = self.cached_source {
             let size = tree.cached_size(id).unwrap_or_default();
             let local_bounds = Rect::new(0.0, 0.0, size.width, size.height);
-            ctx.draw_image(source.clone(), local_bounds, self.content_fit);
+            let tint = with_signal_tracking(id, JobType::Paint, || self.tint.map(|t| t.get()));
+            match tint {
+                Some(tint) => {
+                    ctx.draw_tinted_image(source.clone(), local_bounds, self.content_fit, tint)
+                }
+                None => ctx.draw_image(source.clone(), local_bounds, self.content_fit),
+            }
         }
     }
 
@@ -291,3 +335,18 @@ impl Widget for Image {
 pub fn image<M>(source: impl IntoSignal<ImageSource, M>) -> Image {
     Image::new(source)
 }
+
+/// Create an icon: an image sized to sit on a line of default-size text.
+///
+/// Use [`Image::size`] to match another text size and [`Image::tint`] to
+/// color monochrome SVGs.
+///
+/// ```ignore
+/// container()
+///     .layout(Flex::row().spacing(6.0))
+///     .child(icon("./icons/wifi.svg").tint(Color::WHITE))
+///     .child(text("Connected"))
+/// ```
+pub fn icon<M>(source: impl IntoSignal<ImageSource, M>) -> Image {
+    Image::new(source).size(ICON_SIZE)
+}
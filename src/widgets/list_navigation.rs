@@ -0,0 +1,192 @@
+//! Keyboard navigation for lists and menus (roving focus).
+//!
+//! A [`ListNavigation`] turns a container into a single keyboard stop: clicking
+//! the container focuses it, and while focused the arrow keys move a
+//! highlighted index over its children and Enter activates the highlighted
+//! item. Scrollable containers keep the highlighted child scrolled into view.
+//!
+//! # Example
+//!
+//! ```ignore
+//! let nav = list_navigation().on_activate(move |index| run_action(index));
+//! let highlighted = nav.highlighted();
+//!
+//! container()
+//!     .scrollable(ScrollAxis::Vertical)
+//!     .list_navigation(nav)
+//!     .children(items.iter().enumerate().map(|(i, item)| {
+//!         container()
+//!             .background(move || {
+//!                 if highlighted.get() == Some(i) {
+//!                     Color::rgb(0.3, 0.3, 0.4)
+//!                 } else {
+//!                     Color::TRANSPARENT
+//!                 }
+//!             })
+//!             .child(text(item.clone()))
+//!     }))
+//! ```
+
+use std::rc::Rc;
+
+use crate::reactive::{RwSignal, create_signal};
+
+use super::widget::Key;
+
+/// Callback invoked with the index of the activated item.
+pub type ActivateCallback = Rc<dyn Fn(usize)>;
+
+/// Result of feeding a key press to a [`ListNavigation`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum NavOutcome {
+    /// The key is not a navigation key (or there is nothing to navigate).
+    Ignored,
+    /// The highlight moved to the given index.
+    Moved(usize),
+    /// The highlighted item was activated.
+    Activated,
+}
+
+/// Roving-focus keyboard navigation state for a list or menu container.
+///
+/// Create with [`list_navigation()`] and attach with `Container::list_navigation`.
+#[derive(Clone)]
+pub struct ListNavigation {
+    highlighted: RwSignal<Option<usize>>,
+    on_activate: Option<ActivateCallback>,
+    wrap: bool,
+}
+
+impl ListNavigation {
+    pub fn new() -> Self {
+        Self {
+            highlighted: create_signal(None),
+            on_activate: None,
+            wrap: false,
+        }
+    }
+
+    /// The highlighted item index, or `None` when nothing is highlighted.
+    ///
+    /// Read it to style the highlighted item; write it to move the highlight
+    /// programmatically (e.g. on hover).
+    pub fn highlighted(&self) -> RwSignal<Option<usize>> {
+        self.highlighted
+    }
+
+    /// Set the callback fired when Enter is pressed on the highlighted item.
+    pub fn on_activate<F: Fn(usize) + 'static>(mut self, callback: F) -> Self {
+        self.on_activate = Some(Rc::new(callback));
+        self
+    }
+
+    /// Wrap around from the last item to the first (and vice versa).
+    pub fn wrap(mut self, wrap: bool) -> Self {
+        self.wrap = wrap;
+        self
+    }
+
+    /// Handle a key press for a list with `count` items.
+    pub(crate) fn handle_key(&self, key: &Key, count: usize) -> NavOutcome {
+        if count == 0 {
+            return NavOutcome::Ignored;
+        }
+        let current = self.highlighted.get_untracked().filter(|&i| i < count);
+        let last = count - 1;
+        let next = match key {
+            Key::Down => match current {
+                None => 0,
+                Some(i) if i < last => i + 1,
+                Some(_) if self.wrap => 0,
+                Some(i) => i,
+            },
+            Key::Up => match current {
+                None => last,
+                Some(i) if i > 0 => i - 1,
+                Some(_) if self.wrap => last,
+                Some(i) => i,
+            },
+            Key::Home => 0,
+            Key::End => last,
+            Key::Enter => {
+                return match (current, &self.on_activate) {
+                    (Some(index), Some(callback)) => {
+                        callback(index);
+                        NavOutcome::Activated
+                    }
+                    _ => NavOutcome::Ignored,
+                };
+            }
+            _ => return NavOutcome::Ignored,
+        };
+        self.highlighted.set(Some(next));
+        NavOutcome::Moved(next)
+    }
+}
+
+impl Default for ListNavigation {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Create a new list navigation helper.
+pub fn list_navigation() -> ListNavigation {
+    ListNavigation::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use super::*;
+
+    #[test]
+    fn test_down_starts_at_first_and_stops_at_last() {
+        let nav = list_navigation();
+        assert_eq!(nav.handle_key(&Key::Down, 3), NavOutcome::Moved(0));
+        assert_eq!(nav.handle_key(&Key::Down, 3), NavOutcome::Moved(1));
+        assert_eq!(nav.handle_key(&Key::Down, 3), NavOutcome::Moved(2));
+        assert_eq!(nav.handle_key(&Key::Down, 3), NavOutcome::Moved(2));
+        assert_eq!(nav.highlighted().get_untracked(), Some(2));
+    }
+
+    #[test]
+    fn test_up_starts_at_last_and_wraps_when_enabled() {
+        let nav = list_navigation().wrap(true);
+        assert_eq!(nav.handle_key(&Key::Up, 3), NavOutcome::Moved(2));
+        nav.highlighted().set(Some(0));
+        assert_eq!(nav.handle_key(&Key::Up, 3), NavOutcome::Moved(2));
+        assert_eq!(nav.handle_key(&Key::Down, 3), NavOutcome::Moved(0));
+    }
+
+    #[test]
+    fn test_home_end_and_stale_index() {
+        let nav = list_navigation();
+        assert_eq!(nav.handle_key(&Key::End, 4), NavOutcome::Moved(3));
+        assert_eq!(nav.handle_key(&Key::Home, 4), NavOutcome::Moved(0));
+        // Index beyond the list (e.g. items removed) is treated as no highlight
+        nav.highlighted().set(Some(10));
+        assert_eq!(nav.handle_key(&Key::Down, 2), NavOutcome::Moved(0));
+    }
+
+    #[test]
+    fn test_enter_activates_highlighted_item() {
+        let activated = Rc::new(Cell::new(None));
+        let activated_clone = activated.clone();
+        let nav = list_navigation().on_activate(move |i| activated_clone.set(Some(i)));
+
+        assert_eq!(nav.handle_key(&Key::Enter, 3), NavOutcome::Ignored);
+        nav.handle_key(&Key::Down, 3);
+        nav.handle_key(&Key::Down, 3);
+        assert_eq!(nav.handle_key(&Key::Enter, 3), NavOutcome::Activated);
+        assert_eq!(activated.get(), Some(1));
+    }
+
+    #[test]
+    fn test_empty_list_ignores_keys() {
+        let nav = list_navigation();
+        assert_eq!(nav.handle_key(&Key::Down, 0), NavOutcome::Ignored);
+        assert_eq!(nav.highlighted().get_untracked(), None);
+    }
+}
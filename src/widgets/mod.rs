@@ -1,26 +1,49 @@
+pub mod button;
 pub mod children;
 pub mod container;
 pub mod font;
+pub mod icon_button;
 pub mod image;
 pub mod into_child;
+pub mod list_navigation;
+pub mod radio_group;
+pub mod resize_handle;
 pub mod scroll;
+pub mod skeleton;
+pub mod split_pane;
 pub mod state_layer;
+pub mod style;
 pub mod text;
 pub mod text_input;
+pub mod toast;
+pub mod virtual_list;
 pub mod widget;
 
+pub use button::{Button, ButtonVariant, button};
 pub use children::ChildrenSource;
-pub use container::{Border, Container, GradientDirection, LinearGradient, Overflow, container};
-pub use font::{FontFamily, FontWeight};
-pub use image::{ContentFit, Image, ImageSource, image};
+pub use container::{
+    Border, Container, GradientDirection, LinearGradient, Overflow, column, constrained, container,
+    repaint_boundary, row, stack,
+};
+pub use font::{FontFamily, FontStyle, FontWeight, TextDirection, WrapMode};
+pub use icon_button::{IconButton, icon_button};
+pub use image::{ContentFit, Image, ImageSource, icon, image};
 pub use into_child::{DynamicChildren, IntoChild, IntoChildren, StaticChildren};
-pub use scroll::{ScrollAxis, ScrollbarBuilder, ScrollbarConfig, ScrollbarVisibility};
+pub use list_navigation::{ListNavigation, list_navigation};
+pub use radio_group::radio_group;
+pub use resize_handle::{ResizeHandle, resize_handle};
+pub use scroll::{ScrollAxis, ScrollbarBuilder, ScrollbarConfig, ScrollbarVisibility, SnapAlign};
+pub use skeleton::{Skeleton, skeleton};
+pub use split_pane::{Pane, SplitPane, split_pane};
 pub use state_layer::{BackgroundOverride, RippleConfig, StateStyle};
+pub use style::WidgetStyle;
 pub use text::{Text, text};
 pub use text_input::{Selection, TextInput, text_input};
+pub use toast::{ToastCorner, ToastId, ToastStack, dismiss_toast, show_toast, toast_stack};
+pub use virtual_list::{VirtualList, virtual_list};
 pub use widget::{
-    AnyWidget, Color, Event, EventResponse, Key, LayoutHints, Modifiers, MouseButton, Padding,
-    Rect, ScrollSource, Widget,
+    AnyWidget, Color, Event, EventResponse, GesturePhase, Key, KeyEvent, LayoutHints, Modifiers,
+    MouseButton, Padding, Rect, ScrollSource, Widget,
 };
 
 // IntoVal<Padding> impls for closures returning numeric types
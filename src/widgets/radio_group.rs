@@ -0,0 +1,211 @@
+//! Mutually exclusive options bound to a signal.
+//!
+//! [`radio_group`] renders one radio button per option. Clicking an option
+//! selects it and writes its value to the bound signal; clicking the selected
+//! option again keeps it selected. The group is a single keyboard stop: once
+//! focused, the arrow keys move the selection to the previous or next option
+//! (wrapping around), and Home/End select the first or last one.
+//!
+//! # Example
+//!
+//! ```ignore
+//! #[derive(Clone, PartialEq)]
+//! enum Theme { Light, Dark, System }
+//!
+//! let theme = create_signal(Theme::System);
+//!
+//! radio_group(theme, [
+//!     (Theme::Light, "Light"),
+//!     (Theme::Dark, "Dark"),
+//!     (Theme::System, "Follow system"),
+//! ])
+//! ```
+
+use crate::accessibility::{AccessInfo, Role};
+use crate::layout::{CrossAlignment, Flex, MainAlignment};
+use crate::reactive::RwSignal;
+
+use super::button::DEFAULT_BUTTON_COLOR;
+use super::container::{Container, container};
+use super::state_layer::StateStyle;
+use super::text::text;
+use super::widget::{Color, EventResponse, Key, Padding};
+
+/// Diameter of the radio indicator ring
+const INDICATOR_SIZE: f32 = 18.0;
+
+/// Diameter of the dot shown in the selected indicator
+const DOT_SIZE: f32 = 10.0;
+
+/// Color of the group's focus ring
+const FOCUS_RING_COLOR: Color = Color::rgba(1.0, 1.0, 1.0, 0.7);
+
+/// Index selected by pressing `key` when option `current` of `count` is selected.
+///
+/// Returns `None` for keys that don't move the selection.
+fn step(key: &Key, current: Option<usize>, count: usize) -> Option<usize> {
+    if count == 0 {
+        return None;
+    }
+    let last = count - 1;
+    match key {
+        Key::Down | Key::Right => Some(match current {
+            Some(i) if i < last => i + 1,
+            _ => 0,
+        }),
+        Key::Up | Key::Left => Some(match current {
+            Some(i) if i > 0 && i <= last => i - 1,
+            _ => last,
+        }),
+        Key::Home => Some(0),
+        Key::End => Some(last),
+        _ => None,
+    }
+}
+
+/// Create a group of radio buttons, one per `(value, label)` option.
+///
+/// The option whose value equals `selected` is shown as selected, and picking
+/// another option (with the pointer or the arrow keys) sets `selected` to its
+/// value. Options are stacked in a column; call
+/// [`layout`](Container::layout) with [`Flex::row`] for a horizontal group.
+pub fn radio_group<T, L>(
+    selected: RwSignal<T>,
+    options: impl IntoIterator<Item = (T, L)>,
+) -> Container
+where
+    T: Clone + PartialEq + Send + 'static,
+    L: Into<String>,
+{
+    let options: Vec<(T, String)> = options
+        .into_iter()
+        .map(|(value, label)| (value, label.into()))
+        .collect();
+    let values: Vec<T> = options.iter().map(|(value, _)| value.clone()).collect();
+    let accent = DEFAULT_BUTTON_COLOR;
+
+    container()
+        .layout(Flex::column().spacing(4.0))
+        .corner_radius(8.0)
+        .focusable()
+        .access_info(AccessInfo::new(Role::RadioGroup))
+        .focused_state(|s: StateStyle| s.border(2.0, FOCUS_RING_COLOR))
+        .on_key(move |event| {
+            let current = selected.with_untracked(|s| values.iter().position(|v| v == s));
+            match step(&event.key, current, values.len()) {
+                Some(index) => {
+                    selected.set(values[index].clone());
+                    EventResponse::Handled
+                }
+                None => EventResponse::Ignored,
+            }
+        })
+        .children(options.into_iter().map(move |(value, label)| {
+            let is_selected = {
+                let value = value.clone();
+                move || selected.with(|s| *s == value)
+            };
+            let dot_color = {
+                let is_selected = is_selected.clone();
+                move || {
+                    if is_selected() {
+                        accent
+                    } else {
+                        Color::TRANSPARENT
+                    }
+                }
+            };
+            container()
+                .layout(
+                    Flex::row()
+                        .spacing(8.0)
+                        .cross_alignment(CrossAlignment::Center),
+                )
+                .padding(Padding::symmetric(8.0, 6.0))
+                .corner_radius(6.0)
+                .access_info(move || AccessInfo::new(Role::RadioButton).checked(is_selected()))
+                .hover_state(move |s: StateStyle| s.background(accent.with_alpha(0.08)))
+                .pressed_state(move |s: StateStyle| s.background(accent.with_alpha(0.12)))
+                .on_click(move || {
+                    // Selecting the selected option again is a no-op
+                    if selected.with_untracked(|s| *s != value) {
+                        selected.set(value.clone());
+                    }
+                })
+                .child(
+                    container()
+                        .layout(
+                            Flex::column()
+                                .main_alignment(MainAlignment::Center)
+                                .cross_alignment(CrossAlignment::Center),
+                        )
+                        .width(INDICATOR_SIZE)
+                        .height(INDICATOR_SIZE)
+                        .corner_radius(INDICATOR_SIZE / 2.0)
+                        .border(2.0, accent)
+                        .child(
+                            container()
+                                .width(DOT_SIZE)
+                                .height(DOT_SIZE)
+                                .corner_radius(DOT_SIZE / 2.0)
+                                .background(dot_color),
+                        ),
+                )
+                .child(text(label))
+        }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arrows_move_and_wrap() {
+        assert_eq!(step(&Key::Down, Some(0), 3), Some(1));
+        assert_eq!(step(&Key::Right, Some(2), 3), Some(0));
+        assert_eq!(step(&Key::Up, Some(0), 3), Some(2));
+        assert_eq!(step(&Key::Left, Some(2), 3), Some(1));
+    }
+
+    #[test]
+    fn test_no_selection_and_other_keys() {
+        // Without a selected option, forward keys pick the first, backward the last
+        assert_eq!(step(&Key::Down, None, 3), Some(0));
+        assert_eq!(step(&Key::Up, None, 3), Some(2));
+        assert_eq!(step(&Key::End, Some(0), 3), Some(2));
+        assert_eq!(step(&Key::Home, Some(2), 3), Some(0));
+        assert_eq!(step(&Key::Enter, Some(1), 3), None);
+        assert_eq!(step(&Key::Down, None, 0), None);
+    }
+
+    #[test]
+    fn test_accessibility_reports_checked_option() {
+        use crate::accessibility::access_tree;
+        use crate::layout::{Constraints, Size};
+        use crate::reactive::create_signal;
+        use crate::tree::Tree;
+
+        let selected = create_signal(2);
+        let mut tree = Tree::new();
+        let id = tree.register(Box::new(radio_group(selected, [(1, "One"), (2, "Two")])));
+        tree.with_widget_mut(id, |widget, id, tree| widget.register_children(tree, id));
+        tree.with_widget_mut(id, |widget, id, tree| {
+            widget.layout(tree, id, Constraints::loose(Size::new(200.0, 200.0)))
+        });
+
+        let group = access_tree(&tree, id);
+        assert_eq!(group.role, Role::RadioGroup);
+        let options: Vec<_> = group
+            .children
+            .iter()
+            .map(|option| (option.role, option.name.as_deref(), option.checked))
+            .collect();
+        assert_eq!(
+            options,
+            [
+                (Role::RadioButton, Some("One"), Some(false)),
+                (Role::RadioButton, Some("Two"), Some(true)),
+            ]
+        );
+    }
+}
@@ -0,0 +1,302 @@
+//! Drag handle for resizable panels.
+//!
+//! A [`ResizeHandle`] is a thin bar placed on the edge of a panel. Dragging it
+//! writes the panel's new size into a signal, clamped to a min/max range, and
+//! hovering it shows the matching resize cursor. The drag keeps going while
+//! the button is held, even when the pointer leaves the handle.
+//!
+//! # Example
+//!
+//! ```ignore
+//! let sidebar_width = create_signal(240.0f32);
+//!
+//! container()
+//!     .layout(Flex::row())
+//!     .child(container().width(move || sidebar_width.get()).child(sidebar()))
+//!     .child(resize_handle(Axis::Horizontal, sidebar_width).min(120.0).max(480.0))
+//!     .child(container().width(fill()).child(content()))
+//! ```
+
+use crate::jobs::{JobRequest, JobType, request_job};
+use crate::layout::{Axis, Constraints, Size};
+use crate::reactive::{
+    CursorIcon, IntoSignal, OptionSignalExt, RwSignal, Signal, set_cursor, with_signal_tracking,
+};
+use crate::renderer::PaintContext;
+use crate::tree::{Tree, WidgetId};
+
+use super::widget::{Color, Event, EventResponse, MouseButton, Rect, Widget};
+
+/// Default thickness of the handle in logical pixels
+const DEFAULT_THICKNESS: f32 = 6.0;
+
+/// An in-progress drag: pointer position and size when it started.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Drag {
+    start_pointer: f32,
+    start_size: f32,
+}
+
+impl Drag {
+    pub(crate) fn new(start_pointer: f32, start_size: f32) -> Self {
+        Self {
+            start_pointer,
+            start_size,
+        }
+    }
+
+    /// Size after moving the pointer to `pointer`, before clamping.
+    pub(crate) fn size_at(&self, pointer: f32, reverse: bool) -> f32 {
+        let delta = pointer - self.start_pointer;
+        if reverse {
+            self.start_size - delta
+        } else {
+            self.start_size + delta
+        }
+    }
+}
+
+/// A bar that resizes a panel when dragged.
+pub struct ResizeHandle {
+    axis: Axis,
+    size: RwSignal<f32>,
+    min: f32,
+    max: f32,
+    reverse: bool,
+    thickness: f32,
+    color: Option<Signal<Color>>,
+    hover_color: Option<Signal<Color>>,
+    hovered: bool,
+    drag: Option<Drag>,
+}
+
+impl ResizeHandle {
+    /// Create a handle that resizes along `axis`, writing the size to `size`.
+    pub fn new(axis: Axis, size: RwSignal<f32>) -> Self {
+        Self {
+            axis,
+            size,
+            min: 0.0,
+            max: f32::INFINITY,
+            reverse: false,
+            thickness: DEFAULT_THICKNESS,
+            color: None,
+            hover_color: None,
+            hovered: false,
+            drag: None,
+        }
+    }
+
+    /// Smallest size the drag can produce.
+    pub fn min(mut self, min: f32) -> Self {
+        self.min = min;
+        self
+    }
+
+    /// Largest size the drag can produce.
+    pub fn max(mut self, max: f32) -> Self {
+        self.max = max;
+        self
+    }
+
+    /// Grow the size when dragging toward the start (left/up) instead of the end.
+    ///
+    /// Use this when the handle sits before the panel it resizes, e.g. on the
+    /// left edge of a right-hand sidebar.
+    pub fn reverse(mut self) -> Self {
+        self.reverse = true;
+        self
+    }
+
+    /// Thickness of the bar across the drag axis.
+    pub fn thickness(mut self, thickness: f32) -> Self {
+        self.thickness = thickness;
+        self
+    }
+
+    /// Bar color (transparent by default).
+    pub fn color<M>(mut self, color: impl IntoSignal<Color, M>) -> Self {
+        self.color = Some(color.into_signal());
+        self
+    }
+
+    /// Bar color while hovered or dragged.
+    pub fn hover_color<M>(mut self, color: impl IntoSignal<Color, M>) -> Self {
+        self.hover_color = Some(color.into_signal());
+        self
+    }
+}
+
+/// Cursor shown while hovering or dragging a handle on `axis`.
+pub(crate) fn resize_cursor(axis: Axis) -> CursorIcon {
+    match axis {
+        Axis::Horizontal => CursorIcon::ColResize,
+        Axis::Vertical => CursorIcon::RowResize,
+    }
+}
+
+/// Pointer position along `axis`.
+pub(crate) fn along(axis: Axis, x: f32, y: f32) -> f32 {
+    match axis {
+        Axis::Horizontal => x,
+        Axis::Vertical => y,
+    }
+}
+
+impl ResizeHandle {
+    fn set_hovered(&mut self, id: WidgetId, hovered: bool) {
+        if self.hovered == hovered {
+            return;
+        }
+        self.hovered = hovered;
+        if self.drag.is_none() {
+            set_cursor(if hovered {
+                resize_cursor(self.axis)
+            } else {
+                CursorIcon::Default
+            });
+        }
+        request_job(id, JobRequest::Paint);
+    }
+}
+
+impl Widget for ResizeHandle {
+    fn layout(&mut self, tree: &mut Tree, id: WidgetId, constraints: Constraints) -> Size {
+        tree.set_relayout_boundary(id, false);
+
+        // Thin along the drag axis, stretched across it
+        let cross = |max: f32, min: f32| if max.is_finite() { max } else { min };
+        let size = match self.axis {
+            Axis::Horizontal => Size::new(
+                self.thickness
+                    .max(constraints.min_width)
+                    .min(constraints.max_width),
+                cross(constraints.max_height, constraints.min_height),
+            ),
+            Axis::Vertical => Size::new(
+                cross(constraints.max_width, constraints.min_width),
+                self.thickness
+                    .max(constraints.min_height)
+                    .min(constraints.max_height),
+            ),
+        };
+
+        tree.cache_layout(id, constraints, size);
+        tree.clear_needs_layout(id);
+        size
+    }
+
+    fn paint(&self, tree: &Tree, id: WidgetId, ctx: &mut PaintContext) {
+        let active = self.hovered || self.drag.is_some();
+        let color = with_signal_tracking(id, JobType::Paint, || {
+            if active && let Some(hover) = self.hover_color {
+                hover.get()
+            } else {
+                self.color.get_or(Color::TRANSPARENT)
+            }
+        });
+        if color.a <= 0.0 {
+            return;
+        }
+        let size = tree.cached_size(id).unwrap_or_default();
+        ctx.draw_rounded_rect(Rect::new(0.0, 0.0, size.width, size.height), color, 0.0);
+    }
+
+    fn event(&mut self, tree: &mut Tree, id: WidgetId, event: &Event) -> EventResponse {
+        let bounds = tree.get_bounds(id).unwrap_or_default();
+        match *event {
+            Event::MouseEnter { x, y } | Event::MouseMove { x, y } => {
+                self.set_hovered(id, bounds.contains(x, y));
+                if let Some(drag) = self.drag {
+                    let size = drag
+                        .size_at(along(self.axis, x, y), self.reverse)
+                        .clamp(self.min, self.max.max(self.min));
+                    if size != self.size.get_untracked() {
+                        self.size.set(size);
+                    }
+                    return EventResponse::Handled;
+                }
+            }
+            Event::MouseDown {
+                x,
+                y,
+                button: MouseButton::Left,
+            } if bounds.contains(x, y) => {
+                self.drag = Some(Drag::new(along(self.axis, x, y), self.size.get_untracked()));
+                request_job(id, JobRequest::Paint);
+                return EventResponse::Handled;
+            }
+            Event::MouseUp {
+                button: MouseButton::Left,
+                ..
+            } if self.drag.is_some() => {
+                self.drag = None;
+                if !self.hovered {
+                    set_cursor(CursorIcon::Default);
+                }
+                request_job(id, JobRequest::Paint);
+                return EventResponse::Handled;
+            }
+            Event::MouseLeave => {
+                self.drag = None;
+                self.set_hovered(id, false);
+            }
+            _ => {}
+        }
+        EventResponse::Ignored
+    }
+}
+
+/// Create a handle that resizes a panel along `axis`, writing its size to `size`.
+///
+/// Place it in a row (`Axis::Horizontal`) or column (`Axis::Vertical`) right
+/// after the panel whose size it controls.
+pub fn resize_handle(axis: Axis, size: RwSignal<f32>) -> ResizeHandle {
+    ResizeHandle::new(axis, size)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reactive::create_signal;
+
+    fn send(tree: &mut Tree, id: WidgetId, event: Event) -> EventResponse {
+        tree.with_widget_mut(id, |widget, id, tree| widget.event(tree, id, &event))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_drag_updates_size_within_limits() {
+        let width = create_signal(200.0f32);
+        let mut tree = Tree::new();
+        let id = tree.register(Box::new(
+            resize_handle(Axis::Horizontal, width).min(100.0).max(250.0),
+        ));
+        tree.with_widget_mut(id, |widget, id, tree| {
+            widget.layout(tree, id, Constraints::loose(Size::new(400.0, 50.0)))
+        });
+
+        let down = Event::MouseDown {
+            x: 3.0,
+            y: 10.0,
+            button: MouseButton::Left,
+        };
+        assert_eq!(send(&mut tree, id, down), EventResponse::Handled);
+
+        send(&mut tree, id, Event::MouseMove { x: 33.0, y: 10.0 });
+        assert_eq!(width.get_untracked(), 230.0);
+        send(&mut tree, id, Event::MouseMove { x: 300.0, y: 10.0 });
+        assert_eq!(width.get_untracked(), 250.0);
+        send(&mut tree, id, Event::MouseMove { x: -300.0, y: 10.0 });
+        assert_eq!(width.get_untracked(), 100.0);
+
+        let up = Event::MouseUp {
+            x: -300.0,
+            y: 10.0,
+            button: MouseButton::Left,
+        };
+        send(&mut tree, id, up);
+        send(&mut tree, id, Event::MouseMove { x: 50.0, y: 10.0 });
+        assert_eq!(width.get_untracked(), 100.0);
+    }
+}
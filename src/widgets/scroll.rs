@@ -1,5 +1,10 @@
 //! Scroll configuration types for scrollable containers.
 
+use std::time::{Duration, Instant};
+
+use crate::animation::clock::{self, MAX_ANIMATION_STEP};
+use crate::animation::skip_animations;
+
 use super::widget::{Color, Rect};
 
 /// Axis for scrollbar calculations (vertical or horizontal)
@@ -43,6 +48,52 @@ pub enum ScrollbarVisibility {
     Always,
     /// Never show scrollbar (content still scrollable)
     Hidden,
+    /// Show the scrollbar while scrolling or hovering it, then fade it out
+    /// once it has been idle for `timeout_ms` (1500 is a good default)
+    AutoHide { timeout_ms: u64 },
+}
+
+impl ScrollbarVisibility {
+    /// Whether the scrollbar should be shown after `idle` without scrolling or hovering.
+    pub(crate) fn shown_after_idle(&self, idle: Duration) -> bool {
+        match self {
+            ScrollbarVisibility::Always => true,
+            ScrollbarVisibility::Hidden => false,
+            ScrollbarVisibility::AutoHide { timeout_ms } => {
+                idle < Duration::from_millis(*timeout_ms)
+            }
+        }
+    }
+}
+
+/// Where a child aligns within the viewport when a snapping scroll settles
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SnapAlign {
+    /// Align the child's leading edge with the viewport's leading edge
+    #[default]
+    Start,
+    /// Center the child in the viewport
+    Center,
+    /// Align the child's trailing edge with the viewport's trailing edge
+    End,
+}
+
+/// Which sticky header is pinned at scroll `offset`, and how far it is
+/// shifted down from its natural position.
+///
+/// `headers` are `(y, height)` in content order. The pinned header is the
+/// last one scrolled past the viewport top; the next header pushes it out.
+pub(crate) fn pinned_header(headers: &[(f32, f32)], offset: f32) -> Option<(usize, f32)> {
+    let index = headers
+        .partition_point(|&(y, _)| y <= offset)
+        .checked_sub(1)?;
+    let (y, height) = headers[index];
+    let pinned = match headers.get(index + 1) {
+        Some(&(next_y, _)) => offset.min(next_y - height),
+        None => offset,
+    };
+    let shift = pinned - y;
+    (shift > 0.0).then_some((index, shift))
 }
 
 /// Configuration for scrollbar appearance
@@ -254,7 +305,14 @@ pub(crate) struct ScrollState {
     pub velocity_x: f32,
     pub velocity_y: f32,
     /// Timestamp of last scroll event (for detecting when scrolling stops)
-    pub last_scroll_time: Option<std::time::Instant>,
+    pub last_scroll_time: Option<Instant>,
+    /// A snapping container is waiting for scrolling to settle before snapping
+    pub snap_pending: bool,
+    /// Offsets the snap animation is moving towards
+    pub snap_target_x: Option<f32>,
+    pub snap_target_y: Option<f32>,
+    /// Animation time of the previous snap step
+    pub snap_last_step: Option<Instant>,
 }
 
 impl ScrollState {
@@ -268,6 +326,28 @@ impl ScrollState {
         (self.content_height - self.viewport_height).max(0.0)
     }
 
+    /// Strength (0.0 to 1.0) of the edge fades for a fade of `size` pixels,
+    /// as `[top, bottom, left, right]`.
+    ///
+    /// An edge fades in as content scrolls past it, reaching full strength
+    /// once `size` pixels are hidden beyond that edge.
+    pub fn edge_fades(&self, axis: ScrollAxis, size: f32) -> [f32; 4] {
+        if size <= 0.0 {
+            return [0.0; 4];
+        }
+        let strength = |hidden: f32| (hidden / size).clamp(0.0, 1.0);
+        let (mut top, mut bottom, mut left, mut right) = (0.0, 0.0, 0.0, 0.0);
+        if axis.allows_vertical() {
+            top = strength(self.offset_y);
+            bottom = strength(self.max_scroll_y() - self.offset_y);
+        }
+        if axis.allows_horizontal() {
+            left = strength(self.offset_x);
+            right = strength(self.max_scroll_x() - self.offset_x);
+        }
+        [top, bottom, left, right]
+    }
+
     /// Check if content overflows vertically
     pub fn needs_vertical_scrollbar(&self) -> bool {
         self.content_height > self.viewport_height
@@ -295,7 +375,7 @@ impl ScrollState {
 
         let scroll_stopped = self
             .last_scroll_time
-            .map(|t| t.elapsed().as_millis() > SCROLL_TIMEOUT_MS)
+            .map(|t| clock::elapsed_since(t).as_millis() > SCROLL_TIMEOUT_MS)
             .unwrap_or(true);
 
         has_velocity && scroll_stopped
@@ -349,6 +429,98 @@ impl ScrollState {
         animating
     }
 
+    /// Check if a pending snap can start (scrolling and momentum have stopped)
+    pub fn should_snap(&self) -> bool {
+        const VELOCITY_THRESHOLD: f32 = 0.5;
+        const SNAP_DELAY_MS: u128 = 120; // Wheel notches arrive in bursts
+
+        let settled = self.velocity_x.abs() <= VELOCITY_THRESHOLD
+            && self.velocity_y.abs() <= VELOCITY_THRESHOLD;
+        let scroll_stopped = self
+            .last_scroll_time
+            .map(|t| clock::elapsed_since(t).as_millis() > SNAP_DELAY_MS)
+            .unwrap_or(true);
+
+        self.snap_pending && settled && scroll_stopped
+    }
+
+    /// Find the snap offset nearest to the current offset for the given axis.
+    ///
+    /// `items` are `(start, size)` extents of the children along the axis in
+    /// content coordinates. Returns `None` if there are no items.
+    pub fn nearest_snap_offset(
+        &self,
+        axis: ScrollbarAxis,
+        align: SnapAlign,
+        items: &[(f32, f32)],
+    ) -> Option<f32> {
+        let (current, viewport) = match axis {
+            ScrollbarAxis::Vertical => (self.offset_y, self.viewport_height),
+            ScrollbarAxis::Horizontal => (self.offset_x, self.viewport_width),
+        };
+        let max = self.max_scroll(axis);
+        items
+            .iter()
+            .map(|&(start, size)| {
+                let offset = match align {
+                    SnapAlign::Start => start,
+                    SnapAlign::Center => start + size / 2.0 - viewport / 2.0,
+                    SnapAlign::End => start + size - viewport,
+                };
+                offset.clamp(0.0, max)
+            })
+            .min_by(|a, b| (a - current).abs().total_cmp(&(b - current).abs()))
+    }
+
+    /// Advance the snap animation, returns true if still animating.
+    ///
+    /// Each step closes a fixed share of the remaining distance per 60Hz
+    /// frame of animation time, so the snap takes as long at any frame rate.
+    /// With reduced motion the offsets jump to their targets.
+    pub fn advance_snap(&mut self) -> bool {
+        const EASE_PER_FRAME: f32 = 0.25;
+        const FRAME: Duration = Duration::from_micros(16_667);
+        const DISTANCE_THRESHOLD: f32 = 0.5;
+
+        let now = clock::now();
+        let dt = self
+            .snap_last_step
+            .map_or(Duration::ZERO, |last| now.saturating_duration_since(last))
+            .min(MAX_ANIMATION_STEP);
+        self.snap_last_step = Some(now);
+        let ease = if skip_animations() {
+            1.0
+        } else {
+            1.0 - (1.0 - EASE_PER_FRAME).powf(dt.as_secs_f32() / FRAME.as_secs_f32())
+        };
+
+        let step = |offset: &mut f32, target: &mut Option<f32>| {
+            if let Some(t) = *target {
+                *offset += (t - *offset) * ease;
+                if (t - *offset).abs() <= DISTANCE_THRESHOLD {
+                    *offset = t;
+                    *target = None;
+                }
+            }
+        };
+
+        step(&mut self.offset_x, &mut self.snap_target_x);
+        step(&mut self.offset_y, &mut self.snap_target_y);
+        let animating = self.snap_target_x.is_some() || self.snap_target_y.is_some();
+        if !animating {
+            self.snap_last_step = None;
+        }
+        animating
+    }
+
+    /// Cancel any pending or running snap (e.g. when the user scrolls again)
+    pub fn cancel_snap(&mut self) {
+        self.snap_pending = false;
+        self.snap_target_x = None;
+        self.snap_target_y = None;
+        self.snap_last_step = None;
+    }
+
     /// Get scrollbar track rectangle for the given axis
     pub fn scrollbar_track_rect(
         &self,
@@ -598,6 +770,43 @@ mod tests {
         assert!(ScrollAxis::Both.allows_horizontal());
     }
 
+    #[test]
+    fn test_scrollbar_visibility_after_idle() {
+        let auto_hide = ScrollbarVisibility::AutoHide { timeout_ms: 1500 };
+        assert!(auto_hide.shown_after_idle(Duration::from_millis(1499)));
+        assert!(!auto_hide.shown_after_idle(Duration::from_millis(1500)));
+        assert!(ScrollbarVisibility::Always.shown_after_idle(Duration::from_secs(60)));
+        assert!(!ScrollbarVisibility::Hidden.shown_after_idle(Duration::ZERO));
+    }
+
+    #[test]
+    fn test_edge_fades_follow_scroll_position() {
+        let mut state = ScrollState {
+            content_height: 1000.0,
+            viewport_height: 400.0,
+            ..Default::default()
+        };
+        let axis = ScrollAxis::Vertical;
+        assert_eq!(state.edge_fades(axis, 24.0), [0.0, 1.0, 0.0, 0.0]);
+
+        state.offset_y = 12.0;
+        assert_eq!(state.edge_fades(axis, 24.0), [0.5, 1.0, 0.0, 0.0]);
+
+        state.offset_y = 600.0;
+        assert_eq!(state.edge_fades(axis, 24.0), [1.0, 0.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_pinned_header_is_pushed_out_by_next() {
+        let headers = [(0.0, 20.0), (100.0, 20.0), (250.0, 20.0)];
+        assert_eq!(pinned_header(&headers, 0.0), None);
+        assert_eq!(pinned_header(&headers, 50.0), Some((0, 50.0)));
+        // The second header pushes the first out over its last 20px
+        assert_eq!(pinned_header(&headers, 90.0), Some((0, 80.0)));
+        assert_eq!(pinned_header(&headers, 100.0), None);
+        assert_eq!(pinned_header(&headers, 300.0), Some((2, 50.0)));
+    }
+
     #[test]
     fn test_scroll_state_max_scroll() {
         let state = ScrollState {
@@ -783,4 +992,111 @@ mod tests {
         let offset = state.scrollbar_handle_offset(ScrollbarAxis::Vertical, 400.0, 200.0);
         assert_eq!(offset, 100.0);
     }
+
+    #[test]
+    fn test_nearest_snap_offset() {
+        // Three 120px items in a 200px viewport
+        let items = [(0.0, 120.0), (120.0, 120.0), (240.0, 120.0)];
+        let state = ScrollState {
+            viewport_width: 200.0,
+            content_width: 360.0,
+            offset_x: 110.0,
+            ..Default::default()
+        };
+
+        let start = state.nearest_snap_offset(ScrollbarAxis::Horizontal, SnapAlign::Start, &items);
+        assert_eq!(start, Some(120.0));
+
+        // Center of the middle item is at 180, so offset 80 centers it
+        let center =
+            state.nearest_snap_offset(ScrollbarAxis::Horizontal, SnapAlign::Center, &items);
+        assert_eq!(center, Some(80.0));
+
+        // Offsets past the end are clamped to the max scroll
+        let end = state.nearest_snap_offset(ScrollbarAxis::Horizontal, SnapAlign::End, &items);
+        assert_eq!(end, Some(160.0));
+
+        assert_eq!(
+            state.nearest_snap_offset(ScrollbarAxis::Horizontal, SnapAlign::Start, &[]),
+            None
+        );
+    }
+
+    #[test]
+    fn test_advance_snap_reaches_target() {
+        let mut state = ScrollState {
+            offset_x: 100.0,
+            snap_target_x: Some(120.0),
+            ..Default::default()
+        };
+
+        clock::advance_clock(Duration::ZERO);
+        let mut frames = 0;
+        while state.advance_snap() {
+            clock::advance_clock(Duration::from_millis(16));
+            frames += 1;
+            assert!(frames < 100, "snap never settled");
+        }
+        assert_eq!(state.offset_x, 120.0);
+        assert_eq!(state.snap_target_x, None);
+        clock::resume_clock();
+    }
+
+    #[test]
+    fn test_advance_snap_follows_animation_time() {
+        let snapping = || ScrollState {
+            offset_y: 0.0,
+            snap_target_y: Some(100.0),
+            ..Default::default()
+        };
+        clock::advance_clock(Duration::ZERO);
+
+        // The first step only starts the clock
+        let mut state = snapping();
+        assert!(state.advance_snap());
+        assert_eq!(state.offset_y, 0.0);
+
+        // Two 60Hz frames cover the same distance as one 30Hz frame
+        let mut fast = snapping();
+        fast.advance_snap();
+        clock::advance_clock(Duration::from_micros(16_667));
+        fast.advance_snap();
+        clock::advance_clock(Duration::from_micros(16_667));
+        fast.advance_snap();
+        state.advance_snap();
+        assert!((state.offset_y - fast.offset_y).abs() < 0.01);
+        assert!((state.offset_y - 43.75).abs() < 0.01, "{}", state.offset_y);
+
+        // A stall only moves the snap by one capped step
+        let mut stalled = snapping();
+        stalled.advance_snap();
+        clock::advance_clock(Duration::from_secs(2));
+        assert!(stalled.advance_snap());
+        assert!(stalled.offset_y < 100.0);
+
+        // With reduced motion the snap lands at once
+        crate::animation::set_reduce_motion(true);
+        let mut reduced = snapping();
+        assert!(!reduced.advance_snap());
+        assert_eq!(reduced.offset_y, 100.0);
+        crate::animation::set_reduce_motion(false);
+        clock::resume_clock();
+    }
+
+    #[test]
+    fn test_snap_waits_for_scrolling_to_settle() {
+        clock::advance_clock(Duration::ZERO);
+        let mut state = ScrollState {
+            snap_pending: true,
+            last_scroll_time: Some(clock::now()),
+            ..Default::default()
+        };
+        assert!(!state.should_snap());
+        clock::advance_clock(Duration::from_millis(200));
+        assert!(state.should_snap());
+
+        state.velocity_y = 4.0;
+        assert!(!state.should_snap());
+        clock::resume_clock();
+    }
 }
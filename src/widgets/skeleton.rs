@@ -0,0 +1,198 @@
+//! Shimmering placeholder shown while content loads.
+//!
+//! A [`Skeleton`] is a rounded rect with a highlight band sweeping across it.
+//! The band moves once per rendered frame while the skeleton is shown, and the
+//! frame loop goes back to sleep once it is removed. With reduced motion the
+//! skeleton stays still.
+//!
+//! # Example
+//!
+//! ```ignore
+//! let items = create_signal(None::<Vec<String>>);
+//!
+//! container().layout(Flex::column().spacing(8.0)).children(move || {
+//!     let loaded = items.get();
+//!     (0..3u64).map(move |i| {
+//!         let label = loaded.as_ref().map(|items| items[i as usize].clone());
+//!         // A new key once loaded replaces the placeholder
+//!         (i * 2 + label.is_some() as u64, move || match label.clone() {
+//!             Some(label) => container().child(text(label)),
+//!             None => container().child(skeleton(200.0, 16.0)),
+//!         })
+//!     })
+//! })
+//! ```
+
+use crate::animation::reduce_motion;
+use crate::jobs::JobType;
+use crate::layout::{Constraints, Size};
+use crate::reactive::{
+    IntoSignal, OptionSignalExt, Signal, create_animation_frame, with_signal_tracking,
+};
+use crate::renderer::{Gradient, GradientDir, PaintContext};
+use crate::tree::{Tree, WidgetId};
+
+use super::widget::{Color, Event, EventResponse, Rect, Widget};
+
+/// Default placeholder color, visible on light and dark backgrounds
+const DEFAULT_COLOR: Color = Color::rgba(0.5, 0.5, 0.55, 0.25);
+
+/// Default color at the center of the shimmer band
+const DEFAULT_HIGHLIGHT: Color = Color::rgba(1.0, 1.0, 1.0, 0.18);
+
+/// Default corner radius in logical pixels
+const DEFAULT_CORNER_RADIUS: f32 = 4.0;
+
+/// Seconds for the band to sweep across once
+const SHIMMER_PERIOD: f64 = 1.4;
+
+/// Horizontal extent `(x, width)` of the shimmer band at `time` seconds.
+///
+/// The band is 40% of the skeleton wide and travels from fully left of it to
+/// fully right of it, so each sweep starts and ends off-screen.
+fn shimmer_band(width: f32, time: f64) -> (f32, f32) {
+    let band = width * 0.4;
+    let progress = (time / SHIMMER_PERIOD).fract() as f32;
+    (-band + (width + band) * progress, band)
+}
+
+/// A loading placeholder with an animated shimmer.
+pub struct Skeleton {
+    width: Signal<f32>,
+    height: Signal<f32>,
+    corner_radius: Option<Signal<f32>>,
+    color: Option<Signal<Color>>,
+    highlight_color: Option<Signal<Color>>,
+    time: Signal<f64>,
+}
+
+impl Skeleton {
+    /// Create a skeleton of the given size.
+    pub fn new<M1, M2>(width: impl IntoSignal<f32, M1>, height: impl IntoSignal<f32, M2>) -> Self {
+        Self {
+            width: width.into_signal(),
+            height: height.into_signal(),
+            corner_radius: None,
+            color: None,
+            highlight_color: None,
+            time: create_animation_frame(),
+        }
+    }
+
+    /// Set the corner radius (4 by default).
+    pub fn corner_radius<M>(mut self, radius: impl IntoSignal<f32, M>) -> Self {
+        self.corner_radius = Some(radius.into_signal());
+        self
+    }
+
+    /// Set the placeholder color.
+    pub fn color<M>(mut self, color: impl IntoSignal<Color, M>) -> Self {
+        self.color = Some(color.into_signal());
+        self
+    }
+
+    /// Set the color at the center of the shimmer band.
+    pub fn highlight_color<M>(mut self, color: impl IntoSignal<Color, M>) -> Self {
+        self.highlight_color = Some(color.into_signal());
+        self
+    }
+}
+
+impl Widget for Skeleton {
+    fn layout(&mut self, tree: &mut Tree, id: WidgetId, constraints: Constraints) -> Size {
+        tree.set_relayout_boundary(id, false);
+
+        let (width, height) = with_signal_tracking(id, JobType::Layout, || {
+            (self.width.get(), self.height.get())
+        });
+        let size = Size::new(
+            width.max(constraints.min_width).min(constraints.max_width),
+            height
+                .max(constraints.min_height)
+                .min(constraints.max_height),
+        );
+
+        tree.cache_layout(id, constraints, size);
+        tree.clear_needs_layout(id);
+        size
+    }
+
+    fn paint(&self, tree: &Tree, id: WidgetId, ctx: &mut PaintContext) {
+        let size = tree.cached_size(id).unwrap_or_default();
+        let bounds = Rect::new(0.0, 0.0, size.width, size.height);
+        let (radius, color, highlight) = with_signal_tracking(id, JobType::Paint, || {
+            (
+                self.corner_radius.get_or(DEFAULT_CORNER_RADIUS),
+                self.color.get_or(DEFAULT_COLOR),
+                self.highlight_color.get_or(DEFAULT_HIGHLIGHT),
+            )
+        });
+        ctx.draw_rounded_rect(bounds, color, radius);
+
+        if reduce_motion() || size.width <= 0.0 {
+            return;
+        }
+        // Reading the frame time repaints the skeleton every frame
+        let time = with_signal_tracking(id, JobType::Paint, || self.time.get());
+        let (x, band) = shimmer_band(size.width, time);
+        let half = band / 2.0;
+        let clear = highlight.with_alpha(0.0);
+
+        ctx.set_clip(bounds, radius, 1.0);
+        ctx.draw_gradient_rect(
+            Rect::new(x, 0.0, half, size.height),
+            Gradient {
+                start_color: clear,
+                end_color: highlight,
+                direction: GradientDir::Horizontal,
+            },
+            0.0,
+            1.0,
+        );
+        ctx.draw_gradient_rect(
+            Rect::new(x + half, 0.0, half, size.height),
+            Gradient {
+                start_color: highlight,
+                end_color: clear,
+                direction: GradientDir::Horizontal,
+            },
+            0.0,
+            1.0,
+        );
+    }
+
+    fn event(&mut self, _tree: &mut Tree, _id: WidgetId, _event: &Event) -> EventResponse {
+        EventResponse::Ignored
+    }
+}
+
+/// Create a shimmering placeholder of the given size.
+///
+/// ```ignore
+/// skeleton(120.0, 14.0)
+/// skeleton(48.0, 48.0).corner_radius(24.0) // avatar
+/// ```
+pub fn skeleton<M1, M2>(
+    width: impl IntoSignal<f32, M1>,
+    height: impl IntoSignal<f32, M2>,
+) -> Skeleton {
+    Skeleton::new(width, height)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shimmer_band_sweeps_across() {
+        // Starts just left of the skeleton
+        assert_eq!(shimmer_band(100.0, 0.0), (-40.0, 40.0));
+        // Halfway through the sweep the band is centered
+        let (x, band) = shimmer_band(100.0, SHIMMER_PERIOD / 2.0);
+        assert!((x + band / 2.0 - 50.0).abs() < 1e-3);
+        // Repeats every period
+        let (x, _) = shimmer_band(100.0, SHIMMER_PERIOD * 3.25);
+        let (expected, _) = shimmer_band(100.0, SHIMMER_PERIOD * 0.25);
+        assert!((x - expected).abs() < 1e-3);
+    }
+}
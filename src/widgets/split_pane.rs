@@ -0,0 +1,479 @@
+//! Two panes separated by a draggable divider.
+//!
+//! A [`SplitPane`] shares its main-axis space between two panes according to
+//! a ratio signal (the first pane's share, 0.0 to 1.0). Dragging the divider
+//! updates the ratio within its min/max range, double-clicking it resets the
+//! default ratio, and either pane can be collapsed to give the other all the
+//! space.
+//!
+//! # Example
+//!
+//! ```ignore
+//! let split = create_signal(0.25f32);
+//!
+//! split_pane(file_tree(), editor(), Axis::Horizontal)
+//!     .ratio(split)
+//!     .min_ratio(0.15)
+//!     .max_ratio(0.5)
+//!     .collapsed(move || (!show_sidebar.get()).then_some(Pane::First))
+//! ```
+
+use std::cell::Cell;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use crate::animation::clock;
+use crate::jobs::{JobRequest, JobType, request_job};
+use crate::layout::{Axis, Constraints, Layout, Size, fill};
+use crate::reactive::{
+    CursorIcon, IntoSignal, OptionSignalExt, RwSignal, Signal, create_signal, set_cursor,
+    with_signal_tracking,
+};
+use crate::renderer::PaintContext;
+use crate::tree::{Tree, WidgetId};
+
+use super::container::{Container, Overflow, container};
+use super::into_child::IntoChild;
+use super::resize_handle::{Drag, along, resize_cursor};
+use super::widget::{Color, Event, EventResponse, MouseButton, Rect, Widget, delegate_widget};
+
+/// Default divider thickness in logical pixels
+const DEFAULT_DIVIDER_THICKNESS: f32 = 6.0;
+
+/// Maximum time between two presses on the divider to count as a double-click
+const DOUBLE_CLICK_TIME: Duration = Duration::from_millis(400);
+
+/// One of the two panes of a [`SplitPane`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pane {
+    First,
+    Second,
+}
+
+/// Configuration shared by the split layout, the divider and the builder.
+struct SplitState {
+    axis: Axis,
+    ratio: Cell<RwSignal<f32>>,
+    /// Whether `ratio` was supplied by the user
+    external_ratio: Cell<bool>,
+    default_ratio: Cell<f32>,
+    min_ratio: Cell<f32>,
+    max_ratio: Cell<f32>,
+    divider_thickness: Cell<f32>,
+    divider_color: Cell<Option<Signal<Color>>>,
+    divider_hover_color: Cell<Option<Signal<Color>>>,
+    collapsed: Cell<Option<Signal<Option<Pane>>>>,
+    /// Main-axis space shared by the panes at the last layout
+    available: Cell<f32>,
+}
+
+impl SplitState {
+    fn clamp_ratio(&self, ratio: f32) -> f32 {
+        let min = self.min_ratio.get();
+        ratio.clamp(min, self.max_ratio.get().max(min))
+    }
+}
+
+/// Two panes with a draggable divider between them.
+///
+/// Created with [`split_pane`].
+pub struct SplitPane {
+    inner: Container,
+    state: Rc<SplitState>,
+}
+
+impl SplitPane {
+    /// Use `ratio` as the first pane's share of the space (0.0 to 1.0).
+    ///
+    /// Dragging the divider writes to it, so it can be persisted or shared.
+    pub fn ratio(self, ratio: RwSignal<f32>) -> Self {
+        self.state.ratio.set(ratio);
+        self.state.external_ratio.set(true);
+        self
+    }
+
+    /// Ratio restored by double-clicking the divider (0.5 by default).
+    ///
+    /// Also sets the starting ratio unless a [`ratio`](Self::ratio) signal was given.
+    pub fn default_ratio(self, ratio: f32) -> Self {
+        self.state.default_ratio.set(ratio);
+        if !self.state.external_ratio.get() {
+            self.state.ratio.get().set(ratio);
+        }
+        self
+    }
+
+    /// Smallest share the first pane can be dragged to.
+    pub fn min_ratio(self, ratio: f32) -> Self {
+        self.state.min_ratio.set(ratio);
+        self
+    }
+
+    /// Largest share the first pane can be dragged to.
+    pub fn max_ratio(self, ratio: f32) -> Self {
+        self.state.max_ratio.set(ratio);
+        self
+    }
+
+    /// Collapse a pane, giving the other one all the space and hiding the divider.
+    pub fn collapsed<M>(self, pane: impl IntoSignal<Option<Pane>, M>) -> Self {
+        self.state.collapsed.set(Some(pane.into_signal()));
+        self
+    }
+
+    /// Thickness of the divider across the split axis.
+    pub fn divider_thickness(self, thickness: f32) -> Self {
+        self.state.divider_thickness.set(thickness);
+        self
+    }
+
+    /// Divider color (transparent by default).
+    pub fn divider_color<M>(self, color: impl IntoSignal<Color, M>) -> Self {
+        self.state.divider_color.set(Some(color.into_signal()));
+        self
+    }
+
+    /// Divider color while hovered or dragged.
+    pub fn divider_hover_color<M>(self, color: impl IntoSignal<Color, M>) -> Self {
+        self.state
+            .divider_hover_color
+            .set(Some(color.into_signal()));
+        self
+    }
+
+    /// Tag the split pane for [`Tree::find_by_test_id`].
+    pub fn test_id(mut self, id: impl Into<String>) -> Self {
+        self.inner = self.inner.test_id(id);
+        self
+    }
+
+    /// Stacking order among siblings (see [`Container::z_index`]).
+    pub fn z_index<M>(mut self, z: impl IntoSignal<i32, M>) -> Self {
+        self.inner = self.inner.z_index(z);
+        self
+    }
+}
+
+impl Widget for SplitPane {
+    delegate_widget!(inner);
+}
+
+/// Lays out `[first, divider, second]` along the split axis.
+struct SplitLayout {
+    state: Rc<SplitState>,
+}
+
+impl Layout for SplitLayout {
+    fn layout(
+        &mut self,
+        tree: &mut Tree,
+        children: &[WidgetId],
+        constraints: Constraints,
+        origin: (f32, f32),
+    ) -> Size {
+        let &[first, divider, second] = children else {
+            return constraints.constrain(Size::zero());
+        };
+        let state = &self.state;
+        let axis = state.axis;
+
+        let finite_or = |max: f32, min: f32| if max.is_finite() { max } else { min };
+        let (main, cross) = match axis {
+            Axis::Horizontal => (
+                finite_or(constraints.max_width, constraints.min_width),
+                finite_or(constraints.max_height, constraints.min_height),
+            ),
+            Axis::Vertical => (
+                finite_or(constraints.max_height, constraints.min_height),
+                finite_or(constraints.max_width, constraints.min_width),
+            ),
+        };
+
+        // Track the ratio on the split pane's container so changes re-layout it
+        let read = || (state.ratio.get().get(), state.collapsed.get().get_or(None));
+        let (ratio, collapsed) = match tree.get_parent(first) {
+            Some(parent) => with_signal_tracking(parent, JobType::Layout, read),
+            None => read(),
+        };
+
+        let thickness = if collapsed.is_some() {
+            0.0
+        } else {
+            state.divider_thickness.get()
+        };
+        let available = (main - thickness).max(0.0);
+        state.available.set(available);
+        let first_len = match collapsed {
+            Some(Pane::First) => 0.0,
+            Some(Pane::Second) => available,
+            None => (available * state.clamp_ratio(ratio)).round(),
+        };
+
+        let mut offset = 0.0;
+        for (child, len) in [
+            (first, first_len),
+            (divider, thickness),
+            (second, available - first_len),
+        ] {
+            let (size, position) = match axis {
+                Axis::Horizontal => (Size::new(len, cross), (origin.0 + offset, origin.1)),
+                Axis::Vertical => (Size::new(cross, len), (origin.0, origin.1 + offset)),
+            };
+            tree.with_widget_mut(child, |widget, id, tree| {
+                widget.layout(tree, id, Constraints::tight(size))
+            });
+            tree.set_origin(child, position.0, position.1);
+            offset += len;
+        }
+
+        match axis {
+            Axis::Horizontal => Size::new(main, cross),
+            Axis::Vertical => Size::new(cross, main),
+        }
+    }
+}
+
+/// The draggable bar between the panes.
+struct SplitDivider {
+    state: Rc<SplitState>,
+    hovered: bool,
+    drag: Option<Drag>,
+    last_press: Option<Instant>,
+}
+
+impl SplitDivider {
+    fn set_hovered(&mut self, id: WidgetId, hovered: bool) {
+        if self.hovered == hovered {
+            return;
+        }
+        self.hovered = hovered;
+        if self.drag.is_none() {
+            set_cursor(if hovered {
+                resize_cursor(self.state.axis)
+            } else {
+                CursorIcon::Default
+            });
+        }
+        request_job(id, JobRequest::Paint);
+    }
+}
+
+impl Widget for SplitDivider {
+    fn layout(&mut self, tree: &mut Tree, id: WidgetId, constraints: Constraints) -> Size {
+        tree.set_relayout_boundary(id, false);
+        let size = Size::new(constraints.min_width, constraints.min_height);
+        tree.cache_layout(id, constraints, size);
+        tree.clear_needs_layout(id);
+        size
+    }
+
+    fn paint(&self, tree: &Tree, id: WidgetId, ctx: &mut PaintContext) {
+        let active = self.hovered || self.drag.is_some();
+        let color = with_signal_tracking(id, JobType::Paint, || {
+            if active && let Some(hover) = self.state.divider_hover_color.get() {
+                hover.get()
+            } else {
+                self.state.divider_color.get().get_or(Color::TRANSPARENT)
+            }
+        });
+        if color.a <= 0.0 {
+            return;
+        }
+        let size = tree.cached_size(id).unwrap_or_default();
+        ctx.draw_rounded_rect(Rect::new(0.0, 0.0, size.width, size.height), color, 0.0);
+    }
+
+    fn event(&mut self, tree: &mut Tree, id: WidgetId, event: &Event) -> EventResponse {
+        let bounds = tree.get_bounds(id).unwrap_or_default();
+        let state = Rc::clone(&self.state);
+        match *event {
+            Event::MouseEnter { x, y } | Event::MouseMove { x, y } => {
+                self.set_hovered(id, bounds.contains(x, y));
+                if let Some(drag) = self.drag {
+                    let available = state.available.get();
+                    if available > 0.0 {
+                        let ratio = drag.size_at(along(state.axis, x, y), false) / available;
+                        let ratio = state.clamp_ratio(ratio);
+                        if ratio != state.ratio.get().get_untracked() {
+                            state.ratio.get().set(ratio);
+                        }
+                    }
+                    return EventResponse::Handled;
+                }
+            }
+            Event::MouseDown {
+                x,
+                y,
+                button: MouseButton::Left,
+            } if bounds.contains(x, y) => {
+                let now = clock::now();
+                if self
+                    .last_press
+                    .is_some_and(|last| now.saturating_duration_since(last) < DOUBLE_CLICK_TIME)
+                {
+                    self.last_press = None;
+                    state.ratio.get().set(state.default_ratio.get());
+                } else {
+                    self.last_press = Some(now);
+                    let start = state.ratio.get().get_untracked() * state.available.get();
+                    self.drag = Some(Drag::new(along(state.axis, x, y), start));
+                }
+                request_job(id, JobRequest::Paint);
+                return EventResponse::Handled;
+            }
+            Event::MouseUp {
+                button: MouseButton::Left,
+                ..
+            } if self.drag.is_some() => {
+                self.drag = None;
+                if !self.hovered {
+                    set_cursor(CursorIcon::Default);
+                }
+                request_job(id, JobRequest::Paint);
+                return EventResponse::Handled;
+            }
+            Event::MouseLeave => {
+                self.drag = None;
+                self.set_hovered(id, false);
+            }
+            _ => {}
+        }
+        EventResponse::Ignored
+    }
+}
+
+/// Create a split pane with `first` before `second` along `axis`.
+///
+/// `Axis::Horizontal` places the panes side by side, `Axis::Vertical` stacks
+/// them. Each pane clips its content to its share of the space, and the split
+/// pane fills the space its parent gives it.
+pub fn split_pane<M1, M2>(
+    first: impl IntoChild<M1>,
+    second: impl IntoChild<M2>,
+    axis: Axis,
+) -> SplitPane {
+    let state = Rc::new(SplitState {
+        axis,
+        ratio: Cell::new(create_signal(0.5)),
+        external_ratio: Cell::new(false),
+        default_ratio: Cell::new(0.5),
+        min_ratio: Cell::new(0.0),
+        max_ratio: Cell::new(1.0),
+        divider_thickness: Cell::new(DEFAULT_DIVIDER_THICKNESS),
+        divider_color: Cell::new(None),
+        divider_hover_color: Cell::new(None),
+        collapsed: Cell::new(None),
+        available: Cell::new(0.0),
+    });
+    let divider = SplitDivider {
+        state: Rc::clone(&state),
+        hovered: false,
+        drag: None,
+        last_press: None,
+    };
+    let inner = container()
+        .layout(SplitLayout {
+            state: Rc::clone(&state),
+        })
+        .width(fill())
+        .height(fill())
+        .child(container().overflow(Overflow::Hidden).child(first))
+        .child(divider)
+        .child(container().overflow(Overflow::Hidden).child(second));
+    SplitPane { inner, state }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(x: f32) -> [Event; 2] {
+        [
+            Event::MouseDown {
+                x,
+                y: 10.0,
+                button: MouseButton::Left,
+            },
+            Event::MouseUp {
+                x,
+                y: 10.0,
+                button: MouseButton::Left,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_divider_drag_and_double_click_reset() {
+        clock::pause_clock();
+        let ratio = create_signal(0.5f32);
+        let mut tree = Tree::new();
+        let root = tree.register(Box::new(
+            split_pane(container(), container(), Axis::Horizontal)
+                .ratio(ratio)
+                .max_ratio(0.8)
+                .divider_thickness(10.0),
+        ));
+        let layout = |tree: &mut Tree| {
+            tree.with_widget_mut(root, |widget, id, tree| {
+                widget.layout(tree, id, Constraints::tight(Size::new(410.0, 100.0)))
+            });
+        };
+        tree.with_widget_mut(root, |widget, id, tree| widget.register_children(tree, id));
+        layout(&mut tree);
+
+        let children = tree.get_children(root).to_vec();
+        assert_eq!(tree.get_bounds(children[0]).unwrap().width, 200.0);
+        assert_eq!(tree.get_bounds(children[1]).unwrap().x, 200.0);
+        assert_eq!(tree.get_bounds(children[2]).unwrap().width, 200.0);
+
+        let send = |tree: &mut Tree, event: &Event| {
+            tree.with_widget_mut(root, |widget, id, tree| widget.event(tree, id, event));
+        };
+        let [down, up] = press(205.0);
+        send(&mut tree, &down);
+        send(&mut tree, &Event::MouseMove { x: 245.0, y: 10.0 });
+        assert_eq!(ratio.get_untracked(), 0.6);
+        send(&mut tree, &Event::MouseMove { x: 405.0, y: 10.0 });
+        assert_eq!(ratio.get_untracked(), 0.8);
+        send(&mut tree, &up);
+        tree.mark_needs_layout(root);
+        layout(&mut tree);
+        assert_eq!(tree.get_bounds(children[0]).unwrap().width, 320.0);
+
+        // Two presses far apart are two drags, not a double click
+        clock::advance_clock(DOUBLE_CLICK_TIME * 2);
+        for event in press(325.0) {
+            send(&mut tree, &event);
+        }
+        clock::advance_clock(DOUBLE_CLICK_TIME * 2);
+        for event in press(325.0) {
+            send(&mut tree, &event);
+        }
+        assert_eq!(ratio.get_untracked(), 0.8);
+
+        // Two quick presses on the divider restore the default split
+        clock::advance_clock(DOUBLE_CLICK_TIME / 4);
+        for event in press(325.0) {
+            send(&mut tree, &event);
+        }
+        assert_eq!(ratio.get_untracked(), 0.5);
+        clock::resume_clock();
+    }
+
+    #[test]
+    fn test_wrapper_forwards_widget_hooks() {
+        let mut tree = Tree::new();
+        let root = tree.register(Box::new(
+            split_pane(container(), container(), Axis::Vertical)
+                .test_id("editor-split")
+                .z_index(2),
+        ));
+
+        assert_eq!(tree.find_by_test_id("editor-split"), Some(root));
+        assert_eq!(tree.with_widget(root, |w| w.z_index()), Some(2));
+        assert_eq!(
+            tree.with_widget(root, |w| w.debug_name()),
+            Some("Container")
+        );
+    }
+}
@@ -13,6 +13,7 @@
 //!     .child(text("Interactive button"))
 //! ```
 
+use crate::animation::TransitionConfig;
 use crate::transform::Transform;
 use crate::widgets::Color;
 
@@ -25,6 +26,11 @@ pub struct RippleConfig {
     pub expand_speed: f32,
     /// Speed multiplier for ripple fade out (higher = faster)
     pub fade_speed: f32,
+    /// Whether the ripple is clipped to the container's rounded bounds.
+    ///
+    /// Bounded ripples spread from the press point. Unbounded ripples spread
+    /// from the center and overflow the bounds, as on icon buttons.
+    pub bounded: bool,
 }
 
 impl Default for RippleConfig {
@@ -33,6 +39,7 @@ impl Default for RippleConfig {
             color: Color::rgba(1.0, 1.0, 1.0, 0.3),
             expand_speed: 1.0,
             fade_speed: 1.0,
+            bounded: true,
         }
     }
 }
@@ -50,6 +57,21 @@ impl RippleConfig {
             ..Default::default()
         }
     }
+
+    /// Create a ripple clipped to the container, spreading from the press point.
+    pub fn bounded(color: Color) -> Self {
+        Self::with_color(color)
+    }
+
+    /// Create a ripple that spreads from the container's center and overflows
+    /// its bounds.
+    pub fn unbounded(color: Color) -> Self {
+        Self {
+            color,
+            bounded: false,
+            ..Default::default()
+        }
+    }
 }
 
 /// How to override the background color in a state.
@@ -84,6 +106,14 @@ pub struct StateStyle {
     pub alpha: Option<f32>,
     /// Ripple effect configuration (typically used in pressed_state)
     pub ripple: Option<RippleConfig>,
+    /// Transition used when the background animates into this state
+    pub background_transition: Option<TransitionConfig>,
+    /// Transition used when the border width and color animate into this state
+    pub border_transition: Option<TransitionConfig>,
+    /// Transition used when the corner radius animates into this state
+    pub corner_radius_transition: Option<TransitionConfig>,
+    /// Transition used when the transform animates into this state
+    pub transform_transition: Option<TransitionConfig>,
 }
 
 impl StateStyle {
@@ -224,6 +254,53 @@ impl StateStyle {
         self.ripple = Some(config);
         self
     }
+
+    /// Animate the background into this state with `transition`.
+    ///
+    /// The container animates its background without a separate
+    /// `animate_background` call. Leaving the state plays the container's own
+    /// background transition if it has one, and this one otherwise.
+    ///
+    /// # Example
+    /// ```ignore
+    /// container()
+    ///     .background(Color::rgb(0.2, 0.2, 0.3))
+    ///     .hover_state(|s| {
+    ///         s.lighter(0.1)
+    ///             .transition_background(Transition::new(150.0, TimingFunction::EaseOut))
+    ///     })
+    /// ```
+    pub fn transition_background(mut self, transition: impl Into<TransitionConfig>) -> Self {
+        self.background_transition = Some(transition.into());
+        self
+    }
+
+    /// Animate the border width and color into this state with `transition`.
+    pub fn transition_border(mut self, transition: impl Into<TransitionConfig>) -> Self {
+        self.border_transition = Some(transition.into());
+        self
+    }
+
+    /// Animate the corner radius into this state with `transition`.
+    pub fn transition_corner_radius(mut self, transition: impl Into<TransitionConfig>) -> Self {
+        self.corner_radius_transition = Some(transition.into());
+        self
+    }
+
+    /// Animate the transform into this state with `transition`.
+    ///
+    /// # Example
+    /// ```ignore
+    /// container()
+    ///     .pressed_state(|s| {
+    ///         s.transform(Transform::scale(0.95))
+    ///             .transition_transform(Transition::spring(SpringConfig::SNAPPY))
+    ///     })
+    /// ```
+    pub fn transition_transform(mut self, transition: impl Into<TransitionConfig>) -> Self {
+        self.transform_transition = Some(transition.into());
+        self
+    }
 }
 
 /// Resolve a background override to an actual color.
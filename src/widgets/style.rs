@@ -0,0 +1,136 @@
+//! Reusable container styles.
+//!
+//! A [`WidgetStyle`] bundles the styling a group of containers share, so it can
+//! be defined once and applied with [`Container::style`](super::Container::style)
+//! instead of repeating the same builder chain. Values set directly on a
+//! container always win over the style's, whichever is called first.
+//!
+//! Styles are plain values: derive a variant by cloning a style and changing
+//! the fields that differ.
+//!
+//! # Example
+//! ```ignore
+//! let card_style = WidgetStyle::new()
+//!     .padding(16.0)
+//!     .background(Color::rgb(0.15, 0.15, 0.2))
+//!     .corner_radius(8.0)
+//!     .border(1.0, Color::rgb(0.3, 0.3, 0.4))
+//!     .hover_state(|s| s.lighter(0.05));
+//! let selected_card = card_style.clone().border(2.0, Color::rgb(0.4, 0.6, 1.0));
+//!
+//! container()
+//!     .layout(Flex::column().spacing(8.0))
+//!     .child(container().style(&card_style).child(text("One")))
+//!     .child(container().style(&selected_card).child(text("Two")))
+//!     // The inline background overrides the style's
+//!     .child(
+//!         container()
+//!             .style(&card_style)
+//!             .background(Color::rgb(0.3, 0.1, 0.1))
+//!             .child(text("Three")),
+//!     )
+//! ```
+
+use crate::layout::Length;
+use crate::reactive::{IntoSignal, Signal};
+
+use super::state_layer::StateStyle;
+use super::widget::{Color, Padding};
+
+/// Styling shared by several containers.
+///
+/// Every property is optional; unset properties leave the container's own
+/// value (or its default) alone.
+#[derive(Clone, Default)]
+pub struct WidgetStyle {
+    pub(super) padding: Option<Signal<Padding>>,
+    pub(super) background: Option<Signal<Color>>,
+    pub(super) corner_radius: Option<Signal<f32>>,
+    pub(super) border_width: Option<Signal<f32>>,
+    pub(super) border_color: Option<Signal<Color>>,
+    pub(super) elevation: Option<Signal<f32>>,
+    pub(super) shadow_color: Option<Signal<Color>>,
+    pub(super) width: Option<Signal<Length>>,
+    pub(super) height: Option<Signal<Length>>,
+    pub(super) hover_state: Option<StateStyle>,
+    pub(super) pressed_state: Option<StateStyle>,
+    pub(super) focused_state: Option<StateStyle>,
+}
+
+impl WidgetStyle {
+    /// Create an empty style.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the padding inside the border.
+    pub fn padding<M>(mut self, value: impl IntoSignal<Padding, M>) -> Self {
+        self.padding = Some(value.into_signal());
+        self
+    }
+
+    /// Set the background color.
+    pub fn background<M>(mut self, color: impl IntoSignal<Color, M>) -> Self {
+        self.background = Some(color.into_signal());
+        self
+    }
+
+    /// Set the corner radius in logical pixels.
+    pub fn corner_radius<M>(mut self, radius: impl IntoSignal<f32, M>) -> Self {
+        self.corner_radius = Some(radius.into_signal());
+        self
+    }
+
+    /// Set the border width and color.
+    pub fn border<M1, M2>(
+        mut self,
+        width: impl IntoSignal<f32, M1>,
+        color: impl IntoSignal<Color, M2>,
+    ) -> Self {
+        self.border_width = Some(width.into_signal());
+        self.border_color = Some(color.into_signal());
+        self
+    }
+
+    /// Set the elevation (shadow level).
+    pub fn elevation<M>(mut self, level: impl IntoSignal<f32, M>) -> Self {
+        self.elevation = Some(level.into_signal());
+        self
+    }
+
+    /// Tint the elevation shadow.
+    pub fn shadow_color<M>(mut self, color: impl IntoSignal<Color, M>) -> Self {
+        self.shadow_color = Some(color.into_signal());
+        self
+    }
+
+    /// Set the width.
+    pub fn width<M>(mut self, width: impl IntoSignal<Length, M>) -> Self {
+        self.width = Some(width.into_signal());
+        self
+    }
+
+    /// Set the height.
+    pub fn height<M>(mut self, height: impl IntoSignal<Length, M>) -> Self {
+        self.height = Some(height.into_signal());
+        self
+    }
+
+    /// Set style overrides for the hover state.
+    pub fn hover_state(mut self, f: impl FnOnce(StateStyle) -> StateStyle) -> Self {
+        self.hover_state = Some(f(StateStyle::new()));
+        self
+    }
+
+    /// Set style overrides for the pressed state.
+    pub fn pressed_state(mut self, f: impl FnOnce(StateStyle) -> StateStyle) -> Self {
+        self.pressed_state = Some(f(StateStyle::new()));
+        self
+    }
+
+    /// Set style overrides for when the container or a child has focus.
+    pub fn focused_state(mut self, f: impl FnOnce(StateStyle) -> StateStyle) -> Self {
+        self.focused_state = Some(f(StateStyle::new()));
+        self
+    }
+}
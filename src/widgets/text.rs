@@ -1,26 +1,216 @@
+use std::ops::Range;
+use std::time::Instant;
+
+use crate::accessibility::{AccessInfo, Role};
+use crate::animation::{clock, reduce_motion};
 use crate::default_font_family;
-use crate::jobs::JobType;
+use crate::jobs::{JobRequest, JobType, RequiredJob, request_job};
 use crate::layout::{Constraints, Size};
 use crate::reactive::{IntoSignal, OptionSignalExt, Signal, with_signal_tracking};
-use crate::renderer::{PaintContext, measure_text_styled};
+use crate::renderer::{
+    DEFAULT_TAB_WIDTH, PaintContext, TextMeasureParams, clamp_text_lines, measure_text_with_params,
+    measurer_font_generation, text_baseline, text_range_rects,
+};
 use crate::tree::{Tree, WidgetId};
 
-use super::font::{FontFamily, FontWeight};
-use super::widget::{Color, EventResponse, Rect, Widget};
+use super::container::LinearGradient;
+use super::font::{FontFamily, FontStyle, FontWeight, TextDirection, WrapMode};
+use super::widget::{Color, Event, EventResponse, Rect, Widget};
+
+/// Default marquee scroll speed in logical pixels per second
+const DEFAULT_MARQUEE_SPEED: f32 = 30.0;
+/// Default pause at each end of the marquee loop in seconds
+const DEFAULT_MARQUEE_PAUSE: f32 = 1.5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MarqueePhase {
+    /// Holding at the start before scrolling
+    PauseStart,
+    /// Scrolling towards the end of the text
+    Scrolling,
+    /// Holding at the end before jumping back to the start
+    PauseEnd,
+}
+
+/// Marquee scrolling state (boxed since most text never scrolls)
+struct Marquee {
+    /// Scroll speed in logical pixels per second
+    speed: f32,
+    /// Pause at each end in seconds
+    pause: f32,
+    /// How far the text overflows its bounds (0 when it fits)
+    overflow: f32,
+    /// Current horizontal scroll offset
+    offset: f32,
+    phase: MarqueePhase,
+    /// Seconds spent in the current phase (excluding time paused on hover)
+    phase_elapsed: f32,
+    last_tick: Instant,
+    hovered: bool,
+}
+
+impl Marquee {
+    fn new() -> Self {
+        Self {
+            speed: DEFAULT_MARQUEE_SPEED,
+            pause: DEFAULT_MARQUEE_PAUSE,
+            overflow: 0.0,
+            offset: 0.0,
+            phase: MarqueePhase::PauseStart,
+            phase_elapsed: 0.0,
+            last_tick: clock::now(),
+            hovered: false,
+        }
+    }
+
+    fn is_running(&self) -> bool {
+        self.overflow > 0.0 && !self.hovered && !reduce_motion()
+    }
+
+    /// Reset to the start of the loop.
+    fn reset(&mut self) {
+        self.offset = 0.0;
+        self.phase = MarqueePhase::PauseStart;
+        self.phase_elapsed = 0.0;
+        self.last_tick = clock::now();
+    }
+
+    /// Advance the loop by `dt` seconds.
+    fn advance(&mut self, dt: f32) {
+        self.phase_elapsed += dt;
+        match self.phase {
+            MarqueePhase::PauseStart => {
+                if self.phase_elapsed >= self.pause {
+                    self.phase = MarqueePhase::Scrolling;
+                    self.phase_elapsed = 0.0;
+                }
+            }
+            MarqueePhase::Scrolling => {
+                self.offset = (self.offset + self.speed * dt).min(self.overflow);
+                if self.offset >= self.overflow {
+                    self.phase = MarqueePhase::PauseEnd;
+                    self.phase_elapsed = 0.0;
+                }
+            }
+            MarqueePhase::PauseEnd => {
+                if self.phase_elapsed >= self.pause {
+                    self.offset = 0.0;
+                    self.phase = MarqueePhase::PauseStart;
+                    self.phase_elapsed = 0.0;
+                }
+            }
+        }
+    }
+}
+
+/// Line clamp and the clamped text for the last layout (boxed since most
+/// text is never clamped)
+struct LineClamp {
+    max_lines: usize,
+    /// Width the clamp was computed for
+    max_width: Option<f32>,
+    /// Text the clamp was computed from
+    source: String,
+    /// Measurement font generation the clamp was computed with; fonts added
+    /// at runtime can change where lines break
+    font_generation: usize,
+    /// The clamped text, or `None` when the source fits
+    clamped: Option<String>,
+}
+
+/// Character ranges drawn over a background color (boxed since most text
+/// has no highlights)
+struct Highlight {
+    ranges: Signal<Vec<Range<usize>>>,
+    color: Signal<Color>,
+    /// Width the text wrapped at in the last layout
+    max_width: Option<f32>,
+    /// The text as given, which the ranges index into
+    source: String,
+    /// Index in the shown text of each source character, `None` once clamped away
+    shown_index: Vec<Option<usize>>,
+}
+
+/// Index in `shown` of each character of `source`, plus one entry for the
+/// end of the text.
+///
+/// The shown text differs from the source by inserted direction marks, line
+/// breaks joined into spaces, and line clamping, which can break lines, drop
+/// the spaces it breaks at and cut the rest behind an ellipsis. Dropped
+/// characters map to the next shown one; characters cut away map to `None`.
+fn shown_char_indices(source: &str, shown: &str) -> Vec<Option<usize>> {
+    let source: Vec<char> = source.chars().collect();
+    let shown: Vec<char> = shown.chars().collect();
+    let is_mark = |c: char| c == '\u{200E}' || c == '\u{200F}';
+    let mut indices = vec![None; source.len() + 1];
+    let (mut i, mut j) = (0, 0);
+    while i < source.len() && j < shown.len() {
+        let (s, t) = (source[i], shown[j]);
+        if s == t || (s == '\n' && t == ' ') || (s.is_whitespace() && t == '\n') {
+            // Same character, or a break swapped for a space or vice versa
+            indices[i] = Some(j);
+            i += 1;
+            j += 1;
+        } else if s == '\r' && source.get(i + 1) == Some(&'\n') && t == ' ' {
+            // "\r\n" joined into one space
+            indices[i] = Some(j);
+            i += 1;
+        } else if is_mark(t) || t == '\n' {
+            // Inserted direction mark or wrap break
+            j += 1;
+        } else if s.is_whitespace() && t != '…' {
+            // Space dropped at a wrap break
+            indices[i] = Some(j);
+            i += 1;
+        } else {
+            // Cut off behind the ellipsis
+            return indices;
+        }
+    }
+    if i == source.len() {
+        indices[i] = Some(shown.len());
+    }
+    indices
+}
+
+/// The shown-text range covering the source characters of `range`, or `None`
+/// when all of them are cut away.
+fn shown_range(shown_index: &[Option<usize>], range: Range<usize>) -> Option<Range<usize>> {
+    let mut shown = range
+        .filter_map(|i| shown_index.get(i).copied().flatten())
+        .peekable();
+    let start = *shown.peek()?;
+    let end = shown.last()? + 1;
+    Some(start..end)
+}
 
 pub struct Text {
     content: Signal<String>,
     color: Option<Signal<Color>>,
+    /// Gradient replacing `color` across the text bounds
+    gradient: Option<LinearGradient>,
     font_size: Option<Signal<f32>>,
     font_family: Option<Signal<FontFamily>>,
     font_weight: Option<Signal<FontWeight>>,
-    /// If true, text won't wrap and will be clipped by parent container
-    nowrap: bool,
+    font_style: Option<Signal<FontStyle>>,
+    /// How lines break at the available width
+    wrap: WrapMode,
+    /// Tab stop width in spaces
+    tab_width: u16,
+    /// Paragraph direction, detected from the text by default
+    direction: TextDirection,
+    /// Horizontal scrolling for text wider than its bounds
+    marquee: Option<Box<Marquee>>,
+    /// Maximum number of lines, ending in an ellipsis when cut
+    line_clamp: Option<Box<LineClamp>>,
+    /// Marked character ranges, like a highlighter pen
+    highlight: Option<Box<Highlight>>,
     /// Cached values for painting (avoid re-reading signals)
     cached_text: String,
     cached_font_size: f32,
     cached_font_family: FontFamily,
     cached_font_weight: FontWeight,
+    cached_font_style: FontStyle,
 }
 
 impl Text {
@@ -33,14 +223,22 @@ impl Text {
         Self {
             content,
             color: None,
+            gradient: None,
             font_size: None,
             font_family: None,
             font_weight: None,
-            nowrap: false,
+            font_style: None,
+            wrap: WrapMode::Word,
+            tab_width: DEFAULT_TAB_WIDTH,
+            direction: TextDirection::Auto,
+            marquee: None,
+            line_clamp: None,
+            highlight: None,
             cached_text: String::new(), // Will be set during first layout
             cached_font_size: 14.0,
             cached_font_family: default_family,
             cached_font_weight: FontWeight::NORMAL,
+            cached_font_style: FontStyle::Normal,
         }
     }
 
@@ -49,6 +247,24 @@ impl Text {
         self
     }
 
+    /// Fill the glyphs with a gradient instead of a solid [`color`](Self::color).
+    ///
+    /// The gradient spans the text's bounds, so a vertical gradient runs from
+    /// the top of the first line to the bottom of the last one.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// text("Guido")
+    ///     .font_size(40.0)
+    ///     .bold()
+    ///     .gradient_fill(LinearGradient::vertical(Color::from_hex(0xFFD27F), Color::from_hex(0xFF6B6B)))
+    /// ```
+    pub fn gradient_fill(mut self, gradient: LinearGradient) -> Self {
+        self.gradient = Some(gradient);
+        self
+    }
+
     pub fn font_size<M>(mut self, size: impl IntoSignal<f32, M>) -> Self {
         self.font_size = Some(size.into_signal());
         self
@@ -91,7 +307,45 @@ impl Text {
         self.font_weight(FontWeight::BOLD)
     }
 
-    /// Shorthand for monospace font (FontFamily::Monospace).
+    /// Set the font style (upright, italic or oblique).
+    ///
+    /// Uses the family's slanted face; if it has none, the slant is synthesized.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// text("Hello").font_style(FontStyle::Oblique)
+    /// ```
+    pub fn font_style<M>(mut self, style: impl IntoSignal<FontStyle, M>) -> Self {
+        self.font_style = Some(style.into_signal());
+        self
+    }
+
+    /// Shorthand for italic text (FontStyle::Italic).
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// text("note").italic()
+    /// ```
+    pub fn italic(self) -> Self {
+        self.font_style(FontStyle::Italic)
+    }
+
+    /// Use a fixed-width font (FontFamily::Monospace), e.g. for code and logs.
+    ///
+    /// Combine with [`tab_width`](Self::tab_width) to align tab-separated columns.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// text("name\tsize\nfoo.rs\t12K").monospace()
+    /// ```
+    pub fn monospace(self) -> Self {
+        self.font_family(FontFamily::Monospace)
+    }
+
+    /// Shorthand for [`monospace`](Self::monospace).
     ///
     /// # Examples
     ///
@@ -99,16 +353,218 @@ impl Text {
     /// text("Hello").mono()
     /// ```
     pub fn mono(self) -> Self {
-        self.font_family(FontFamily::Monospace)
+        self.monospace()
+    }
+
+    /// Set the tab stop width in spaces (default 4).
+    ///
+    /// Tab characters advance to the next multiple of this many space widths,
+    /// so tab-separated columns line up.
+    pub fn tab_width(mut self, spaces: u16) -> Self {
+        self.tab_width = spaces.max(1);
+        self
+    }
+
+    /// Set how the text breaks into lines (default [`WrapMode::Word`]).
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// text(url).wrap(WrapMode::BreakWord) // long tokens break too
+    /// text(snippet).mono().wrap(WrapMode::Pre) // lines exactly as written
+    /// ```
+    pub fn wrap(mut self, mode: WrapMode) -> Self {
+        self.wrap = mode;
+        self
+    }
+
+    /// Set the paragraph direction (default [`TextDirection::Auto`]).
+    ///
+    /// By default each paragraph takes its direction from its first strong
+    /// character, so Arabic or Hebrew text already reads right-to-left. Force
+    /// a direction when the text starts with neutral or foreign characters,
+    /// such as a number or a Latin brand name in a right-to-left sentence.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// text("2024 \u{0645}\u{0631}\u{062D}\u{0628}\u{0627}").direction(TextDirection::Rtl)
+    /// ```
+    pub fn direction(mut self, direction: TextDirection) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    /// Show at most `lines` lines, ending the last one with "…" when the text
+    /// is cut. Text that fits is shown unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// text(move || item.get().description).max_lines(2)
+    /// ```
+    pub fn max_lines(mut self, lines: usize) -> Self {
+        self.line_clamp = Some(Box::new(LineClamp {
+            max_lines: lines.max(1),
+            max_width: None,
+            source: String::new(),
+            font_generation: 0,
+            clamped: None,
+        }));
+        self
     }
 
     /// Prevent text from wrapping. Text will be clipped by parent container.
     /// Use this for text inside animated containers to prevent re-wrapping during animation.
-    pub fn nowrap(mut self) -> Self {
-        self.nowrap = true;
+    ///
+    /// Shorthand for `wrap(WrapMode::NoWrap)`.
+    pub fn nowrap(self) -> Self {
+        self.wrap(WrapMode::NoWrap)
+    }
+
+    /// Scroll the text horizontally on a loop when it is wider than its bounds.
+    ///
+    /// The text pauses at each end, jumps back to the start and repeats.
+    /// Scrolling pauses while hovered. Text that fits stays static.
+    /// Implies [`nowrap`](Self::nowrap).
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// text(move || track.get()).marquee()
+    /// ```
+    pub fn marquee(mut self) -> Self {
+        self.wrap = WrapMode::NoWrap;
+        self.marquee.get_or_insert_with(|| Box::new(Marquee::new()));
+        self
+    }
+
+    /// Set the marquee scroll speed in logical pixels per second (default 30).
+    ///
+    /// Enables [`marquee`](Self::marquee) if it isn't already.
+    pub fn marquee_speed(mut self, pixels_per_second: f32) -> Self {
+        self = self.marquee();
+        if let Some(ref mut marquee) = self.marquee {
+            marquee.speed = pixels_per_second.max(1.0);
+        }
+        self
+    }
+
+    /// Set how long the marquee holds at each end in seconds (default 1.5).
+    ///
+    /// Enables [`marquee`](Self::marquee) if it isn't already.
+    pub fn marquee_pause(mut self, seconds: f32) -> Self {
+        self = self.marquee();
+        if let Some(ref mut marquee) = self.marquee {
+            marquee.pause = seconds.max(0.0);
+        }
+        self
+    }
+
+    /// Draw `color` behind the characters in `ranges`, like a highlighter.
+    ///
+    /// Ranges are character (not byte) indices into the text as given, so
+    /// search matches can be marked directly; they follow the characters
+    /// through direction marks and line clamping, and parts cut off by the
+    /// clamp are not drawn. A range that spans a line break is
+    /// drawn as one rect per line, and the marks move with the text when it
+    /// is transformed.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let query = create_signal(String::from("error"));
+    /// text(line.clone()).highlight(
+    ///     move || find_matches(&line, &query.get()),
+    ///     Color::rgba(1.0, 0.85, 0.0, 0.6),
+    /// )
+    /// ```
+    pub fn highlight<M1, M2>(
+        mut self,
+        ranges: impl IntoSignal<Vec<Range<usize>>, M1>,
+        color: impl IntoSignal<Color, M2>,
+    ) -> Self {
+        self.highlight = Some(Box::new(Highlight {
+            ranges: ranges.into_signal(),
+            color: color.into_signal(),
+            max_width: None,
+            source: String::new(),
+            shown_index: Vec::new(),
+        }));
         self
     }
 
+    /// The text as laid out and drawn: clamped when it has too many lines.
+    fn shown_text(&self) -> &str {
+        self.line_clamp
+            .as_ref()
+            .and_then(|clamp| clamp.clamped.as_deref())
+            .unwrap_or(&self.cached_text)
+    }
+
+    /// Measurement settings for the cached font at `max_width`.
+    fn measure_params(&self, max_width: Option<f32>) -> TextMeasureParams {
+        TextMeasureParams::new(self.cached_font_size)
+            .max_width(max_width)
+            .font_family(self.cached_font_family.clone())
+            .font_weight(self.cached_font_weight)
+            .font_style(self.cached_font_style)
+            .tab_width(self.tab_width)
+            .wrap(self.wrap)
+    }
+
+    /// Recompute the line clamp if the text, width or font changed.
+    fn update_line_clamp(&mut self, max_width: Option<f32>, font_changed: bool) {
+        let Some(ref clamp) = self.line_clamp else {
+            return;
+        };
+        let font_generation = measurer_font_generation();
+        if !font_changed
+            && clamp.font_generation == font_generation
+            && clamp.max_width == max_width
+            && clamp.source == self.cached_text
+        {
+            return;
+        }
+        let params = self.measure_params(max_width);
+        let Some(ref mut clamp) = self.line_clamp else {
+            return;
+        };
+        clamp.clamped = clamp_text_lines(&self.cached_text, &params, clamp.max_lines)
+            .map(|clamped| self.direction.apply(&clamped).unwrap_or(clamped));
+        clamp.max_width = max_width;
+        clamp.font_generation = font_generation;
+        clamp.source.clone_from(&self.cached_text);
+    }
+
+    /// Draw the highlight rects behind the glyphs of `text_bounds`.
+    fn paint_highlight(
+        &self,
+        highlight: &Highlight,
+        id: WidgetId,
+        text_bounds: Rect,
+        ctx: &mut PaintContext,
+    ) {
+        let (ranges, color) = with_signal_tracking(id, JobType::Paint, || {
+            (highlight.ranges.get(), highlight.color.get())
+        });
+        let params = self.measure_params(highlight.max_width);
+        let shown_ranges = ranges
+            .into_iter()
+            .filter_map(|range| shown_range(&highlight.shown_index, range));
+        for range in shown_ranges {
+            for rect in text_range_rects(self.shown_text(), &params, range) {
+                let rect = Rect::new(
+                    text_bounds.x + rect.x,
+                    text_bounds.y + rect.y,
+                    rect.width,
+                    rect.height,
+                );
+                ctx.draw_rounded_rect(rect, color, 0.0);
+            }
+        }
+    }
+
     /// Refresh cached values from reactive properties.
     /// Uses signal tracking to register layout dependencies so the widget
     /// is re-laid out when any of these signals change.
@@ -118,7 +574,18 @@ impl Text {
             self.cached_font_size = self.font_size.get_or(14.0);
             self.cached_font_family = self.font_family.get_or_else(default_font_family);
             self.cached_font_weight = self.font_weight.get_or(FontWeight::NORMAL);
+            self.cached_font_style = self.font_style.get_or(FontStyle::Normal);
         });
+        if let Some(ref mut highlight) = self.highlight {
+            highlight.source.clone_from(&self.cached_text);
+        }
+        // A single line: hard breaks read as spaces
+        if self.wrap == WrapMode::NoWrap && self.cached_text.contains('\n') {
+            self.cached_text = self.cached_text.replace("\r\n", " ").replace('\n', " ");
+        }
+        if let Some(directed) = self.direction.apply(&self.cached_text) {
+            self.cached_text = directed;
+        }
     }
 }
 
@@ -129,11 +596,25 @@ impl Widget for Text {
 
         // Refresh cached values from reactive properties
         // This reads signals and registers layout dependencies
+        let previous_text = std::mem::take(&mut self.cached_text);
+        let previous_font = (
+            self.cached_font_size,
+            self.cached_font_family.clone(),
+            self.cached_font_weight,
+            self.cached_font_style,
+        );
         self.refresh(id);
+        let font_changed = previous_font
+            != (
+                self.cached_font_size,
+                self.cached_font_family.clone(),
+                self.cached_font_weight,
+                self.cached_font_style,
+            );
 
         // Determine the effective max_width for measurement
-        // If nowrap is true, don't pass max_width so text won't wrap
-        let max_width = if self.nowrap {
+        // Modes that don't wrap measure without max_width, at their natural width
+        let max_width = if !self.wrap.wraps() {
             None
         } else if constraints.max_width.is_finite() {
             Some(constraints.max_width)
@@ -141,14 +622,21 @@ impl Widget for Text {
             None
         };
 
+        self.update_line_clamp(max_width, font_changed);
+        let shown_index = self
+            .highlight
+            .as_ref()
+            .map(|highlight| shown_char_indices(&highlight.source, self.shown_text()));
+        if let Some(ref mut highlight) = self.highlight
+            && let Some(shown_index) = shown_index
+        {
+            highlight.max_width = max_width;
+            highlight.shown_index = shown_index;
+        }
+
         // Measure text (TextMeasurer caches results internally)
-        let measured = measure_text_styled(
-            &self.cached_text,
-            self.cached_font_size,
-            max_width,
-            &self.cached_font_family,
-            self.cached_font_weight,
-        );
+        let params = self.measure_params(max_width);
+        let measured = measure_text_with_params(self.shown_text(), &params);
 
         let size = Size::new(
             measured
@@ -161,6 +649,23 @@ impl Widget for Text {
                 .min(constraints.max_height),
         );
 
+        // Start (or stop) the marquee depending on whether the text overflows
+        if let Some(ref mut marquee) = self.marquee {
+            let overflow = measured.width - size.width;
+            let overflow = if overflow > 0.5 { overflow } else { 0.0 };
+            if overflow != marquee.overflow || previous_text != self.cached_text {
+                marquee.overflow = overflow;
+                marquee.reset();
+            }
+            if marquee.is_running() {
+                request_job(id, JobRequest::Animation(RequiredJob::Paint));
+            }
+        }
+
+        // Expose the first line's baseline for CrossAlignment::Baseline
+        let baseline = text_baseline(self.shown_text(), &params);
+        tree.set_baseline(id, Some(baseline));
+
         // Cache constraints and size for partial layout
         tree.cache_layout(id, constraints, size);
 
@@ -170,6 +675,10 @@ impl Widget for Text {
         size
     }
 
+    fn accessibility(&self) -> Option<AccessInfo> {
+        Some(AccessInfo::new(Role::Label).name(self.cached_text.clone()))
+    }
+
     fn paint(&self, tree: &Tree, id: WidgetId, ctx: &mut PaintContext) {
         // Draw in LOCAL coordinates (0,0 is widget origin)
         // Parent Container sets position transform
@@ -177,22 +686,70 @@ impl Widget for Text {
         let local_bounds = Rect::new(0.0, 0.0, size.width, size.height);
         // Read color with tracking so signal changes trigger repaint
         let color = with_signal_tracking(id, JobType::Paint, || self.color.get_or(Color::WHITE));
-        ctx.draw_text_styled(
-            &self.cached_text,
-            local_bounds,
-            color,
-            self.cached_font_size,
-            self.cached_font_family.clone(),
-            self.cached_font_weight,
-        );
+        let text_bounds = match self.marquee {
+            Some(ref marquee) if marquee.overflow > 0.0 => {
+                // Draw the full-width text shifted left, clipped to our bounds
+                ctx.set_clip_rect(local_bounds);
+                let offset = if reduce_motion() { 0.0 } else { marquee.offset };
+                Rect::new(
+                    -offset,
+                    0.0,
+                    size.width + marquee.overflow + 1.0,
+                    size.height,
+                )
+            }
+            _ => local_bounds,
+        };
+        if let Some(ref highlight) = self.highlight {
+            self.paint_highlight(highlight, id, text_bounds, ctx);
+        }
+        let params = self.measure_params(None);
+        if let Some(ref gradient) = self.gradient {
+            ctx.draw_gradient_text(self.shown_text(), text_bounds, gradient.into(), &params);
+        } else {
+            ctx.draw_text_styled(self.shown_text(), text_bounds, color, &params);
+        }
+    }
+
+    fn advance_animations(&mut self, _tree: &mut Tree, id: WidgetId) -> bool {
+        let Some(ref mut marquee) = self.marquee else {
+            return false;
+        };
+        if !marquee.is_running() {
+            return false;
+        }
+        let now = clock::now();
+        // Capped so a stalled loop doesn't skip the text ahead
+        let dt = now
+            .saturating_duration_since(marquee.last_tick)
+            .min(clock::MAX_ANIMATION_STEP)
+            .as_secs_f32();
+        marquee.last_tick = now;
+        marquee.advance(dt);
+        request_job(id, JobRequest::Animation(RequiredJob::Paint));
+        true
     }
 
-    fn event(
-        &mut self,
-        _tree: &mut Tree,
-        _id: WidgetId,
-        _event: &super::widget::Event,
-    ) -> EventResponse {
+    fn event(&mut self, tree: &mut Tree, id: WidgetId, event: &Event) -> EventResponse {
+        let Some(ref mut marquee) = self.marquee else {
+            return EventResponse::Ignored;
+        };
+        let hovered = match event {
+            Event::MouseEnter { x, y } | Event::MouseMove { x, y } => tree
+                .get_bounds(id)
+                .is_some_and(|bounds| bounds.contains(*x, *y)),
+            Event::MouseLeave => false,
+            _ => return EventResponse::Ignored,
+        };
+        if hovered != marquee.hovered {
+            marquee.hovered = hovered;
+            if marquee.is_running() {
+                // Resume without counting the time spent hovered
+                marquee.last_tick = clock::now();
+                request_job(id, JobRequest::Animation(RequiredJob::Paint));
+            }
+        }
+        // Hover tracking must not stop siblings from seeing the event
         EventResponse::Ignored
     }
 }
@@ -208,3 +765,124 @@ impl Widget for Text {
 pub fn text<M>(content: impl IntoSignal<String, M>) -> Text {
     Text::new(content)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_highlight_follows_direction_marks() {
+        let source = "ab\ncd\nef";
+        let shown = TextDirection::Ltr.apply(source).unwrap();
+        let index = shown_char_indices(source, &shown);
+        // One mark ahead of each line shifts every line by one more character
+        assert_eq!(shown_range(&index, 0..2), Some(1..3));
+        assert_eq!(shown_range(&index, 3..5), Some(5..7));
+        assert_eq!(shown_range(&index, 6..8), Some(9..11));
+        let shown_chars: Vec<char> = shown.chars().collect();
+        assert_eq!(shown_chars[9..11], ['e', 'f']);
+    }
+
+    #[test]
+    fn test_highlight_follows_joined_lines() {
+        let index = shown_char_indices("ab\r\ncd", "ab cd");
+        assert_eq!(shown_range(&index, 4..6), Some(3..5));
+        assert_eq!(index[6], Some(5));
+    }
+
+    #[test]
+    fn test_highlight_on_clamped_text() {
+        // Wrapped at the spaces and cut after two lines
+        let source = "hello world again";
+        let shown = "hello\nworld…";
+        let index = shown_char_indices(source, shown);
+        assert_eq!(shown_range(&index, 6..11), Some(6..11));
+        // Cut away behind the ellipsis
+        assert_eq!(shown_range(&index, 12..17), None);
+        // Partly cut: only the shown part is marked
+        assert_eq!(shown_range(&index, 8..14), Some(8..11));
+
+        // A word cut by the ellipsis, with direction marks
+        let shown = TextDirection::Rtl.apply("hello\nwor…").unwrap();
+        let index = shown_char_indices(source, &shown);
+        assert_eq!(shown_range(&index, 6..11), Some(8..11));
+    }
+
+    #[test]
+    fn test_marquee_scrolls_pauses_and_wraps_with_the_clock() {
+        use crate::renderer::{DrawCommand, RenderNode};
+        use crate::widgets::container::container;
+        use std::time::Duration;
+
+        clock::pause_clock();
+        let mut tree = Tree::new();
+        let root = tree.register(Box::new(
+            container().width(40.0).child(
+                text("a line far too long for its box")
+                    .marquee_speed(100.0)
+                    .marquee_pause(0.5),
+            ),
+        ));
+        tree.with_widget_mut(root, |widget, id, tree| widget.register_children(tree, id));
+        tree.with_widget_mut(root, |widget, id, tree| {
+            widget.layout(tree, id, Constraints::loose(Size::new(400.0, 100.0)))
+        });
+        let id = tree.get_children(root)[0];
+
+        let offset = |tree: &Tree| {
+            let mut node = RenderNode::new(id.as_u64());
+            tree.with_widget(id, |widget| {
+                widget.paint(tree, id, &mut PaintContext::new(&mut node))
+            });
+            node.commands
+                .iter()
+                .find_map(|cmd| match **cmd {
+                    DrawCommand::Text { rect, .. } => Some(-rect.x),
+                    _ => None,
+                })
+                .unwrap()
+        };
+        let tick = |tree: &mut Tree, ms: u64| {
+            clock::advance_clock(Duration::from_millis(ms));
+            tree.with_widget_mut(id, |widget, id, tree| widget.advance_animations(tree, id))
+                .unwrap()
+        };
+        let send = |tree: &mut Tree, event: Event| {
+            tree.with_widget_mut(id, |widget, id, tree| widget.event(tree, id, &event));
+        };
+
+        // Holds at the start for the pause, then scrolls at its speed
+        for _ in 0..5 {
+            assert!(tick(&mut tree, 100));
+        }
+        assert_eq!(offset(&tree), 0.0);
+        tick(&mut tree, 100);
+        assert!((offset(&tree) - 10.0).abs() < 0.01, "{}", offset(&tree));
+
+        // Hovering stops it, without counting the hovered time afterwards
+        send(&mut tree, Event::MouseEnter { x: 5.0, y: 5.0 });
+        assert!(!tick(&mut tree, 1000));
+        assert!((offset(&tree) - 10.0).abs() < 0.01);
+        send(&mut tree, Event::MouseLeave);
+        tick(&mut tree, 100);
+        assert!((offset(&tree) - 20.0).abs() < 0.01, "{}", offset(&tree));
+
+        // Runs to the end, holds there, then starts over
+        let mut furthest = 0.0f32;
+        while offset(&tree) > 0.0 {
+            tick(&mut tree, 100);
+            furthest = furthest.max(offset(&tree));
+            assert!(furthest < 1000.0, "marquee never wrapped");
+        }
+        let size = tree.cached_size(id).unwrap();
+        let full = measure_text_with_params(
+            "a line far too long for its box",
+            &TextMeasureParams::new(14.0).wrap(WrapMode::NoWrap),
+        );
+        assert!(
+            (furthest - (full.width - size.width)).abs() < 1.0,
+            "{furthest}"
+        );
+        clock::resume_clock();
+    }
+}
@@ -11,28 +11,26 @@
 use std::collections::VecDeque;
 use std::time::{Duration, Instant};
 
+use crate::accessibility::{AccessInfo, Role};
 use crate::default_font_family;
 use crate::jobs::{JobRequest, JobType, RequiredJob, request_job};
 use crate::layout::{Constraints, Size};
+use crate::reactive::focus::report_caret;
 use crate::reactive::{
     CursorIcon, IntoSignal, OptionSignalExt, RwSignal, Signal, clipboard_copy, clipboard_paste,
     has_focus, release_focus, request_focus, set_cursor, with_signal_tracking,
 };
-use crate::renderer::{PaintContext, char_index_from_x_styled, measure_text_styled};
+use crate::renderer::{
+    PaintContext, TextMeasureParams, caret_offsets, char_index_from_x_styled, measure_text_styled,
+};
 use crate::tree::{Tree, WidgetId};
 
-use super::font::{FontFamily, FontWeight};
-use super::widget::{Color, Event, EventResponse, Key, Modifiers, MouseButton, Rect, Widget};
+use super::font::{FontFamily, FontWeight, WrapMode};
+use super::widget::{Color, Event, EventResponse, Key, MouseButton, Rect, Widget};
 
 /// Cursor blink interval in milliseconds
 const CURSOR_BLINK_MS: u64 = 530;
 
-/// Key repeat delay (time before repeat starts) in milliseconds
-const KEY_REPEAT_DELAY_MS: u64 = 400;
-
-/// Key repeat interval (time between repeats) in milliseconds
-const KEY_REPEAT_INTERVAL_MS: u64 = 35;
-
 /// Maximum number of undo history entries
 const MAX_HISTORY_SIZE: usize = 100;
 
@@ -45,6 +43,17 @@ const HISTORY_COALESCE_MS: u64 = 500;
 /// Type alias for text input callbacks
 type TextCallback = Box<dyn Fn(&str)>;
 
+/// Index of the caret position closest to `x` (the first one on a tie).
+fn nearest_position(positions: &[f32], x: f32) -> usize {
+    let mut nearest = 0;
+    for (i, position) in positions.iter().enumerate() {
+        if (position - x).abs() < (positions[nearest] - x).abs() {
+            nearest = i;
+        }
+    }
+    nearest
+}
+
 /// A snapshot of text input state for undo/redo
 #[derive(Clone, Debug)]
 struct HistoryEntry {
@@ -200,9 +209,11 @@ pub struct TextInput {
     // Measurement cache (avoid repeated text shaping in paint)
     /// Total width of display text
     cached_text_width: f32,
-    /// Cumulative width at each character index (length = char_count + 1)
-    /// cached_glyph_positions[i] = width of text[0..i]
+    /// Caret x position before each character index (length = char_count + 1),
+    /// in visual order, so unsorted for bidirectional text
     cached_glyph_positions: Vec<f32>,
+    /// Whether the text's paragraph direction is right-to-left
+    cached_rtl: bool,
     /// Whether measurements need to be recalculated
     measurements_dirty: bool,
 
@@ -228,11 +239,6 @@ pub struct TextInput {
     cursor_visible: bool,
     last_cursor_toggle: Instant,
 
-    // Key repeat state
-    pressed_key: Option<(Key, Modifiers)>,
-    key_press_time: Instant,
-    last_repeat_time: Instant,
-
     // Mouse drag selection
     is_dragging: bool,
 
@@ -267,6 +273,7 @@ impl TextInput {
             display_text_dirty: true,
             cached_text_width: 0.0,
             cached_glyph_positions: Vec::new(),
+            cached_rtl: false,
             measurements_dirty: true,
             text_color: None,
             cursor_color: None,
@@ -282,9 +289,6 @@ impl TextInput {
             selection: Selection::new(0),
             cursor_visible: true,
             last_cursor_toggle: Instant::now(),
-            pressed_key: None,
-            key_press_time: Instant::now(),
-            last_repeat_time: Instant::now(),
             is_dragging: false,
             is_hovered: false,
             history: History::new(),
@@ -408,42 +412,40 @@ impl TextInput {
         let font_family = &self.cached_font_family;
         let font_weight = self.cached_font_weight;
 
-        // Build cumulative position array: positions[i] = width of text[0..i]
-        // Length is char_count + 1 to include position 0 and position at end
-        let char_count = self.cached_char_count;
-        self.cached_glyph_positions.clear();
-        self.cached_glyph_positions.reserve(char_count + 1);
-        self.cached_glyph_positions.push(0.0); // Position at index 0
-
-        // Measure width at each character boundary
-        for (i, (byte_idx, _)) in display.char_indices().enumerate() {
-            // Width up to this character
-            let prefix = &display[..byte_idx];
-            let width = if prefix.is_empty() {
-                0.0
-            } else {
-                measure_text_styled(prefix, font_size, None, font_family, font_weight).width
-            };
-            // Update position for this index (already have 0 at index 0)
-            if i > 0 {
-                self.cached_glyph_positions.push(width);
-            }
-        }
-
-        // Add final position (total width)
+        // Caret positions come from the shaped glyphs, so right-to-left and
+        // mixed-direction text places the caret where the glyphs are drawn
+        let params = TextMeasureParams::new(font_size)
+            .font_family(font_family.clone())
+            .font_weight(font_weight);
+        let (positions, rtl) = caret_offsets(display, &params);
+        self.cached_glyph_positions = positions;
+        self.cached_rtl = rtl;
         self.cached_text_width =
             measure_text_styled(display, font_size, None, font_family, font_weight).width;
-        self.cached_glyph_positions.push(self.cached_text_width);
 
         self.measurements_dirty = false;
     }
 
-    /// Get cached width at a character index (0 to char_count inclusive)
+    /// Get the cached caret x at a character index (0 to char_count inclusive)
     fn cached_width_at_char(&self, char_index: usize) -> f32 {
         self.cached_glyph_positions
             .get(char_index)
             .copied()
-            .unwrap_or(self.cached_text_width)
+            .unwrap_or(if self.cached_rtl {
+                0.0
+            } else {
+                self.cached_text_width
+            })
+    }
+
+    /// Shift of the text within `bounds_width`: right-to-left text shorter
+    /// than the input is drawn against its right edge.
+    fn align_offset(&self, bounds_width: f32) -> f32 {
+        if self.cached_rtl {
+            (bounds_width - self.cached_text_width).max(0.0)
+        } else {
+            0.0
+        }
     }
 
     /// Convert a character index to a byte index in the cached value
@@ -526,48 +528,14 @@ impl TextInput {
         self.last_cursor_toggle = Instant::now();
     }
 
-    /// Handle key repeat for held keys
-    fn handle_key_repeat(&mut self, tree: &Tree, id: WidgetId) {
-        if !has_focus(id) {
-            self.pressed_key = None;
-            return;
-        }
-
-        if let Some((key, modifiers)) = self.pressed_key {
-            let now = Instant::now();
-            let since_press = now.duration_since(self.key_press_time);
-            let since_repeat = now.duration_since(self.last_repeat_time);
-
-            // Check if we're past the initial delay
-            if since_press >= Duration::from_millis(KEY_REPEAT_DELAY_MS) {
-                // Check if it's time for another repeat
-                if since_repeat >= Duration::from_millis(KEY_REPEAT_INTERVAL_MS) {
-                    let bounds_width = tree.cached_size(id).map(|s| s.width).unwrap_or(0.0);
-                    self.handle_key(&key, modifiers.ctrl, modifiers.shift, bounds_width);
-                    self.last_repeat_time = now;
-                }
-            }
-
-            // Keep requesting layout while a key is held so handle_key_repeat() runs each frame
-            request_job(id, JobRequest::Layout);
-        }
-    }
-
     /// Get character index from x coordinate relative to text start.
-    /// Uses cached glyph positions for O(log n) binary search.
+    /// Picks the cached caret position closest to `x`.
     fn char_index_at_x(&self, x: f32, bounds: Rect) -> usize {
-        let text_x = bounds.x;
+        let text_x = bounds.x + self.align_offset(bounds.width);
         // Account for scroll offset
         let relative_x = x - text_x + self.scroll_offset;
 
-        if relative_x <= 0.0 {
-            return 0;
-        }
-        if relative_x >= self.cached_text_width {
-            return self.cached_char_count;
-        }
-
-        // Binary search on cached glyph positions
+        // Positions aren't sorted for bidirectional text, so scan them all
         let positions = &self.cached_glyph_positions;
         if positions.is_empty() {
             // Fallback if cache not populated (shouldn't happen after layout)
@@ -580,30 +548,7 @@ impl TextInput {
                 self.cached_font_weight,
             );
         }
-
-        // Find the insertion point using binary search
-        let mut left = 0;
-        let mut right = positions.len();
-        while left < right {
-            let mid = (left + right) / 2;
-            if positions[mid] < relative_x {
-                left = mid + 1;
-            } else {
-                right = mid;
-            }
-        }
-
-        // left now points to first position >= relative_x
-        // Check if click is closer to the previous character
-        if left > 0 && left < positions.len() {
-            let prev_x = positions[left - 1];
-            let curr_x = positions[left];
-            if (relative_x - prev_x) < (curr_x - relative_x) {
-                return left - 1;
-            }
-        }
-
-        left.min(self.cached_char_count)
+        nearest_position(positions, relative_x).min(self.cached_char_count)
     }
 
     /// Ensure the cursor is visible by adjusting scroll offset
@@ -1014,9 +959,6 @@ impl Widget for TextInput {
         // This reads signals and registers layout dependencies
         self.refresh(id);
 
-        // Handle key repeat for held keys
-        self.handle_key_repeat(tree, id);
-
         // Update measurement cache (has internal dirty check)
         self.update_measurements();
 
@@ -1049,6 +991,11 @@ impl Widget for TextInput {
         size
     }
 
+    fn accessibility(&self) -> Option<AccessInfo> {
+        // Password contents are only exposed masked
+        Some(AccessInfo::new(Role::TextInput).value(self.display_text_cached()))
+    }
+
     fn paint(&self, tree: &Tree, id: WidgetId, ctx: &mut PaintContext) {
         // Draw in LOCAL coordinates (0,0 is widget origin)
         // Parent Container sets position transform
@@ -1071,10 +1018,16 @@ impl Widget for TextInput {
         // Draw selection highlight if focused and has selection (LOCAL coords)
         if is_focused && self.selection.has_selection() {
             let (start, end) = self.selection.range();
-            let start_x = self.cached_width_at_char(start) - self.scroll_offset;
-            let end_x = self.cached_width_at_char(end) - self.scroll_offset;
+            let offset = self.align_offset(bounds.width) - self.scroll_offset;
+            let start_x = self.cached_width_at_char(start) + offset;
+            let end_x = self.cached_width_at_char(end) + offset;
 
-            let selection_rect = Rect::new(start_x, 0.0, end_x - start_x, bounds.height);
+            let selection_rect = Rect::new(
+                start_x.min(end_x),
+                0.0,
+                (end_x - start_x).abs(),
+                bounds.height,
+            );
             ctx.draw_rounded_rect(selection_rect, selection_color, 0.0);
         }
 
@@ -1085,25 +1038,28 @@ impl Widget for TextInput {
             self.cached_text_width.max(bounds.width),
             bounds.height,
         );
-        ctx.draw_text_styled(
-            display,
-            text_bounds,
-            text_color,
-            self.cached_font_size,
-            self.cached_font_family.clone(),
-            self.cached_font_weight,
-        );
+        let params = TextMeasureParams::new(self.cached_font_size)
+            .font_family(self.cached_font_family.clone())
+            .font_weight(self.cached_font_weight)
+            .wrap(WrapMode::BreakWord);
+        ctx.draw_text_styled(display, text_bounds, text_color, &params);
 
         // Draw cursor if focused and visible (LOCAL coords)
-        if is_focused && self.cursor_visible {
-            let cursor_x = self.cached_width_at_char(self.selection.cursor) - self.scroll_offset;
+        if is_focused {
+            let cursor_x = self.cached_width_at_char(self.selection.cursor)
+                + self.align_offset(bounds.width)
+                - self.scroll_offset;
             let cursor_rect = Rect::new(
                 cursor_x,
                 0.0,
                 1.5, // cursor width
                 bounds.height,
             );
-            ctx.draw_rounded_rect(cursor_rect, cursor_color, 0.0);
+            // Input methods place their popups next to the caret, blinking or not
+            report_caret(id, cursor_rect);
+            if self.cursor_visible {
+                ctx.draw_rounded_rect(cursor_rect, cursor_color, 0.0);
+            }
         }
     }
 
@@ -1112,21 +1068,21 @@ impl Widget for TextInput {
         let bounds = tree.get_bounds(id).unwrap_or_default();
 
         match event {
-            Event::MouseDown { x, y, button } => {
-                if bounds.contains(*x, *y) && *button == MouseButton::Left {
-                    // Request focus and start cursor blink animation
-                    request_focus(id);
-                    request_job(id, JobRequest::Animation(RequiredJob::Paint));
-
-                    // Set cursor position
-                    let char_index = self.char_index_at_x(*x, bounds);
-                    self.selection = Selection::new(char_index);
-                    self.is_dragging = true;
-                    self.reset_cursor_blink();
-                    self.ensure_cursor_visible(bounds.width);
-
-                    return EventResponse::Handled;
-                }
+            Event::MouseDown { x, y, button }
+                if bounds.contains(*x, *y) && *button == MouseButton::Left =>
+            {
+                // Request focus and start cursor blink animation
+                request_focus(id);
+                request_job(id, JobRequest::Animation(RequiredJob::Paint));
+
+                // Set cursor position
+                let char_index = self.char_index_at_x(*x, bounds);
+                self.selection = Selection::new(char_index);
+                self.is_dragging = true;
+                self.reset_cursor_blink();
+                self.ensure_cursor_visible(bounds.width);
+
+                return EventResponse::Handled;
             }
             Event::MouseMove { x, y, .. } => {
                 let in_bounds = bounds.contains(*x, *y);
@@ -1149,49 +1105,27 @@ impl Widget for TextInput {
                     return EventResponse::Handled;
                 }
             }
-            Event::MouseUp { button, .. } => {
-                if *button == MouseButton::Left && self.is_dragging {
-                    self.is_dragging = false;
-                    return EventResponse::Handled;
-                }
-            }
-            Event::KeyDown { key, modifiers } => {
-                if has_focus(id) {
-                    // Track key for repeat
-                    let now = Instant::now();
-                    self.pressed_key = Some((*key, *modifiers));
-                    self.key_press_time = now;
-                    self.last_repeat_time = now;
-
-                    let response =
-                        self.handle_key(key, modifiers.ctrl, modifiers.shift, bounds.width);
-                    if response == EventResponse::Handled {
-                        request_job(id, JobRequest::Paint);
-                    }
-                    return response;
-                }
-            }
-            Event::KeyUp { key, .. } => {
-                // Stop repeating when key is released
-                if let Some((pressed_key, _)) = self.pressed_key
-                    && pressed_key == *key
-                {
-                    self.pressed_key = None;
-                }
+            Event::MouseUp { button, .. } if *button == MouseButton::Left && self.is_dragging => {
+                self.is_dragging = false;
+                return EventResponse::Handled;
             }
-            Event::FocusOut => {
-                if has_focus(id) {
-                    release_focus(id);
-                    self.cursor_visible = false;
-                    self.is_dragging = false;
+            Event::KeyDown { key, modifiers } if has_focus(id) => {
+                // Held keys arrive as repeated KeyDown events from the platform
+                let response = self.handle_key(key, modifiers.ctrl, modifiers.shift, bounds.width);
+                if response == EventResponse::Handled {
                     request_job(id, JobRequest::Paint);
                 }
+                return response;
             }
-            Event::MouseLeave => {
-                if self.is_hovered {
-                    self.is_hovered = false;
-                    set_cursor(CursorIcon::Default);
-                }
+            Event::FocusOut if has_focus(id) => {
+                release_focus(id);
+                self.cursor_visible = false;
+                self.is_dragging = false;
+                request_job(id, JobRequest::Paint);
+            }
+            Event::MouseLeave if self.is_hovered => {
+                self.is_hovered = false;
+                set_cursor(CursorIcon::Default);
             }
             _ => {}
         }
@@ -1210,3 +1144,57 @@ impl Widget for TextInput {
 pub fn text_input(signal: RwSignal<String>) -> TextInput {
     TextInput::new(signal)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nearest_position() {
+        // Left-to-right: positions grow with the index
+        assert_eq!(nearest_position(&[0.0, 10.0, 20.0], 14.0), 1);
+        assert_eq!(nearest_position(&[0.0, 10.0, 20.0], -5.0), 0);
+        assert_eq!(nearest_position(&[0.0, 10.0, 20.0], 99.0), 2);
+        // Right-to-left: the first character starts at the right edge
+        assert_eq!(nearest_position(&[20.0, 10.0, 0.0], 2.0), 2);
+        assert_eq!(nearest_position(&[20.0, 10.0, 0.0], 18.0), 0);
+    }
+
+    #[test]
+    fn test_focused_input_reports_its_caret() {
+        use crate::layout::Size;
+        use crate::reactive::create_signal;
+        use crate::reactive::focus::{clear_focus, focused_caret, request_focus};
+        use crate::renderer::RenderNode;
+
+        let mut tree = Tree::new();
+        let id = tree.register(Box::new(text_input(create_signal("hello".to_string()))));
+        tree.with_widget_mut(id, |widget, id, tree| {
+            widget.layout(tree, id, Constraints::loose(Size::new(200.0, 40.0)))
+        });
+        let caret_after = |tree: &mut Tree, key: Key| {
+            let event = Event::KeyDown {
+                key,
+                modifiers: Default::default(),
+            };
+            tree.with_widget_mut(id, |widget, id, tree| widget.event(tree, id, &event));
+            let mut node = RenderNode::new(id.as_u64());
+            tree.with_widget(id, |widget| {
+                widget.paint(tree, id, &mut PaintContext::new(&mut node))
+            });
+            focused_caret()
+        };
+
+        request_focus(id);
+        let (caret_id, end) = caret_after(&mut tree, Key::End).unwrap();
+        assert_eq!(caret_id, id);
+        assert!(end.x > 10.0, "{end:?}");
+        assert_eq!(end.height, tree.cached_size(id).unwrap().height);
+        let (_, home) = caret_after(&mut tree, Key::Home).unwrap();
+        assert_eq!(home.x, 0.0);
+
+        // Nothing to report once the input loses focus
+        clear_focus();
+        assert_eq!(focused_caret(), None);
+    }
+}
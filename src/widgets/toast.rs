@@ -0,0 +1,278 @@
+//! Transient notifications stacked in a corner of a surface.
+//!
+//! [`show_toast`] can be called from anywhere (an event handler, an effect, a
+//! service callback) and adds a message to the app-wide toast list. A
+//! [`toast_stack`] placed over a surface's content shows that list: new toasts
+//! slide in from the side, each one slides back out once its duration has
+//! passed, and the remaining toasts close the gap.
+//!
+//! # Example
+//!
+//! ```ignore
+//! container()
+//!     .layout(Overlay::new())
+//!     .child(content)
+//!     .child(toast_stack().corner(ToastCorner::TopRight))
+//!
+//! button("Save").on_click(move || {
+//!     save(document);
+//!     show_toast("Saved", Duration::from_secs(3));
+//! });
+//! ```
+
+use std::cell::{Cell, RefCell};
+use std::time::Duration;
+
+use crate::animation::{TimingFunction, Transition};
+use crate::component;
+use crate::layout::{CrossAlignment, MainAlignment, fill};
+use crate::reactive::{RwSignal, create_signal, create_timeout, without_owner};
+use crate::surface::breakpoint;
+
+use super::container::{column, container};
+use super::text::text;
+use super::widget::{Color, Padding};
+
+/// Default toast background
+const DEFAULT_TOAST_BACKGROUND: Color = Color::rgba(0.16, 0.16, 0.19, 0.95);
+
+/// Default toast text color
+const DEFAULT_TOAST_TEXT_COLOR: Color = Color::rgb(0.92, 0.92, 0.95);
+
+/// Milliseconds a toast takes to slide in or out
+const SLIDE_MS: u64 = 250;
+
+/// Identifies a toast created by [`show_toast`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ToastId(u64);
+
+/// Corner of the surface a [`toast_stack`] is placed in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ToastCorner {
+    /// Toasts stack downwards from the top-left corner
+    TopLeft,
+    /// Toasts stack downwards from the top-right corner
+    TopRight,
+    /// Toasts stack upwards from the bottom-left corner
+    BottomLeft,
+    /// Toasts stack upwards from the bottom-right corner
+    #[default]
+    BottomRight,
+}
+
+impl ToastCorner {
+    fn is_top(self) -> bool {
+        matches!(self, ToastCorner::TopLeft | ToastCorner::TopRight)
+    }
+
+    fn is_left(self) -> bool {
+        matches!(self, ToastCorner::TopLeft | ToastCorner::BottomLeft)
+    }
+}
+
+/// A toast in the list, and whether it is sliding out.
+#[derive(Debug, Clone, PartialEq)]
+struct Toast {
+    id: ToastId,
+    message: String,
+    leaving: bool,
+}
+
+thread_local! {
+    static TOASTS: RefCell<Option<RwSignal<Vec<Toast>>>> = const { RefCell::new(None) };
+    static NEXT_TOAST_ID: Cell<u64> = const { Cell::new(0) };
+}
+
+/// Get (or create) the app-wide toast list.
+///
+/// The list outlives the component that first asks for it, so it is created
+/// outside of any owner.
+fn toasts() -> RwSignal<Vec<Toast>> {
+    TOASTS.with(|toasts| {
+        *toasts
+            .borrow_mut()
+            .get_or_insert_with(|| without_owner(|| create_signal(Vec::new())))
+    })
+}
+
+/// Show `message` in every [`toast_stack`] for `duration`.
+///
+/// Returns an id that can be passed to [`dismiss_toast`] to hide the toast
+/// early.
+pub fn show_toast(message: impl Into<String>, duration: Duration) -> ToastId {
+    let id = ToastId(NEXT_TOAST_ID.with(|next| next.replace(next.get() + 1)));
+    toasts().update(|list| {
+        list.push(Toast {
+            id,
+            message: message.into(),
+            leaving: false,
+        })
+    });
+    // Dismiss even if the caller is disposed before the toast expires
+    without_owner(|| create_timeout(duration, move || dismiss_toast(id)));
+    id
+}
+
+/// Slide a toast out and remove it. Does nothing if it is already gone.
+pub fn dismiss_toast(id: ToastId) {
+    let toasts = toasts();
+    let mut started = false;
+    toasts.update(|list| started = start_leaving(list, id));
+    if started {
+        without_owner(|| {
+            create_timeout(Duration::from_millis(SLIDE_MS), move || {
+                toasts.update(|list| list.retain(|toast| toast.id != id));
+            })
+        });
+    }
+}
+
+/// Mark a toast as leaving, returning whether it was shown until now.
+fn start_leaving(list: &mut [Toast], id: ToastId) -> bool {
+    match list
+        .iter_mut()
+        .find(|toast| toast.id == id && !toast.leaving)
+    {
+        Some(toast) => {
+            toast.leaving = true;
+            true
+        }
+        None => false,
+    }
+}
+
+/// Forget all toasts.
+///
+/// Called during `App::drop()`.
+pub(crate) fn reset_toasts() {
+    TOASTS.with(|toasts| toasts.borrow_mut().take());
+    NEXT_TOAST_ID.with(|next| next.set(0));
+}
+
+// The macro-generated `ToastStack::new()` is wrapped by the public
+// `toast_stack()` below, matching the other ready-made components.
+mod component {
+    use super::*;
+
+    #[component]
+    pub fn toast_stack(
+        #[prop(default = "ToastCorner::BottomRight")] corner: ToastCorner,
+        #[prop(default = "DEFAULT_TOAST_BACKGROUND")] background: Color,
+        #[prop(default = "DEFAULT_TOAST_TEXT_COLOR")] text_color: Color,
+        #[prop(default = "Padding::all(16.0)")] padding: Padding,
+        #[prop(default = "8.0")] spacing: f32,
+    ) -> impl Widget {
+        let toasts = toasts();
+        // Toasts slide by the surface width, so they start and end off-screen
+        let surface_width = breakpoint().width_signal();
+
+        container()
+            .width(fill())
+            .height(fill())
+            .padding(padding)
+            .main_align(move || {
+                if corner.get().is_top() {
+                    MainAlignment::Start
+                } else {
+                    MainAlignment::End
+                }
+            })
+            .cross_align(move || {
+                if corner.get().is_left() {
+                    CrossAlignment::Start
+                } else {
+                    CrossAlignment::End
+                }
+            })
+            .child(
+                column(move || {
+                    toasts.get().into_iter().map(move |toast| {
+                        (toast.id.0, move || {
+                            // Starts off-screen and slides in on the next turn of the loop
+                            let shown = create_signal(false);
+                            create_timeout(Duration::ZERO, move || shown.set(true));
+                            let id = toast.id;
+                            let offset = move || {
+                                let leaving = toasts
+                                    .with(|list| list.iter().any(|t| t.id == id && t.leaving));
+                                if shown.get() && !leaving {
+                                    0.0
+                                } else if corner.get().is_left() {
+                                    -(surface_width.get() as f32)
+                                } else {
+                                    surface_width.get() as f32
+                                }
+                            };
+                            container()
+                                .padding(Padding::symmetric(16.0, 10.0))
+                                .corner_radius(8.0)
+                                .background(background)
+                                .translate(offset, 0.0)
+                                .animate_transform(Transition::new(
+                                    SLIDE_MS as f32,
+                                    TimingFunction::EaseOut,
+                                ))
+                                .child(text(toast.message.clone()).color(text_color))
+                        })
+                    })
+                })
+                .gap(spacing)
+                .cross_align(move || {
+                    if corner.get().is_left() {
+                        CrossAlignment::Start
+                    } else {
+                        CrossAlignment::End
+                    }
+                }),
+            )
+    }
+}
+
+pub use component::ToastStack;
+
+/// Create an overlay showing the toasts added with [`show_toast`].
+///
+/// Place it above the surface content, e.g. as the last child of a container
+/// with an [`Overlay`](crate::layout::Overlay) layout. It fills its parent and only the
+/// toasts themselves are drawn.
+pub fn toast_stack() -> ToastStack {
+    ToastStack::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn toast(id: u64) -> Toast {
+        Toast {
+            id: ToastId(id),
+            message: format!("toast {id}"),
+            leaving: false,
+        }
+    }
+
+    #[test]
+    fn test_start_leaving_only_once() {
+        let mut list = vec![toast(0), toast(1)];
+        assert!(start_leaving(&mut list, ToastId(1)));
+        assert!(list[1].leaving);
+        assert!(!list[0].leaving);
+        // Dismissing again (e.g. early and then on timeout) is a no-op
+        assert!(!start_leaving(&mut list, ToastId(1)));
+        assert!(!start_leaving(&mut list, ToastId(7)));
+    }
+
+    #[test]
+    fn test_toast_list_outlives_first_stack() {
+        use crate::reactive::{dispose_owner, with_owner};
+
+        // The first stack to read the list is disposed (e.g. its surface closed)
+        let (_, stack_owner) = with_owner(toasts);
+        dispose_owner(stack_owner);
+
+        let (id, caller) = with_owner(|| show_toast("Saved", Duration::from_secs(3)));
+        dispose_owner(caller);
+        assert_eq!(toasts().with_untracked(|list| list[0].id), id);
+        reset_toasts();
+    }
+}
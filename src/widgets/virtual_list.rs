@@ -0,0 +1,269 @@
+//! Scrollable list that only builds the rows in view.
+//!
+//! A [`VirtualList`] shows `count` rows of a fixed height but only creates the
+//! widgets of the rows intersecting the viewport, plus a few buffer rows on
+//! each side. Rows scrolled out of the buffer are dropped, and spacers above
+//! and below the built rows keep the scroll range of the full list.
+//!
+//! With an [`entrance`](VirtualList::entrance) transition, rows scrolled into
+//! the buffer slide into place instead of popping in.
+//!
+//! # Example
+//!
+//! ```ignore
+//! let messages = create_signal(load_messages());
+//!
+//! virtual_list(move || messages.with(Vec::len), 32.0, move |i| {
+//!     text(messages.with(|m| m[i].subject.clone()))
+//! })
+//! .entrance(Transition::new(180.0, TimingFunction::EaseOut))
+//! ```
+
+use std::cell::{Cell, RefCell};
+use std::ops::Range;
+use std::rc::Rc;
+use std::time::Duration;
+
+use crate::animation::TransitionConfig;
+use crate::layout::fill;
+use crate::reactive::{IntoSignal, create_signal, create_timeout};
+use crate::widget_ref::create_widget_ref;
+
+use super::container::{Container, column, container};
+use super::scroll::ScrollAxis;
+use super::widget::{Widget, delegate_widget};
+
+/// Rows built beyond each edge of the viewport by default
+const DEFAULT_BUFFER_ROWS: usize = 4;
+
+/// How far an entering row starts from its place, as a fraction of the row height
+const ENTRANCE_OFFSET: f32 = 0.5;
+
+/// Keys of the spacers, kept clear of row indices
+const TOP_SPACER_KEY: u64 = u64::MAX;
+const BOTTOM_SPACER_KEY: u64 = u64::MAX - 1;
+
+/// Settings read by the row builder, shared with the builder methods.
+struct VirtualState {
+    buffer: Cell<usize>,
+    entrance: RefCell<Option<TransitionConfig>>,
+    /// Rows built by the previous pass
+    built: Cell<(usize, usize)>,
+    /// Scroll offset seen by the previous pass
+    offset: Cell<f32>,
+}
+
+/// A vertically scrolling list building only its visible rows.
+///
+/// Created with [`virtual_list`].
+pub struct VirtualList {
+    inner: Container,
+    state: Rc<VirtualState>,
+}
+
+impl VirtualList {
+    /// Rows built beyond each edge of the viewport (4 by default).
+    ///
+    /// A larger buffer keeps more rows alive, so fast scrolling shows fewer
+    /// freshly built rows.
+    pub fn buffer(self, rows: usize) -> Self {
+        self.state.buffer.set(rows);
+        self
+    }
+
+    /// Slide rows scrolled into view into place with `transition`.
+    ///
+    /// Rows come from the edge they are scrolled in from. Rows built when the
+    /// list first appears, or because the row count or viewport changed, show
+    /// up in place.
+    pub fn entrance(self, transition: impl Into<TransitionConfig>) -> Self {
+        *self.state.entrance.borrow_mut() = Some(transition.into());
+        self
+    }
+
+    /// Tag the list for [`Tree::find_by_test_id`](crate::tree::Tree::find_by_test_id).
+    pub fn test_id(mut self, id: impl Into<String>) -> Self {
+        self.inner = self.inner.test_id(id);
+        self
+    }
+}
+
+impl Widget for VirtualList {
+    delegate_widget!(inner);
+}
+
+/// Rows intersecting a viewport of height `viewport` scrolled to `offset`,
+/// extended by `buffer` rows on each side and limited to `count` rows.
+fn visible_rows(
+    offset: f32,
+    viewport: f32,
+    row_height: f32,
+    buffer: usize,
+    count: usize,
+) -> Range<usize> {
+    if row_height <= 0.0 {
+        return 0..0;
+    }
+    let top = offset.max(0.0);
+    let first = (top / row_height).floor() as usize;
+    let last = ((top + viewport.max(0.0)) / row_height).ceil() as usize;
+    first.saturating_sub(buffer).min(count)..last.saturating_add(buffer).min(count)
+}
+
+/// Create a list of `count` rows, each `row_height` logical pixels tall,
+/// built by `row` from the row index.
+///
+/// The list fills its parent and scrolls vertically. Rows are keyed by index,
+/// so a row keeps its widget while it stays within the buffer.
+pub fn virtual_list<M, W: Widget + 'static>(
+    count: impl IntoSignal<usize, M>,
+    row_height: f32,
+    row: impl Fn(usize) -> W + 'static,
+) -> VirtualList {
+    let count = count.into_signal();
+    let row = Rc::new(row);
+    let state = Rc::new(VirtualState {
+        buffer: Cell::new(DEFAULT_BUFFER_ROWS),
+        entrance: RefCell::new(None),
+        built: Cell::new((0, 0)),
+        offset: Cell::new(0.0),
+    });
+    let list_ref = create_widget_ref();
+
+    // Spacers outlive the rows they stand for, so they track the range themselves
+    let range_state = state.clone();
+    let visible_range = Rc::new(move || {
+        visible_rows(
+            list_ref.scroll_offset_signal().get().1,
+            list_ref.rect().get().height,
+            row_height,
+            range_state.buffer.get(),
+            count.get(),
+        )
+    });
+
+    let rows_state = state.clone();
+    let rows = move || {
+        let state = &rows_state;
+        let offset = list_ref.scroll_offset_signal().get().1;
+        let visible = visible_range();
+
+        // Only rows brought in by scrolling animate
+        let scrolled = offset != state.offset.replace(offset);
+        let (prev_start, prev_end) = state.built.replace((visible.start, visible.end));
+        let entrance = state.entrance.borrow().clone().filter(|_| scrolled);
+
+        let range = visible_range.clone();
+        let top_spacer: Box<dyn FnOnce() -> Container> = Box::new(move || {
+            container()
+                .width(fill())
+                .height(move || range().start as f32 * row_height)
+        });
+        let range = visible_range.clone();
+        let bottom_spacer: Box<dyn FnOnce() -> Container> = Box::new(move || {
+            container()
+                .width(fill())
+                .height(move || count.get().saturating_sub(range().end) as f32 * row_height)
+        });
+        let mut items = Vec::with_capacity(visible.len() + 2);
+        items.push((TOP_SPACER_KEY, top_spacer));
+        for i in visible.clone() {
+            let row = row.clone();
+            let entering = if i < prev_start || i >= prev_end {
+                entrance.clone()
+            } else {
+                None
+            };
+            // Rows below the previous range come up from below, and rows
+            // above it come down from above
+            let from = if i >= prev_end {
+                row_height * ENTRANCE_OFFSET
+            } else {
+                -row_height * ENTRANCE_OFFSET
+            };
+            let build: Box<dyn FnOnce() -> Container> = Box::new(move || {
+                let row_container = container().width(fill()).height(row_height);
+                let row_container = match entering {
+                    Some(transition) => {
+                        // Starts offset and slides in on the next turn of the loop
+                        let shown = create_signal(false);
+                        create_timeout(Duration::ZERO, move || shown.set(true));
+                        row_container
+                            .translate(0.0, move || if shown.get() { 0.0 } else { from })
+                            .animate_transform(transition)
+                    }
+                    None => row_container,
+                };
+                row_container.child(row(i))
+            });
+            items.push((i as u64, build));
+        }
+        items.push((BOTTOM_SPACER_KEY, bottom_spacer));
+        items
+    };
+
+    VirtualList {
+        inner: column(rows)
+            .width(fill())
+            .height(fill())
+            .scrollable(ScrollAxis::Vertical)
+            .widget_ref(list_ref),
+        state,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout::{Constraints, Size};
+    use crate::tree::Tree;
+    use crate::widgets::Rect;
+
+    #[test]
+    fn test_visible_rows() {
+        // 100px viewport over 20px rows, scrolled by 30px: rows 1..7 intersect
+        assert_eq!(visible_rows(30.0, 100.0, 20.0, 0, 50), 1..7);
+        assert_eq!(visible_rows(30.0, 100.0, 20.0, 2, 50), 0..9);
+        // Clamped to the row count, including a viewport past the end
+        assert_eq!(visible_rows(950.0, 100.0, 20.0, 2, 50), 45..50);
+        assert_eq!(visible_rows(0.0, 100.0, 20.0, 4, 3), 0..3);
+        assert_eq!(visible_rows(0.0, 100.0, 0.0, 4, 3), 0..0);
+    }
+
+    #[test]
+    fn test_only_visible_rows_are_built() {
+        let built = Rc::new(Cell::new(0));
+        let built_count = built.clone();
+        let mut tree = Tree::new();
+        let root = tree.register(Box::new(
+            virtual_list(1000usize, 20.0, move |_| {
+                built_count.set(built_count.get() + 1);
+                container()
+            })
+            .buffer(1),
+        ));
+        let layout = |tree: &mut Tree| {
+            tree.with_widget_mut(root, |widget, id, tree| {
+                widget.layout(tree, id, Constraints::tight(Size::new(200.0, 100.0)))
+            });
+            crate::widget_ref::update_widget_refs(tree);
+        };
+        tree.with_widget_mut(root, |widget, id, tree| widget.register_children(tree, id));
+        layout(&mut tree);
+        // Re-run the row builder now that the viewport height is known
+        tree.with_widget_mut(root, |widget, id, tree| widget.reconcile_children(tree, id));
+        tree.mark_subtree_needs_layout(root);
+        layout(&mut tree);
+
+        // Five visible rows, one buffer row below, plus the two spacers
+        assert_eq!(built.get(), 6);
+        // The bottom spacer follows the built rows and stands for the rest
+        let bounds: Vec<Rect> = tree
+            .get_children(root)
+            .iter()
+            .filter_map(|&child| tree.get_bounds(child))
+            .collect();
+        assert!(bounds.contains(&Rect::new(0.0, 120.0, 190.0, 994.0 * 20.0)));
+        crate::widget_ref::reset_widget_refs();
+    }
+}
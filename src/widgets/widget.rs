@@ -1,3 +1,4 @@
+use crate::accessibility::AccessInfo;
 use crate::layout::{Constraints, Size};
 use crate::renderer::PaintContext;
 use crate::tree::{Tree, WidgetId};
@@ -185,6 +186,11 @@ impl Rect {
         }
     }
 
+    /// Center point of the rect.
+    pub fn center(&self) -> (f32, f32) {
+        (self.x + self.width / 2.0, self.y + self.height / 2.0)
+    }
+
     pub fn inset(&self, amount: f32) -> Self {
         Self {
             x: self.x + amount,
@@ -201,6 +207,15 @@ impl Rect {
             && self.y + self.height > other.y
     }
 
+    /// Smallest rect containing both rects.
+    pub fn union(&self, other: &Rect) -> Rect {
+        let min_x = self.x.min(other.x);
+        let min_y = self.y.min(other.y);
+        let max_x = (self.x + self.width).max(other.x + other.width);
+        let max_y = (self.y + self.height).max(other.y + other.height);
+        Rect::new(min_x, min_y, max_x - min_x, max_y - min_y)
+    }
+
     pub fn contains(&self, x: f32, y: f32) -> bool {
         x >= self.x && x < self.x + self.width && y >= self.y && y < self.y + self.height
     }
@@ -436,6 +451,19 @@ pub enum ScrollSource {
     Continuous,
 }
 
+/// Phase of a touchpad gesture (pinch or swipe)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GesturePhase {
+    /// Fingers touched down and the gesture was recognized
+    Begin,
+    /// Fingers moved
+    Update,
+    /// Fingers lifted
+    End,
+    /// The gesture was cancelled (e.g. taken over by the compositor)
+    Cancel,
+}
+
 /// Keyboard modifier state
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub struct Modifiers {
@@ -445,6 +473,15 @@ pub struct Modifiers {
     pub logo: bool,
 }
 
+/// A key press delivered to [`Container::on_key`](super::Container::on_key)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyEvent {
+    /// The key that was pressed
+    pub key: Key,
+    /// Current modifier state
+    pub modifiers: Modifiers,
+}
+
 /// Named keys for special keyboard keys
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Key {
@@ -498,6 +535,34 @@ pub enum Event {
         delta_y: f32,
         /// Source of the scroll event
         source: ScrollSource,
+        /// Modifier state when the scroll happened (e.g. Ctrl for zoom)
+        modifiers: Modifiers,
+    },
+    /// Touchpad pinch gesture
+    Pinch {
+        /// X position of the pointer (gesture center)
+        x: f32,
+        /// Y position of the pointer (gesture center)
+        y: f32,
+        /// Scale factor since the previous pinch event (> 1 = fingers spreading)
+        scale: f32,
+        /// Gesture phase
+        phase: GesturePhase,
+    },
+    /// Touchpad multi-finger swipe gesture
+    Swipe {
+        /// X position of the pointer
+        x: f32,
+        /// Y position of the pointer
+        y: f32,
+        /// Horizontal finger movement in pixels since the previous event
+        delta_x: f32,
+        /// Vertical finger movement in pixels since the previous event
+        delta_y: f32,
+        /// Number of fingers on the touchpad
+        fingers: u32,
+        /// Gesture phase
+        phase: GesturePhase,
     },
     /// Key pressed
     KeyDown {
@@ -534,6 +599,8 @@ impl Event {
             Event::MouseUp { x, y, .. } => Some((*x, *y)),
             Event::MouseEnter { x, y } => Some((*x, *y)),
             Event::Scroll { x, y, .. } => Some((*x, *y)),
+            Event::Pinch { x, y, .. } => Some((*x, *y)),
+            Event::Swipe { x, y, .. } => Some((*x, *y)),
             Event::MouseLeave
             | Event::KeyDown { .. }
             | Event::KeyUp { .. }
@@ -561,6 +628,7 @@ impl Event {
                 delta_x,
                 delta_y,
                 source,
+                modifiers,
                 ..
             } => Event::Scroll {
                 x: new_x,
@@ -568,6 +636,27 @@ impl Event {
                 delta_x: *delta_x,
                 delta_y: *delta_y,
                 source: *source,
+                modifiers: *modifiers,
+            },
+            Event::Pinch { scale, phase, .. } => Event::Pinch {
+                x: new_x,
+                y: new_y,
+                scale: *scale,
+                phase: *phase,
+            },
+            Event::Swipe {
+                delta_x,
+                delta_y,
+                fingers,
+                phase,
+                ..
+            } => Event::Swipe {
+                x: new_x,
+                y: new_y,
+                delta_x: *delta_x,
+                delta_y: *delta_y,
+                fingers: *fingers,
+                phase: *phase,
             },
             Event::MouseLeave => Event::MouseLeave,
             // Keyboard/focus events don't have coordinates
@@ -589,6 +678,8 @@ impl Event {
 pub struct LayoutHints {
     pub fill_width: bool,
     pub fill_height: bool,
+    /// Extra space the parent layout keeps around the widget
+    pub margin: Padding,
 }
 
 pub trait Widget {
@@ -612,6 +703,53 @@ pub trait Widget {
         LayoutHints::default()
     }
 
+    /// Stacking order of this widget among its siblings.
+    ///
+    /// Siblings are painted in ascending z-index order and receive pointer
+    /// events in descending order, so a higher value is drawn on top and hit
+    /// first. Siblings with equal z-index keep their declaration order.
+    fn z_index(&self) -> i32 {
+        0
+    }
+
+    /// Describe this widget for assistive technology.
+    ///
+    /// Widgets returning `None` are exported as plain groups by
+    /// [`access_tree`](crate::accessibility::access_tree).
+    fn accessibility(&self) -> Option<AccessInfo> {
+        None
+    }
+
+    /// Whether this widget may draw outside its layout bounds, e.g. through a
+    /// transform or a drop shadow.
+    ///
+    /// Damage for such widgets (and their descendants) can't be derived from
+    /// layout bounds, so the tree falls back to full-surface damage for them.
+    fn paints_outside_bounds(&self) -> bool {
+        false
+    }
+
+    /// Whether a scrollable parent pins this child to the top of its viewport
+    /// while the child's section is scrolled.
+    fn is_sticky(&self) -> bool {
+        false
+    }
+
+    /// Stable name used to locate this widget with
+    /// [`Tree::find_by_test_id`](crate::tree::Tree::find_by_test_id).
+    fn test_id(&self) -> Option<&str> {
+        None
+    }
+
+    /// Short name identifying the widget type in tree dumps and inspectors.
+    ///
+    /// Defaults to the last path segment of the type name (e.g. `Container`).
+    fn debug_name(&self) -> &'static str {
+        let name = std::any::type_name::<Self>();
+        let name = name.split('<').next().unwrap_or(name);
+        name.rsplit("::").next().unwrap_or(name)
+    }
+
     fn layout(&mut self, tree: &mut Tree, id: WidgetId, constraints: Constraints) -> Size;
     fn paint(&self, tree: &Tree, id: WidgetId, ctx: &mut PaintContext);
     fn event(&mut self, tree: &mut Tree, id: WidgetId, event: &Event) -> EventResponse {
@@ -653,6 +791,100 @@ pub trait Widget {
     }
 }
 
+/// Implement every [`Widget`] method by forwarding it to the field `$inner`.
+///
+/// Used inside `impl Widget for ...` blocks of wrappers that add behavior
+/// around another widget (ownership, a fixed layout), so new trait hooks only
+/// need to be forwarded here.
+macro_rules! delegate_widget {
+    ($inner:ident) => {
+        fn advance_animations(
+            &mut self,
+            tree: &mut $crate::tree::Tree,
+            id: $crate::tree::WidgetId,
+        ) -> bool {
+            $crate::widgets::Widget::advance_animations(&mut self.$inner, tree, id)
+        }
+
+        fn reconcile_children(
+            &mut self,
+            tree: &mut $crate::tree::Tree,
+            id: $crate::tree::WidgetId,
+        ) -> bool {
+            $crate::widgets::Widget::reconcile_children(&mut self.$inner, tree, id)
+        }
+
+        fn register_children(&mut self, tree: &mut $crate::tree::Tree, id: $crate::tree::WidgetId) {
+            $crate::widgets::Widget::register_children(&mut self.$inner, tree, id)
+        }
+
+        fn layout_hints(&self) -> $crate::widgets::LayoutHints {
+            $crate::widgets::Widget::layout_hints(&self.$inner)
+        }
+
+        fn z_index(&self) -> i32 {
+            $crate::widgets::Widget::z_index(&self.$inner)
+        }
+
+        fn accessibility(&self) -> Option<$crate::accessibility::AccessInfo> {
+            $crate::widgets::Widget::accessibility(&self.$inner)
+        }
+
+        fn paints_outside_bounds(&self) -> bool {
+            $crate::widgets::Widget::paints_outside_bounds(&self.$inner)
+        }
+
+        fn is_sticky(&self) -> bool {
+            $crate::widgets::Widget::is_sticky(&self.$inner)
+        }
+
+        fn test_id(&self) -> Option<&str> {
+            $crate::widgets::Widget::test_id(&self.$inner)
+        }
+
+        fn debug_name(&self) -> &'static str {
+            $crate::widgets::Widget::debug_name(&self.$inner)
+        }
+
+        fn layout(
+            &mut self,
+            tree: &mut $crate::tree::Tree,
+            id: $crate::tree::WidgetId,
+            constraints: $crate::layout::Constraints,
+        ) -> $crate::layout::Size {
+            $crate::widgets::Widget::layout(&mut self.$inner, tree, id, constraints)
+        }
+
+        fn paint(
+            &self,
+            tree: &$crate::tree::Tree,
+            id: $crate::tree::WidgetId,
+            ctx: &mut $crate::renderer::PaintContext,
+        ) {
+            $crate::widgets::Widget::paint(&self.$inner, tree, id, ctx)
+        }
+
+        fn event(
+            &mut self,
+            tree: &mut $crate::tree::Tree,
+            id: $crate::tree::WidgetId,
+            event: &$crate::widgets::Event,
+        ) -> $crate::widgets::EventResponse {
+            $crate::widgets::Widget::event(&mut self.$inner, tree, id, event)
+        }
+
+        fn has_focus_descendant(
+            &self,
+            tree: &$crate::tree::Tree,
+            focused_id: $crate::tree::WidgetId,
+        ) -> bool {
+            $crate::widgets::Widget::has_focus_descendant(&self.$inner, tree, focused_id)
+        }
+    };
+}
+
+pub(crate) use delegate_widget;
+
 /// A type-erased widget. Shorthand for `Box<dyn Widget>`.
 pub type AnyWidget = Box<dyn Widget>;
 
@@ -666,6 +898,24 @@ impl Widget for Box<dyn Widget> {
     fn layout_hints(&self) -> LayoutHints {
         (**self).layout_hints()
     }
+    fn z_index(&self) -> i32 {
+        (**self).z_index()
+    }
+    fn accessibility(&self) -> Option<AccessInfo> {
+        (**self).accessibility()
+    }
+    fn paints_outside_bounds(&self) -> bool {
+        (**self).paints_outside_bounds()
+    }
+    fn is_sticky(&self) -> bool {
+        (**self).is_sticky()
+    }
+    fn test_id(&self) -> Option<&str> {
+        (**self).test_id()
+    }
+    fn debug_name(&self) -> &'static str {
+        (**self).debug_name()
+    }
     fn layout(&mut self, tree: &mut Tree, id: WidgetId, constraints: Constraints) -> Size {
         (**self).layout(tree, id, constraints)
     }
@@ -237,3 +237,27 @@ fn test_where_clause_generic() {
     });
     assert_eq!(signals.data.get(), vec![1, 2, 3]);
 }
+
+#[cfg(feature = "serde")]
+#[derive(Clone, PartialEq, serde::Serialize, serde::Deserialize, SignalFields)]
+#[signal_fields(persist = "settings")]
+struct Settings {
+    volume: u8,
+    muted: bool,
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_persisted_fields_are_registered() {
+    let settings = SettingsSignals::new(Settings {
+        volume: 40,
+        muted: false,
+    });
+    let snapshot = snapshot_state();
+    assert_eq!(snapshot["settings.volume"], 40);
+    assert_eq!(snapshot["settings.muted"], false);
+
+    settings.volume.set(90);
+    restore_state(&snapshot).unwrap();
+    assert_eq!(settings.volume.get(), 40);
+}